@@ -0,0 +1,377 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input,
+    Attribute,
+    Data,
+    DeriveInput,
+    Error,
+    Expr,
+    ExprLit,
+    Fields,
+    Ident,
+    Lit,
+    LitStr,
+    Meta,
+    Type,
+};
+
+/// How a field is edited.
+enum Kind {
+    Number,
+    Text,
+    Toggle,
+    Choice,
+}
+
+/// Options set with `#[form(...)]`.
+#[derive(Default)]
+struct Options {
+    skip: bool,
+    choice: bool,
+    label: Option<String>,
+    help: Option<String>,
+    bounds: Option<Expr>,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Options> {
+        let mut opts = Options::default();
+        for attr in
+            attrs.iter().filter(|a| a.path().is_ident("form"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    opts.skip = true;
+                } else if meta.path.is_ident("choice") {
+                    opts.choice = true;
+                } else if meta.path.is_ident("label") {
+                    let value: LitStr =
+                        meta.value()?.parse()?;
+                    opts.label = Some(value.value());
+                } else if meta.path.is_ident("help") {
+                    let value: LitStr =
+                        meta.value()?.parse()?;
+                    opts.help = Some(value.value());
+                } else if meta.path.is_ident("bounds") {
+                    opts.bounds = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(
+                        meta.error("unknown form option")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        Ok(opts)
+    }
+}
+
+/// The field's doc comment, if any, used as its help text.
+fn doc_comment(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// E.g. `base_outlook` -> `Base Outlook`.
+fn title_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(c) => {
+                    c.to_uppercase().chain(chars).collect()
+                }
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// If the type is an unsigned integer.
+fn is_unsigned(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|seg| {
+        matches!(
+            seg.ident.to_string().as_str(),
+            "u8" | "u16" | "u32" | "u64" | "usize"
+        )
+    })
+}
+
+/// Infer how to edit a field from its type.
+fn kind_of(ty: &Type) -> Option<Kind> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let ident = path.path.segments.last()?.ident.to_string();
+    match ident.as_str() {
+        "f32" | "f64" | "u8" | "u16" | "u32" | "u64"
+        | "usize" | "i8" | "i16" | "i32" | "i64" | "isize" => {
+            Some(Kind::Number)
+        }
+        "String" => Some(Kind::Text),
+        "bool" => Some(Kind::Toggle),
+        _ => None,
+    }
+}
+
+/// Derive `hes_engine::form::EditorForm` for a struct,
+/// so the editor can generate a form for it.
+///
+/// Numbers, strings, and bools are handled automatically.
+/// Enums need `#[form(choice)]` and must derive strum's
+/// `EnumIter`, `EnumString`, and `IntoStaticStr`. Any other
+/// field must be marked `#[form(skip)]`, so that fields
+/// can't silently go missing from the editor.
+///
+/// Labels default to the title-cased field name and help
+/// text to the field's doc comment; either can be set with
+/// `#[form(label = "...", help = "...")]`. A number's valid
+/// range can be set with `#[form(bounds = Bounds::FRACTION)]`
+/// (any expression for a `hes_engine::Bounds`); unsigned
+/// numbers default to being non-negative.
+#[proc_macro_derive(EditorForm, attributes(form))]
+pub fn derive_editor_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `hes_engine::form::Documented` for a struct,
+/// exposing its fields' doc comments so the editor can
+/// use them as help text.
+#[proc_macro_derive(Documented)]
+pub fn derive_documented(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_documented(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Get a struct's named fields.
+fn named_fields(
+    input: &DeriveInput,
+    derive: &str,
+) -> syn::Result<Vec<syn::Field>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            format!("{derive} can only be derived for structs"),
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            Span::call_site(),
+            format!("{derive} requires named fields"),
+        ));
+    };
+    Ok(fields.named.iter().cloned().collect())
+}
+
+fn expand_documented(
+    input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let docs = named_fields(&input, "Documented")?
+        .iter()
+        .filter_map(|field| {
+            let key = field.ident.as_ref()?.to_string();
+            let doc = doc_comment(&field.attrs);
+            (!doc.is_empty()).then(|| quote! { (#key, #doc) })
+        })
+        .collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::hes_engine::form::Documented for #name #ty_generics #where_clause {
+            fn field_docs() -> &'static [(&'static str, &'static str)] {
+                &[#(#docs),*]
+            }
+        }
+    })
+}
+
+fn expand(
+    input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let fields = named_fields(&input, "EditorForm")?;
+
+    let mut descs = vec![];
+    let mut getters = vec![];
+    let mut setters = vec![];
+    for field in &fields {
+        let opts = Options::parse(&field.attrs)?;
+        if opts.skip {
+            continue;
+        }
+
+        let ident: &Ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let key = ident.to_string();
+        let label =
+            opts.label.unwrap_or_else(|| title_case(&key));
+        let help = opts
+            .help
+            .unwrap_or_else(|| doc_comment(&field.attrs));
+
+        let kind = if opts.choice {
+            Kind::Choice
+        } else {
+            kind_of(ty).ok_or_else(|| {
+                Error::new_spanned(
+                    field,
+                    "EditorForm doesn't know how to edit this type; \
+                     mark it with #[form(choice)] if it's an enum, \
+                     or #[form(skip)] to leave it out of the form",
+                )
+            })?
+        };
+
+        let bounds = match (&kind, opts.bounds) {
+            (Kind::Number, Some(bounds)) => {
+                quote! { Some(#bounds) }
+            }
+            (Kind::Number, None) if is_unsigned(ty) => quote! {
+                Some(::hes_engine::Bounds::NON_NEGATIVE)
+            },
+            (_, Some(bounds)) => {
+                return Err(Error::new_spanned(
+                    bounds,
+                    "only numbers can have bounds",
+                ))
+            }
+            _ => quote! { None },
+        };
+
+        descs.push(quote! {
+            ::hes_engine::form::Field {
+                name: #key,
+                label: #label,
+                help: #help,
+                bounds: #bounds,
+            }
+        });
+
+        let (getter, setter) = match kind {
+            Kind::Number => (
+                quote! {
+                    ::hes_engine::form::FieldValue::Number(
+                        self.#ident as f32,
+                    )
+                },
+                quote! {
+                    ::hes_engine::form::FieldValue::Number(value) => {
+                        self.#ident = value as #ty;
+                        true
+                    }
+                },
+            ),
+            Kind::Text => (
+                quote! {
+                    ::hes_engine::form::FieldValue::Text(
+                        self.#ident.clone(),
+                    )
+                },
+                quote! {
+                    ::hes_engine::form::FieldValue::Text(value) => {
+                        self.#ident = value;
+                        true
+                    }
+                },
+            ),
+            Kind::Toggle => (
+                quote! {
+                    ::hes_engine::form::FieldValue::Toggle(self.#ident)
+                },
+                quote! {
+                    ::hes_engine::form::FieldValue::Toggle(value) => {
+                        self.#ident = value;
+                        true
+                    }
+                },
+            ),
+            Kind::Choice => (
+                quote! {
+                    ::hes_engine::form::FieldValue::Choice {
+                        value: (&self.#ident).into(),
+                        options: <#ty as ::hes_engine::form::strum::IntoEnumIterator>::iter()
+                            .map(|var| var.into())
+                            .collect(),
+                    }
+                },
+                quote! {
+                    ::hes_engine::form::FieldValue::Choice { value, .. } => {
+                        match value.parse::<#ty>() {
+                            Ok(value) => {
+                                self.#ident = value;
+                                true
+                            }
+                            Err(_) => false,
+                        }
+                    }
+                },
+            ),
+        };
+        getters.push(quote! { #key => Some(#getter), });
+        setters.push(quote! {
+            #key => match value {
+                #setter
+                _ => false,
+            },
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::hes_engine::form::EditorForm for #name #ty_generics #where_clause {
+            fn fields() -> Vec<::hes_engine::form::Field> {
+                vec![#(#descs),*]
+            }
+
+            fn get_field(
+                &self,
+                name: &str,
+            ) -> Option<::hes_engine::form::FieldValue> {
+                match name {
+                    #(#getters)*
+                    _ => None,
+                }
+            }
+
+            fn set_field(
+                &mut self,
+                name: &str,
+                value: ::hes_engine::form::FieldValue,
+            ) -> bool {
+                match name {
+                    #(#setters)*
+                    _ => false,
+                }
+            }
+        }
+    })
+}
@@ -17,6 +17,27 @@ fn main() {
     )
     .unwrap();
 
+    // The game's icons, so they can be picked and previewed.
+    // Only icons under `/icons` are copied over to the editor.
+    let icons = fs::read_to_string("../hes-game/src/icons.rs")
+        .unwrap_or_else(|_| panic!("Couldn't read game icons"));
+    let dest_path = Path::new(&out_dir).join("icons.rs");
+    let mut file = fs::File::create(&dest_path).unwrap();
+    writeln!(file, "pub const ICONS: &[(&str, &str)] = &[")
+        .unwrap();
+    for (name, path) in icon_defs(&icons) {
+        if path.starts_with("/icons/") {
+            writeln!(
+                file,
+                "    (\"{}\", \"/public{}\"),",
+                name.to_lowercase(),
+                path
+            )
+            .unwrap();
+        }
+    }
+    writeln!(file, "];").unwrap();
+
     let output = std::process::Command::new("git")
         .args(&["rev-parse", "--short", "HEAD"])
         .output()
@@ -24,3 +45,22 @@ fn main() {
     let git_hash = String::from_utf8(output.stdout).unwrap();
     println!("cargo:rustc-env=GIT_HASH={}", git_hash);
 }
+
+/// Parse the `NAME: "path"` entries of
+/// the game's `icons!` definitions.
+fn icon_defs(src: &str) -> Vec<(&str, &str)> {
+    let Some((_, defs)) = src.split_once("icons! {") else {
+        return vec![];
+    };
+    defs.lines()
+        .map(|line| line.trim())
+        .take_while(|line| *line != "}")
+        .filter_map(|line| {
+            let (name, path) = line.split_once(':')?;
+            let path = path.trim().trim_end_matches(',');
+            let path =
+                path.strip_prefix('"')?.strip_suffix('"')?;
+            Some((name, path))
+        })
+        .collect()
+}
@@ -0,0 +1,112 @@
+// Save and open worlds with the File System Access API,
+// for browsers that support it, or natively in the
+// desktop app.
+
+const TYPES = [
+  {
+    description: "World",
+    accept: { "application/json": [".world"] },
+  },
+];
+
+const FILTERS = [{ name: "World", extensions: ["world"] }];
+const IMAGE_FILTERS = [
+  { name: "Images", extensions: ["png", "jpg", "jpeg", "gif", "webp"] },
+];
+
+// Where images were last picked from.
+const LIBRARY_ITEM = "image-library";
+
+// Set when running in the desktop app.
+const tauri = window.__TAURI__;
+
+export function isDesktop() {
+  return !!tauri;
+}
+
+// In the desktop app a file's handle is just its path,
+// along with its name like browser file handles have.
+function desktopHandle(path) {
+  return { name: path.split(/[\\/]/).pop(), path };
+}
+
+export function hasFileSystemAccess() {
+  return isDesktop() || "showSaveFilePicker" in window;
+}
+
+// Returns `null` if the user cancels.
+export async function pickSaveFile(suggestedName) {
+  try {
+    if (tauri) {
+      const path = await tauri.dialog.save({
+        defaultPath: suggestedName,
+        filters: FILTERS,
+      });
+      return path ? desktopHandle(path) : null;
+    }
+    return await window.showSaveFilePicker({ suggestedName, types: TYPES });
+  } catch (err) {
+    if (err.name === "AbortError") return null;
+    throw err.message || String(err);
+  }
+}
+
+// Returns `null` if the user cancels.
+export async function pickOpenFile() {
+  try {
+    if (tauri) {
+      const path = await tauri.dialog.open({ filters: FILTERS });
+      return path ? desktopHandle(path) : null;
+    }
+    const [handle] = await window.showOpenFilePicker({ types: TYPES });
+    return handle;
+  } catch (err) {
+    if (err.name === "AbortError") return null;
+    throw err.message || String(err);
+  }
+}
+
+export async function writeFile(handle, data) {
+  try {
+    if (handle.path) {
+      await tauri.fs.writeFile(handle.path, data);
+      return;
+    }
+    const writable = await handle.createWritable();
+    await writable.write(data);
+    await writable.close();
+  } catch (err) {
+    throw err.message || String(err);
+  }
+}
+
+export async function readFile(handle) {
+  try {
+    if (handle.path) {
+      return await tauri.fs.readFile(handle.path);
+    }
+    const file = await handle.getFile();
+    return new Uint8Array(await file.arrayBuffer());
+  } catch (err) {
+    throw err.message || String(err);
+  }
+}
+
+// Pick an image from disk in the desktop app, starting
+// from wherever the last one was picked from. Returns
+// its bytes and type, or `null` if the user cancels.
+export async function pickImage() {
+  const path = await tauri.dialog.open({
+    defaultPath: localStorage.getItem(LIBRARY_ITEM) || undefined,
+    filters: IMAGE_FILTERS,
+  });
+  if (!path) return null;
+
+  const dir = path.slice(0, path.length - desktopHandle(path).name.length);
+  localStorage.setItem(LIBRARY_ITEM, dir);
+
+  const bytes = await tauri.fs.readFile(path);
+  const ext = path.split(".").pop().toLowerCase();
+  const mime = ext === "jpg" ? "image/jpeg" : `image/${ext}`;
+  return { bytes, mime };
+}
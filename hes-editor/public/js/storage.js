@@ -0,0 +1,142 @@
+// Recently opened worlds and named checkpoints,
+// kept in IndexedDB.
+
+const DB_NAME = "hes-editor";
+const DB_VERSION = 2;
+const STORE = "recent-worlds";
+const CHECKPOINTS = "checkpoints";
+
+function promisify(request) {
+  return new Promise((resolve, reject) => {
+    request.onsuccess = () => resolve(request.result);
+    request.onerror = () => reject(request.error?.message);
+  });
+}
+
+function openDb() {
+  const request = indexedDB.open(DB_NAME, DB_VERSION);
+  request.onupgradeneeded = () => {
+    const db = request.result;
+    if (!db.objectStoreNames.contains(STORE)) {
+      db.createObjectStore(STORE, { keyPath: "name" });
+    }
+    if (!db.objectStoreNames.contains(CHECKPOINTS)) {
+      db.createObjectStore(CHECKPOINTS, { keyPath: "created" });
+    }
+  };
+  return promisify(request);
+}
+
+async function store(mode, name = STORE) {
+  const db = await openDb();
+  return db.transaction(name, mode).objectStore(name);
+}
+
+// Most recently opened first.
+async function allRecent() {
+  const entries = await promisify((await store("readonly")).getAll());
+  return entries.sort((a, b) => b.opened - a.opened);
+}
+
+// Add or update a world, keeping only the `max` most recent.
+export async function putRecent(entry, max) {
+  await promisify((await store("readwrite")).put(entry));
+  const stale = (await allRecent()).slice(max);
+  for (const { name } of stale) {
+    await promisify((await store("readwrite")).delete(name));
+  }
+}
+
+// Just what's needed to list them, without the world data.
+export async function listRecent() {
+  return (await allRecent()).map(({ name, opened, thumbnail }) => ({
+    name,
+    opened,
+    thumbnail,
+  }));
+}
+
+// Get a world's data, reading it fresh from its file if
+// there's a file handle and permission is granted.
+// Returns `null` if the world isn't found.
+export async function openRecent(name) {
+  const entry = await promisify((await store("readonly")).get(name));
+  if (!entry) return null;
+
+  let { data, handle } = entry;
+  if (handle?.path) {
+    // Files opened in the desktop app can be read directly.
+    try {
+      data = await window.__TAURI__.fs.readFile(handle.path);
+    } catch {
+      handle = null;
+    }
+  } else if (handle) {
+    try {
+      const perm = await handle.requestPermission({ mode: "readwrite" });
+      if (perm === "granted") {
+        const file = await handle.getFile();
+        data = new Uint8Array(await file.arrayBuffer());
+      } else {
+        handle = null;
+      }
+    } catch {
+      handle = null;
+    }
+  }
+  return { name, data, handle: handle || null };
+}
+
+export async function removeRecent(name) {
+  await promisify((await store("readwrite")).delete(name));
+}
+
+// Checkpoints are keyed by when they were created.
+export async function putCheckpoint(entry) {
+  await promisify((await store("readwrite", CHECKPOINTS)).put(entry));
+}
+
+// Most recent first, without the world data.
+export async function listCheckpoints() {
+  const entries = await promisify(
+    (await store("readonly", CHECKPOINTS)).getAll(),
+  );
+  return entries
+    .sort((a, b) => b.created - a.created)
+    .map(({ name, created }) => ({ name, created }));
+}
+
+// Returns `null` if the checkpoint isn't found.
+export async function getCheckpoint(created) {
+  const entry = await promisify(
+    (await store("readonly", CHECKPOINTS)).get(created),
+  );
+  return entry ? entry.data : null;
+}
+
+export async function removeCheckpoint(created) {
+  await promisify((await store("readwrite", CHECKPOINTS)).delete(created));
+}
+
+// Scale an image down to a small JPEG data URL.
+// Returns `null` if the image fails to load.
+export function makeThumbnail(src, width, height) {
+  return new Promise((resolve) => {
+    const img = new Image();
+    img.onload = () => {
+      const canvas = document.createElement("canvas");
+      canvas.width = width;
+      canvas.height = height;
+      const ctx = canvas.getContext("2d");
+
+      // Crop to fill the thumbnail.
+      const scale = Math.max(width / img.width, height / img.height);
+      const w = img.width * scale;
+      const h = img.height * scale;
+      ctx.drawImage(img, (width - w) / 2, (height - h) / 2, w, h);
+      resolve(canvas.toDataURL("image/jpeg", 0.7));
+    };
+    img.onerror = () => resolve(null);
+    img.src = src;
+  });
+}
@@ -0,0 +1,271 @@
+use hes_engine::{
+    flavor::{Image, ImageData},
+    Byproduct,
+    Cost,
+    EventPhase,
+    Output,
+    Process,
+    ProjectType,
+    Resource,
+    State,
+    World,
+};
+use strum::IntoEnumIterator;
+
+/// How many times larger or smaller than the median
+/// a value can be before it's flagged.
+const OUTLIER_FACTOR: f32 = 100.;
+
+/// Suggested values are within this factor of the median.
+const SUGGESTED_FACTOR: f32 = 10.;
+
+/// A value that's suspiciously far from its peers,
+/// e.g. because the wrong units were used.
+pub struct Outlier {
+    pub entity: String,
+    pub field: String,
+    pub value: f32,
+    pub median: f32,
+    pub suggested: (f32, f32),
+}
+impl Outlier {
+    fn new(
+        entity: &str,
+        field: String,
+        value: f32,
+        median: f32,
+    ) -> Self {
+        let (lo, hi) =
+            (median / SUGGESTED_FACTOR, median * SUGGESTED_FACTOR);
+        Outlier {
+            entity: entity.to_string(),
+            field,
+            value,
+            median,
+            suggested: (lo.min(hi), lo.max(hi)),
+        }
+    }
+}
+
+fn median(mut values: Vec<f32>) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2.)
+    } else {
+        Some(values[mid])
+    }
+}
+
+/// Is the value more than `OUTLIER_FACTOR` away from the median?
+/// Zero values are skipped as they usually mean
+/// "not applicable" rather than a unit mistake.
+fn is_outlier(value: f32, median: f32) -> bool {
+    if value == 0. || median == 0. {
+        return false;
+    }
+    let ratio = (value / median).abs();
+    ratio > OUTLIER_FACTOR || ratio < 1. / OUTLIER_FACTOR
+}
+
+/// Flag process values that are far from those of
+/// other processes with the same output.
+fn process_outliers(
+    processes: &[&Process],
+    field: &str,
+    get: impl Fn(&Process) -> f32,
+) -> Vec<Outlier> {
+    let values = processes
+        .iter()
+        .map(|p| get(p))
+        .filter(|v| *v != 0.)
+        .collect::<Vec<_>>();
+
+    // Need enough peers for a meaningful comparison.
+    if values.len() < 3 {
+        return vec![];
+    }
+
+    let median = median(values).unwrap();
+    processes
+        .iter()
+        .filter(|p| is_outlier(get(p), median))
+        .map(|p| {
+            Outlier::new(&p.name, field.to_string(), get(p), median)
+        })
+        .collect()
+}
+
+pub fn analyze(world: &World) -> Vec<Outlier> {
+    let mut outliers = vec![];
+
+    for output in Output::iter() {
+        let peers = world
+            .processes
+            .iter()
+            .filter(|p| p.output == output)
+            .collect::<Vec<_>>();
+        for resource in Resource::iter() {
+            outliers.extend(process_outliers(
+                &peers,
+                &format!("{resource} per {output}"),
+                |p| p.resources[resource],
+            ));
+        }
+        for byproduct in Byproduct::iter() {
+            outliers.extend(process_outliers(
+                &peers,
+                &format!("{byproduct} per {output}"),
+                |p| p.byproducts[byproduct],
+            ));
+        }
+    }
+
+    // Project costs are compared against projects of the same type,
+    // as policy costs are in political capital while
+    // research and initiative costs are in years.
+    for kind in ProjectType::iter() {
+        let costs = world
+            .projects
+            .iter()
+            .filter(|p| p.kind == kind)
+            .filter_map(|p| match p.base_cost {
+                Cost::Fixed(cost) => Some((p, cost as f32)),
+                Cost::Dynamic(..) => None,
+            })
+            .collect::<Vec<_>>();
+        let Some(median) =
+            median(costs.iter().map(|(_, cost)| *cost).collect())
+        else {
+            continue;
+        };
+
+        for (project, cost) in costs {
+            let has_effects = !project.effects.is_empty()
+                || project
+                    .outcomes
+                    .iter()
+                    .any(|outcome| !outcome.effects.is_empty());
+            let trivial = cost == 0. && has_effects;
+            if trivial || is_outlier(cost, median) {
+                outliers.push(Outlier::new(
+                    &project.name,
+                    format!("{kind:?} cost"),
+                    cost,
+                    median,
+                ));
+            }
+        }
+    }
+
+    outliers
+}
+
+/// Aggregate statistics for sanity-checking a world.
+pub struct Stats {
+    pub processes_per_output: Vec<(Output, usize)>,
+    pub events_per_phase: Vec<(EventPhase, usize)>,
+
+    /// Emissions for the starting year, in GtCO2eq.
+    pub baseline_emissions: f32,
+
+    /// Total size of embedded images and sounds, in bytes.
+    pub asset_bytes: usize,
+
+    pub region_populations: Vec<(String, f32)>,
+    pub total_population: f32,
+}
+
+fn image_bytes(image: &Image) -> usize {
+    match &image.data {
+        ImageData::File(_) => 0,
+        ImageData::Data { bytes, .. } => bytes.len(),
+    }
+}
+
+pub fn stats(world: &World) -> Stats {
+    let processes_per_output = Output::iter()
+        .map(|output| {
+            let n = world
+                .processes
+                .iter()
+                .filter(|p| p.output == output)
+                .count();
+            (output, n)
+        })
+        .collect();
+
+    let events_per_phase = EventPhase::iter()
+        .map(|phase| {
+            let n = world
+                .events
+                .iter()
+                .filter(|ev| ev.phase == phase)
+                .count();
+            (phase, n)
+        })
+        .filter(|(_, n)| *n > 0)
+        .collect();
+
+    let baseline_emissions =
+        State::new(world.clone()).emissions.as_gtco2eq();
+
+    let asset_bytes = world
+        .processes
+        .iter()
+        .map(|p| image_bytes(&p.flavor.image))
+        .chain(
+            world
+                .projects
+                .iter()
+                .map(|p| image_bytes(&p.flavor.image)),
+        )
+        .chain(
+            world
+                .industries
+                .iter()
+                .map(|p| image_bytes(&p.flavor.image)),
+        )
+        .chain(
+            world
+                .regions
+                .iter()
+                .map(|p| image_bytes(&p.flavor.image)),
+        )
+        .chain(world.events.iter().map(|ev| {
+            ev.flavor.image.as_ref().map_or(0, image_bytes)
+        }))
+        .chain(
+            world
+                .events
+                .iter()
+                .flat_map(|ev| ev.flavor.variants.iter())
+                .map(image_bytes),
+        )
+        .chain(world.events.iter().map(|ev| {
+            ev.flavor
+                .sound
+                .as_ref()
+                .map_or(0, |s| s.bytes.len())
+        }))
+        .sum();
+
+    let region_populations = world
+        .regions
+        .iter()
+        .map(|r| (r.name.clone(), r.population))
+        .collect();
+    let total_population = world.regions.population();
+
+    Stats {
+        processes_per_output,
+        events_per_phase,
+        baseline_emissions,
+        asset_bytes,
+        region_populations,
+        total_population,
+    }
+}
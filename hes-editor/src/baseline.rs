@@ -0,0 +1,37 @@
+use hes_engine::World;
+use leptos::*;
+
+/// The world that fields are compared against,
+/// e.g. the vanilla world when editing a mod.
+/// `None` if not comparing.
+#[derive(Clone, Copy)]
+pub struct Baseline(pub RwSignal<Option<World>>);
+
+/// Get a value from the baseline world, if comparing
+/// and the baseline world has it.
+pub fn use_baseline<T: Clone + 'static>(
+    get: impl Fn(&World) -> Option<T> + 'static,
+) -> Signal<Option<T>> {
+    let ctx = use_context::<Baseline>();
+    Signal::derive(move || {
+        ctx.and_then(|baseline| {
+            baseline
+                .0
+                .with(|world| world.as_ref().and_then(&get))
+        })
+    })
+}
+
+/// Get a field from a baseline value, i.e.
+/// a `Signal<Option<T>>` from `use_baseline`.
+#[macro_export]
+macro_rules! baseline {
+    ($base:ident.$($field:tt).+) => {{
+        let base = $base;
+        Signal::derive(move || {
+            with!(|base| base
+                .as_ref()
+                .map(|base| base.$($field).+.clone()))
+        })
+    }};
+}
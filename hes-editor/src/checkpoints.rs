@@ -0,0 +1,223 @@
+//! Named snapshots of the world, saved deliberately
+//! (e.g. "before energy rebalance") so that it can be
+//! restored to or compared against later. They're kept
+//! in the browser's storage, separately from the world.
+
+use hes_engine::{Collection, HasId, World};
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "/public/js/storage.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = putCheckpoint, catch)]
+    async fn put_checkpoint(
+        entry: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = listCheckpoints, catch)]
+    async fn list_checkpoints() -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = getCheckpoint, catch)]
+    async fn get_checkpoint(
+        created: f64,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = removeCheckpoint, catch)]
+    async fn remove_checkpoint(
+        created: f64,
+    ) -> Result<JsValue, JsValue>;
+}
+
+/// A saved checkpoint, without its world.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub name: String,
+
+    /// When the checkpoint was created, in milliseconds
+    /// since the epoch. This also identifies it.
+    pub created: f64,
+}
+
+fn get(obj: &JsValue, key: &str) -> JsValue {
+    Reflect::get(obj, &key.into()).unwrap_or(JsValue::NULL)
+}
+
+/// Save the world as it is now under the given name.
+pub async fn create(
+    name: &str,
+    world: &World,
+) -> Result<(), JsValue> {
+    let data = Uint8Array::from(world.to_binary().as_slice());
+    let entry = Object::new();
+    Reflect::set(&entry, &"name".into(), &name.into())?;
+    Reflect::set(
+        &entry,
+        &"created".into(),
+        &js_sys::Date::now().into(),
+    )?;
+    Reflect::set(&entry, &"data".into(), &data.into())?;
+    put_checkpoint(&entry).await?;
+    Ok(())
+}
+
+/// Saved checkpoints, most recent first.
+pub async fn checkpoints() -> Vec<Checkpoint> {
+    let Ok(list) = list_checkpoints().await else {
+        return vec![];
+    };
+    Array::from(&list)
+        .iter()
+        .map(|entry| Checkpoint {
+            name: get(&entry, "name")
+                .as_string()
+                .unwrap_or_default(),
+            created: get(&entry, "created")
+                .as_f64()
+                .unwrap_or(0.),
+        })
+        .collect()
+}
+
+/// Load a checkpoint's world.
+pub async fn load(
+    checkpoint: &Checkpoint,
+) -> Result<World, String> {
+    let data = get_checkpoint(checkpoint.created)
+        .await
+        .map_err(|err| format!("{err:?}"))?;
+    if data.is_null() {
+        return Err(format!(
+            "\"{}\" is no longer saved.",
+            checkpoint.name
+        ));
+    }
+    let data = Uint8Array::new(&data).to_vec();
+    World::from_bytes(&data).map_err(|err| err.to_string())
+}
+
+pub async fn remove(
+    checkpoint: &Checkpoint,
+) -> Result<(), JsValue> {
+    remove_checkpoint(checkpoint.created).await?;
+    Ok(())
+}
+
+/// How an entity differs from the checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+impl Change {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Change::Added => "Added",
+            Change::Removed => "Removed",
+            Change::Modified => "Modified",
+        }
+    }
+}
+
+/// A difference between the current world and a checkpoint.
+#[derive(Debug, Clone)]
+pub struct Difference {
+    pub kind: &'static str,
+    pub name: String,
+    pub change: Change,
+}
+
+fn diff_collection<T: HasId + PartialEq>(
+    kind: &'static str,
+    current: &Collection<T>,
+    checkpoint: &Collection<T>,
+    name: impl Fn(&T) -> &str,
+    diffs: &mut Vec<Difference>,
+) {
+    let mut push = |item: &T, change| {
+        diffs.push(Difference {
+            kind,
+            name: name(item).to_string(),
+            change,
+        });
+    };
+    for item in current.iter() {
+        match checkpoint.try_get(item.id()) {
+            None => push(item, Change::Added),
+            Some(prev) if prev != item => {
+                push(item, Change::Modified)
+            }
+            _ => (),
+        }
+    }
+    for item in checkpoint.iter() {
+        if current.try_get(item.id()).is_none() {
+            push(item, Change::Removed);
+        }
+    }
+}
+
+/// What's changed in the world since the checkpoint.
+/// More detailed, per-field differences can be seen by
+/// comparing against the checkpoint.
+pub fn diff(
+    current: &World,
+    checkpoint: &World,
+) -> Vec<Difference> {
+    let mut diffs = vec![];
+    diff_collection(
+        "Region",
+        &current.regions,
+        &checkpoint.regions,
+        |item| &item.name,
+        &mut diffs,
+    );
+    diff_collection(
+        "Industry",
+        &current.industries,
+        &checkpoint.industries,
+        |item| &item.name,
+        &mut diffs,
+    );
+    diff_collection(
+        "Process",
+        &current.processes,
+        &checkpoint.processes,
+        |item| &item.name,
+        &mut diffs,
+    );
+    diff_collection(
+        "Project",
+        &current.projects,
+        &checkpoint.projects,
+        |item| &item.name,
+        &mut diffs,
+    );
+    diff_collection(
+        "Event",
+        &current.events,
+        &checkpoint.events,
+        |item| &item.name,
+        &mut diffs,
+    );
+
+    // Everything else is lumped together as the planet.
+    let without_entities = |world: &World| {
+        let mut world = world.clone();
+        world.regions = Collection::default();
+        world.industries = Collection::default();
+        world.processes = Collection::default();
+        world.projects = Collection::default();
+        world.events = Collection::default();
+        world
+    };
+    if without_entities(current) != without_entities(checkpoint)
+    {
+        diffs.push(Difference {
+            kind: "Planet",
+            name: current.metadata.title.clone(),
+            change: Change::Modified,
+        });
+    }
+    diffs
+}
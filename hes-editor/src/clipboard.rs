@@ -0,0 +1,148 @@
+use hes_engine::{
+    Collection,
+    Event,
+    HasId,
+    Id,
+    Industry,
+    Process,
+    Project,
+    World,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::validate::{
+    validate_event,
+    validate_process,
+    validate_project,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText)]
+    fn write_text(text: &str) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = readText)]
+    fn read_text() -> js_sys::Promise;
+}
+
+pub async fn copy_text(text: &str) -> Result<(), String> {
+    JsFuture::from(write_text(text))
+        .await
+        .map(|_| ())
+        .map_err(|_| "Couldn't write to the clipboard.".into())
+}
+
+pub async fn paste_text() -> Result<String, String> {
+    JsFuture::from(read_text())
+        .await
+        .ok()
+        .and_then(|val| val.as_string())
+        .ok_or_else(|| "Couldn't read from the clipboard.".into())
+}
+
+/// An entity that can be copied to and pasted
+/// from the clipboard as JSON.
+pub trait Clipboard:
+    Serialize + DeserializeOwned + HasId + Sized
+{
+    /// Check that the entity is valid for the world,
+    /// i.e. that it doesn't refer to missing entities.
+    fn validate(&self, world: &World) -> Vec<String>;
+}
+
+impl Clipboard for Process {
+    fn validate(&self, world: &World) -> Vec<String> {
+        validate_process(self, world)
+    }
+}
+
+impl Clipboard for Industry {
+    /// Industries don't refer to other entities.
+    fn validate(&self, _world: &World) -> Vec<String> {
+        vec![]
+    }
+}
+
+impl Clipboard for Project {
+    fn validate(&self, world: &World) -> Vec<String> {
+        validate_project(self, world)
+    }
+}
+
+impl Clipboard for Event {
+    fn validate(&self, world: &World) -> Vec<String> {
+        validate_event(self, world)
+    }
+}
+
+pub fn to_json<T: Clipboard>(item: &T) -> String {
+    serde_json::to_string_pretty(item).unwrap()
+}
+
+/// Parse an entity from pasted JSON. If its id
+/// conflicts with an existing entity it's given a new one.
+pub fn from_json<T: Clipboard>(
+    data: &str,
+    existing: &Collection<T>,
+    world: &World,
+) -> Result<T, Vec<String>> {
+    let item: T = serde_json::from_str(data).map_err(|_| {
+        vec!["The clipboard doesn't contain a valid entity of this type.".into()]
+    })?;
+    adopt(item, existing, world)
+}
+
+/// Prepare an entity from elsewhere to be added to
+/// the world, checking that it's valid for it and
+/// giving it a new id if its id is already taken.
+pub fn adopt<T: Clipboard>(
+    item: T,
+    existing: &Collection<T>,
+    world: &World,
+) -> Result<T, Vec<String>> {
+    let errors = item.validate(world);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    if existing.try_get(item.id()).is_some() {
+        reassign(item)
+    } else {
+        Ok(item)
+    }
+}
+
+/// Give the entity a new id, along with any references
+/// it has to itself, e.g. an event that follows up with
+/// itself, so they don't refer to the original.
+fn reassign<T: Clipboard>(item: T) -> Result<T, Vec<String>> {
+    let old = item.id().to_string();
+    let new = Id::new_v4().to_string();
+    let mut value = serde_json::to_value(&item)
+        .map_err(|err| vec![err.to_string()])?;
+    replace_id(&mut value, &old, &new);
+    serde_json::from_value(value)
+        .map_err(|err| vec![err.to_string()])
+}
+
+fn replace_id(value: &mut Value, old: &str, new: &str) {
+    match value {
+        Value::String(id) if id == old => {
+            *id = new.to_string();
+        }
+        Value::Array(items) => {
+            for item in items {
+                replace_id(item, old, new);
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                replace_id(field, old, new);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,778 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use hes_engine::{Id, World};
+use leptos::*;
+use leptos_use::{use_document, use_event_listener};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{MessageEvent, WebSocket};
+
+use crate::t;
+
+const DEFAULT_RELAY: &str = "ws://localhost:9001";
+const RELAY_ITEM: &str = "collab-relay";
+const NAME_ITEM: &str = "collab-name";
+
+/// Top-level fields that are collections of entities.
+/// These are synced per entity field rather than as
+/// a whole, so authors can edit different entities, or
+/// different fields of the same entity, at the same time.
+const COLLECTIONS: [&str; 4] =
+    ["industries", "processes", "projects", "events"];
+
+/// Fields that aren't synced, as they're
+/// only set when exporting.
+const UNSYNCED: [&str; 1] = ["integrity"];
+
+/// Orders changes, with ties broken by client.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+struct Stamp {
+    clock: u64,
+    client: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    stamp: Stamp,
+    value: Value,
+}
+
+/// The world as a last-writer-wins map, keyed by path:
+/// top-level fields by name (e.g. `year`), whether an
+/// entity exists by `processes/<id>`, and the entity's
+/// fields by `processes/<id>/<field>`. A collection's own
+/// key holds the order of its entities.
+///
+/// Merging keeps the latest entry for each key, so
+/// every editor ends up with the same world whatever
+/// order changes arrive in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Doc {
+    clock: u64,
+    entries: BTreeMap<String, Entry>,
+}
+impl Doc {
+    /// Apply an entry if it's later than the
+    /// current one, returning whether it was.
+    fn apply(&mut self, key: String, entry: Entry) -> bool {
+        self.clock = self.clock.max(entry.stamp.clock);
+        match self.entries.get(&key) {
+            Some(current) if current.stamp >= entry.stamp => {
+                false
+            }
+            _ => {
+                self.entries.insert(key, entry);
+                true
+            }
+        }
+    }
+
+    fn merge(&mut self, other: Doc) -> bool {
+        other.entries.into_iter().fold(
+            false,
+            |changed, (key, entry)| {
+                self.apply(key, entry) || changed
+            },
+        )
+    }
+
+    /// Record where the world differs from the document,
+    /// returning the changes to send to other editors.
+    fn record(
+        &mut self,
+        client: u64,
+        world: &World,
+    ) -> Vec<(String, Entry)> {
+        let mut current = flatten(world);
+
+        // Entities that were removed.
+        for (key, entry) in &self.entries {
+            if is_entity(key)
+                && entry.value == Value::Bool(true)
+                && !current.contains_key(key)
+            {
+                current.insert(key.clone(), Value::Null);
+            }
+        }
+
+        let changed = current
+            .into_iter()
+            .filter(|(key, value)| {
+                self.entries
+                    .get(key)
+                    .map_or(true, |entry| &entry.value != value)
+            })
+            .collect::<Vec<_>>();
+        changed
+            .into_iter()
+            .map(|(key, value)| {
+                self.clock += 1;
+                let entry = Entry {
+                    stamp: Stamp {
+                        clock: self.clock,
+                        client,
+                    },
+                    value,
+                };
+                self.entries.insert(key.clone(), entry.clone());
+                (key, entry)
+            })
+            .collect()
+    }
+
+    fn to_world(&self) -> Result<World, serde_json::Error> {
+        let mut fields = Map::new();
+        let mut alive = BTreeSet::new();
+        let mut entities: HashMap<_, Map<String, Value>> =
+            HashMap::new();
+        for (key, entry) in &self.entries {
+            let mut parts = key.splitn(3, '/');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(field), None, None) => {
+                    fields.insert(
+                        field.into(),
+                        entry.value.clone(),
+                    );
+                }
+                (Some(field), Some(id), None) => {
+                    if entry.value == Value::Bool(true) {
+                        alive.insert((field, id));
+                    }
+                }
+                (Some(field), Some(id), Some(name)) => {
+                    entities
+                        .entry((field, id))
+                        .or_default()
+                        .insert(
+                            name.into(),
+                            entry.value.clone(),
+                        );
+                }
+                _ => (),
+            }
+        }
+
+        for field in COLLECTIONS {
+            let order: Vec<String> = fields
+                .get(field)
+                .and_then(Value::as_array)
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            // Entities added at the same time by different
+            // editors may be missing from the order,
+            // in which case they go at the end.
+            let ordered = order
+                .iter()
+                .map(String::as_str)
+                .filter(|id| alive.contains(&(field, *id)));
+            let unordered = alive
+                .iter()
+                .filter(|(f, id)| {
+                    *f == field
+                        && !order.iter().any(|o| o == id)
+                })
+                .map(|(_, id)| *id);
+            let items = ordered
+                .chain(unordered)
+                .filter_map(|id| entities.remove(&(field, id)))
+                .map(Value::Object)
+                .collect();
+            fields.insert(field.into(), Value::Array(items));
+        }
+        serde_json::from_value(Value::Object(fields))
+    }
+}
+
+fn is_entity(key: &str) -> bool {
+    key.matches('/').count() == 1
+}
+
+/// Flatten the world into the paths used by `Doc`.
+fn flatten(world: &World) -> BTreeMap<String, Value> {
+    let mut flat = BTreeMap::new();
+    let Ok(Value::Object(fields)) = serde_json::to_value(world)
+    else {
+        return flat;
+    };
+    for (field, value) in fields {
+        if UNSYNCED.contains(&field.as_str()) {
+            continue;
+        }
+        match value {
+            Value::Array(items)
+                if COLLECTIONS.contains(&field.as_str()) =>
+            {
+                let mut order = vec![];
+                for item in items {
+                    let Value::Object(item) = item else {
+                        continue;
+                    };
+                    let Some(id) = item
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                    else {
+                        continue;
+                    };
+                    let key = format!("{field}/{id}");
+                    for (name, value) in item {
+                        flat.insert(
+                            format!("{key}/{name}"),
+                            value,
+                        );
+                    }
+                    flat.insert(key, Value::Bool(true));
+                    order.push(Value::String(id));
+                }
+                flat.insert(field, Value::Array(order));
+            }
+            value => {
+                flat.insert(field, value);
+            }
+        }
+    }
+    flat
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+enum Message {
+    /// A newly joined editor asking for the world.
+    Hello { client: u64 },
+
+    /// The whole world, in reply to `Hello`.
+    Snapshot { client: u64, doc: Doc },
+
+    Changes {
+        client: u64,
+        changes: Vec<(String, Entry)>,
+    },
+
+    /// Who an editor is and what they're editing.
+    Presence {
+        client: u64,
+        name: String,
+        editing: Option<Id>,
+    },
+
+    /// Sent by the relay when an editor disconnects.
+    Leave { client: u64 },
+
+    /// Sent by the relay when an editor fell behind and
+    /// missed changes, so it should ask for the world again.
+    Resync,
+}
+
+/// Another editor in the session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Peer {
+    pub name: String,
+    pub color: String,
+    pub editing: Option<Id>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Offline,
+
+    /// Waiting for another editor to send the world.
+    Joining,
+
+    Connected,
+}
+
+struct Session {
+    socket: WebSocket,
+    client: u64,
+    name: String,
+    editing: Option<Id>,
+    doc: Doc,
+
+    /// Whether we have the session's world yet.
+    synced: bool,
+}
+impl Session {
+    fn send(&self, msg: &Message) {
+        if self.socket.ready_state() != WebSocket::OPEN {
+            return;
+        }
+        let data = serde_json::to_string(msg)
+            .expect("Messages are always serializable");
+        if let Err(err) = self.socket.send_with_str(&data) {
+            log::warn!("Failed to send to relay: {err:?}");
+        }
+    }
+
+    fn announce(&self) {
+        self.send(&Message::Presence {
+            client: self.client,
+            name: self.name.clone(),
+            editing: self.editing,
+        });
+    }
+}
+
+/// Live collaboration with other editors through a
+/// relay (see `util/relay`), provided as context.
+#[derive(Clone, Copy)]
+pub struct Collab {
+    pub status: RwSignal<Status>,
+    pub peers: RwSignal<BTreeMap<u64, Peer>>,
+    world: RwSignal<World>,
+    session: StoredValue<Option<Session>>,
+}
+impl Collab {
+    pub fn provide(world: RwSignal<World>) -> Collab {
+        let collab = Collab {
+            status: create_rw_signal(Status::Offline),
+            peers: create_rw_signal(BTreeMap::new()),
+            world,
+            session: store_value(None),
+        };
+
+        // Send local changes as they're made.
+        create_effect(move |_| {
+            world.with(|world| collab.record(world));
+        });
+
+        // Track which entity is being edited, so other
+        // editors can see it.
+        let _ = use_event_listener(
+            use_document(),
+            ev::focusin,
+            move |ev| {
+                let editing = ev
+                    .target()
+                    .and_then(|target| {
+                        target
+                            .dyn_into::<web_sys::Element>()
+                            .ok()
+                    })
+                    .and_then(|el| {
+                        el.closest(".scroll-list-item").ok()?
+                    })
+                    .and_then(|item| {
+                        let id = item.id();
+                        Id::parse_str(id.strip_prefix("item-")?)
+                            .ok()
+                    });
+                collab.session.update_value(|session| {
+                    if let Some(session) = session {
+                        if session.editing != editing {
+                            session.editing = editing;
+                            session.announce();
+                        }
+                    }
+                });
+            },
+        );
+
+        provide_context(collab);
+        collab
+    }
+
+    fn record(&self, world: &World) {
+        self.session.update_value(|session| {
+            let Some(session) =
+                session.as_mut().filter(|s| s.synced)
+            else {
+                return;
+            };
+            let changes =
+                session.doc.record(session.client, world);
+            if !changes.is_empty() {
+                session.send(&Message::Changes {
+                    client: session.client,
+                    changes,
+                });
+            }
+        });
+    }
+
+    /// Connect to a room on the relay. The host shares
+    /// their world, while others joining get the
+    /// host's world in place of their own.
+    pub fn connect(
+        self,
+        relay: &str,
+        room: &str,
+        name: String,
+        host: bool,
+    ) -> Result<(), String> {
+        self.disconnect();
+        let url = format!(
+            "{}/{}",
+            relay.trim_end_matches('/'),
+            js_sys::encode_uri_component(room)
+        );
+        let socket = WebSocket::new(&url).map_err(|_| {
+            format!("Invalid relay address: {url}")
+        })?;
+        let client =
+            (js_sys::Math::random() * u32::MAX as f64) as u64;
+
+        let mut doc = Doc::default();
+        if host {
+            self.world.with_untracked(|world| {
+                doc.record(client, world);
+            });
+        }
+        self.session.set_value(Some(Session {
+            socket: socket.clone(),
+            client,
+            name,
+            editing: None,
+            doc,
+            synced: host,
+        }));
+
+        let on_open = Closure::<dyn FnMut()>::new(move || {
+            self.status.set(if host {
+                Status::Connected
+            } else {
+                Status::Joining
+            });
+            self.session.with_value(|session| {
+                if let Some(session) = session {
+                    if !host {
+                        session
+                            .send(&Message::Hello { client });
+                    }
+                    session.announce();
+                }
+            });
+        });
+        let on_message =
+            Closure::<dyn FnMut(MessageEvent)>::new(
+                move |ev: MessageEvent| {
+                    let msg = ev.data().as_string().and_then(
+                        |data| {
+                            serde_json::from_str::<Message>(
+                                &data,
+                            )
+                            .ok()
+                        },
+                    );
+                    if let Some(msg) = msg {
+                        self.receive(msg);
+                    }
+                },
+            );
+        let on_close = Closure::<dyn FnMut()>::new(move || {
+            let ours = self.session.with_value(|session| {
+                session
+                    .as_ref()
+                    .is_some_and(|s| s.client == client)
+            });
+            if ours {
+                self.session.set_value(None);
+                self.peers.set(BTreeMap::new());
+                self.status.set(Status::Offline);
+            }
+        });
+
+        // These are owned by JS from here on, so
+        // they live as long as the socket does.
+        socket.set_onopen(Some(
+            on_open.into_js_value().unchecked_ref(),
+        ));
+        socket.set_onmessage(Some(
+            on_message.into_js_value().unchecked_ref(),
+        ));
+        socket.set_onclose(Some(
+            on_close.into_js_value().unchecked_ref(),
+        ));
+        Ok(())
+    }
+
+    pub fn disconnect(self) {
+        self.session.with_value(|session| {
+            if let Some(session) = session {
+                session.socket.set_onclose(None);
+                let _ = session.socket.close();
+            }
+        });
+        self.session.set_value(None);
+        self.peers.set(BTreeMap::new());
+        self.status.set(Status::Offline);
+    }
+
+    fn receive(self, msg: Message) {
+        match msg {
+            Message::Presence {
+                client,
+                name,
+                editing,
+            } => {
+                self.peers.update(|peers| {
+                    peers.insert(
+                        client,
+                        Peer {
+                            name,
+                            color: color_for(client),
+                            editing,
+                        },
+                    );
+                });
+            }
+            Message::Leave { client } => {
+                self.peers.update(|peers| {
+                    peers.remove(&client);
+                });
+            }
+            msg => {
+                // The world is set outside of the session so
+                // recording the change doesn't borrow it twice.
+                let updated = self
+                    .session
+                    .try_update_value(|session| {
+                        session.as_mut().and_then(|session| {
+                            sync(session, msg)
+                        })
+                    })
+                    .flatten();
+                match updated {
+                    Some(Ok(world)) => {
+                        self.status.set(Status::Connected);
+                        self.world.set(world);
+                    }
+                    Some(Err(err)) => {
+                        log::warn!(
+                            "Failed to apply changes: {err}"
+                        );
+                    }
+                    None => (),
+                }
+            }
+        }
+    }
+}
+
+/// Apply a message to the session, returning
+/// the updated world if it changed.
+fn sync(
+    session: &mut Session,
+    msg: Message,
+) -> Option<Result<World, serde_json::Error>> {
+    match msg {
+        Message::Hello { .. } => {
+            if session.synced {
+                session.send(&Message::Snapshot {
+                    client: session.client,
+                    doc: session.doc.clone(),
+                });
+            }
+            session.announce();
+            None
+        }
+        // Snapshots are merged rather than replacing
+        // the document, so our own changes are kept.
+        Message::Resync => {
+            session.send(&Message::Hello {
+                client: session.client,
+            });
+            None
+        }
+        Message::Snapshot { doc, .. } => {
+            let changed = session.doc.merge(doc);
+            let first = !session.synced;
+            session.synced = true;
+            (changed || first).then(|| session.doc.to_world())
+        }
+        Message::Changes { changes, .. } => {
+            let changed = changes.into_iter().fold(
+                false,
+                |changed, (key, entry)| {
+                    session.doc.apply(key, entry) || changed
+                },
+            );
+            (changed && session.synced)
+                .then(|| session.doc.to_world())
+        }
+        Message::Presence { .. } | Message::Leave { .. } => {
+            None
+        }
+    }
+}
+
+fn color_for(client: u64) -> String {
+    format!("hsl({}, 70%, 55%)", client % 360)
+}
+
+fn stored(key: &str) -> Option<String> {
+    window()
+        .local_storage()
+        .ok()
+        .flatten()?
+        .get_item(key)
+        .ok()?
+}
+
+fn store(key: &str, value: &str) {
+    if let Some(storage) =
+        window().local_storage().ok().flatten()
+    {
+        let _ = storage.set_item(key, value);
+    }
+}
+
+/// Start, join, or leave a collaboration session.
+#[component]
+pub fn CollabMenu() -> impl IntoView {
+    let collab = expect_context::<Collab>();
+    let open = create_rw_signal(false);
+    let error = create_rw_signal::<Option<String>>(None);
+    let relay = create_rw_signal(
+        stored(RELAY_ITEM)
+            .unwrap_or_else(|| DEFAULT_RELAY.into()),
+    );
+    let name =
+        create_rw_signal(stored(NAME_ITEM).unwrap_or_default());
+    let room = create_rw_signal(String::new());
+
+    let connect = move |host: bool| {
+        let (relay, name, room) = (
+            relay.get_untracked(),
+            name.get_untracked(),
+            room.get_untracked(),
+        );
+        if room.trim().is_empty() {
+            error.set(Some("Enter a room name.".into()));
+            return;
+        }
+        store(RELAY_ITEM, &relay);
+        store(NAME_ITEM, &name);
+        let name = if name.trim().is_empty() {
+            "Anonymous".to_string()
+        } else {
+            name
+        };
+        match collab.connect(&relay, room.trim(), name, host) {
+            Ok(()) => {
+                error.set(None);
+                open.set(false);
+            }
+            Err(err) => error.set(Some(err)),
+        }
+    };
+
+    let peers = move || {
+        collab.peers.with(|peers| {
+            peers
+                .values()
+                .map(|peer| {
+                    view! {
+                        <div class="collab-peer"
+                            style:background=peer.color.clone()
+                            title=peer.name.clone()>
+                            {initial(&peer.name)}
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let status = move || match collab.status.get() {
+        Status::Offline => "Collaborate",
+        Status::Joining => "Joining…",
+        Status::Connected => "Collaborating",
+    };
+
+    view! {
+        <div class="collab">
+            <div class="collab-peers">{peers}</div>
+            <div class="collab-open"
+                class:connected=move || collab.status.get() != Status::Offline
+                on:click=move |_| update!(|open| *open = !*open)>
+                {status}
+            </div>
+            <Show when=move || open.get()>
+                <div class="collab-inner">
+                    <Show
+                        when=move || collab.status.get() == Status::Offline
+                        fallback=move || view! {
+                            <button on:click=move |_| {
+                                collab.disconnect();
+                                open.set(false);
+                            }>{t!("Leave Session")}</button>
+                        }>
+                        <label>{t!("Relay")}</label>
+                        <input type="text"
+                            prop:value=relay
+                            on:input=move |ev| relay.set(event_target_value(&ev)) />
+                        <label>{t!("Room")}</label>
+                        <input type="text"
+                            prop:value=room
+                            on:input=move |ev| room.set(event_target_value(&ev)) />
+                        <label>{t!("Your Name")}</label>
+                        <input type="text"
+                            prop:value=name
+                            on:input=move |ev| name.set(event_target_value(&ev)) />
+                        <div class="collab-actions">
+                            <button
+                                title=t!("Share your current world with the room.")
+                                on:click=move |_| connect(true)>{t!("Start")}</button>
+                            <button
+                                title=t!("Replace your current world with the room's.")
+                                on:click=move |_| connect(false)>{t!("Join")}</button>
+                        </div>
+                        {move || error.get().map(|err| view! {
+                            <div class="collab-error">{err}</div>
+                        })}
+                    </Show>
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+/// Show which other editors are editing an entity.
+#[component]
+pub fn Presence(id: Id) -> impl IntoView {
+    let collab = expect_context::<Collab>();
+    let editors = move || {
+        collab.peers.with(|peers| {
+            peers
+                .values()
+                .filter(|peer| peer.editing == Some(id))
+                .map(|peer| {
+                    view! {
+                        <div class="collab-peer"
+                            style:background=peer.color.clone()
+                            title=format!("{} is editing this", peer.name)>
+                            {initial(&peer.name)}
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    view! {
+        <div class="presence">{editors}</div>
+    }
+}
+
+fn initial(name: &str) -> String {
+    name.chars()
+        .next()
+        .unwrap_or('?')
+        .to_uppercase()
+        .to_string()
+}
@@ -0,0 +1,161 @@
+//! Combinations of field values that don't make sense
+//! together, e.g. a process that produces output
+//! without using or emitting anything at all.
+//! Unlike lints these depend on several fields,
+//! so they're configured per entity type.
+
+use hes_engine::{
+    Cost,
+    Event,
+    Feedstock,
+    Industry,
+    Process,
+    Project,
+    ProjectType,
+};
+use leptos::*;
+
+use crate::t;
+
+/// A conflict between some of an entity's fields.
+pub struct Rule<T> {
+    /// The fields involved, which is where
+    /// the conflict is shown.
+    pub fields: &'static [&'static str],
+
+    /// Explains the conflict.
+    pub message: &'static str,
+
+    /// If the entity has the conflict.
+    pub check: fn(&T) -> bool,
+}
+
+pub trait Conflicts: Sized + 'static {
+    const RULES: &'static [Rule<Self>];
+
+    /// The rules this entity breaks.
+    fn conflicts(&self) -> Vec<&'static Rule<Self>> {
+        Self::RULES
+            .iter()
+            .filter(|rule| (rule.check)(self))
+            .collect()
+    }
+}
+
+fn all_zero(values: &[f32]) -> bool {
+    values.iter().all(|val| *val == 0.)
+}
+
+impl Conflicts for Process {
+    const RULES: &'static [Rule<Self>] = &[
+        Rule {
+            fields: &["resources", "byproducts"],
+            message: "Uses no resources and has no byproducts, so its output is free.",
+            check: |process| {
+                all_zero(&process.resources.values())
+                    && all_zero(&process.byproducts.values())
+            },
+        },
+        Rule {
+            fields: &["feedstock"],
+            message: "Has a feedstock type but doesn't use any of it.",
+            check: |process| {
+                !matches!(
+                    process.feedstock.0,
+                    Feedstock::Other | Feedstock::Soil
+                ) && process.feedstock.1 == 0.
+            },
+        },
+        Rule {
+            fields: &["locked", "mix_share"],
+            message: "Is locked but already has a mix share, so it produces before it's unlocked.",
+            check: |process| {
+                process.locked && process.mix_share > 0
+            },
+        },
+        Rule {
+            fields: &["limit", "mix_share"],
+            message: "Has a mix share but an output limit of zero.",
+            check: |process| {
+                process.limit == Some(0.) && process.mix_share > 0
+            },
+        },
+    ];
+}
+
+impl Conflicts for Industry {
+    const RULES: &'static [Rule<Self>] = &[Rule {
+        fields: &["resources", "byproducts"],
+        message: "Uses no resources and has no byproducts, so it has no impact.",
+        check: |industry| {
+            all_zero(&industry.resources.values())
+                && all_zero(&industry.byproducts.values())
+        },
+    }];
+}
+
+impl Conflicts for Project {
+    const RULES: &'static [Rule<Self>] = &[
+        Rule {
+            fields: &["kind", "gradual"],
+            message: "Is gradual, but only initiatives can be (e.g. it was changed from an initiative).",
+            check: |project| {
+                project.kind != ProjectType::Initiative
+                    && project.gradual
+            },
+        },
+        Rule {
+            fields: &["kind", "base_cost"],
+            message: "Has no cost, so it's completed as soon as it's started.",
+            check: |project| {
+                project.kind != ProjectType::Policy
+                    && project.base_cost == Cost::Fixed(0)
+            },
+        },
+        Rule {
+            fields: &["effects", "outcomes"],
+            message: "Has no effects or outcome effects, so it does nothing.",
+            check: |project| {
+                project.effects.is_empty()
+                    && project
+                        .outcomes
+                        .iter()
+                        .all(|outcome| outcome.effects.is_empty())
+            },
+        },
+    ];
+}
+
+impl Conflicts for Event {
+    const RULES: &'static [Rule<Self>] = &[Rule {
+        fields: &["probabilities"],
+        message: "Has no probabilities, so it can never occur.",
+        check: |event| event.probabilities.is_empty(),
+    }];
+}
+
+/// Show the conflicts involving any of the given fields,
+/// alongside the inputs for those fields. A conflict
+/// is only shown where its first field is, so that
+/// each is shown once.
+#[component]
+pub fn FieldConflicts<T: Conflicts>(
+    item: Signal<T>,
+    fields: &'static [&'static str],
+) -> impl IntoView {
+    move || {
+        item.with(|item| {
+            item.conflicts()
+                .into_iter()
+                .filter(|rule| fields.contains(&rule.fields[0]))
+                .map(|rule| {
+                    view! {
+                        <div class="input-error input-conflict">
+                            {t!(rule.message)}
+                        </div>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    }
+}
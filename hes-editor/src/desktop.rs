@@ -0,0 +1,28 @@
+use js_sys::{Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "/public/js/files.js")]
+extern "C" {
+    /// If running in the desktop app rather than a browser.
+    #[wasm_bindgen(js_name = isDesktop)]
+    pub fn is_desktop() -> bool;
+
+    #[wasm_bindgen(js_name = pickImage, catch)]
+    async fn pick_image_file() -> Result<JsValue, JsValue>;
+}
+
+/// Pick an image from disk, returning its bytes and
+/// mime type. Only available in the desktop app.
+pub async fn pick_image() -> Option<(Vec<u8>, String)> {
+    let image = match pick_image_file().await {
+        Ok(image) if !image.is_null() => image,
+        Ok(_) => return None,
+        Err(err) => {
+            log::warn!("Failed to pick image: {err:?}");
+            return None;
+        }
+    };
+    let bytes = Reflect::get(&image, &"bytes".into()).ok()?;
+    let mime = Reflect::get(&image, &"mime".into()).ok()?;
+    Some((Uint8Array::new(&bytes).to_vec(), mime.as_string()?))
+}
@@ -12,7 +12,7 @@ fn serialize(world: &World) -> Result<String> {
     Ok(BASE64_STANDARD.encode(compressed))
 }
 
-fn compress(input: &[u8]) -> Result<Vec<u8>> {
+pub fn compress(input: &[u8]) -> Result<Vec<u8>> {
     let mut compressed = Vec::new();
     {
         // Maximum compression
@@ -35,7 +35,7 @@ fn deserialize(data: String) -> Result<World> {
     Ok(world)
 }
 
-fn decompress(input: &[u8]) -> Result<Vec<u8>> {
+pub fn decompress(input: &[u8]) -> Result<Vec<u8>> {
     let mut decompressed = Vec::new();
     let mut decompressor =
         Decompressor::new(Cursor::new(input), 4096);
@@ -0,0 +1,299 @@
+use fastrand::Rng;
+use hes_engine::{
+    Bounds,
+    Comparator,
+    Condition,
+    Effect,
+    Event,
+    Likelihood,
+    Output,
+    PlayerVariable,
+    Probability,
+    Region,
+    World,
+    WorldVariable,
+};
+use leptos::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    inputs::{read_only, SliderInput},
+    t,
+    templates::two_line_dialogue,
+};
+
+/// Generated events are tagged with this, so
+/// they're easy to find and review.
+const TAG: &str = "generated";
+
+const COUNT: Bounds = Bounds::new(1., 50., 1.);
+const INTENSITY: Bounds = Bounds::new(0.25, 3., 0.25);
+
+/// What a pattern's placeholders are filled with.
+struct Fill<'a> {
+    region: &'a Region,
+    output: Output,
+}
+
+/// A parameterized event, with `{region}` and `{output}`
+/// in its text filled in from the world and its
+/// effects' magnitudes randomized.
+struct Pattern {
+    name: &'static str,
+    arc: &'static str,
+    lines: (&'static str, &'static str),
+    likelihood: Likelihood,
+
+    /// The range of temperature anomalies the event's
+    /// threshold is picked from, if it has one.
+    temperature: Option<(f32, f32)>,
+
+    /// Effects given the fill and a magnitude,
+    /// which is around 1 at normal intensity.
+    effects: fn(&Fill, f32) -> Vec<Effect>,
+}
+
+const PATTERNS: &[Pattern] = &[
+    Pattern {
+        name: "Crisis in {region}",
+        arc: "A Dying Planet",
+        lines: (
+            "A disaster has struck {region}, disrupting {output} production.",
+            "Relief efforts are underway, but many have lost their homes.",
+        ),
+        likelihood: Likelihood::Random,
+        temperature: Some((1., 2.5)),
+        effects: |fill, m| {
+            vec![
+                Effect::Output(fill.output, -0.05 * m),
+                Effect::RegionHabitability(
+                    fill.region.latitude,
+                    -2. * m,
+                ),
+                Effect::WorldVariable(
+                    WorldVariable::Outlook,
+                    -2. * m,
+                ),
+            ]
+        },
+    },
+    Pattern {
+        name: "{output} Shortage",
+        arc: "A Dying Planet",
+        lines: (
+            "Supply chains for {output} are faltering, with {region} hit hardest.",
+            "Prices are climbing and people are worried.",
+        ),
+        likelihood: Likelihood::Unlikely,
+        temperature: Some((0.5, 2.)),
+        effects: |fill, m| {
+            vec![
+                Effect::Output(fill.output, -0.03 * m),
+                Effect::PlayerVariable(
+                    PlayerVariable::PoliticalCapital,
+                    -10. * m,
+                ),
+            ]
+        },
+    },
+    Pattern {
+        name: "Unrest in {region}",
+        arc: "Political Turmoil",
+        lines: (
+            "Protests have broken out across {region} over the cost of {output}.",
+            "Your opponents are using it against you.",
+        ),
+        likelihood: Likelihood::Unlikely,
+        temperature: None,
+        effects: |_, m| {
+            vec![
+                Effect::WorldVariable(
+                    WorldVariable::Outlook,
+                    -3. * m,
+                ),
+                Effect::PlayerVariable(
+                    PlayerVariable::PoliticalCapital,
+                    -15. * m,
+                ),
+            ]
+        },
+    },
+    Pattern {
+        name: "{output} Breakthrough in {region}",
+        arc: "Eden Earth",
+        lines: (
+            "Researchers in {region} have found a way to make {output} more efficiently.",
+            "It's already being adopted elsewhere.",
+        ),
+        likelihood: Likelihood::Rare,
+        temperature: None,
+        effects: |fill, m| {
+            vec![
+                Effect::Output(fill.output, 0.05 * m),
+                Effect::WorldVariable(WorldVariable::Outlook, m),
+            ]
+        },
+    },
+    Pattern {
+        name: "Changing Tastes in {region}",
+        arc: "Eden Earth",
+        lines: (
+            "People in {region} are turning away from {output}.",
+            "The trend looks like it's catching on.",
+        ),
+        likelihood: Likelihood::Rare,
+        temperature: None,
+        effects: |fill, m| vec![Effect::Demand(fill.output, -0.05 * m)],
+    },
+];
+
+fn fill(text: &str, fill: &Fill) -> String {
+    text.replace("{region}", &fill.region.name)
+        .replace("{output}", &fill.output.to_string())
+}
+
+/// Generate a batch of filler events from the patterns,
+/// with randomized regions, outputs, and magnitudes.
+/// `intensity` scales the magnitudes.
+pub fn generate_events(
+    world: &World,
+    count: usize,
+    intensity: f32,
+    seed: u64,
+) -> Vec<Event> {
+    let mut rng = Rng::with_seed(seed);
+    let outputs = Output::iter().collect::<Vec<_>>();
+    if world.regions.is_empty() {
+        return vec![];
+    }
+
+    (0..count)
+        .map(|_| {
+            let pattern =
+                &PATTERNS[rng.usize(..PATTERNS.len())];
+            let fill_with = Fill {
+                region: world
+                    .regions
+                    .by_idx(rng.usize(..world.regions.len())),
+                output: outputs[rng.usize(..outputs.len())],
+            };
+
+            // Vary magnitudes by up to half either way.
+            let magnitude = intensity * (0.5 + rng.f32());
+            let effects =
+                (pattern.effects)(&fill_with, magnitude)
+                    .into_iter()
+                    .map(round_effect)
+                    .collect();
+
+            let mut conditions =
+                vec![Condition::WorldVariable(
+                    WorldVariable::Year,
+                    Comparator::Greater,
+                    world.year as f32,
+                )];
+            if let Some((lo, hi)) = pattern.temperature {
+                let threshold = lo + rng.f32() * (hi - lo);
+                conditions.push(Condition::WorldVariable(
+                    WorldVariable::Temperature,
+                    Comparator::GreaterEqual,
+                    (threshold * 10.).round() / 10.,
+                ));
+            }
+
+            let mut event = Event::new();
+            event.name = fill(pattern.name, &fill_with);
+            event.probabilities = vec![Probability {
+                likelihood: pattern.likelihood,
+                conditions,
+            }];
+            event.effects = effects;
+            event.flavor.arc = pattern.arc.into();
+            event.flavor.dialogue = two_line_dialogue(
+                &fill(pattern.lines.0, &fill_with),
+                &fill(pattern.lines.1, &fill_with),
+            );
+            event.tags = vec![TAG.into()];
+            event
+        })
+        .collect()
+}
+
+/// Round randomized amounts so they're easier to read.
+fn round_effect(effect: Effect) -> Effect {
+    let round = |value: f32| (value * 100.).round() / 100.;
+    match effect {
+        Effect::Output(output, value) => {
+            Effect::Output(output, round(value))
+        }
+        Effect::Demand(output, value) => {
+            Effect::Demand(output, round(value))
+        }
+        Effect::RegionHabitability(lat, value) => {
+            Effect::RegionHabitability(lat, round(value))
+        }
+        Effect::WorldVariable(var, value) => {
+            Effect::WorldVariable(var, round(value))
+        }
+        Effect::PlayerVariable(var, value) => {
+            Effect::PlayerVariable(var, value.round())
+        }
+        effect => effect,
+    }
+}
+
+/// Fill the world with a batch of generated events.
+#[component]
+pub fn EventBatch(world: RwSignal<World>) -> impl IntoView {
+    let read_only = read_only();
+    let open = create_rw_signal(false);
+    let count = create_rw_signal(10.);
+    let intensity = create_rw_signal(1.);
+
+    let generate = move |_| {
+        if read_only.get_untracked() {
+            return;
+        }
+        let seed =
+            (js_sys::Math::random() * u64::MAX as f64) as u64;
+        let events = with!(|world| generate_events(
+            world,
+            count.get_untracked() as usize,
+            intensity.get_untracked(),
+            seed
+        ));
+        update!(|world| {
+            for event in events {
+                world.events.push_front(event);
+            }
+        });
+        open.set(false);
+    };
+
+    view! {
+        <div class="batch-item"
+            class:hidden=read_only
+            title=t!("Generate a batch of filler events to edit afterwards.")
+            on:click=move |_| update!(|open| *open = !*open)>
+            {t!("Generate")}
+        </div>
+        <Show when=move || open.get() && !read_only.get()>
+            <div class="batch-panel">
+                <SliderInput
+                    label="Events"
+                    help="How many events to generate."
+                    bounds=COUNT
+                    signal=create_slice(count, |n| *n, |n, val| *n = val) />
+                <SliderInput
+                    label="Intensity"
+                    help="Scales the effects of the generated events."
+                    bounds=INTENSITY
+                    signal=create_slice(intensity, |n| *n, |n, val| *n = val) />
+                <div class="input-help">
+                    "Generated events are tagged \"generated\" so you can filter to them."
+                </div>
+                <button on:click=generate>{t!("Generate Events")}</button>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,60 @@
+use crate::icons::icon_path;
+use regex_lite::{Captures, Regex};
+
+/// Escape HTML so that the preview can't inject markup.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Render flavor text to HTML for previewing.
+///
+/// This mirrors how the game formats text, i.e.
+/// variables like `{region}` and icon references like
+/// `[political_capital]`. The values of variables aren't
+/// known until the game is played so they're highlighted
+/// rather than filled in. Icons the game doesn't have
+/// are shown by name.
+///
+/// Basic markdown emphasis (`**bold**`, `*italic*`)
+/// and paragraphs are also rendered.
+pub fn render(text: &str) -> String {
+    let bold = Regex::new(r"\*\*(.+?)\*\*").unwrap();
+    let italic = Regex::new(r"\*(.+?)\*").unwrap();
+    let vars = Regex::new(r"\{([a-z_]+)\}").unwrap();
+    let icons = Regex::new(r"\[([a-z_]+)\]").unwrap();
+
+    escape(text)
+        .split("\n\n")
+        .filter(|para| !para.trim().is_empty())
+        .map(|para| {
+            let html = bold.replace_all(para, "<b>$1</b>");
+            let html = italic.replace_all(&html, "<i>$1</i>");
+            let html =
+                vars.replace_all(&html, |caps: &Captures| {
+                    format!(
+                        "<span class=\"text-var\">{}</span>",
+                        &caps[1]
+                    )
+                });
+            let html =
+                icons.replace_all(&html, |caps: &Captures| {
+                    match icon_path(&caps[1]) {
+                        Some(path) => format!(
+                            "<img class=\"text-icon\" src=\"{path}\" title=\"{}\">",
+                            &caps[1]
+                        ),
+                        None => format!(
+                            "<span class=\"text-icon\">{}</span>",
+                            &caps[1]
+                        ),
+                    }
+                });
+            format!(
+                "<p>{}</p>",
+                html.trim().replace('\n', "<br>")
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,64 @@
+use hes_engine::{Bounds, World, WorldParams};
+use leptos::*;
+
+use crate::{inputs::SliderInput, t};
+
+/// Scaffold a new world from a few high-level settings,
+/// starting from the base world's processes, projects,
+/// and events for the author to refine.
+#[component]
+pub fn Generator(
+    open: RwSignal<bool>,
+    #[prop(into)] on_generate: Callback<World>,
+) -> impl IntoView {
+    let max_regions = World::default().regions.len();
+    let params = create_rw_signal(WorldParams {
+        regions: max_regions,
+        energy_bias: 0.,
+        crisis_frequency: 1.,
+        optimism: 0.,
+    });
+
+    view! {
+        <div class="generator-overlay">
+            <div class="generator">
+                <h2>{t!("Generate a World")}</h2>
+                <SliderInput
+                    label="Regions"
+                    help="How many regions there are. Regions are merged with their neighbors to get there."
+                    bounds=Bounds::new(1., max_regions as f32, 1.)
+                    signal=create_slice(params,
+                        |params| params.regions as f32,
+                        |params, val| params.regions = val as usize) />
+                <SliderInput
+                    label="Energy Mix"
+                    help="From a mostly fossil (-1) to a mostly renewable (1) starting mix of electricity and fuel."
+                    bounds=WorldParams::ENERGY_BIAS
+                    signal=create_slice(params,
+                        |params| params.energy_bias,
+                        |params, val| params.energy_bias = val) />
+                <SliderInput
+                    label="Crisis Frequency"
+                    help="How often crises happen relative to the base world, e.g. 2 for twice as often."
+                    bounds=WorldParams::CRISIS_FREQUENCY
+                    signal=create_slice(params,
+                        |params| params.crisis_frequency,
+                        |params, val| params.crisis_frequency = val) />
+                <SliderInput
+                    label="Optimism"
+                    help="From a despairing (-1) to a hopeful (1) public, which sets the starting outlook."
+                    bounds=WorldParams::OPTIMISM
+                    signal=create_slice(params,
+                        |params| params.optimism,
+                        |params, val| params.optimism = val) />
+                <div class="generator-actions">
+                    <button on:click=move |_| open.set(false)>{t!("Cancel")}</button>
+                    <button on:click=move |_| {
+                        on_generate.call(with!(|params| World::generate(params)));
+                        open.set(false);
+                    }>{t!("Generate")}</button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,121 @@
+//! Translations of the editor's own interface,
+//! as opposed to a world's content, which is
+//! translated in the Translations tab.
+
+use leptos::window;
+use std::{cell::RefCell, collections::BTreeMap};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
+
+const DEFAULT_LANGUAGE: &str = "en";
+const LANGUAGE_ITEM: &str = "editor-language";
+pub const AVAILABLE_LANGUAGES: &[&str] = &[
+    "en", "pt", "pt-br", "pt-pt", "es", "de-de", "jp", "fr-fr",
+    "th", "tr-tr",
+];
+
+thread_local! {
+    static PHRASES: RefCell<Option<BTreeMap<String, String>>> =
+        const { RefCell::new(None) };
+}
+
+#[macro_export]
+macro_rules! t {
+    ($text:expr) => {{
+        $crate::i18n::t($text)
+    }};
+    ($text:expr, $($key:ident: $val:expr),* $(,)?) => {{
+        let mut result = $crate::i18n::t($text);
+        $(
+            let pattern = concat!("{", stringify!($key), "}");
+            result = result.replace(pattern, &$val.to_string());
+        )*
+        result
+    }};
+}
+
+pub fn t(s: &str) -> String {
+    PHRASES.with_borrow(|phrases| {
+        phrases
+            .as_ref()
+            .and_then(|phrases| phrases.get(s))
+            .filter(|t| !t.is_empty())
+            .cloned()
+            .unwrap_or_else(|| s.to_string())
+    })
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+/// First check for exact match,
+/// otherwise search for matches at start of the string.
+fn get_language_match(target: &str) -> &'static str {
+    let target = target.to_lowercase();
+    AVAILABLE_LANGUAGES
+        .iter()
+        .find(|lang| **lang == target)
+        .or_else(|| {
+            AVAILABLE_LANGUAGES
+                .iter()
+                .find(|lang| lang.starts_with(&target))
+        })
+        .unwrap_or(&DEFAULT_LANGUAGE)
+}
+
+/// The language chosen in the editor,
+/// otherwise the browser's.
+pub fn preferred_language() -> &'static str {
+    let stored = storage()
+        .and_then(|storage| storage.get_item(LANGUAGE_ITEM).ok())
+        .flatten();
+    if let Some(lang) = stored {
+        return get_language_match(&lang);
+    }
+    if let Some(lang) = window().navigator().language() {
+        return get_language_match(&lang);
+    }
+    DEFAULT_LANGUAGE
+}
+
+/// Remember the language for the next time the
+/// editor loads, which is when it takes effect.
+pub fn set_language(lang: &str) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(LANGUAGE_ITEM, lang);
+    }
+}
+
+async fn fetch_phrases(
+    lang: &str,
+) -> Option<BTreeMap<String, String>> {
+    let url = format!("/public/lang/{lang}.json");
+    let resp = JsFuture::from(window().fetch_with_str(&url))
+        .await
+        .ok()?
+        .dyn_into::<Response>()
+        .ok()?;
+    if !resp.ok() {
+        return None;
+    }
+    let text = JsFuture::from(resp.text().ok()?).await.ok()?;
+    serde_json::from_str(&text.as_string()?).ok()
+}
+
+/// Load the phrases for a language. Phrases that
+/// haven't been translated, or that can't be
+/// loaded at all, are left in English.
+pub async fn load_language(lang: &str) {
+    let phrases = if lang == DEFAULT_LANGUAGE {
+        None
+    } else {
+        let phrases = fetch_phrases(lang).await;
+        if phrases.is_none() {
+            log::warn!("No editor translations for {lang}");
+        }
+        phrases
+    };
+    PHRASES.set(phrases);
+}
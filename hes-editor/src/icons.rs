@@ -0,0 +1,12 @@
+// The game's icons, as `(slug, path)`,
+// generated from the game's icon definitions.
+include!(concat!(env!("OUT_DIR"), "/icons.rs"));
+
+/// Get an icon's path from its slug,
+/// e.g. `"political_capital"`.
+pub fn icon_path(slug: &str) -> Option<&'static str> {
+    ICONS
+        .iter()
+        .find(|(name, _)| *name == slug)
+        .map(|(_, path)| *path)
+}
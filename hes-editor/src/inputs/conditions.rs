@@ -1,9 +1,12 @@
-use crate::{enum_slice, inputs::*, subsignal};
+use crate::{enum_slice, inputs::*, subsignal, t};
 use hes_engine::{
     Condition,
     ConditionKind,
+    Flag,
     Process,
     Project,
+    TippingPoint,
+    Variable,
     WorldVariable,
     NPC,
 };
@@ -24,11 +27,27 @@ where
     let projects =
         expect_context::<Signal<Collection<Ref<Project>>>>();
     let npcs = expect_context::<Signal<Collection<Ref<NPC>>>>();
+    let variables =
+        expect_context::<Signal<Collection<Ref<Variable>>>>();
+    let tipping_points =
+        expect_context::<Signal<Collection<Ref<TippingPoint>>>>();
+    let flags_set = expect_context::<Signal<Vec<Flag>>>();
+    let never_set = move |flag: Flag| {
+        with!(|flags_set| !flags_set.contains(&flag))
+    };
 
     let input = move || {
         match read.get() {
             Condition::WorldVariable(var, comp, value) => {
                 let inner = match var {
+                    WorldVariable::Year => {
+                        view! {
+                            <TimelineInput
+                                label="Year"
+                                help="The in-game year. Other events' year conditions are marked on the timeline."
+                                signal=enum_slice!(|write| Condition::WorldVariable(var, comp, [value])) />
+                        }.into_view()
+                    }
                     WorldVariable::Temperature => {
                         view! {
                             <NumericInput
@@ -65,6 +84,42 @@ where
                                 signal=enum_slice!(|write| Condition::WorldVariable(var, comp, [value])) />
                         }.into_view()
                     }
+                    WorldVariable::Acidification => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The drop in the oceans' pH since pre-industrial times."
+                                signal=enum_slice!(|write| Condition::WorldVariable(var, comp, [value])) />
+                        }.into_view()
+                    }
+                    WorldVariable::WaterEfficiency => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The share of agricultural and industrial water demand saved by efficiency, from 0 to 1."
+                                signal=enum_slice!(|write| Condition::WorldVariable(var, comp, [value])) />
+                        }.into_view()
+                    }
+                    WorldVariable::SoilHealth => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The health of farmed soils, relative to the start, i.e. 1 is as healthy as they were at the start."
+                                signal=enum_slice!(|write| Condition::WorldVariable(var, comp, [value])) />
+                        }.into_view()
+                    }
+                    WorldVariable::Preparedness => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="How prepared the world is for disease outbreaks, from 0 to 1."
+                                signal=enum_slice!(|write| Condition::WorldVariable(var, comp, [value])) />
+                        }.into_view()
+                    }
                     WorldVariable::Emissions => {
                         view! {
                             <NumericInput
@@ -94,7 +149,7 @@ where
                     }
                 };
                 view! {
-                    <div class="input-help">"Compare against a global variable."</div>
+                    <div class="input-help">{t!("Compare against a global variable.")}</div>
                         <EnumInput
                             label="Variable"
                             help="The reference variable."
@@ -107,7 +162,7 @@ where
                     }.into_view()
             }
             Condition::LocalVariable(var, comp, value) => view! {
-                <div class="input-help">"Compare against a local (regional) variable."</div>
+                <div class="input-help">{t!("Compare against a local (regional) variable.")}</div>
                 <EnumInput
                     label="Variable"
                     help="The reference variable."
@@ -124,7 +179,7 @@ where
             }.into_view(),
 
             Condition::PlayerVariable(var, comp, value) => view! {
-                <div class="input-help">"Compare against a player variable."</div>
+                <div class="input-help">{t!("Compare against a player variable.")}</div>
                 <EnumInput
                     label="Variable"
                     help="The reference variable."
@@ -141,7 +196,7 @@ where
             }.into_view(),
 
             Condition::ProcessOutput(id, comp, value) => view! {
-                <div class="input-help">"Compare against the output of a process."</div>
+                <div class="input-help">{t!("Compare against the output of a process.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -159,7 +214,7 @@ where
             }.into_view(),
 
             Condition::ProcessMixShare(id, comp, value) => view! {
-                <div class="input-help">"Compare against the mix share (percentage) of a process."</div>
+                <div class="input-help">{t!("Compare against the mix share (percentage) of a process.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -171,13 +226,14 @@ where
                     signal=enum_slice!(|write| Condition::ProcessMixShare(id, [comp], value)) />
                 <PercentInput
                     inline=true
+                    fraction=true
                     label="Mix Share"
                     help="The mix share to compare against."
                     signal=enum_slice!(|write| Condition::ProcessMixShare(id, comp, [value])) />
             }.into_view(),
 
             Condition::ProcessMixShareFeature(feat, comp, value) => view! {
-                <div class="input-help">"Compare against the total mix share (percentage) of processes with a particular feature."</div>
+                <div class="input-help">{t!("Compare against the total mix share (percentage) of processes with a particular feature.")}</div>
                 <EnumInput
                     label="Process"
                     help="Which process feature to compare against."
@@ -188,13 +244,14 @@ where
                     signal=enum_slice!(|write| Condition::ProcessMixShareFeature(feat, [comp], value)) />
                 <PercentInput
                     inline=true
+                    fraction=true
                     label="Mix Share"
                     help="The mix share to compare against."
                     signal=enum_slice!(|write| Condition::ProcessMixShareFeature(feat, comp, [value])) />
             }.into_view(),
 
             Condition::ResourcePressure(resource, comp, value) => view! {
-                <div class="input-help">"Compare against the pressure on a particular resource. Pressure is represented as a percentage, where 0% means there is no pressure on the resource (demand for it is 0) and 100% means the demand for the resource equals its total supply."</div>
+                <div class="input-help">{t!("Compare against the pressure on a particular resource. Pressure is represented as a percentage, where 0% means there is no pressure on the resource (demand for it is 0) and 100% means the demand for the resource equals its total supply.")}</div>
                 <EnumInput
                     label="Resource"
                     help="Which resource to compare against."
@@ -211,7 +268,7 @@ where
             }.into_view(),
 
             Condition::ResourceDemandGap(resource, comp, value) => view! {
-                <div class="input-help">"Compare against the gap between the demand and the supply of a particular resource, in the resource's units."</div>
+                <div class="input-help">{t!("Compare against the gap between the demand and the supply of a particular resource, in the resource's units.")}</div>
                 <EnumInput
                     label="Resource"
                     help="Which resource to compare against."
@@ -228,7 +285,7 @@ where
             }.into_view(),
 
             Condition::OutputDemandGap(output, comp, value) => view! {
-                <div class="input-help">"Compare against the gap between the demand and the supply of a particular output, in the output's units."</div>
+                <div class="input-help">{t!("Compare against the gap between the demand and the supply of a particular output, in the output's units.")}</div>
                 <EnumInput
                     label="Output"
                     help="Which output to compare against."
@@ -245,7 +302,7 @@ where
             }.into_view(),
 
             Condition::Demand(output, comp, value) => view! {
-                <div class="input-help">"Compare against the demand for a particular output, in the output's units."</div>
+                <div class="input-help">{t!("Compare against the demand for a particular output, in the output's units.")}</div>
                 <EnumInput
                     label="Output"
                     help="Which output to compare against."
@@ -262,7 +319,7 @@ where
             }.into_view(),
 
             Condition::ProjectStatus(id, status) => view! {
-                <div class="input-help">"Check if the status of a particular project matches the specified value."</div>
+                <div class="input-help">{t!("Check if the status of a particular project matches the specified value.")}</div>
                 <EntityPicker
                     label="Project"
                     opts=projects
@@ -275,7 +332,7 @@ where
             }.into_view(),
 
             Condition::ActiveProjectUpgrades(id, comp, count) => view! {
-                <div class="input-help">"Compare against the number of active upgrades of a particular project."</div>
+                <div class="input-help">{t!("Compare against the number of active upgrades of a particular project.")}</div>
                 <EntityPicker
                     label="Project"
                     opts=projects
@@ -293,7 +350,7 @@ where
             }.into_view(),
 
             Condition::RunsPlayed(comp, count) => view! {
-                <div class="input-help">"Compare against the number of times the player has played the game. Note that the number of runs played is tracked locally within a browser, so it will not carry across browsers and if the browsing data (local storage) is cleared, the value will reset."</div>
+                <div class="input-help">{t!("Compare against the number of times the player has played the game. Note that the number of runs played is tracked locally within a browser, so it will not carry across browsers and if the browsing data (local storage) is cleared, the value will reset.")}</div>
                 <EnumInput
                     label="Comparator"
                     help="The comparison operation."
@@ -306,7 +363,7 @@ where
             }.into_view(),
 
             Condition::NPCRelationship(id, relation) => view! {
-                <div class="input-help">"Check if the relationship status with a particular NPC matches the specified value."</div>
+                <div class="input-help">{t!("Check if the relationship status with a particular NPC matches the specified value.")}</div>
                 <EntityPicker
                     label="NPC"
                     opts=npcs
@@ -319,7 +376,7 @@ where
             }.into_view(),
 
             Condition::FeedstockYears(feedstock, comp, value) => view! {
-                <div class="input-help">"Compare against the estimated number of years before a particular feedstock is depleted."</div>
+                <div class="input-help">{t!("Compare against the estimated number of years before a particular feedstock is depleted.")}</div>
                 <EnumInput
                     label="Feedstock"
                     help="Which feedstock to compare against."
@@ -336,7 +393,7 @@ where
             }.into_view(),
 
             Condition::RegionFlag(flag) => view! {
-                <div class="input-help">"Check if a matching region flag exists on a region."</div>
+                <div class="input-help">{t!("Check if a matching region flag exists on a region.")}</div>
                 <EnumInput
                     label="Flag"
                     help="Which flag to compare against."
@@ -344,19 +401,25 @@ where
             }.into_view(),
 
             Condition::HasFlag(flag) => view! {
-                <div class="input-help">"Check if a matching flag exists."</div>
-                <EnumInput
+                <div class="input-help">{t!("Check if a matching flag exists.")}</div>
+                <SearchableEnumInput
                     label="Flag"
                     help="Which flag to compare against."
                     signal=enum_slice!(|write| Condition::HasFlag([flag])) />
+                <Show when=move || never_set(flag)>
+                    <div class="input-error">{t!("No effect in this world sets this flag, so this may never be true.")}</div>
+                </Show>
             }.into_view(),
 
             Condition::WithoutFlag(flag) => view! {
-                <div class="input-help">"Check if a matching flag doesn't exist."</div>
-                <EnumInput
+                <div class="input-help">{t!("Check if a matching flag doesn't exist.")}</div>
+                <SearchableEnumInput
                     label="Flag"
                     help="Which flag to compare against."
                     signal=enum_slice!(|write| Condition::WithoutFlag([flag])) />
+                <Show when=move || never_set(flag)>
+                    <div class="input-error">{t!("No effect in this world sets this flag, so this may always be true.")}</div>
+                </Show>
             }.into_view(),
 
             Condition::HeavyProjects(comp, count) => view! {
@@ -373,30 +436,78 @@ where
             }.into_view(),
 
             Condition::ProtectLand(comp, value) => view! {
-                <div class="input-help">"Compare against the percentage of land under protection."</div>
+                <div class="input-help">{t!("Compare against the percentage of land under protection.")}</div>
                 <EnumInput
                     label="Comparator"
                     help="The comparison operation."
                     signal=enum_slice!(|write| Condition::ProtectLand([comp], value)) />
                 <PercentInput
                     inline=true
+                    fraction=true
                     label="Land Under Protection"
                     help="The value to compare against."
                     signal=enum_slice!(|write| Condition::ProtectLand(comp, [value])) />
             }.into_view(),
 
             Condition::WaterStress(comp, value) => view! {
-                <div class="input-help">"Compare against the percentage of water demanded over water availabile."</div>
+                <div class="input-help">{t!("Compare against the percentage of water demanded over water availabile.")}</div>
                 <EnumInput
                     label="Comparator"
                     help="The comparison operation."
                     signal=enum_slice!(|write| Condition::WaterStress([comp], value)) />
                 <PercentInput
                     inline=true
+                    fraction=true
                     label="Percent of available water in use."
                     help="The value to compare against."
                     signal=enum_slice!(|write| Condition::WaterStress(comp, [value])) />
             }.into_view(),
+
+            Condition::Variable(id, comp, value) => view! {
+                <div class="input-help">{t!("Compare against one of the world's variables. Flags are 1 when set and 0 otherwise.")}</div>
+                <EntityPicker
+                    label="Variable"
+                    opts=variables
+                    help="Which variable to compare against."
+                    signal=enum_slice!(|write| Condition::Variable([id], comp, value)) />
+                <EnumInput
+                    label="Comparator"
+                    help="The comparison operation."
+                    signal=enum_slice!(|write| Condition::Variable(id, [comp], value)) />
+                <NumericInput
+                    inline=true
+                    label="Value"
+                    help="The value to compare against."
+                    signal=enum_slice!(|write| Condition::Variable(id, comp, [value])) />
+            }.into_view(),
+
+            Condition::TippingPointCrossed(id) => view! {
+                <div class="input-help">{t!("If a climate tipping point has been crossed.")}</div>
+                <EntityPicker
+                    label="Tipping Point"
+                    opts=tipping_points
+                    help="Which tipping point."
+                    signal=enum_slice!(|write| Condition::TippingPointCrossed([id])) />
+            }.into_view(),
+
+            Condition::TippingPointNotCrossed(id) => view! {
+                <div class="input-help">{t!("If a climate tipping point hasn't been crossed yet.")}</div>
+                <EntityPicker
+                    label="Tipping Point"
+                    opts=tipping_points
+                    help="Which tipping point."
+                    signal=enum_slice!(|write| Condition::TippingPointNotCrossed([id])) />
+            }.into_view(),
+
+            Condition::Expr(src) => view! {
+                <div class="input-help">{t!("A condition written as an expression, for when the other conditions aren't enough.")}</div>
+                <TextInput
+                    label="Expression"
+                    help="E.g. `temperature > 2 && project(\"Solar Radiation Management\").active`."
+                    lints=Lints::CONDITION_EXPR
+                    commit=Commit::Debounce
+                    signal=enum_slice!(|write| Condition::Expr([src])) />
+            }.into_view(),
         }
     };
     let label = move || {
@@ -407,8 +518,8 @@ where
     view! {
         <div class="condition mutable-list-item">
             <div class="mutable-list-item-header">
-                <label>{label}</label>
-                <div class="mutable-list-item-remove" title="Ctrl-click to remove without confirmation." on:click=on_remove>"✗"</div>
+                <label>{t!(&label)}</label>
+                <div class="mutable-list-item-remove" title=t!("Ctrl-click to remove without confirmation.") on:click=on_remove>"✗"</div>
             </div>
             {input}
         </div>
@@ -432,19 +543,36 @@ pub fn Conditions(
     let projects =
         expect_context::<Signal<Collection<Ref<Project>>>>();
     let npcs = expect_context::<Signal<Collection<Ref<NPC>>>>();
+    let variables =
+        expect_context::<Signal<Collection<Ref<Variable>>>>();
 
     let default_process =
         move || with!(|processes| processes.first().id);
     let default_project =
         move || with!(|projects| projects.first().id);
     let default_npc = move || with!(|npcs| npcs.first().id);
+    // Worlds don't have to have any variables.
+    let default_variable = move || {
+        with!(|variables| variables
+            .iter()
+            .next()
+            .map_or(Id::nil(), |var| var.id))
+    };
+    let tipping_points =
+        expect_context::<Signal<Collection<Ref<TippingPoint>>>>();
+    let default_tipping_point = move || {
+        with!(|tipping_points| tipping_points
+            .iter()
+            .next()
+            .map_or(Id::nil(), |point| point.id))
+    };
 
     view! {
         <div class="conditions mutable-list">
             <div class="mutable-list-header">
-                <h2>Conditions</h2>
+                <h2>{t!("Conditions")}</h2>
                 <div class="mutable-list-add">
-                    <EnumInput
+                    <SearchableEnumInput
                         label="Condition Kind"
                         help="What kind of condition to create."
                         signal=(new_kind.into(), set_new_kind.into()) />
@@ -454,6 +582,8 @@ pub fn Conditions(
                             default_process(),
                             default_project(),
                             default_npc(),
+                            default_variable(),
+                            default_tipping_point(),
                             );
                         let mut conditions = read.get();
                         conditions.insert(0, condition);
@@ -465,7 +595,7 @@ pub fn Conditions(
                  let empty = with!(|read| read.is_empty());
                  if empty {
                      Some(view! {
-                         <div class="empty">No conditions defined.</div>
+                         <div class="empty">{t!("No conditions defined.")}</div>
                      })
                  } else {
                      None
@@ -1,4 +1,4 @@
-use crate::{enum_slice, inputs::*, subsignal};
+use crate::{enum_slice, inputs::*, subsignal, t};
 use hes_engine::{
     Effect,
     EffectKind,
@@ -6,6 +6,7 @@ use hes_engine::{
     Industry,
     Process,
     Project,
+    Variable,
     WorldVariable,
     NPC,
 };
@@ -30,6 +31,8 @@ where
     let industries =
         expect_context::<Signal<Collection<Ref<Industry>>>>();
     let npcs = expect_context::<Signal<Collection<Ref<NPC>>>>();
+    let variables =
+        expect_context::<Signal<Collection<Ref<Variable>>>>();
 
     let input = move || {
         match read.get() {
@@ -71,6 +74,42 @@ where
                                 signal=enum_slice!(|write| Effect::WorldVariable(var, [value])) />
                         }.into_view()
                     }
+                    WorldVariable::Acidification => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The amount to change ocean acidification by, in pH. Negative values reduce acidification."
+                                signal=enum_slice!(|write| Effect::WorldVariable(var, [value])) />
+                        }.into_view()
+                    }
+                    WorldVariable::WaterEfficiency => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The amount to change water efficiency by, as a share of agricultural and industrial water demand, e.g. 0.1 to save 10%."
+                                signal=enum_slice!(|write| Effect::WorldVariable(var, [value])) />
+                        }.into_view()
+                    }
+                    WorldVariable::SoilHealth => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The amount to change soil health by, relative to the start, e.g. 0.1 for 10% healthier soil. Crop yields change with it."
+                                signal=enum_slice!(|write| Effect::WorldVariable(var, [value])) />
+                        }.into_view()
+                    }
+                    WorldVariable::Preparedness => {
+                        view! {
+                            <NumericInput
+                                inline=true
+                                label="Value"
+                                help="The amount to change disease preparedness by, from 0 to 1, e.g. 0.1. Preparedness makes outbreaks rarer, milder and shorter."
+                                signal=enum_slice!(|write| Effect::WorldVariable(var, [value])) />
+                        }.into_view()
+                    }
                     WorldVariable::Emissions => {
                         view! {
                             <NumericInput
@@ -121,7 +160,7 @@ where
             }.into_view(),
 
             Effect::RegionHabitability(lat, value) => view! {
-                <div class="input-help">"Modify the habitability of all regions at the given latitude."</div>
+                <div class="input-help">{t!("Modify the habitability of all regions at the given latitude.")}</div>
                 <EnumInput
                     label="Latitude"
                     help="What latitude is affected."
@@ -134,7 +173,7 @@ where
             }.into_view(),
 
             Effect::Resource(resource, value) => view! {
-                <div class="input-help">"Modify the availability of the specified resource by an absolute amount. Note that this won't do anything for fuel and electricity as those are dynamically calculated."</div>
+                <div class="input-help">{t!("Modify the availability of the specified resource by an absolute amount. Note that this won't do anything for fuel and electricity as those are dynamically calculated.")}</div>
                 <EnumInput
                     label="Resource"
                     help="What resource is affected."
@@ -147,7 +186,7 @@ where
             }.into_view(),
 
             Effect::Demand(output, value) => view! {
-                <div class="input-help">"Modify all demand for the specified output by a percentage."</div>
+                <div class="input-help">{t!("Modify all demand for the specified output by a percentage.")}</div>
                 <EnumInput
                     label="Output"
                     help="What output is affected."
@@ -160,7 +199,7 @@ where
             }.into_view(),
 
             Effect::DemandAmount(output, value) => view! {
-                <div class="input-help">"Modify all demand for the specified output by an absolute amount."</div>
+                <div class="input-help">{t!("Modify all demand for the specified output by an absolute amount.")}</div>
                 <EnumInput
                     label="Output"
                     help="What output is affected."
@@ -173,7 +212,7 @@ where
             }.into_view(),
 
             Effect::Output(output, value) => view! {
-                <div class="input-help">"Modify all production for the specified output by a percentage."</div>
+                <div class="input-help">{t!("Modify all production for the specified output by a percentage.")}</div>
                 <EnumInput
                     label="Output"
                     help="What output is affected."
@@ -186,7 +225,7 @@ where
             }.into_view(),
 
             Effect::OutputForFeature(feat, value) => view! {
-                <div class="input-help">"Modify the production efficiency of processes with the specified feature by a percentage. For example, a value of 10% means 10% more output is produced for the same resources/byproduct as the baseline."</div>
+                <div class="input-help">{t!("Modify the production efficiency of processes with the specified feature by a percentage. For example, a value of 10% means 10% more output is produced for the same resources/byproduct as the baseline.")}</div>
                 <EnumInput
                     label="Feature"
                     help="What process feature is affected."
@@ -199,7 +238,7 @@ where
             }.into_view(),
 
             Effect::OutputForProcess(id, value) => view! {
-                <div class="input-help">"Modify the production efficiency of a single process by a percentage. For example, a value of 10% means 10% more output is produced for the same resources/byproduct as the baseline."</div>
+                <div class="input-help">{t!("Modify the production efficiency of a single process by a percentage. For example, a value of 10% means 10% more output is produced for the same resources/byproduct as the baseline.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -213,7 +252,7 @@ where
             }.into_view(),
 
             Effect::CO2ForFeature(feat, value) => view! {
-                <div class="input-help">"Modify CO2 emitted for processes with the specified feature by a percentage."</div>
+                <div class="input-help">{t!("Modify CO2 emitted for processes with the specified feature by a percentage.")}</div>
                 <EnumInput
                     label="Feature"
                     help="What process feature is affected."
@@ -226,7 +265,7 @@ where
             }.into_view(),
 
             Effect::BiodiversityPressureForFeature(feat, value) => view! {
-                <div class="input-help">"Modify biodiversity pressure for processes with the specified feature by a percentage."</div>
+                <div class="input-help">{t!("Modify biodiversity pressure for processes with the specified feature by a percentage.")}</div>
                 <EnumInput
                     label="Feature"
                     help="What process feature is affected."
@@ -239,7 +278,7 @@ where
             }.into_view(),
 
             Effect::ProcessLimit(id, value) => view! {
-                <div class="input-help">"Modify the limit of the specified process by an absolute amount. If no process limit is defined for the process this will do nothing."</div>
+                <div class="input-help">{t!("Modify the limit of the specified process by an absolute amount. If no process limit is defined for the process this will do nothing.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -253,7 +292,7 @@ where
             }.into_view(),
 
             Effect::Feedstock(feedstock, value) => view! {
-                <div class="input-help">"Modify the specified feedstock's reserves by a percentage."</div>
+                <div class="input-help">{t!("Modify the specified feedstock's reserves by a percentage.")}</div>
                 <EnumInput
                     label="Feedstock"
                     help="What feedstock is affected."
@@ -266,7 +305,7 @@ where
             }.into_view(),
 
             Effect::AddEvent(id) => view! {
-                <div class="input-help">"Add an event to the event pool (i.e. unlock it). Note: This effect is always hidden (not displayed to the user)."</div>
+                <div class="input-help">{t!("Add an event to the event pool (i.e. unlock it). Note: This effect is always hidden (not displayed to the user).")}</div>
                 <EntityPicker
                     label="Event"
                     opts=events
@@ -275,7 +314,7 @@ where
             }.into_view(),
 
             Effect::TriggerEvent(id, years) => view! {
-                <div class="input-help">"Trigger an event after a specified number of years. Note: This effect is always hidden (not displayed to the user)."</div>
+                <div class="input-help">{t!("Trigger an event after a specified number of years. Note: This effect is always hidden (not displayed to the user).")}</div>
                 <EntityPicker
                     label="Event"
                     opts=events
@@ -289,7 +328,7 @@ where
             }.into_view(),
 
             Effect::LocksProject(id) => view! {
-                <div class="input-help">"Locks a project (it will no longer be available)."</div>
+                <div class="input-help">{t!("Locks a project (it will no longer be available).")}</div>
                 <EntityPicker
                     label="Project"
                     opts=projects
@@ -298,7 +337,7 @@ where
             }.into_view(),
 
             Effect::UnlocksProject(id) => view! {
-                <div class="input-help">"Unlocks a project."</div>
+                <div class="input-help">{t!("Unlocks a project.")}</div>
                 <EntityPicker
                     label="Project"
                     opts=projects
@@ -307,7 +346,7 @@ where
             }.into_view(),
 
             Effect::UnlocksProcess(id) => view! {
-                <div class="input-help">"Unlocks a process."</div>
+                <div class="input-help">{t!("Unlocks a process.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -316,7 +355,7 @@ where
             }.into_view(),
 
             Effect::UnlocksNPC(id) => view! {
-                <div class="input-help">"Unlocks an NPC."</div>
+                <div class="input-help">{t!("Unlocks an NPC.")}</div>
                 <EntityPicker
                     label="NPC"
                     opts=npcs
@@ -325,7 +364,7 @@ where
             }.into_view(),
 
             Effect::ProjectRequest(id, active, bounty) => view! {
-                <div class="input-help">"Starts a request for a project."</div>
+                <div class="input-help">{t!("Starts a request for a project.")}</div>
                 <EntityPicker
                     label="Project"
                     opts=projects
@@ -343,7 +382,7 @@ where
             }.into_view(),
 
             Effect::ProcessRequest(id, active, bounty) => view! {
-                <div class="input-help">"Starts a request for a process."</div>
+                <div class="input-help">{t!("Starts a request for a process.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -361,15 +400,15 @@ where
             }.into_view(),
 
             Effect::Migration => view! {
-                <div class="input-help">"Triggers a wave of migration across regions."</div>
+                <div class="input-help">{t!("Triggers a wave of migration across regions.")}</div>
             }.into_view(),
 
             Effect::RegionLeave => view! {
-                <div class="input-help">"Triggers a wave of migration across regions."</div>
+                <div class="input-help">{t!("Triggers a wave of migration across regions.")}</div>
             }.into_view(),
 
             Effect::AddRegionFlag(flag) => view! {
-                <div class="input-help">"Add a flag to a region."</div>
+                <div class="input-help">{t!("Add a flag to a region.")}</div>
                 <EnumInput
                     label="Flag"
                     help="Which flag to add."
@@ -377,15 +416,15 @@ where
             }.into_view(),
 
             Effect::AddFlag(flag) => view! {
-                <div class="input-help">"Set a flag."</div>
-                <EnumInput
+                <div class="input-help">{t!("Set a flag.")}</div>
+                <SearchableEnumInput
                     label="Flag"
                     help="Which flag to add."
                     signal=enum_slice!(|write| Effect::AddFlag([flag])) />
             }.into_view(),
 
             Effect::NPCRelationship(id, change) => view! {
-                <div class="input-help">"Change the relationship with an NPC."</div>
+                <div class="input-help">{t!("Change the relationship with an NPC.")}</div>
                 <EntityPicker
                     label="NPC"
                     opts=npcs
@@ -399,7 +438,7 @@ where
             }.into_view(),
 
             Effect::ModifyProcessByproducts(id, byproduct, value) => view! {
-                <div class="input-help">"Modify the amount of a single byproduct for a single process by a percentage."</div>
+                <div class="input-help">{t!("Modify the amount of a single byproduct for a single process by a percentage.")}</div>
                 <EntityPicker
                     label="Process"
                     opts=processes
@@ -417,7 +456,7 @@ where
             }.into_view(),
 
             Effect::ModifyIndustryByproducts(id, byproduct, value) => view! {
-                <div class="input-help">"Modify the amount of a single byproduct for a single industry by a percentage. Note that the byproducts for many industries aren't inherent to the industry but are rather because of emissions from its energy use. This modifier does *not* affect energy-use emissions, only direct emissions from the industry."</div>
+                <div class="input-help">{t!("Modify the amount of a single byproduct for a single industry by a percentage. Note that the byproducts for many industries aren't inherent to the industry but are rather because of emissions from its energy use. This modifier does *not* affect energy-use emissions, only direct emissions from the industry.")}</div>
                 <EntityPicker
                     label="Industry"
                     opts=industries
@@ -435,7 +474,7 @@ where
             }.into_view(),
 
             Effect::ModifyIndustryResources(id, resource, value) => view! {
-                <div class="input-help">"Modify the amount of a single resource used by a single industry by a percentage."</div>
+                <div class="input-help">{t!("Modify the amount of a single resource used by a single industry by a percentage.")}</div>
                 <EntityPicker
                     label="Industry"
                     opts=industries
@@ -453,7 +492,7 @@ where
             }.into_view(),
 
             Effect::ModifyIndustryResourcesAmount(id, resource, value) => view! {
-                <div class="input-help">"Modify the amount of a single resource used by a single industry by an absolute amount."</div>
+                <div class="input-help">{t!("Modify the amount of a single resource used by a single industry by an absolute amount.")}</div>
                 <EntityPicker
                     label="Industry"
                     opts=industries
@@ -471,7 +510,7 @@ where
             }.into_view(),
 
             Effect::ModifyIndustryDemand(id, value) => view! {
-                <div class="input-help">"Modify the demand for a single industry by a percentage."</div>
+                <div class="input-help">{t!("Modify the demand for a single industry by a percentage.")}</div>
                 <EntityPicker
                     label="Industry"
                     opts=industries
@@ -485,7 +524,7 @@ where
             }.into_view(),
 
             Effect::ModifyEventProbability(id, value) => view! {
-                <div class="input-help">"Modify the probability of an event occurring."</div>
+                <div class="input-help">{t!("Modify the probability of an event occurring.")}</div>
                 <EntityPicker
                     label="Event"
                     opts=events
@@ -499,7 +538,7 @@ where
             }.into_view(),
 
             Effect::DemandOutlookChange(output, mult) => view! {
-                <div class="input-help">"Apply a change in contentedness to every region based on its level of demand for the specified output, multiplied by the specified factor. Demand level ranges from [1, 5], where 1 is the lowest demand level and 5 is the highest. For example, with `Output::Fuel` and a factor of 0.5 and a region with demand level 2, that means `2 * 0.5 = 1` will be added to that region's contentedness. Note that this value is rounded, so if it were `3 * 0.5 = 1.5` this would be rounded to `2.0`."</div>
+                <div class="input-help">{t!("Apply a change in contentedness to every region based on its level of demand for the specified output, multiplied by the specified factor. Demand level ranges from [1, 5], where 1 is the lowest demand level and 5 is the highest. For example, with `Output::Fuel` and a factor of 0.5 and a region with demand level 2, that means `2 * 0.5 = 1` will be added to that region's contentedness. Note that this value is rounded, so if it were `3 * 0.5 = 1.5` this would be rounded to `2.0`.")}</div>
                 <EnumInput
                     label="Output"
                     help="What output is affected."
@@ -512,7 +551,7 @@ where
             }.into_view(),
 
             Effect::IncomeOutlookChange(mult) => view! {
-                <div class="input-help">"Apply a change in contentedness to every region based on its income level, multiplied by the specified factor. Income level ranges from [0, 3], where 0 is the lowest income level and 3 is the highest. For example, with a factor of 0.5 and a region with income level 2, that means `2 * 0.5 = 1` will be added to that region's contentedness. Note that this value is rounded, so if it were `3 * 0.5 = 1.5` this would be rounded to `2.0`."</div>
+                <div class="input-help">{t!("Apply a change in contentedness to every region based on its income level, multiplied by the specified factor. Income level ranges from [0, 3], where 0 is the lowest income level and 3 is the highest. For example, with a factor of 0.5 and a region with income level 2, that means `2 * 0.5 = 1` will be added to that region's contentedness. Note that this value is rounded, so if it were `3 * 0.5 = 1.5` this would be rounded to `2.0`.")}</div>
                 <NumericInput
                     inline=true
                     label="Factor"
@@ -521,7 +560,7 @@ where
             }.into_view(),
 
             Effect::ProjectCostModifier(id, change) => view! {
-                <div class="input-help">"Modifies the cost a project by a percentage."</div>
+                <div class="input-help">{t!("Modifies the cost a project by a percentage.")}</div>
                 <EntityPicker
                     label="Project"
                     opts=projects
@@ -535,7 +574,7 @@ where
             }.into_view(),
 
             Effect::ProtectLand(amount) => view! {
-                <div class="input-help">"Change the amount of land under protection by a percentage."</div>
+                <div class="input-help">{t!("Change the amount of land under protection by a percentage.")}</div>
                 <PercentInput
                     inline=true
                     label="Percent Change"
@@ -543,8 +582,45 @@ where
                     signal=enum_slice!(|write| Effect::ProtectLand([amount])) />
             }.into_view(),
 
+            Effect::Afforest(amount) => view! {
+                <div class="input-help">{t!("Plant forest on a percentage of every region's unused land.")}</div>
+                <PercentInput
+                    inline=true
+                    label="Percent"
+                    help="The percent of each region's land to plant forest on. It's limited by how much land is unused."
+                    signal=enum_slice!(|write| Effect::Afforest([amount])) />
+            }.into_view(),
+
+            Effect::SetVariable(id, value) => view! {
+                <div class="input-help">{t!("Set one of the world's variables to a value. Flags are set by any non-zero value.")}</div>
+                <EntityPicker
+                    label="Variable"
+                    opts=variables
+                    help="Which variable to set."
+                    signal=enum_slice!(|write| Effect::SetVariable([id], value)) />
+                <NumericInput
+                    inline=true
+                    label="Value"
+                    help="The value to set the variable to."
+                    signal=enum_slice!(|write| Effect::SetVariable(id, [value])) />
+            }.into_view(),
+
+            Effect::ChangeVariable(id, change) => view! {
+                <div class="input-help">{t!("Change one of the world's variables by an amount.")}</div>
+                <EntityPicker
+                    label="Variable"
+                    opts=variables
+                    help="Which variable to change."
+                    signal=enum_slice!(|write| Effect::ChangeVariable([id], change)) />
+                <NumericInput
+                    inline=true
+                    label="Change"
+                    help="The amount to change the variable by."
+                    signal=enum_slice!(|write| Effect::ChangeVariable(id, [change])) />
+            }.into_view(),
+
             Effect::BailOut(amount) => view! {
-                <div class="input-help">"Bail the player out by providing some political capital."</div>
+                <div class="input-help">{t!("Bail the player out by providing some political capital.")}</div>
                 <NumericInput
                     inline=true
                     label="Amount"
@@ -557,7 +633,27 @@ where
             }.into_view(),
 
             Effect::GameOver => view! {
-                <div class="input-help">"Trigger an immediate game over."</div>
+                <div class="input-help">{t!("Trigger an immediate game over.")}</div>
+            }.into_view(),
+
+            Effect::Script(script) => view! {
+                <div class="input-help">{t!("Run a script that decides which effects to apply, e.g. to scale an effect by the temperature.")}</div>
+                <TextArea
+                    label="Script"
+                    help="A Rhai script that adds effects to `effects`, e.g. `effects.push(#{ WorldVariable: [\"Outlook\", temperature * -2.] });`"
+                    lints=Lints::SCRIPT
+                    commit=Commit::Debounce
+                    signal=enum_slice!(|write| Effect::Script([script])) />
+            }.into_view(),
+
+            Effect::Expr(src) => view! {
+                <div class="input-help">{t!("Change values with expressions, one per line, e.g. to scale an effect by the temperature.")}</div>
+                <TextArea
+                    label="Expressions"
+                    help="Lines like `outlook -= temperature * 2`, `political_capital += 10`, `var(PublicTrust) = 0` or `flag(Vegan) = true`."
+                    lints=Lints::EFFECTS_EXPR
+                    commit=Commit::Debounce
+                    signal=enum_slice!(|write| Effect::Expr([src])) />
             }.into_view(),
         }
     };
@@ -569,8 +665,8 @@ where
     view! {
         <div class="effect mutable-list-item">
             <div class="mutable-list-item-header">
-                <label>{label}</label>
-                <div class="mutable-list-item-remove" title="Ctrl-click to remove without confirmation." on:click=on_remove>"✗"</div>
+                <label>{t!(&label)}</label>
+                <div class="mutable-list-item-remove" title=t!("Ctrl-click to remove without confirmation.") on:click=on_remove>"✗"</div>
             </div>
             {input}
         </div>
@@ -595,6 +691,8 @@ pub fn Effects(
     let industries =
         expect_context::<Signal<Collection<Ref<Industry>>>>();
     let npcs = expect_context::<Signal<Collection<Ref<NPC>>>>();
+    let variables =
+        expect_context::<Signal<Collection<Ref<Variable>>>>();
 
     let default_process =
         move || with!(|processes| processes.first().id);
@@ -605,13 +703,20 @@ pub fn Effects(
     let default_event =
         move || with!(|events| events.first().id);
     let default_npc = move || with!(|npcs| npcs.first().id);
+    // Worlds don't have to have any variables.
+    let default_variable = move || {
+        with!(|variables| variables
+            .iter()
+            .next()
+            .map_or(Id::nil(), |var| var.id))
+    };
 
     view! {
         <div class="effects mutable-list" class:mutable-list-double-col={double_col}>
             <div class="mutable-list-header">
-                <h2>Effects</h2>
+                <h2>{t!("Effects")}</h2>
                 <div class="mutable-list-add">
-                    <EnumInput
+                    <SearchableEnumInput
                         label="Effect Kind"
                         help="What kind of effect to create."
                         signal=(new_kind.into(), set_new_kind.into()) />
@@ -623,6 +728,7 @@ pub fn Effects(
                             default_industry(),
                             default_event(),
                             default_npc(),
+                            default_variable(),
                             );
                         let mut effects = read.get();
                         effects.insert(0, effect);
@@ -634,7 +740,7 @@ pub fn Effects(
                  let no_effects = with!(|read| read.is_empty());
                  if no_effects {
                      Some(view! {
-                         <div class="empty">No effects defined.</div>
+                         <div class="empty">{t!("No effects defined.")}</div>
                      })
                  } else {
                      None
@@ -0,0 +1,133 @@
+use super::{NumericInput, TextInput, ToggleInput};
+use crate::t;
+use hes_engine::form::{EditorForm, FieldValue};
+use leptos::*;
+
+/// A form with an input for each of the type's
+/// fields, as generated by `#[derive(EditorForm)]`.
+#[component]
+pub fn Form<T: EditorForm + Clone + 'static>(
+    signal: (Signal<T>, SignalSetter<T>),
+) -> impl IntoView {
+    let (read, write) = signal;
+
+    T::fields()
+        .into_iter()
+        .map(move |field| {
+            let name = field.name;
+            let value =
+                move || with!(|read| read.get_field(name));
+            let set = move |value: FieldValue| {
+                let mut data = read.get();
+                if data.set_field(name, value) {
+                    write.set(data);
+                }
+            };
+
+            match read.with_untracked(|read| read.get_field(name)) {
+                Some(FieldValue::Number(_)) => view! {
+                    <NumericInput
+                        label=field.label
+                        help=field.help
+                        bounds=field.bounds
+                        signal=(
+                            Signal::derive(move || match value() {
+                                Some(FieldValue::Number(n)) => n,
+                                _ => 0.,
+                            }),
+                            SignalSetter::map(move |n| set(FieldValue::Number(n))),
+                        ) />
+                }
+                .into_view(),
+                Some(FieldValue::Text(_)) => view! {
+                    <TextInput
+                        label=field.label
+                        help=field.help
+                        signal=(
+                            Signal::derive(move || match value() {
+                                Some(FieldValue::Text(text)) => text,
+                                _ => String::new(),
+                            }),
+                            SignalSetter::map(move |text| set(FieldValue::Text(text))),
+                        ) />
+                }
+                .into_view(),
+                Some(FieldValue::Toggle(_)) => view! {
+                    <ToggleInput
+                        label=field.label
+                        help=field.help
+                        signal=(
+                            Signal::derive(move || {
+                                matches!(value(), Some(FieldValue::Toggle(true)))
+                            }),
+                            SignalSetter::map(move |on| set(FieldValue::Toggle(on))),
+                        ) />
+                }
+                .into_view(),
+                Some(FieldValue::Choice { options, .. }) => view! {
+                    <ChoiceInput
+                        label=field.label
+                        help=field.help
+                        options
+                        signal=(
+                            Signal::derive(move || match value() {
+                                Some(FieldValue::Choice { value, .. }) => value,
+                                _ => "",
+                            }),
+                            SignalSetter::map(move |choice| {
+                                set(FieldValue::Choice {
+                                    value: choice,
+                                    options: vec![],
+                                })
+                            }),
+                        ) />
+                }
+                .into_view(),
+                None => ().into_view(),
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Like `EnumInput` but for a variant's name,
+/// as the enum type isn't known to the form.
+#[component]
+fn ChoiceInput(
+    signal: (Signal<&'static str>, SignalSetter<&'static str>),
+    options: Vec<&'static str>,
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = super::read_only();
+    let opts = store_value(options);
+
+    view! {
+        <div class="input-group enum-select">
+            <div class="enum-select-inner">
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value">{read}</div>
+                    }>
+                    <select
+                        prop:value=read
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            let choice = opts.with_value(|opts| {
+                                opts.iter().find(|opt| **opt == value).copied()
+                            });
+                            if let Some(choice) = choice {
+                                write.set(choice);
+                            }
+                        }>
+                        {move || opts.get_value().into_iter().map(|opt| view! {
+                            <option value=opt>{opt}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </Show>
+            </div>
+            <div class="input-help">{t!(&help)}</div>
+        </div>
+    }
+}
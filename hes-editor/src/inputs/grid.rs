@@ -0,0 +1,227 @@
+//! Spreadsheet-style keyboard navigation for tables of
+//! inputs, for entering lots of numbers quickly, e.g.
+//! when transcribing them from research papers.
+//!
+//! Cells are navigated with the arrow keys and Tab.
+//! Enter, F2, or typing starts editing a cell, Enter
+//! or Tab finishes, and Escape cancels the edit.
+
+use leptos::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Element,
+    Event,
+    EventInit,
+    HtmlElement,
+    HtmlInputElement,
+    HtmlSelectElement,
+    HtmlTextAreaElement,
+    KeyboardEvent,
+};
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn is_editor(el: &Element) -> bool {
+    matches!(
+        el.tag_name().as_str(),
+        "INPUT" | "SELECT" | "TEXTAREA"
+    )
+}
+
+fn cell_of(el: &Element) -> Option<Element> {
+    el.closest("td").ok().flatten()
+}
+
+fn editor_in(cell: &Element) -> Option<Element> {
+    cell.query_selector("input, select, textarea")
+        .ok()
+        .flatten()
+}
+
+fn focus(el: &Element) {
+    if let Some(el) = el.dyn_ref::<HtmlElement>() {
+        let _ = el.focus();
+    }
+}
+
+fn value_of(el: &Element) -> Option<String> {
+    if let Some(input) = el.dyn_ref::<HtmlInputElement>() {
+        Some(input.value())
+    } else if let Some(select) =
+        el.dyn_ref::<HtmlSelectElement>()
+    {
+        Some(select.value())
+    } else {
+        el.dyn_ref::<HtmlTextAreaElement>()
+            .map(HtmlTextAreaElement::value)
+    }
+}
+
+/// Set the editor's value, notifying its input
+/// component as if it had been typed.
+fn set_value(el: &Element, value: &str) {
+    if let Some(input) = el.dyn_ref::<HtmlInputElement>() {
+        input.set_value(value);
+    } else if let Some(select) =
+        el.dyn_ref::<HtmlSelectElement>()
+    {
+        select.set_value(value);
+    } else if let Some(area) =
+        el.dyn_ref::<HtmlTextAreaElement>()
+    {
+        area.set_value(value);
+    }
+    for name in ["input", "change"] {
+        let ev = Event::new_with_event_init_dict(
+            name,
+            EventInit::new().bubbles(true),
+        );
+        if let Ok(ev) = ev {
+            let _ = el.dispatch_event(&ev);
+        }
+    }
+}
+
+fn column_index(cell: &Element) -> u32 {
+    let mut idx = 0;
+    let mut prev = cell.previous_element_sibling();
+    while let Some(el) = prev {
+        idx += 1;
+        prev = el.previous_element_sibling();
+    }
+    idx
+}
+
+fn neighbor(cell: &Element, dir: Direction) -> Option<Element> {
+    match dir {
+        Direction::Left => cell.previous_element_sibling(),
+        Direction::Right => cell.next_element_sibling(),
+        Direction::Up | Direction::Down => {
+            let row = cell.parent_element()?;
+            let row = match dir {
+                Direction::Up => row.previous_element_sibling(),
+                _ => row.next_element_sibling(),
+            }?;
+            row.children().item(column_index(cell))
+        }
+    }
+}
+
+/// The next cell in reading order, wrapping to the
+/// next row, or the previous one if going backwards.
+fn advance(cell: &Element, backwards: bool) -> Option<Element> {
+    if backwards {
+        cell.previous_element_sibling().or_else(|| {
+            cell.parent_element()?
+                .previous_element_sibling()?
+                .last_element_child()
+        })
+    } else {
+        cell.next_element_sibling().or_else(|| {
+            cell.parent_element()?
+                .next_element_sibling()?
+                .first_element_child()
+        })
+    }
+}
+
+/// Start editing a cell, optionally replacing
+/// its contents with what was typed.
+fn edit(cell: &Element, typed: Option<&str>) -> bool {
+    let Some(editor) = editor_in(cell) else {
+        return false;
+    };
+    focus(&editor);
+    if let Some(input) = editor.dyn_ref::<HtmlInputElement>() {
+        match typed {
+            Some(typed) => set_value(&editor, typed),
+            None => input.select(),
+        }
+    }
+    true
+}
+
+/// Wrap a table whose cells contain inputs to navigate and
+/// edit it with the keyboard. The table's cells should
+/// have `tabindex="-1"` so they can be focused.
+#[component]
+pub fn KeyboardGrid(children: Children) -> impl IntoView {
+    // The value of the cell being edited
+    // from before editing it, to cancel.
+    let original = store_value::<Option<String>>(None);
+
+    let on_focus = move |ev: web_sys::FocusEvent| {
+        let target = event_target::<Element>(&ev);
+        if is_editor(&target) {
+            original.set_value(value_of(&target));
+        }
+    };
+
+    let on_keydown = move |ev: KeyboardEvent| {
+        let target = event_target::<Element>(&ev);
+        let Some(cell) = cell_of(&target) else { return };
+        let key = ev.key();
+        let next = if is_editor(&target) {
+            match key.as_str() {
+                "Escape" => {
+                    if let Some(value) = original.get_value() {
+                        set_value(&target, &value);
+                    }
+                    Some(cell.clone())
+                }
+                "Enter" => Some(
+                    neighbor(&cell, Direction::Down)
+                        .unwrap_or_else(|| cell.clone()),
+                ),
+                "Tab" => Some(
+                    advance(&cell, ev.shift_key())
+                        .unwrap_or_else(|| cell.clone()),
+                ),
+                _ => None,
+            }
+        } else {
+            let modified =
+                ev.ctrl_key() || ev.meta_key() || ev.alt_key();
+            match key.as_str() {
+                "ArrowUp" => neighbor(&cell, Direction::Up),
+                "ArrowDown" => neighbor(&cell, Direction::Down),
+                "ArrowLeft" => neighbor(&cell, Direction::Left),
+                "ArrowRight" => {
+                    neighbor(&cell, Direction::Right)
+                }
+                "Tab" => advance(&cell, ev.shift_key()),
+                "Enter" | "F2" => {
+                    if edit(&cell, None) {
+                        ev.prevent_default();
+                    }
+                    return;
+                }
+                _ if key.chars().count() == 1 && !modified => {
+                    if edit(&cell, Some(&key)) {
+                        ev.prevent_default();
+                    }
+                    return;
+                }
+                _ => return,
+            }
+        };
+        if let Some(next) = next {
+            ev.prevent_default();
+
+            // Moving focus off the editor commits its value.
+            focus(&next);
+        }
+    };
+
+    view! {
+        <div class="keyboard-grid" on:focusin=on_focus on:keydown=on_keydown>
+            {children()}
+        </div>
+    }
+}
@@ -1,11 +1,24 @@
 mod conditions;
 mod effects;
+mod form;
+mod grid;
+mod numbers;
 
 use base64::prelude::*;
+use crate::{
+    desktop,
+    icons::{icon_path, ICONS},
+    lint::Lints,
+    t,
+    thumbnails::Thumbnail,
+};
 pub use conditions::Conditions;
 pub use effects::Effects;
+pub use form::Form;
+pub use grid::KeyboardGrid;
 use hes_engine::{
-    flavor::{Image, ImageData},
+    flavor::{Audio, Image, ImageData},
+    Bounds,
     ByproductMap,
     Collection,
     FeedstockMap,
@@ -16,8 +29,16 @@ use hes_engine::{
 };
 use js_sys::Uint8Array;
 use leptos::*;
-use leptos_use::on_click_outside;
-use num::Num;
+use leptos_use::{on_click_outside, use_debounce_fn_with_arg};
+use num::{Num, NumCast, ToPrimitive};
+use numbers::{
+    evaluate,
+    format_number,
+    normalize,
+    parse_finite,
+    parse_row,
+};
+pub use numbers::Unit;
 use std::{
     fmt::{Debug, Display},
     str::FromStr,
@@ -62,6 +83,20 @@ macro_rules! subsignal {
     }};
 }
 
+/// Help text for a field, from the engine's documentation
+/// of it, e.g. `field_doc!(World.temperature)`, so that it
+/// stays in sync with the engine. This fails to compile if
+/// there's no such field.
+#[macro_export]
+macro_rules! field_doc {
+    ($ty:ident.$field:ident) => {{
+        let _ = |data: &$ty| &data.$field;
+        <$ty as hes_engine::form::Documented>::field_doc(
+            stringify!($field),
+        )
+    }};
+}
+
 /// Conveniently create a slice from an enum variant.
 #[macro_export]
 macro_rules! enum_slice {
@@ -73,28 +108,333 @@ macro_rules! enum_slice {
     };
 }
 
+/// Whether the editor is in read-only review mode,
+/// in which inputs display their current values
+/// but can't be edited.
+#[derive(Clone, Copy)]
+pub struct ReadOnly(pub RwSignal<bool>);
+
+/// Get the read-only state from context, defaulting
+/// to editable if it hasn't been provided.
+pub fn read_only() -> Signal<bool> {
+    let ctx = use_context::<ReadOnly>();
+    Signal::derive(move || ctx.map_or(false, |ro| ro.0.get()))
+}
+
+/// The span of the game, from the world's starting year
+/// to when the game ends, along with the years events are
+/// conditioned on, for placing years on a timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timeline {
+    pub start: f32,
+    pub end: f32,
+    pub markers: Vec<(f32, String)>,
+}
+impl Timeline {
+    /// How far along the timeline a year is, as a percent.
+    fn percent(&self, year: f32) -> f32 {
+        let span = (self.end - self.start).max(1.);
+        ((year - self.start) / span * 100.).clamp(0., 100.)
+    }
+}
+
+/// How long to wait after typing stops
+/// before writing a debounced text field.
+const DEBOUNCE_MS: f64 = 250.;
+
+/// When a text field writes its value. Writing on every
+/// keystroke can be slow for fields that lots of other
+/// things depend on, e.g. entity names.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Commit {
+    /// Write on every keystroke.
+    #[default]
+    Input,
+
+    /// Write once typing pauses.
+    Debounce,
+
+    /// Write when the field loses focus.
+    Blur,
+}
+
+/// The `input` and `change` event handlers for
+/// a text field, which write its value per `commit`.
+fn commit_handlers(
+    write: SignalSetter<String>,
+    commit: Commit,
+) -> (
+    impl Fn(web_sys::Event) + Clone + 'static,
+    impl Fn(web_sys::Event) + Clone + 'static,
+) {
+    let debounced = use_debounce_fn_with_arg(
+        move |value: String| write.set(value),
+        DEBOUNCE_MS,
+    );
+    let on_input = move |ev: web_sys::Event| {
+        let value = event_target_value(&ev);
+        match commit {
+            Commit::Input => write.set(value),
+            Commit::Debounce => {
+                debounced(value);
+            }
+            Commit::Blur => (),
+        }
+    };
+
+    // Also write on change (i.e. blur) when debouncing,
+    // so the value is up-to-date when the user moves on.
+    let on_change = move |ev: web_sys::Event| {
+        if commit != Commit::Input {
+            write.set(event_target_value(&ev));
+        }
+    };
+    (on_input, on_change)
+}
+
+/// Show any problems the lints find in the text.
+#[component]
+fn LintErrors(
+    text: Signal<String>,
+    lints: Lints,
+) -> impl IntoView {
+    move || {
+        with!(|text| lints.check(text))
+            .into_iter()
+            .map(|problem| {
+                view! { <div class="input-error">{problem}</div> }
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+/// A button to revert a field to its default value,
+/// shown only when the current value differs from it.
+#[component]
+fn ResetButton<T: Clone + PartialEq + 'static>(
+    signal: (Signal<T>, SignalSetter<T>),
+    default: Option<T>,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+    let default = store_value(default);
+    let differs = move || {
+        default.with_value(|default| {
+            default.as_ref().is_some_and(|default| {
+                with!(|read| read != default)
+            })
+        })
+    };
+
+    view! {
+        <Show when=move || !read_only.get() && differs()>
+            <div
+                class="reset-default"
+                title=t!("Reset to default")
+                on:click=move |_| {
+                    if let Some(default) = default.get_value() {
+                        write.set(default);
+                    }
+                }>
+                "↺"
+            </div>
+        </Show>
+    }
+}
+
+/// The factors for quickly rescaling a value, e.g. to fix
+/// one that's off by a few orders of magnitude.
+const SCALE_FACTORS: [(&str, f32); 4] = [
+    ("×10", 10.),
+    ("÷10", 0.1),
+    ("×1000", 1000.),
+    ("÷1000", 0.001),
+];
+
+/// Buttons to multiply or divide a value by
+/// a power of ten, clamped to the bounds if any.
+#[component]
+fn ScaleButtons<T: NumCast + Copy + 'static>(
+    signal: (Signal<T>, SignalSetter<T>),
+    bounds: Option<Bounds>,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+
+    view! {
+        <Show when=move || !read_only.get()>
+            <div class="scale-buttons">
+                {SCALE_FACTORS.iter().map(|(label, factor)| {
+                    let factor = *factor;
+                    view! {
+                        <button
+                            type="button"
+                            title=t!("Scale by {label}", label: label)
+                            on:click=move |_| {
+                                let value = read.get_untracked().to_f32().unwrap_or_default();
+                                if let Some(scaled) = num::cast(value * factor) {
+                                    let (scaled, _) = clamp_to_bounds(scaled, bounds);
+                                    write.set(scaled);
+                                }
+                            }>
+                            {*label}
+                        </button>
+                    }
+                }).collect::<Vec<_>>()}
+            </div>
+        </Show>
+    }
+}
+
+/// Marks a field whose value differs from
+/// the baseline world's, if comparing.
+#[component]
+fn BaselineMarker<T: Clone + PartialEq + Display + 'static>(
+    read: Signal<T>,
+    baseline: Option<Signal<Option<T>>>,
+) -> impl IntoView {
+    let changed = move || {
+        baseline.and_then(|baseline| {
+            let base = baseline.get()?;
+            let current = read.get();
+            (base != current).then(|| {
+                format!("Original: {base}\nCurrent: {current}")
+            })
+        })
+    };
+
+    move || {
+        changed().map(|title| {
+            view! {
+                <div class="baseline-changed" title=title>"●"</div>
+            }
+        })
+    }
+}
+
 #[component]
 pub fn TextInput(
     signal: (Signal<String>, SignalSetter<String>),
     #[prop(into, optional)] label: String,
     #[prop(into, optional)] help: String,
     #[prop(into, optional)] inline: bool,
+    #[prop(optional)] lints: Lints,
+    #[prop(optional)] default: Option<String>,
+    #[prop(into, optional)] baseline: Option<
+        Signal<Option<String>>,
+    >,
+    #[prop(optional)] commit: Commit,
 ) -> impl IntoView {
     let (read, write) = signal;
+    let read_only = read_only();
+    let (on_input, on_change) = commit_handlers(write, commit);
 
     view! {
         <div class="input-group" class:inline={inline}>
             <div class="text-group-inner">
-                <label>{label}</label>
-                <input
-                    class="text-input"
-                    value=read.get_untracked()
-                    on:input=move |ev| {
-                        let value = event_target_value(&ev);
-                        write.set(value);
-                    } />
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value">{read}</div>
+                    }>
+                    <input
+                        class="text-input"
+                        value=read.get_untracked()
+                        prop:value=read
+                        on:input=on_input.clone()
+                        on:change=on_change.clone() />
+                </Show>
+                <ResetButton signal default />
+                <BaselineMarker read baseline />
             </div>
-            <div class="input-help">{help}</div>
+            <LintErrors text=read lints />
+            <div class="input-help">{t!(&help)}</div>
+        </div>
+    }
+}
+
+/// Is this a hex color, e.g. `#10AB78` or `#fff`?
+fn is_hex_color(value: &str) -> bool {
+    value.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6)
+            && hex.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+/// A color, stored as a hex string, which can be
+/// edited directly or with the browser's color picker.
+#[component]
+pub fn ColorInput(
+    signal: (Signal<String>, SignalSetter<String>),
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+    #[prop(into, optional)] inline: bool,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let is_valid = create_rw_signal(true);
+
+    // Frankly kind of hacky
+    let input_ref = create_node_ref::<html::Input>();
+    create_effect(move |_| {
+        let new_val = read.get();
+        is_valid.set(true);
+        if let Some(input) = input_ref.get() {
+            input.set_value(&new_val);
+        }
+    });
+
+    let set_value = move |value: String| {
+        let valid = is_hex_color(&value);
+        if valid {
+            write.set(value);
+        }
+        is_valid.set(valid);
+    };
+
+    // The picker only accepts six-digit hex colors.
+    let picker_value = move || {
+        let color = read.get();
+        match color.len() {
+            4 => color
+                .chars()
+                .skip(1)
+                .fold("#".to_string(), |mut acc, c| {
+                    acc.push(c);
+                    acc.push(c);
+                    acc
+                }),
+            _ => color,
+        }
+    };
+
+    let read_only = read_only();
+
+    view! {
+        <div class="input-group color-group" class:inline={inline}>
+            <div class="text-group-inner">
+                <label>{t!(&label)}</label>
+                <div class="color-inputs">
+                    <input
+                        type="color"
+                        disabled=read_only
+                        prop:value=picker_value
+                        on:input=move |ev| set_value(event_target_value(&ev)) />
+                    <Show when=move || !read_only.get()
+                        fallback=move || view! {
+                            <div class="read-only-value">{read}</div>
+                        }>
+                        <input
+                            ref=input_ref
+                            class="text-input"
+                            value=read.get_untracked()
+                            on:change=move |ev| set_value(event_target_value(&ev)) />
+                    </Show>
+                </div>
+            </div>
+            <Show when=move || !is_valid.get()>
+                <div class="input-error">{t!("Must be a hex color, e.g. #10AB78.")}</div>
+            </Show>
+            <div class="input-help">{t!(&help)}</div>
         </div>
     }
 }
@@ -113,9 +453,16 @@ impl NumberError for usize {
     }
 }
 
+/// A number input. If `bounds` are given, values
+/// outside of them are clamped and flagged, as are
+/// values already out of bounds, e.g. from a file.
+/// Set `scalable` for fields where values are easily
+/// off by orders of magnitude, e.g. resource intensities,
+/// to show buttons for scaling by powers of ten.
 #[component]
 pub fn NumericInput<
     T: Num
+        + NumCast
         + Clone
         + Copy
         + std::str::FromStr
@@ -129,46 +476,106 @@ pub fn NumericInput<
     #[prop(into)] label: String,
     #[prop(into)] help: String,
     #[prop(into, optional)] inline: bool,
+    #[prop(optional)] unit: Option<Unit>,
+    #[prop(into, optional)] separators: bool,
+    #[prop(optional)] default: Option<T>,
+    #[prop(into, optional)] baseline: Option<
+        Signal<Option<T>>,
+    >,
+    #[prop(optional_no_strip)] bounds: Option<Bounds>,
+    #[prop(into, optional)] scalable: bool,
 ) -> impl IntoView {
     let (read, write) = signal;
-    let maybe_val = create_rw_signal(Ok(read.get_untracked()));
+    let maybe_val = create_rw_signal(Some(read.get_untracked()));
+    let clamped = create_rw_signal(false);
+    let out_of_bounds = move || {
+        let val = read.get().to_f32().unwrap_or_default();
+        bounds.is_some_and(|bounds| !bounds.contains(val))
+    };
+
+    let to_string = move |val: T| {
+        let val = val.to_string();
+        if separators {
+            format_number(&val)
+        } else {
+            val
+        }
+    };
 
     // Frankly kind of hacky
     let input_ref = create_node_ref::<html::Input>();
-    create_effect(move |_| {
-        let new_val = read.get();
-        maybe_val.set(Ok(new_val));
+    let display = move |val: T| {
         if let Some(input) = input_ref.get() {
-            input.set_value(&new_val.to_string());
+            input.set_value(&to_string(val));
         }
+    };
+    create_effect(move |_| {
+        let new_val = read.get();
+        maybe_val.set(Some(new_val));
+        display(new_val);
     });
 
     let help = store_value(help);
+    let read_only = read_only();
 
     view! {
         <div class="input-group numeric-group tooltip-parent" class:inline={inline}>
             <div class="numeric-group-inner">
-                <label>{label}</label>
-                <input
-                    ref=input_ref
-                    class="numeric-input"
-                    inputmode="decimal"
-                    value=read.get_untracked()
-                    on:change=move |ev| {
-                        let res = event_target_value(&ev).parse::<T>();
-                        if let Ok(value) = &res {
-                            write.set(*value);
-                        }
-                        maybe_val.set(res);
-                    } />
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value numeric-value">{move || to_string(read.get())}</div>
+                    }>
+                    <div class:input-suffixed=unit.is_some()>
+                        <input
+                            ref=input_ref
+                            class="numeric-input"
+                            inputmode="decimal"
+                            value=to_string(read.get_untracked())
+                            on:change=move |ev| {
+                                let value = event_target_value(&ev);
+                                let value = match unit {
+                                    Some(unit) => unit.convert(&value),
+                                    None => evaluate(&value).map(|num| num.to_string()),
+                                };
+                                let res = value
+                                    .and_then(|value| value.parse::<T>().ok())
+                                    .filter(|value| is_finite(*value))
+                                    .map(|value| clamp_to_bounds(value, bounds));
+                                if let Some((value, was_clamped)) = res {
+                                    write.set(value);
+
+                                    // Show the converted value, which won't
+                                    // otherwise update if it didn't change.
+                                    display(value);
+                                    clamped.set(was_clamped);
+                                }
+                                maybe_val.set(res.map(|(value, _)| value));
+                            } />
+                        {unit.map(|unit| view! {
+                            <div class="input-suffix">{unit.label()}</div>
+                        })}
+                    </div>
+                </Show>
+                {scalable.then(|| view! { <ScaleButtons signal bounds /> })}
+                <ResetButton signal default />
+                <BaselineMarker read baseline />
             </div>
-            <Show when=move || with!(|maybe_val| maybe_val.is_err())>
+            <Show when=move || with!(|maybe_val| maybe_val.is_none())>
                 <div class="input-error">{T::error_desc()}</div>
             </Show>
+            {move || bounds.filter(|_| out_of_bounds()).map(|bounds| view! {
+                <div class="input-error">{describe_bounds(&bounds)}</div>
+            })}
+            {move || bounds.filter(|_| clamped.get()).map(|bounds| view! {
+                <div class="input-notice">
+                    {t!("Clamped to the valid range.")}" "{describe_bounds(&bounds)}
+                </div>
+            })}
             {move || {
                  (!help.get_value().is_empty()).then(|| {
                      view! {
-                         <div class="tooltip">{help.get_value()}</div>
+                         <div class="tooltip">{t!(&help.get_value())}</div>
                      }
                  })
             }}
@@ -176,50 +583,676 @@ pub fn NumericInput<
     }
 }
 
+/// Whether the value is an actual number, i.e. not NaN
+/// or infinite, which would poison the simulation. A
+/// value can be finite as an `f64` but not as an `f32`.
+fn is_finite<T: NumCast>(value: T) -> bool {
+    value.to_f32().is_some_and(f32::is_finite)
+}
+
+/// Clamp a value to the bounds, if any,
+/// and whether it had to be clamped.
+fn clamp_to_bounds<T: NumCast + Copy>(
+    value: T,
+    bounds: Option<Bounds>,
+) -> (T, bool) {
+    let Some(bounds) = bounds else {
+        return (value, false);
+    };
+    let val = value.to_f32().unwrap_or_default();
+    if bounds.contains(val) {
+        (value, false)
+    } else {
+        let clamped =
+            num::cast(bounds.clamp(val)).unwrap_or(value);
+        (clamped, true)
+    }
+}
+
+/// Describe the valid range, e.g. for an error message.
+fn describe_bounds(bounds: &Bounds) -> String {
+    if bounds.max.is_infinite() {
+        t!("Must be at least {min}.", min: bounds.min)
+    } else {
+        t!(
+            "Must be between {min} and {max}.",
+            min: bounds.min,
+            max: bounds.max
+        )
+    }
+}
+
+/// Parse a percentage, with or without a trailing "%".
+fn parse_percent(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .trim_end_matches('%')
+        .trim()
+        .parse()
+        .ok()
+        .filter(|value: &f32| value.is_finite())
+}
+
+/// An input for values stored as fractions but
+/// displayed and entered as percentages, e.g.
+/// "35%" is stored as 0.35. If `fraction` is set
+/// the value is clamped to 0–100%.
 #[component]
 pub fn PercentInput(
     signal: (Signal<f32>, SignalSetter<f32>),
     #[prop(into)] label: String,
     #[prop(into)] help: String,
     #[prop(into, optional)] inline: bool,
+    #[prop(into, optional)] fraction: bool,
+    #[prop(optional)] default: Option<f32>,
+    #[prop(into, optional)] baseline: Option<
+        Signal<Option<f32>>,
+    >,
 ) -> impl IntoView {
     let (read, write) = signal;
-    let maybe_val = create_rw_signal(Ok(read.get_untracked()));
+    let maybe_val = create_rw_signal(Some(read.get_untracked()));
 
     // Frankly kind of hacky
     let input_ref = create_node_ref::<html::Input>();
+    let display = move |val: f32| {
+        if let Some(input) = input_ref.get() {
+            input.set_value(&(val * 100.).to_string());
+        }
+    };
     create_effect(move |_| {
         let new_val = read.get();
-        maybe_val.set(Ok(new_val * 100.));
+        maybe_val.set(Some(new_val));
+        display(new_val);
+    });
+
+    let read_only = read_only();
+
+    view! {
+        <div class="input-group numeric-group" class:inline={inline}>
+            <div class="numeric-group-inner">
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value numeric-value">{move || format!("{}%", read.get() * 100.)}</div>
+                    }>
+                    <div class="input-suffixed">
+                        <input
+                            ref={input_ref}
+                            class="numeric-input"
+                            inputmode="decimal"
+                            value=read.get_untracked() * 100.
+                            on:change=move |ev| {
+                                let res = parse_percent(&event_target_value(&ev))
+                                    .map(|value| value / 100.)
+                                    .map(|value| if fraction {
+                                        Bounds::FRACTION.clamp(value)
+                                    } else {
+                                        value
+                                    });
+                                if let Some(value) = res {
+                                    write.set(value);
+
+                                    // Show the clamped value, which won't
+                                    // otherwise update if it didn't change.
+                                    display(value);
+                                }
+                                maybe_val.set(res);
+                            } />
+                        <div class="input-suffix">%</div>
+                    </div>
+                </Show>
+                <ResetButton signal default />
+                <BaselineMarker read baseline />
+            </div>
+            <Show when=move || with!(|maybe_val| maybe_val.is_none())>
+                <div class="input-error">{t!("Must be a number.")}</div>
+            </Show>
+            <div class="input-help">{t!(&help)}</div>
+        </div>
+    }
+}
+
+/// A slider combined with a number field for
+/// bounded quantities. Values outside of the bounds
+/// are rejected rather than written to the world.
+#[component]
+pub fn SliderInput(
+    signal: (Signal<f32>, SignalSetter<f32>),
+    bounds: Bounds,
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+    #[prop(into, optional)] inline: bool,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let error = create_rw_signal(None::<String>);
+
+    // Frankly kind of hacky
+    let input_ref = create_node_ref::<html::Input>();
+    create_effect(move |_| {
+        let new_val = read.get();
+        error.set(None);
         if let Some(input) = input_ref.get() {
-            input.set_value(&(new_val * 100.).to_string());
+            input.set_value(&new_val.to_string());
         }
     });
 
+    let set_value = move |value: &str| match parse_finite(value) {
+        Some(value) if bounds.contains(value) => {
+            write.set(value);
+            error.set(None);
+        }
+        Some(_) => error.set(Some(describe_bounds(&bounds))),
+        None => error.set(Some("Must be a number.".into())),
+    };
+
+    let help = store_value(help);
+    let read_only = read_only();
+
     view! {
-        <div class="input-group numeric-group" class:inline={inline}>
+        <div class="input-group numeric-group slider-group tooltip-parent" class:inline={inline}>
             <div class="numeric-group-inner">
-                <label>{label}</label>
-                <div class="input-suffixed">
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value numeric-value">{move || read.get().to_string()}</div>
+                    }>
+                    <div class="slider-inputs">
+                        <input
+                            type="range"
+                            min=bounds.min
+                            max=bounds.max
+                            step=bounds.step
+                            prop:value=move || read.get()
+                            on:input=move |ev| set_value(&event_target_value(&ev)) />
+                        <input
+                            ref=input_ref
+                            class="numeric-input"
+                            inputmode="decimal"
+                            value=read.get_untracked()
+                            on:change=move |ev| set_value(&event_target_value(&ev)) />
+                    </div>
+                </Show>
+            </div>
+            {move || error.get().map(|err| view! {
+                <div class="input-error">{err}</div>
+            })}
+            {move || {
+                 (!help.get_value().is_empty()).then(|| {
+                     view! {
+                         <div class="tooltip">{t!(&help.get_value())}</div>
+                     }
+                 })
+            }}
+        </div>
+    }
+}
+
+/// Edit a `(min, max)` pair, rejecting
+/// values where the min exceeds the max.
+#[component]
+pub fn RangeInput(
+    signal: (Signal<(f32, f32)>, SignalSetter<(f32, f32)>),
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+    #[prop(into, optional)] inline: bool,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let error = create_rw_signal(None::<&'static str>);
+
+    // Frankly kind of hacky
+    let min_ref = create_node_ref::<html::Input>();
+    let max_ref = create_node_ref::<html::Input>();
+    create_effect(move |_| {
+        let (min, max) = read.get();
+        error.set(None);
+        if let Some(input) = min_ref.get() {
+            input.set_value(&min.to_string());
+        }
+        if let Some(input) = max_ref.get() {
+            input.set_value(&max.to_string());
+        }
+    });
+
+    let update = move || {
+        let (Some(min), Some(max)) = (min_ref.get(), max_ref.get())
+        else {
+            return;
+        };
+        match (parse_finite(&min.value()), parse_finite(&max.value()))
+        {
+            (Some(min), Some(max)) if min <= max => {
+                write.set((min, max));
+                error.set(None);
+            }
+            (Some(_), Some(_)) => error.set(Some(
+                "The min must be less than or equal to the max.",
+            )),
+            _ => error.set(Some("Must be a number.")),
+        }
+    };
+
+    let help = store_value(help);
+    let read_only = read_only();
+
+    view! {
+        <div class="input-group numeric-group range-group tooltip-parent" class:inline={inline}>
+            <div class="numeric-group-inner">
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value numeric-value">{move || {
+                            let (min, max) = read.get();
+                            format!("{min} – {max}")
+                        }}</div>
+                    }>
+                    <div class="range-inputs">
+                        <input
+                            ref=min_ref
+                            class="numeric-input"
+                            inputmode="decimal"
+                            title=t!("Min")
+                            value=read.get_untracked().0
+                            on:change=move |_| update() />
+                        "–"
+                        <input
+                            ref=max_ref
+                            class="numeric-input"
+                            inputmode="decimal"
+                            title=t!("Max")
+                            value=read.get_untracked().1
+                            on:change=move |_| update() />
+                    </div>
+                </Show>
+            </div>
+            {move || error.get().map(|err| view! {
+                <div class="input-error">{err}</div>
+            })}
+            {move || {
+                 (!help.get_value().is_empty()).then(|| {
+                     view! {
+                         <div class="tooltip">{t!(&help.get_value())}</div>
+                     }
+                 })
+            }}
+        </div>
+    }
+}
+
+/// Edit a `(latitude, longitude)` pair in degrees,
+/// rejecting coordinates that are out of range.
+/// If `map` a small world map marks the location.
+#[component]
+pub fn CoordinateInput(
+    signal: (Signal<(f32, f32)>, SignalSetter<(f32, f32)>),
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+    #[prop(into, optional)] inline: bool,
+    #[prop(optional)] map: bool,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let error = create_rw_signal(None::<&'static str>);
+
+    // Frankly kind of hacky
+    let lat_ref = create_node_ref::<html::Input>();
+    let lon_ref = create_node_ref::<html::Input>();
+    create_effect(move |_| {
+        let (lat, lon) = read.get();
+        error.set(None);
+        if let Some(input) = lat_ref.get() {
+            input.set_value(&lat.to_string());
+        }
+        if let Some(input) = lon_ref.get() {
+            input.set_value(&lon.to_string());
+        }
+    });
+
+    let update = move || {
+        let (Some(lat), Some(lon)) = (lat_ref.get(), lon_ref.get())
+        else {
+            return;
+        };
+        match (parse_finite(&lat.value()), parse_finite(&lon.value()))
+        {
+            (Some(lat), _) if !(-90. ..=90.).contains(&lat) => {
+                error.set(Some(
+                    "Latitude must be between -90 and 90.",
+                ))
+            }
+            (_, Some(lon)) if !(-180. ..=180.).contains(&lon) => {
+                error.set(Some(
+                    "Longitude must be between -180 and 180.",
+                ))
+            }
+            (Some(lat), Some(lon)) => {
+                write.set((lat, lon));
+                error.set(None);
+            }
+            _ => error.set(Some("Must be a number.")),
+        }
+    };
+
+    // An equirectangular map, with the tropics
+    // and polar circles marked for reference.
+    let mini_map = move || {
+        map.then(|| {
+            view! {
+                <svg class="coordinate-map" viewBox="-180 -90 360 180">
+                    <rect x={-180} y={-90} width=360 height=180 />
+                    {[-66.5, -23.5, 0., 23.5, 66.5]
+                        .into_iter()
+                        .map(|lat: f32| view! {
+                            <line class:equator={lat == 0.}
+                                x1={-180} x2=180 y1={-lat} y2={-lat} />
+                        })
+                        .collect::<Vec<_>>()}
+                    <circle r=5
+                        cx=move || read.get().1
+                        cy=move || -read.get().0 />
+                </svg>
+            }
+        })
+    };
+
+    let help = store_value(help);
+    let read_only = read_only();
+
+    view! {
+        <div class="input-group numeric-group coordinate-group tooltip-parent" class:inline={inline}>
+            <div class="numeric-group-inner">
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value numeric-value">{move || {
+                            let (lat, lon) = read.get();
+                            format!("{lat}°, {lon}°")
+                        }}</div>
+                    }>
+                    <div class="range-inputs">
+                        <input
+                            ref=lat_ref
+                            class="numeric-input"
+                            inputmode="decimal"
+                            title=t!("Latitude")
+                            value=read.get_untracked().0
+                            on:change=move |_| update() />
+                        ","
+                        <input
+                            ref=lon_ref
+                            class="numeric-input"
+                            inputmode="decimal"
+                            title=t!("Longitude")
+                            value=read.get_untracked().1
+                            on:change=move |_| update() />
+                    </div>
+                </Show>
+            </div>
+            {mini_map}
+            {move || error.get().map(|err| view! {
+                <div class="input-error">{err}</div>
+            })}
+            {move || {
+                 (!help.get_value().is_empty()).then(|| {
+                     view! {
+                         <div class="tooltip">{t!(&help.get_value())}</div>
+                     }
+                 })
+            }}
+        </div>
+    }
+}
+
+/// Edit a year, shown as a marker on a timeline of the
+/// game that can be dragged to change the year. Years that
+/// other events are conditioned on are marked as well,
+/// so it's clear when things happen relative to each other.
+#[component]
+pub fn TimelineInput(
+    signal: (Signal<f32>, SignalSetter<f32>),
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+    #[prop(into, optional)] inline: bool,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let timeline = expect_context::<Signal<Timeline>>();
+    let error = create_rw_signal(None::<&'static str>);
+
+    // Frankly kind of hacky
+    let input_ref = create_node_ref::<html::Input>();
+    create_effect(move |_| {
+        let new_val = read.get();
+        error.set(None);
+        if let Some(input) = input_ref.get() {
+            input.set_value(&new_val.to_string());
+        }
+    });
+
+    let set_year = move |year: f32| {
+        if read.get_untracked() != year {
+            write.set(year);
+        }
+    };
+
+    // Convert a pointer position to the year under it.
+    let track_ref = create_node_ref::<html::Div>();
+    let year_at = move |client_x: i32| -> Option<f32> {
+        let track = track_ref.get_untracked()?;
+        let rect = track.get_bounding_client_rect();
+        if rect.width() <= 0. {
+            return None;
+        }
+        let frac = ((client_x as f64 - rect.left())
+            / rect.width())
+        .clamp(0., 1.) as f32;
+        let (start, end) =
+            timeline.with_untracked(|tl| (tl.start, tl.end));
+        Some((start + frac * (end - start)).round())
+    };
+
+    let read_only = read_only();
+    let dragging = create_rw_signal(false);
+    let on_pointerdown = move |ev: ev::PointerEvent| {
+        if read_only.get_untracked() {
+            return;
+        }
+        if let Some(track) = track_ref.get_untracked() {
+            let _ = track.set_pointer_capture(ev.pointer_id());
+        }
+        dragging.set(true);
+        if let Some(year) = year_at(ev.client_x()) {
+            set_year(year);
+        }
+    };
+    let on_pointermove = move |ev: ev::PointerEvent| {
+        if dragging.get_untracked() {
+            if let Some(year) = year_at(ev.client_x()) {
+                set_year(year);
+            }
+        }
+    };
+    let on_keydown = move |ev: ev::KeyboardEvent| {
+        if read_only.get_untracked() {
+            return;
+        }
+        let step = match ev.key().as_str() {
+            "ArrowLeft" => -1.,
+            "ArrowRight" => 1.,
+            _ => return,
+        };
+        ev.prevent_default();
+        set_year(read.get_untracked().round() + step);
+    };
+
+    let set_value = move |value: &str| match parse_finite(value) {
+        Some(year) => {
+            set_year(year.round());
+            error.set(None);
+        }
+        None => error.set(Some("Must be a year.")),
+    };
+
+    let outside = move || {
+        let year = read.get();
+        with!(|timeline| year < timeline.start
+            || year > timeline.end)
+    };
+    let markers = move || {
+        with!(|timeline| {
+            timeline
+                .markers
+                .iter()
+                .map(|(year, name)| {
+                    let left =
+                        format!("{}%", timeline.percent(*year));
+                    let title = format!("{year}: {name}");
+                    view! {
+                        <div class="timeline-marker" style:left=left title=title />
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+    let handle_left = move || {
+        let year = read.get();
+        with!(|timeline| format!("{}%", timeline.percent(year)))
+    };
+
+    let help = store_value(help);
+
+    view! {
+        <div class="input-group timeline-group tooltip-parent" class:inline={inline}>
+            <div class="timeline-group-inner">
+                <label>{t!(&label)}</label>
+                <div
+                    ref=track_ref
+                    class="timeline-track"
+                    class:dragging=dragging
+                    class:read-only=read_only
+                    tabindex="0"
+                    on:pointerdown=on_pointerdown
+                    on:pointermove=on_pointermove
+                    on:pointerup=move |_| dragging.set(false)
+                    on:pointercancel=move |_| dragging.set(false)
+                    on:keydown=on_keydown>
+                    {markers}
+                    <div class="timeline-handle" style:left=handle_left />
+                </div>
+                <div class="timeline-bounds">
+                    <span>{move || timeline.with(|tl| tl.start)}</span>
+                    <span>{move || timeline.with(|tl| tl.end)}</span>
+                </div>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value numeric-value">{move || read.get().to_string()}</div>
+                    }>
                     <input
-                        ref={input_ref}
+                        ref=input_ref
                         class="numeric-input"
-                        inputmode="decimal"
-                        value=read.get_untracked() * 100.
-                        on:change=move |ev| {
-                            let res = event_target_value(&ev).parse::<f32>();
-                            if let Ok(value) = &res {
-                                write.set(*value/100.);
-                            }
-                            maybe_val.set(res);
-                        } />
-                    <div class="input-suffix">%</div>
-                </div>
+                        inputmode="numeric"
+                        value=read.get_untracked()
+                        on:change=move |ev| set_value(&event_target_value(&ev)) />
+                </Show>
             </div>
-            <Show when=move || with!(|maybe_val| maybe_val.is_err())>
-                <div class="input-error">Must be a number.</div>
+            {move || error.get().map(|err| view! {
+                <div class="input-error">{err}</div>
+            })}
+            <Show when=outside>
+                <div class="input-error">{t!("This year is outside of the game's timespan.")}</div>
+            </Show>
+            {move || {
+                 (!help.get_value().is_empty()).then(|| {
+                     view! {
+                         <div class="tooltip">{t!(&help.get_value())}</div>
+                     }
+                 })
+            }}
+        </div>
+    }
+}
+
+/// Edit a set of weights, e.g. the shares of a mix,
+/// showing each as an adjustable bar along with its
+/// share of the total. The weights are meant to sum to
+/// `total`, and can be scaled to do so. If `whole` they're
+/// kept as whole numbers.
+#[component]
+pub fn WeightsInput(
+    signal: (Signal<Vec<f32>>, SignalSetter<Vec<f32>>),
+    labels: Signal<Vec<String>>,
+    total: f32,
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+    #[prop(into, optional)] whole: bool,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+    let sum = move || with!(|read| read.iter().sum::<f32>());
+    let step = if whole { 1. } else { total / 100. };
+
+    let rows = move || {
+        let labels = labels.get();
+        (0..with!(|read| read.len()))
+            .map(|i| {
+                let weight = move || {
+                    with!(|read| read.get(i).copied().unwrap_or(0.))
+                };
+                let share = move || {
+                    let sum = sum();
+                    if sum > 0. {
+                        format!("{:.1}%", weight() / sum * 100.)
+                    } else {
+                        "–".into()
+                    }
+                };
+                let set = move |value: f32| {
+                    let mut weights = read.get();
+                    weights[i] =
+                        if whole { value.round() } else { value };
+                    write.set(weights);
+                };
+                view! {
+                    <div class="weight">
+                        <div class="weight-label">{labels.get(i).cloned()}</div>
+                        <input
+                            type="range"
+                            min=0
+                            max=move || total.max(weight())
+                            step=step
+                            disabled=read_only
+                            prop:value=weight
+                            on:input=move |ev| {
+                                if let Ok(value) = event_target_value(&ev).parse() {
+                                    set(value);
+                                }
+                            } />
+                        <div class="weight-value">{weight}</div>
+                        <div class="weight-share">{share}</div>
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let on_normalize = move |_| {
+        let weights = read.get();
+        if let Some(weights) = normalize(&weights, total, whole) {
+            write.set(weights);
+        }
+    };
+
+    view! {
+        <div class="input-group weights-group">
+            <h2>{t!(&label)}</h2>
+            <div class="input-help">{t!(&help)}</div>
+            <div class="weights">{rows}</div>
+            <div class="weights-total">
+                {move || format!("Total: {} of {}", sum(), total)}
+                <Show when=move || !read_only.get() && sum() != total>
+                    <div class="button" on:click=on_normalize>{t!("Normalize to 100%")}</div>
+                </Show>
+            </div>
+            <Show when=move || sum() != total>
+                <div class="input-error">{t!("The weights don't add up to 100%.")}</div>
             </Show>
-            <div class="input-help">{help}</div>
         </div>
     }
 }
@@ -227,6 +1260,7 @@ pub fn PercentInput(
 #[component]
 pub fn OptionalNumericInput<
     T: Num
+        + NumCast
         + Clone
         + Copy
         + Default
@@ -240,9 +1274,12 @@ pub fn OptionalNumericInput<
     signal: (Signal<Option<T>>, SignalSetter<Option<T>>),
     #[prop(into)] label: String,
     #[prop(into)] help: String,
+    #[prop(optional_no_strip)] bounds: Option<Bounds>,
 ) -> impl IntoView {
-    let (read, _) = signal;
-    let maybe_val = create_rw_signal(read.get_untracked());
+    let (read, write) = signal;
+
+    // The last set value, so that toggling
+    // off and back on again restores it.
     let value = create_rw_signal::<T>(
         read.get_untracked().unwrap_or_else(T::default),
     );
@@ -252,33 +1289,47 @@ pub fn OptionalNumericInput<
             <ToggleInput
                 label=label
                 help=help
-                signal=create_slice(maybe_val,
-                    move |opt| opt.is_some(),
-                    move |opt, val| {
-                        if val {
-                            *opt = Some(value.get());
-                        } else {
-                            *opt = None;
-                        }
-                    })
+                signal=(
+                    Signal::derive(move || with!(|read| read.is_some())),
+                    SignalSetter::map(move |enable: bool| {
+                        write.set(enable.then(|| value.get()));
+                    }))
                 inner=move || {
                     view! {
-                        <Show when=move || with!(|maybe_val| maybe_val.is_some())>
+                        <Show when=move || with!(|read| read.is_some())>
                             <NumericInput
                                 label=""
                                 help=""
-                                signal=create_slice(maybe_val,
-                                    move |opt| opt.clone().unwrap(),
-                                    move |opt, val| {
-                                        opt.replace(val);
+                                bounds
+                                signal=(
+                                    Signal::derive(move || {
+                                        read.get().unwrap_or_else(|| value.get())
+                                    }),
+                                    SignalSetter::map(move |val: T| {
                                         value.set(val);
-                                    }) />
+                                        write.set(Some(val));
+                                    })) />
                         </Show>
                 }} />
         </div>
     }
 }
 
+/// Get a pasted row of `n` values, e.g. copied from a
+/// spreadsheet, so that a group of inputs can be filled
+/// at once. If this returns values the default paste
+/// behavior is prevented.
+fn pasted_row(
+    ev: &ev::ClipboardEvent,
+    n: usize,
+) -> Option<Vec<f32>> {
+    let text = ev.clipboard_data()?.get_data("text").ok()?;
+    let values =
+        parse_row(&text).filter(|values| values.len() == n)?;
+    ev.prevent_default();
+    Some(values)
+}
+
 #[component]
 pub fn MultiNumericInput<const N: usize>(
     signal: (Signal<[f32; N]>, SignalSetter<[f32; N]>),
@@ -302,16 +1353,21 @@ pub fn MultiNumericInput<const N: usize>(
     view! {
         <div class="map-group">
             <h2 class="tooltip-parent">
-                {label}
+                {t!(&label)}
                 {move || {
                      (!help.get_value().is_empty()).then(|| {
                          view! {
-                             <div class="tooltip">{help.get_value()}</div>
+                             <div class="tooltip">{t!(&help.get_value())}</div>
                          }
                      })
                 }}
             </h2>
-            <div class="map-inputs">
+            <div class="map-inputs" on:paste=move |ev| {
+                if let Some(values) = pasted_row(&ev, N) {
+                    let (_, write) = signal;
+                    write.set(std::array::from_fn(|i| values[i]));
+                }
+            }>
                 {inputs}
             </div>
         </div>
@@ -330,34 +1386,55 @@ pub fn ResourceMapInput(
     view! {
         <div class="map-group resources-group">
             <h2 class="tooltip-parent">
-                {label}
+                {t!(&label)}
                 {move || {
                      (!help.get_value().is_empty()).then(|| {
                          view! {
-                             <div class="tooltip">{help.get_value()}</div>
+                             <div class="tooltip">{t!(&help.get_value())}</div>
                          }
                      })
                 }}
             </h2>
-            <div class="map-inputs">
+            <div class="map-inputs" on:paste=move |ev| {
+                if let Some(values) = pasted_row(&ev, 4) {
+                    let (read, write) = signal;
+                    let mut map = read.get();
+                    map.land = values[0];
+                    map.water = values[1];
+                    map.electricity = values[2];
+                    map.fuel = values[3];
+                    write.set(map);
+                }
+            }>
                 <NumericInput
+                    scalable=true
                     label="Land"
                     help="Land in square meters (m2)."
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.land)
                     />
                 <NumericInput
+                    scalable=true
                     label="Water"
                     help="Water in liters (L)."
+                    unit=Unit::Liters
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.water)
                     />
                 <NumericInput
+                    scalable=true
                     label="Electricity"
                     help="Electricity in kilowatt-hours (kWh)."
+                    unit=Unit::KilowattHours
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.electricity)
                     />
                 <NumericInput
+                    scalable=true
                     label="Fuel"
                     help="Fuel in kilowatt-hours (kWh)."
+                    unit=Unit::KilowattHours
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.fuel)
                     />
             </div>
@@ -377,36 +1454,69 @@ pub fn ByproductMapInput(
     view! {
         <div class="map-group byproducts-group">
             <h2 class="tooltip-parent">
-                {label}
+                {t!(&label)}
                 {move || {
                      (!help.get_value().is_empty()).then(|| {
                          view! {
-                             <div class="tooltip">{help.get_value()}</div>
+                             <div class="tooltip">{t!(&help.get_value())}</div>
                          }
                      })
                 }}
             </h2>
-            <div class="map-inputs">
+            <div class="map-inputs" on:paste=move |ev| {
+                if let Some(values) = pasted_row(&ev, 6) {
+                    let (read, write) = signal;
+                    let mut map = read.get();
+                    map.co2 = values[0];
+                    map.ch4 = values[1];
+                    map.n2o = values[2];
+                    map.biodiversity = values[3];
+                    map.particulates = values[4];
+                    map.plastic = values[5];
+                    write.set(map);
+                }
+            }>
                 <NumericInput
+                    scalable=true
                     label="CO2"
                     help="CO2 in grams."
+                    unit=Unit::Grams
                     signal=subsignal!(map.co2)
                     />
                 <NumericInput
+                    scalable=true
                     label="CH4"
                     help="CH4 (methane) in grams."
+                    unit=Unit::Grams
                     signal=subsignal!(map.ch4)
                     />
                 <NumericInput
+                    scalable=true
                     label="N2O"
                     help="N2O (nitrous oxide) in grams."
+                    unit=Unit::Grams
                     signal=subsignal!(map.n2o)
                     />
                 <NumericInput
+                    scalable=true
                     label="Biodiversity"
                     help=r#"Effects on biodiversity, in "pressure"; e.g. -1 pressure means +1 to the extinction rate."#
                     signal=subsignal!(map.biodiversity)
                     />
+                <NumericInput
+                    scalable=true
+                    label="Particulates"
+                    help="Particulate matter (e.g. soot and dust) in grams. It clears from the air within a year or so."
+                    unit=Unit::Grams
+                    signal=subsignal!(map.particulates)
+                    />
+                <NumericInput
+                    scalable=true
+                    label="Plastic"
+                    help="Plastic released into the environment, in grams. It barely breaks down, so it piles up."
+                    unit=Unit::Grams
+                    signal=subsignal!(map.plastic)
+                    />
             </div>
         </div>
     }
@@ -424,16 +1534,26 @@ pub fn OutputMapInput(
     view! {
         <div class="map-group output-group">
             <h2 class="tooltip-parent">
-                {label}
+                {t!(&label)}
                 {move || {
                      (!help.get_value().is_empty()).then(|| {
                          view! {
-                             <div class="tooltip">{help.get_value()}</div>
+                             <div class="tooltip">{t!(&help.get_value())}</div>
                          }
                      })
                 }}
             </h2>
-            <div class="map-inputs">
+            <div class="map-inputs" on:paste=move |ev| {
+                if let Some(values) = pasted_row(&ev, 4) {
+                    let (read, write) = signal;
+                    let mut map = read.get();
+                    map.fuel = values[0];
+                    map.electricity = values[1];
+                    map.plant_calories = values[2];
+                    map.animal_calories = values[3];
+                    write.set(map);
+                }
+            }>
                 <NumericInput
                     label="Fuel"
                     help="Fuel in kilowatt-hours (kWh)."
@@ -471,44 +1591,68 @@ pub fn FeedstockMapInput(
     view! {
         <div class="map-group feedstocks-group">
             <h2 class="tooltip-parent">
-                {label}
+                {t!(&label)}
                 {move || {
                      (!help.get_value().is_empty()).then(|| {
                          view! {
-                             <div class="tooltip">{help.get_value()}</div>
+                             <div class="tooltip">{t!(&help.get_value())}</div>
                          }
                      })
                 }}
             </h2>
-            <div class="map-inputs">
+            <div class="map-inputs" on:paste=move |ev| {
+                if let Some(values) = pasted_row(&ev, 6) {
+                    let (read, write) = signal;
+                    let mut map = read.get();
+                    map.coal = values[0];
+                    map.oil = values[1];
+                    map.natural_gas = values[2];
+                    map.thorium = values[3];
+                    map.uranium = values[4];
+                    map.lithium = values[5];
+                    write.set(map);
+                }
+            }>
                 <NumericInput
+                    scalable=true
                     label="Coal"
                     help="Coal in grams (g)."
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.coal)
                     />
                 <NumericInput
+                    scalable=true
                     label="Oil"
                     help="Oil in liters (L)."
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.oil)
                     />
                 <NumericInput
+                    scalable=true
                     label="Natural Gas"
                     help="Natural Gas in liters (L)"
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.natural_gas)
                     />
                 <NumericInput
+                    scalable=true
                     label="Thorium"
                     help="Thorium in grams (g)."
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.thorium)
                     />
                 <NumericInput
+                    scalable=true
                     label="Uranium"
                     help="Uranium in grams (g)."
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.uranium)
                     />
                 <NumericInput
+                    scalable=true
                     label="Lithium"
                     help="Lithium in grams (g)."
+                    bounds=Some(Bounds::NON_NEGATIVE)
                     signal=subsignal!(map.lithium)
                     />
             </div>
@@ -532,39 +1676,187 @@ pub fn EnumInput<
     #[prop(into)] label: String,
     #[prop(into)] help: String,
     #[prop(into, optional)] tooltip: bool,
+    #[prop(optional)] default: Option<E>,
+    #[prop(into, optional)] baseline: Option<
+        Signal<Option<E>>,
+    >,
 ) -> impl IntoView
 where
     <E as FromStr>::Err: Debug,
 {
     let (read, write) = signal;
 
-    let opts = move || {
-        let current = read.get_untracked();
-        E::iter()
-            .map(|var| {
-                let label: &'static str = var.into();
-                view! {
-                    <option selected={var == current} value=label>{var.to_string()}</option>
+    let opts = move || {
+        let current = read.get_untracked();
+        E::iter()
+            .map(|var| {
+                let label: &'static str = var.into();
+                view! {
+                    <option selected={var == current} value=label>{var.to_string()}</option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let read_only = read_only();
+
+    view! {
+        <div class="input-group enum-select tooltip-parent">
+            <div class="enum-select-inner">
+                <label>{t!(&label)}</label>
+                <Show when=move || !read_only.get()
+                    fallback=move || view! {
+                        <div class="read-only-value">{move || read.get().to_string()}</div>
+                    }>
+                  <select
+                    prop:value=move || {
+                      let value: &'static str = read.get().into();
+                      value
+                    }
+                    on:change=move |ev| {
+                      let new_value = event_target_value(&ev);
+                      write.set(new_value.parse().unwrap());
+                    }
+                  >
+                    {opts}
+                  </select>
+                </Show>
+                <ResetButton signal default />
+                <BaselineMarker read baseline />
+            </div>
+            <div class:input-help=!tooltip class:tooltip=tooltip>{t!(&help)}</div>
+      </div>
+    }
+}
+
+/// An `EnumInput` for enums with many variants, which
+/// can be filtered by typing and selected with the keyboard.
+#[component]
+pub fn SearchableEnumInput<
+    E: IntoEnumIterator
+        + Debug
+        + Clone
+        + Copy
+        + Display
+        + PartialEq
+        + 'static,
+>(
+    signal: (Signal<E>, SignalSetter<E>),
+    #[prop(into)] label: String,
+    #[prop(into)] help: String,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let filter = create_rw_signal(String::new());
+
+    // Index of the highlighted result,
+    // for selecting with the keyboard.
+    let highlighted = create_rw_signal(0);
+
+    let results = create_memo(move |_| {
+        let filter = filter.get().to_lowercase();
+        E::iter()
+            .filter(|var| {
+                var.to_string().to_lowercase().contains(&filter)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let focused = create_rw_signal(false);
+    let select = move |var: E| {
+        write.set(var);
+        focused.set(false);
+    };
+
+    let ref_input = create_node_ref::<html::Input>();
+    create_effect(move |_| {
+        if focused.get() {
+            filter.set(String::new());
+            highlighted.set(0);
+            if let Some(ref_input) = ref_input.get() {
+                let _ = ref_input.on_mount(|input| {
+                    let _ = input.focus();
+                    input.select();
+                });
+            }
+        }
+    });
+
+    let on_keydown = move |ev: ev::KeyboardEvent| {
+        let n = results.with(|results| results.len());
+        match ev.key().as_str() {
+            "ArrowDown" => {
+                ev.prevent_default();
+                highlighted.update(|i| {
+                    *i = (*i + 1).min(n.saturating_sub(1))
+                });
+            }
+            "ArrowUp" => {
+                ev.prevent_default();
+                highlighted.update(|i| *i = i.saturating_sub(1));
+            }
+            "Enter" => {
+                let var = results.with(|results| {
+                    results.get(highlighted.get()).copied()
+                });
+                if let Some(var) = var {
+                    select(var);
                 }
-            })
-            .collect::<Vec<_>>()
+            }
+            "Escape" => focused.set(false),
+            _ => (),
+        }
     };
 
+    let target = create_node_ref::<html::Div>();
+    let _ = on_click_outside(target, move |_| {
+        focused.set(false);
+    });
+    let read_only = read_only();
+
     view! {
-        <div class="input-group enum-select tooltip-parent">
-            <div class="enum-select-inner">
-                <label>{label}</label>
-                  <select
-                    on:change=move |ev| {
-                      let new_value = event_target_value(&ev);
-                      write.set(new_value.parse().unwrap());
+        <div class="input-group picker-group searchable-enum" ref=target>
+            <div class="picker-group-header">
+                <label>{t!(&label)}</label>
+                <div class="picker-selected" on:click=move |_| {
+                    if !read_only.get() {
+                        focused.set(true);
                     }
-                  >
-                    {opts}
-                  </select>
+                }>{move || read.get().to_string()}</div>
             </div>
-            <div class:input-help=!tooltip class:tooltip=tooltip>{help}</div>
-      </div>
+            <div class="input-help">{t!(&help)}</div>
+            <Show when=move || focused.get()>
+                <div class="picker-filter">
+                    <input type="text"
+                        ref=ref_input
+                        placeholder=t!("Search")
+                        on:keydown=on_keydown
+                        on:input=move |ev| {
+                            filter.set(event_target_value(&ev));
+                            highlighted.set(0);
+                        }
+                    />
+                    <div class="picker-results">
+                        {move || {
+                            results.get()
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, var)| {
+                                    view! {
+                                        <div class="picker-opt"
+                                            class:highlighted=move || highlighted.get() == i
+                                            class:selected=move || read.get() == var
+                                            on:mouseenter=move |_| highlighted.set(i)
+                                            on:click=move |_| select(var)>
+                                            {var.to_string()}
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                        }}
+                    </div>
+                </div>
+            </Show>
+        </div>
     }
 }
 
@@ -593,40 +1885,147 @@ where
     <E as FromStr>::Err: Debug,
 {
     let (read, write) = signal;
+    let read_only = read_only();
+
+    let toggle = move |var: E| {
+        if read_only.get() {
+            return;
+        }
+        let mut current = read.get();
+        if current.contains(&var) {
+            current.retain(|v| v != &var);
+        } else {
+            current.push(var);
+        }
+        write.set(current);
+    };
 
     let opts = move || {
         let current = read.get();
         E::iter()
             .map(|var| {
+                let selected = current.contains(&var);
                 view! {
                     <div
                         class="multi-select-opt tooltip-parent"
-                        class:selected={current.contains(&var)}
-                        on:click=move |_| {
-                            let mut current = read.get();
-                            if current.contains(&var) {
-                                current.retain(|v| v != &var);
-                            } else {
-                                current.push(var);
+                        class:selected=selected
+                        role="checkbox"
+                        tabindex="0"
+                        aria-checked=selected.to_string()
+                        aria-disabled=move || read_only.get().to_string()
+                        aria-description=var.describe()
+                        on:click=move |_| toggle(var)
+                        on:keydown=move |ev| {
+                            if is_activation(&ev) {
+                                ev.prevent_default();
+                                toggle(var);
+                            }
+                        }
+                    >
+                        {var.to_string()}
+                        <div class="tooltip" role="tooltip">{var.describe()}</div>
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The selected variants, which can be dragged
+    // or moved with the arrow keys to change their order.
+    let dragging = create_rw_signal(None::<usize>);
+    let order_ref = create_node_ref::<html::Div>();
+    let move_item = move |from: usize, to: usize| {
+        let mut current = read.get();
+        if from == to || to >= current.len() {
+            return;
+        }
+        let var = current.remove(from);
+        current.insert(to, var);
+        write.set(current);
+    };
+    let on_order_keydown =
+        move |ev: ev::KeyboardEvent, i: usize| {
+            if read_only.get_untracked() {
+                return;
+            }
+            let to = match ev.key().as_str() {
+                "ArrowUp" | "ArrowLeft" => i.saturating_sub(1),
+                "ArrowDown" | "ArrowRight" => i + 1,
+                _ => return,
+            };
+            ev.prevent_default();
+            move_item(i, to);
+
+            // The items are re-rendered, so keep
+            // focus on the one that was moved.
+            request_animation_frame(move || {
+                let item = order_ref.get_untracked().and_then(
+                    |el| {
+                        el.query_selector_all(
+                            ".multi-select-order-item",
+                        )
+                        .ok()?
+                        .item(to as u32)
+                    },
+                );
+                if let Some(item) = item {
+                    let _ = item
+                        .unchecked_ref::<web_sys::HtmlElement>()
+                        .focus();
+                }
+            });
+        };
+    let order = move || {
+        read.get()
+            .into_iter()
+            .enumerate()
+            .map(|(i, var)| {
+                view! {
+                    <div
+                        class="multi-select-order-item"
+                        class:dragging=move || dragging.get() == Some(i)
+                        role="listitem"
+                        tabindex="0"
+                        aria-label=format!("{}. {var}", i + 1)
+                        draggable=move || (!read_only.get()).to_string()
+                        on:dragstart=move |_| dragging.set(Some(i))
+                        on:dragend=move |_| dragging.set(None)
+                        on:dragover=move |ev| ev.prevent_default()
+                        on:drop=move |ev| {
+                            ev.prevent_default();
+                            if let Some(from) = dragging.get() {
+                                move_item(from, i);
                             }
-                            write.set(current);
+                            dragging.set(None);
                         }
+                        on:keydown=move |ev| on_order_keydown(ev, i)
                     >
+                        <span class="multi-select-order-idx">{i + 1}</span>
                         {var.to_string()}
-                        <div class="tooltip">{var.describe()}</div>
                     </div>
                 }
             })
             .collect::<Vec<_>>()
     };
+    let n_selected = move || with!(|read| read.len());
 
     view! {
         <div class="input-group multi-select-group">
-            <label>{label}</label>
-            <div class="input-help">{help}</div>
-            <div class="multi-select-opts">
+            <label>{t!(&label)}</label>
+            <div class="input-help">{t!(&help)}</div>
+            <div class="multi-select-opts" role="group" aria-label=t!(&label)>
                 {opts}
             </div>
+            <Show when=move || n_selected() > 1>
+                <div
+                    ref=order_ref
+                    class="multi-select-order"
+                    role="list"
+                    aria-label=t!("{label} order", label: t!(&label))>
+                    <div class="input-help">{t!("Drag or use the arrow keys to change the order.")}</div>
+                    {order}
+                </div>
+            </Show>
       </div>
     }
 }
@@ -664,6 +2063,7 @@ pub fn MultiEntitySelect<T: AsRef + 'static>(
     #[prop(into, optional)] help: String,
 ) -> impl IntoView {
     let (current, write) = signal;
+    let read_only = read_only();
 
     let opts = move || {
         with!(|current, opts| opts
@@ -677,6 +2077,9 @@ pub fn MultiEntitySelect<T: AsRef + 'static>(
                         class="multi-select-opt"
                         class:selected={current.contains(&id)}
                         on:click=move |_| {
+                            if read_only.get() {
+                                return;
+                            }
                             if selected.contains(&id) {
                                 selected.retain(|v| v != &id);
                             } else {
@@ -694,8 +2097,8 @@ pub fn MultiEntitySelect<T: AsRef + 'static>(
 
     view! {
         <div class="input-group multi-select-group">
-            <label>{label}</label>
-            <div class="input-help">{help}</div>
+            <label>{t!(&label)}</label>
+            <div class="input-help">{t!(&help)}</div>
             <div class="multi-select-opts">
                 {opts}
             </div>
@@ -723,6 +2126,17 @@ pub fn EntityPicker<T: AsRef + 'static>(
     });
     let filter = create_rw_signal(initial);
 
+    // Reconcile external changes, e.g. undo or an import.
+    create_effect(move |_| {
+        let id = read.get();
+        local.set(id);
+        if let Some(label) = opts.with_untracked(|opts| {
+            opts.try_get(&id).map(|v| v.label.clone())
+        }) {
+            filter.set(label);
+        }
+    });
+
     // Does an entity with a matching id exist in the collection?
     let is_valid = move || {
         with!(|local, opts| opts.try_get(&local).is_some())
@@ -774,24 +2188,27 @@ pub fn EntityPicker<T: AsRef + 'static>(
             write.set(local.get());
         }
     });
+    let read_only = read_only();
 
     view! {
         <div class="input-group picker-group" ref=target>
             <div class="picker-group-header">
-                <label>{label}</label>
+                <label>{t!(&label)}</label>
                 <div class="picker-selected" on:click=move |_| {
-                    focused.set(true);
+                    if !read_only.get() {
+                        focused.set(true);
+                    }
                 }>{selected}</div>
             </div>
-            <div class="input-help">{help}</div>
+            <div class="input-help">{t!(&help)}</div>
             <Show when=move || !is_valid()>
-                <div class="input-error">"The selected entity doesn't exist."</div>
+                <div class="input-error">{t!("The selected entity doesn't exist.")}</div>
             </Show>
             <Show when=move || focused.get()>
                 <div class="picker-filter">
                     <input type="text"
                         ref=ref_input
-                        placeholder="Search"
+                        placeholder=t!("Search")
                         value={filter.get_untracked()}
                         on:input=move |ev| {
                             let value = event_target_value(&ev);
@@ -807,14 +2224,9 @@ pub fn EntityPicker<T: AsRef + 'static>(
     }
 }
 
-#[component]
-pub fn ImageInput(
-    signal: (Signal<Image>, SignalSetter<Image>),
-) -> impl IntoView {
-    let (read, write) = signal;
-    let help = "Images will be bundled with your exported world, so it's recommended that you make sure they aren't too big. Recommended size is 360x240.";
-
-    let image_src = move || match read.get().data {
+/// The URL to display an image from.
+pub fn image_src(image: &Image) -> String {
+    match &image.data {
         ImageData::File(fname) => {
             format!("/public/images/{fname}",)
         }
@@ -822,17 +2234,35 @@ pub fn ImageInput(
             "data:{mime};charset=utf-8;base64,{}",
             BASE64_STANDARD.encode(bytes)
         ),
-    };
+    }
+}
+
+#[component]
+pub fn ImageInput(
+    signal: (Signal<Image>, SignalSetter<Image>),
+) -> impl IntoView {
+    let (read, write) = signal;
+    let help = t!("Images will be bundled with your exported world, so it's recommended that you make sure they aren't too big. Recommended size is 360x240.");
+    let read_only = read_only();
 
     view! {
-        <div class="image-input">
-            <img src={image_src} />
+        <div class="image-input" role="group" aria-label="Image">
+            <Thumbnail image=read />
             <TextInput label="Attribution"
                 inline=true
                 signal=subsignal!(signal.attribution) />
+            <TextInput label="License"
+                inline=true
+                help="The license the image is used under, e.g. \"CC BY 4.0\"."
+                signal=subsignal!(signal.license) />
+            <TextInput label="Alt Text"
+                help="Describes the image for players using screen readers."
+                signal=subsignal!(signal.alt) />
             <input
                 type="file"
                 multiple=false
+                class:hidden=read_only
+                aria-label="Upload an image"
                 accept="image/png, image/gif, image/jpeg, image/webp"
                 on:input=move |ev| {
                     let files = ev.target().unwrap()
@@ -852,55 +2282,285 @@ pub fn ImageInput(
                     }
                 }
             />
+            <Show when=move || desktop::is_desktop() && !read_only.get()>
+                <button
+                    title=t!("Pick an image from your computer.")
+                    on:click=move |_| {
+                        spawn_local(async move {
+                            if let Some((bytes, mime)) = desktop::pick_image().await {
+                                let mut image = read.get();
+                                image.data = ImageData::Data {
+                                    bytes,
+                                    mime,
+                                };
+                                write.set(image);
+                            }
+                        })
+                    }>{t!("From Library")}</button>
+            </Show>
             <div class="input-help">{help}</div>
         </div>
     }
 }
 
+/// Edit an ordered list of images.
 #[component]
-pub fn OptionalImageInput(
-    signal: (
-        Signal<Option<Image>>,
-        SignalSetter<Option<Image>>,
-    ),
+pub fn GalleryInput(
+    signal: (Signal<Vec<Image>>, SignalSetter<Vec<Image>>),
+    #[prop(into)] label: String,
+    #[prop(into, optional)] help: String,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+
+    view! {
+        <div class="gallery mutable-list mutable-list-sortable">
+            <div class="mutable-list-header">
+                <h2>{t!(&label)}</h2>
+                <div class="mutable-list-add" class:hidden=read_only>
+                    <div class="mutable-list-add-button" on:click=move |_| {
+                        let mut images = read.get();
+                        images.push(Image::default());
+                        write.set(images);
+                    }>+Add</div>
+                </div>
+            </div>
+            <div class="input-help">{t!(&help)}</div>
+            <Show when=move || with!(|read| read.is_empty())>
+                <div class="empty">{t!("No images.")}</div>
+            </Show>
+            <div class="mutable-list-items">
+                {move || {
+                     let n_images = with!(|read| read.len());
+                     (0..n_images).map(|i| {
+                         view! {
+                             <div class="gallery-image mutable-list-item">
+                                 <div class="mutable-list-item-index">
+                                    <div class="move-up" class:hidden={i == 0 || read_only.get()} on:click=move |_| {
+                                         let mut images = read.get();
+                                         images.swap(i, i-1);
+                                         write.set(images);
+                                    }>"⯅"</div>
+                                    {i}
+                                    <div class="move-down" class:hidden={i == n_images - 1 || read_only.get()} on:click=move |_| {
+                                         let mut images = read.get();
+                                         images.swap(i, i+1);
+                                         write.set(images);
+                                    }>"⯆"</div>
+                                 </div>
+                                 <ImageInput signal=subsignal!(signal[i]) />
+                                 <div class="mutable-list-item-remove" class:hidden=read_only title=t!("Ctrl-click to remove without confirmation.") on:click=move |ev: ev::MouseEvent| {
+                                     let msg = "Are you sure you want to remove this image?";
+                                     if ev.ctrl_key() || window().confirm_with_message(msg).unwrap() {
+                                         let mut images = read.get();
+                                         images.remove(i);
+                                         write.set(images);
+                                     }
+                                 }>"✗"</div>
+                             </div>
+                         }
+                     }).collect::<Vec<_>>()
+                 }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn AudioInput(
+    signal: (Signal<Audio>, SignalSetter<Audio>),
+) -> impl IntoView {
+    let (read, write) = signal;
+    let help = t!("Sounds will be bundled with your exported world, so it's recommended that you keep them short.");
+    let read_only = read_only();
+
+    let audio_src = move || {
+        with!(|read| {
+            (!read.bytes.is_empty()).then(|| {
+                format!(
+                    "data:{};base64,{}",
+                    read.mime,
+                    BASE64_STANDARD.encode(&read.bytes)
+                )
+            })
+        })
+    };
+
+    view! {
+        <div class="audio-input">
+            <audio controls=true src={audio_src} />
+            <input
+                type="file"
+                multiple=false
+                class:hidden=read_only
+                accept="audio/ogg, audio/mpeg"
+                on:input=move |ev| {
+                    let files = ev.target().unwrap()
+                        .unchecked_ref::<web_sys::HtmlInputElement>()
+                        .files().unwrap();
+                    if let Some(file) = files.get(0) {
+                        let mime = file.type_();
+                        spawn_local(async move {
+                            let bytes = read_file(file).await;
+                            write.set(Audio { bytes, mime });
+                        })
+                    }
+                }
+            />
+            <div class="input-help">{help}</div>
+        </div>
+    }
+}
+
+/// Pick one of the game's icons from a searchable grid,
+/// storing its name, e.g. `"political_capital"`.
+#[component]
+pub fn IconInput(
+    signal: (Signal<String>, SignalSetter<String>),
+    #[prop(into)] label: String,
+    #[prop(into, optional)] help: String,
 ) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+    let search = create_rw_signal(String::new());
+
+    let icons = move || {
+        let query = search.get().to_lowercase();
+        ICONS
+            .iter()
+            .filter(|(name, _)| name.contains(&query))
+            .map(|(name, path)| {
+                let selected = move || read.get() == *name;
+                view! {
+                    <div class="icon-option"
+                        class:selected=selected
+                        title=*name
+                        on:click=move |_| write.set(name.to_string())>
+                        <img src=*path />
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let current = move || {
+        let name = read.get();
+        if name.is_empty() {
+            return None;
+        }
+        Some(match icon_path(&name) {
+            Some(path) => view! {
+                <div class="icon-current">
+                    <img src=path />
+                    <span>{name}</span>
+                </div>
+            }
+            .into_view(),
+            None => view! {
+                <div class="input-error">{format!("The game has no icon named \"{name}\".")}</div>
+            }
+            .into_view(),
+        })
+    };
+
+    view! {
+        <div class="input-group icon-group">
+            <label>{t!(&label)}</label>
+            <div class="input-help">{t!(&help)}</div>
+            {current}
+            <Show when=move || !read_only.get()>
+                <input
+                    class="icon-search"
+                    placeholder=t!("Search icons...")
+                    prop:value=search
+                    on:input=move |ev| search.set(event_target_value(&ev)) />
+                <div class="icon-grid">{icons}</div>
+            </Show>
+        </div>
+    }
+}
+
+/// A toggle for an optional value, with an editor
+/// for the value shown when it's set. The last value
+/// is remembered, so toggling off and back on restores it.
+#[component]
+pub fn OptionalInput<T, F, IV>(
+    signal: (Signal<Option<T>>, SignalSetter<Option<T>>),
+    editor: F,
+    #[prop(into)] label: String,
+    #[prop(into, optional)] help: String,
+    #[prop(into, optional)] fallback: ViewFn,
+) -> impl IntoView
+where
+    T: Clone + Default + 'static,
+    F: Fn((Signal<T>, SignalSetter<T>)) -> IV + 'static,
+    IV: IntoView,
+{
     let (read, write) = signal;
     let value = create_rw_signal(
-        read.get_untracked().unwrap_or_else(Image::default),
+        read.get_untracked().unwrap_or_default(),
     );
 
     view! {
         <div class="input-group option-group">
             <ToggleInput
-                label="Include Image"
-                help=""
+                label=label
+                help=help
                 signal=(
                     Signal::derive(move || with!(|read| read.is_some())),
-                    SignalSetter::map(move |enable| {
-                        let value = if enable {
-                            Some(value.get())
-                        } else {
-                            None
-                        };
-                        write.set(value);
+                    SignalSetter::map(move |enable: bool| {
+                        write.set(enable.then(|| value.get()));
                     })) />
-            <Show when=move || with!(|read| read.is_some())
-                fallback=move || view! { <div class="image-placeholder" /> }>
-                <ImageInput
-                    signal=(
-                        Signal::derive(move || read.get().unwrap()),
-                        SignalSetter::map(move |image: Image| {
-                            let mut opt = read.get();
-                            opt.replace(image.clone());
-                            value.set(image);
-                            write.set(opt);
-                        })
-                    ) />
+            <Show when=move || with!(|read| read.is_some()) fallback>
+                {editor((
+                    Signal::derive(move || {
+                        read.get().unwrap_or_else(|| value.get())
+                    }),
+                    SignalSetter::map(move |val: T| {
+                        value.set(val.clone());
+                        write.set(Some(val));
+                    }),
+                ))}
             </Show>
         </div>
     }
 }
 
+#[component]
+pub fn OptionalImageInput(
+    signal: (
+        Signal<Option<Image>>,
+        SignalSetter<Option<Image>>,
+    ),
+) -> impl IntoView {
+    view! {
+        <OptionalInput
+            label="Include Image"
+            signal
+            fallback=move || view! { <div class="image-placeholder" /> }
+            editor=move |signal: (Signal<Image>, SignalSetter<Image>)| {
+                view! { <ImageInput signal /> }
+            } />
+    }
+}
+
+#[component]
+pub fn OptionalAudioInput(
+    signal: (
+        Signal<Option<Audio>>,
+        SignalSetter<Option<Audio>>,
+    ),
+) -> impl IntoView {
+    view! {
+        <OptionalInput
+            label="Include Sound"
+            help="A sound to play when this occurs."
+            signal
+            editor=move |signal: (Signal<Audio>, SignalSetter<Audio>)| {
+                view! { <AudioInput signal /> }
+            } />
+    }
+}
+
 async fn read_file(file: File) -> Vec<u8> {
     let blob: &Blob = file.as_ref();
     let array_buffer_promise = blob.array_buffer();
@@ -913,6 +2573,12 @@ async fn read_file(file: File) -> Vec<u8> {
     uint8_array.to_vec()
 }
 
+/// Whether a key press should activate a focused
+/// control, as a click would.
+fn is_activation(ev: &ev::KeyboardEvent) -> bool {
+    matches!(ev.key().as_str(), "Enter" | " ")
+}
+
 #[component]
 pub fn ToggleInput(
     signal: (Signal<bool>, SignalSetter<bool>),
@@ -924,8 +2590,19 @@ pub fn ToggleInput(
         &'static str,
         &'static str,
     )>,
+    #[prop(into, optional)] baseline: Option<
+        Signal<Option<bool>>,
+    >,
 ) -> impl IntoView {
     let (read, write) = signal;
+    let read_only = read_only();
+    let label = t!(&label);
+    let name = (!label.is_empty()).then(|| label.clone());
+    let toggle = move || {
+        if !read_only.get() {
+            write.set(!read.get());
+        }
+    };
     let display = move || {
         let toggled = read.get();
         icons.map_or_else(
@@ -949,14 +2626,25 @@ pub fn ToggleInput(
     view! {
         <div class="input-group checkbox-group tooltip-parent">
             <div class="checkbox-inner">
-                <label on:click=move |_| {
-                    write.set(!read.get());
-                }>
+                <label
+                    role="switch"
+                    tabindex="0"
+                    aria-label=name
+                    aria-checked=move || read.get().to_string()
+                    aria-disabled=move || read_only.get().to_string()
+                    on:click=move |_| toggle()
+                    on:keydown=move |ev| {
+                        if is_activation(&ev) {
+                            ev.prevent_default();
+                            toggle();
+                        }
+                    }>
                     {display}
                 </label>
+                <BaselineMarker read baseline />
                 {inner.run()}
             </div>
-            <div class:input-help=!tooltip class:tooltip=tooltip>{help}</div>
+            <div class:input-help=!tooltip class:tooltip=tooltip>{t!(&help)}</div>
         </div>
     }
 }
@@ -966,17 +2654,154 @@ pub fn TextArea(
     signal: (Signal<String>, SignalSetter<String>),
     #[prop(into, optional)] label: String,
     #[prop(into, optional)] help: String,
+    #[prop(optional)] lints: Lints,
+    #[prop(optional)] commit: Commit,
 ) -> impl IntoView {
     let (read, write) = signal;
+    let read_only = read_only();
+    let (on_input, on_change) = commit_handlers(write, commit);
     view! {
         <div class="input-group text-area-group">
-            <label>{label}</label>
-            <div class="input-help">{help}</div>
-            <textarea
-                on:input=move |ev| {
-                    let value = event_target_value(&ev);
-                    write.set(value);
-                }>{read.get_untracked()}</textarea>
+            <label>{t!(&label)}</label>
+            <div class="input-help">{t!(&help)}</div>
+            <Show when=move || !read_only.get()
+                fallback=move || view! {
+                    <div class="read-only-value read-only-text">{read}</div>
+                }>
+                <textarea
+                    prop:value=read
+                    on:input=on_input.clone()
+                    on:change=on_change.clone()>{read.get_untracked()}</textarea>
+            </Show>
+            <LintErrors text=read lints />
+        </div>
+    }
+}
+
+/// A text area for flavor text with a live preview,
+/// rendered with the same formatting the game applies.
+#[component]
+pub fn MarkdownInput(
+    signal: (Signal<String>, SignalSetter<String>),
+    #[prop(into, optional)] label: String,
+    #[prop(into, optional)] help: String,
+    #[prop(optional)] lints: Lints,
+    #[prop(optional)] commit: Commit,
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+    let (on_input, on_change) = commit_handlers(write, commit);
+    let preview = move || crate::format::render(&read.get());
+
+    // Insert an icon reference at the cursor,
+    // or at the end if the text hasn't been focused.
+    let textarea_ref = create_node_ref::<html::Textarea>();
+    let picking_icon = create_rw_signal(false);
+    let insert_icon = SignalSetter::map(move |name: String| {
+        let mut text = read.get_untracked();
+        let cursor = textarea_ref
+            .get_untracked()
+            .and_then(|el| el.selection_start().ok().flatten());
+        let idx = match cursor {
+            // The cursor is in UTF-16 code units.
+            Some(cursor) => {
+                let mut units = 0;
+                text.char_indices()
+                    .find(|(_, c)| {
+                        units += c.len_utf16();
+                        units > cursor as usize
+                    })
+                    .map_or(text.len(), |(idx, _)| idx)
+            }
+            None => text.len(),
+        };
+        text.insert_str(idx, &format!("[{name}]"));
+        write.set(text);
+        picking_icon.set(false);
+    });
+
+    view! {
+        <div class="input-group text-area-group markdown-group">
+            <label>{t!(&label)}</label>
+            <div class="input-help">{t!(&help)}</div>
+            <div class="markdown-input">
+                <Show when=move || !read_only.get()>
+                    <textarea
+                        ref=textarea_ref
+                        prop:value=read
+                        on:input=on_input.clone()
+                        on:change=on_change.clone()>{read.get_untracked()}</textarea>
+                </Show>
+                <div class="markdown-preview" inner_html=preview></div>
+            </div>
+            <LintErrors text=read lints />
+            <div class="markdown-help">
+                {t!("Use **bold**, *italic*, {variable} and [icon]. Separate paragraphs with a blank line.")}
+                <Show when=move || !read_only.get()>
+                    <button on:click=move |_| picking_icon.update(|picking| *picking = !*picking)>
+                        {move || if picking_icon.get() { t!("Cancel") } else { t!("Insert Icon") }}
+                    </button>
+                </Show>
+            </div>
+            <Show when=move || picking_icon.get()>
+                <IconInput
+                    label="Insert Icon"
+                    help="Click an icon to insert it at the cursor."
+                    signal=(Signal::derive(String::new), insert_icon) />
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+pub fn TagsInput(
+    signal: (Signal<Vec<String>>, SignalSetter<Vec<String>>),
+) -> impl IntoView {
+    let (read, write) = signal;
+    let read_only = read_only();
+
+    let tags = move || {
+        read.get()
+            .into_iter()
+            .map(|tag| {
+                let label = tag.clone();
+                view! {
+                    <div class="tag">
+                        {label}
+                        <span class="tag-remove" class:hidden=read_only on:click=move |_| {
+                            let mut tags = read.get();
+                            tags.retain(|t| t != &tag);
+                            write.set(tags);
+                        }>"✗"</span>
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div class="input-group tags-group">
+            <label>{t!("Tags")}</label>
+            <div class="tags">
+                {tags}
+                <Show when=move || !read_only.get()>
+                    <input
+                        class="tag-input"
+                        placeholder=t!("+ Tag")
+                        on:keydown=move |ev| {
+                            if ev.key() == "Enter" {
+                                let value = event_target_value(&ev);
+                                let tag = value.trim().to_lowercase();
+                                let mut tags = read.get();
+                                if !tag.is_empty() && !tags.contains(&tag) {
+                                    tags.push(tag);
+                                    write.set(tags);
+                                }
+                                event_target::<web_sys::HtmlInputElement>(&ev).set_value("");
+                            }
+                        } />
+                </Show>
+            </div>
         </div>
     }
 }
@@ -0,0 +1,335 @@
+/// Suffixes for large numbers, e.g. "3.5M".
+/// These are case-sensitive so that they don't
+/// get mixed up with units like "t" (tonnes).
+const SI_SUFFIXES: [(char, f64); 5] = [
+    ('k', 1e3),
+    ('M', 1e6),
+    ('G', 1e9),
+    ('B', 1e9),
+    ('T', 1e12),
+];
+
+/// Parse a plain number, rejecting "inf" and "NaN"
+/// which Rust otherwise accepts.
+pub fn parse_finite(value: &str) -> Option<f32> {
+    value.trim().parse::<f32>().ok().filter(|v| v.is_finite())
+}
+
+/// Parse a number which may be written in scientific
+/// notation ("1.2e9"), with an SI suffix ("12k"),
+/// or with thousands separators ("1,200,000").
+pub fn parse_number(value: &str) -> Option<f64> {
+    let value: String = value
+        .trim()
+        .chars()
+        .filter(|c| *c != ',' && *c != '_')
+        .collect();
+    // Rust parses "inf" and "NaN" as numbers,
+    // but they're never valid values.
+    if let Ok(num) = value.parse::<f64>() {
+        return num.is_finite().then_some(num);
+    }
+
+    let last = value.chars().last()?;
+    let (_, scale) = SI_SUFFIXES
+        .iter()
+        .find(|(suffix, _)| *suffix == last)?;
+    let num: f64 = value[..value.len() - last.len_utf8()]
+        .trim()
+        .parse()
+        .ok()?;
+    Some(num * scale).filter(|num| num.is_finite())
+}
+
+/// Evaluate a simple arithmetic expression,
+/// e.g. "450*8760" or "(1e6 + 2M) / 3". Numbers
+/// can be written in any form `parse_number` accepts.
+pub fn evaluate(expr: &str) -> Option<f64> {
+    if let Some(num) = parse_number(expr) {
+        return Some(num);
+    }
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let value = parser.expr()?;
+    if parser.pos == parser.tokens.len() && value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Num(f64),
+    Op(char),
+}
+
+fn tokenize(expr: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if "+-*/()".contains(c) {
+            tokens.push(Token::Op(c));
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() {
+                let c = chars[i];
+                let is_exp = (c == 'e' || c == 'E')
+                    && chars.get(i + 1).is_some_and(|next| {
+                        next.is_ascii_digit()
+                            || *next == '-'
+                            || *next == '+'
+                    });
+                if is_exp {
+                    // Skip the exponent's sign, if any.
+                    i += 2;
+                } else if c.is_ascii_digit()
+                    || ".,_".contains(c)
+                {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            if chars.get(i).is_some_and(|c| {
+                SI_SUFFIXES
+                    .iter()
+                    .any(|(suffix, _)| suffix == c)
+            }) {
+                i += 1;
+            }
+            let num: String = chars[start..i].iter().collect();
+            tokens.push(Token::Num(parse_number(&num)?));
+        } else {
+            return None;
+        }
+    }
+    Some(tokens)
+}
+
+/// A recursive descent parser for arithmetic expressions.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+impl Parser {
+    fn next_op(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(*op),
+            _ => None,
+        }
+    }
+
+    /// expr = term (("+" | "-") term)*
+    fn expr(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.next_op() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' {
+                value + rhs
+            } else {
+                value - rhs
+            };
+        }
+        Some(value)
+    }
+
+    /// term = factor (("*" | "/") factor)*
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.next_op() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            value = if op == '*' {
+                value * rhs
+            } else {
+                value / rhs
+            };
+        }
+        Some(value)
+    }
+
+    /// factor = "-" factor | "(" expr ")" | number
+    fn factor(&mut self) -> Option<f64> {
+        let token = *self.tokens.get(self.pos)?;
+        self.pos += 1;
+        match token {
+            Token::Num(num) => Some(num),
+            Token::Op('-') => self.factor().map(|v| -v),
+            Token::Op('(') => {
+                let value = self.expr()?;
+                if self.next_op() != Some(')') {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            Token::Op(_) => None,
+        }
+    }
+}
+
+/// Parse a row of values, e.g. copied from a spreadsheet.
+/// Values are separated by tabs or newlines, or otherwise
+/// by semicolons or commas. Returns `None` unless there
+/// are multiple values and all of them are valid.
+pub fn parse_row(text: &str) -> Option<Vec<f32>> {
+    let text = text.trim();
+    let parts: Vec<&str> = if text.contains(['\t', '\n']) {
+        text.split(['\t', '\n']).collect()
+    } else if text.contains(';') {
+        text.split(';').collect()
+    } else {
+        text.split(',').collect()
+    };
+    if parts.len() < 2 {
+        return None;
+    }
+    parts
+        .into_iter()
+        .map(|part| evaluate(part).map(|num| num as f32))
+        .collect()
+}
+
+/// Format a number with thousands separators,
+/// e.g. "1234567.5" becomes "1,234,567.5".
+pub fn format_number(value: &str) -> String {
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", value),
+    };
+    let (int, frac) = match value.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (value, None),
+    };
+
+    // Leave anything unusual, e.g. "1e21", as-is.
+    if !int.chars().all(|c| c.is_ascii_digit()) {
+        return format!("{sign}{value}");
+    }
+
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    match frac {
+        Some(frac) => format!("{sign}{grouped}.{frac}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+/// Scale weights so that they sum to `total`. If `whole`
+/// the results are whole numbers, rounded using the largest
+/// remainder method so that they still sum to `total`.
+/// Returns `None` if the weights sum to zero.
+pub fn normalize(
+    weights: &[f32],
+    total: f32,
+    whole: bool,
+) -> Option<Vec<f32>> {
+    let sum: f32 = weights.iter().sum();
+    if sum <= 0. {
+        return None;
+    }
+    let scaled: Vec<f32> =
+        weights.iter().map(|w| w / sum * total).collect();
+    if !whole {
+        return Some(scaled);
+    }
+
+    let mut rounded: Vec<f32> =
+        scaled.iter().map(|w| w.floor()).collect();
+    let remaining = total - rounded.iter().sum::<f32>();
+    let mut by_remainder: Vec<usize> =
+        (0..scaled.len()).collect();
+    by_remainder.sort_by(|a, b| {
+        let rem_a = scaled[*a] - rounded[*a];
+        let rem_b = scaled[*b] - rounded[*b];
+        rem_b.total_cmp(&rem_a)
+    });
+    for i in by_remainder
+        .into_iter()
+        .take(remaining.round() as usize)
+    {
+        rounded[i] += 1.;
+    }
+    Some(rounded)
+}
+
+/// The unit a numeric value is stored in. Values can
+/// also be entered in larger units of the same kind,
+/// e.g. "2 TWh" for a value stored in kWh, and are
+/// converted to the stored unit when the input changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    KilowattHours,
+    Liters,
+    Grams,
+}
+impl Unit {
+    /// The units that can be entered, and how many
+    /// of the stored unit each is equal to.
+    /// The first is the stored unit.
+    fn scales(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            Unit::KilowattHours => &[
+                ("kWh", 1.),
+                ("MWh", 1e3),
+                ("GWh", 1e6),
+                ("TWh", 1e9),
+            ],
+            Unit::Liters => &[
+                ("L", 1.),
+                ("m3", 1e3),
+                ("m³", 1e3),
+                ("km3", 1e12),
+            ],
+            Unit::Grams => &[
+                ("g", 1.),
+                ("kg", 1e3),
+                ("t", 1e6),
+                ("kt", 1e9),
+                ("Mt", 1e12),
+            ],
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.scales()[0].0
+    }
+
+    /// Parse a value with an optional unit suffix,
+    /// converting it to the stored unit.
+    pub fn convert(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        if let Some(num) = evaluate(value) {
+            return Some(num.to_string());
+        }
+
+        // Check longer units first, so that
+        // e.g. "kg" isn't mistaken for "g".
+        let mut scales = self.scales().to_vec();
+        scales.sort_by_key(|(unit, _)| {
+            std::cmp::Reverse(unit.len())
+        });
+        scales.into_iter().find_map(|(unit, scale)| {
+            let split = value.len().checked_sub(unit.len())?;
+            let (num, suffix) =
+                value.split_at_checked(split)?;
+            if !suffix.eq_ignore_ascii_case(unit) {
+                return None;
+            }
+            let num = evaluate(num)?;
+            Some((num * scale).to_string())
+        })
+    }
+}
@@ -1,18 +1,46 @@
+mod analyze;
+mod baseline;
+mod checkpoints;
+mod clipboard;
+mod collab;
+mod conflicts;
+mod desktop;
 mod files;
+mod filler;
+mod format;
+mod generate;
+pub mod i18n;
+mod icons;
 mod inputs;
+mod lint;
+mod recent;
+mod rescale;
+mod share;
+mod signing;
+mod summary;
 mod tabs;
+mod templates;
+mod theme;
+mod thumbnails;
+mod trash;
 mod validate;
+pub mod worker;
 mod worlds;
 
+use baseline::Baseline;
+use collab::{Collab, CollabMenu};
 use files::load_session;
-use hes_engine::{Collection, World, NPC};
-use inputs::{AsRef, Ref};
+use hes_engine::{Collection, Flag, World, LIFESPAN, NPC};
+use inputs::{AsRef, ReadOnly, Ref, Timeline};
 use leptos::*;
 use leptos_toaster::{Toaster, ToasterPosition};
+use leptos_use::{use_element_size, use_element_visibility};
+use rescale::{BatchScale, Target};
+use share::Shared;
 use strum::{Display, EnumIter, IntoEnumIterator};
 use tabs::*;
 use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::Event;
+use web_sys::{Event, UrlSearchParams};
 use worlds::WorldsMenu;
 
 #[derive(Debug, Clone, Copy, Display, EnumIter, PartialEq)]
@@ -22,6 +50,14 @@ enum Tab {
     Processes,
     Projects,
     Events,
+    Chains,
+    Translations,
+    Find,
+    Trash,
+    Checkpoints,
+    Preview,
+    Playtest,
+    Analysis,
     Help,
 }
 
@@ -44,6 +80,68 @@ fn before_unload() {
     closure.forget();
 }
 
+/// Open a world or entity shared through a link.
+fn open_shared(
+    shared: anyhow::Result<Shared>,
+    world: RwSignal<World>,
+    tab: RwSignal<Tab>,
+) -> Result<(), Vec<String>> {
+    let shared = shared
+        .map_err(|err| vec![format!("The link is invalid: {err}")])?;
+    add_shared(shared, world, tab)
+}
+
+/// Add a shared or restored entity to the world, switching
+/// to its tab. Worlds replace the current one instead.
+fn add_shared(
+    shared: Shared,
+    world: RwSignal<World>,
+    tab: RwSignal<Tab>,
+) -> Result<(), Vec<String>> {
+    match shared {
+        Shared::World(shared) => {
+            world.set(*shared);
+        }
+        Shared::Industry(item) => {
+            let item = with!(|world| clipboard::adopt(
+                item,
+                &world.industries,
+                world
+            ))?;
+            update!(|world| world.industries.push_front(item));
+            tab.set(Tab::Industries);
+        }
+        Shared::Process(item) => {
+            let item = with!(|world| clipboard::adopt(
+                item,
+                &world.processes,
+                world
+            ))?;
+            update!(|world| world.processes.push_front(item));
+            tab.set(Tab::Processes);
+        }
+        Shared::Project(item) => {
+            let item = with!(|world| clipboard::adopt(
+                item,
+                &world.projects,
+                world
+            ))?;
+            update!(|world| world.projects.push_front(item));
+            tab.set(Tab::Projects);
+        }
+        Shared::Event(item) => {
+            let item = with!(|world| clipboard::adopt(
+                item,
+                &world.events,
+                world
+            ))?;
+            update!(|world| world.events.push_front(item));
+            tab.set(Tab::Events);
+        }
+    }
+    Ok(())
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     before_unload();
@@ -57,6 +155,37 @@ pub fn App() -> impl IntoView {
     let world = create_rw_signal(start_world);
     let npcs = NPC::load();
 
+    if let Some(shared) = share::take_shared() {
+        if let Err(errs) = open_shared(shared, world, tab) {
+            let msg = format!(
+                "Couldn't open the shared link:\n{}",
+                errs.join("\n")
+            );
+            let _ = window().alert_with_message(&msg);
+        }
+    }
+
+    // Worlds shared for review can be opened with
+    // `?review` to start in read-only mode.
+    let review = window()
+        .location()
+        .search()
+        .and_then(|query| UrlSearchParams::new_with_str(&query))
+        .is_ok_and(|params| params.has("review"));
+    let read_only = create_rw_signal(review);
+    provide_context(ReadOnly(read_only));
+
+    // The world to compare against, if any.
+    provide_context(Baseline(create_rw_signal(None)));
+
+    theme::ThemeSetting::provide();
+
+    // Other editors working on the same world, if any.
+    Collab::provide(world);
+
+    // Deleted entities, so they can be restored.
+    trash::TrashBin::provide();
+
     provide_context(Signal::derive(move || {
         npcs.clone()
             .iter()
@@ -92,11 +221,42 @@ pub fn App() -> impl IntoView {
             .map(|item| item.as_ref())
             .collect::<Collection<Ref<_>>>())
     }));
+    provide_context(Signal::derive(move || {
+        with!(|world| world
+            .variables
+            .iter()
+            .map(|item| item.as_ref())
+            .collect::<Collection<Ref<_>>>())
+    }));
+    provide_context(Signal::derive(move || {
+        with!(|world| world
+            .tipping_points
+            .iter()
+            .map(|item| item.as_ref())
+            .collect::<Collection<Ref<_>>>())
+    }));
+
+    // Flags set by effects, so conditions on flags
+    // that are never set can be flagged.
+    let flags_set = create_memo(move |_| {
+        with!(|world| validate::flags_set(world))
+    });
+    provide_context(Signal::<Vec<Flag>>::from(flags_set));
+
+    // The game's timespan, for placing years on a timeline.
+    let timeline = create_memo(move |_| {
+        with!(|world| Timeline {
+            start: world.year as f32,
+            end: (world.year + LIFESPAN) as f32,
+            markers: validate::year_markers(world),
+        })
+    });
+    provide_context(Signal::<Timeline>::from(timeline));
 
     let tabs = move || {
         Tab::iter()
             .map(|t| {
-                let name = t.to_string();
+                let name = t!(&t.to_string());
                 view! {
                     <div class="tab"
                         class:selected=move || { tab.get() == t }
@@ -112,10 +272,15 @@ pub fn App() -> impl IntoView {
     let git_hash = env!("GIT_HASH");
 
     view! {
-        <main>
-            <div class="git-hash" title="Current Version">{git_hash}</div>
+        <main class:read-only=read_only>
+            <div class="git-hash" title=t!("Current Version")>{git_hash}</div>
+            <Show when=move || read_only.get()>
+                <div id="read-only-notice">
+                    {t!("Review Mode: editing is disabled.")}
+                </div>
+            </Show>
             <div id="save-tip">
-                Ctrl+S: Save the current session.
+                Ctrl+S: Save the current world.
             </div>
             <Toaster
                 position=ToasterPosition::BottomRight
@@ -123,14 +288,23 @@ pub fn App() -> impl IntoView {
                 <div id="tabs">
                     <WorldsMenu world />
                     {tabs}
+                    <CollabMenu />
                 </div>
                 {move || {
                     match tab.get() {
                         Tab::Planet => view! { <World world / > }.into_view(),
-                        Tab::Industries => view! { <Industries world / > }.into_view(),
-                        Tab::Processes => view! { <Processes world / > }.into_view(),
+                        Tab::Industries => view! { <Industries world / ><BatchScale world target=Target::Industries /> }.into_view(),
+                        Tab::Processes => view! { <Processes world / ><BatchScale world target=Target::Processes /> }.into_view(),
                         Tab::Projects => view! { <Projects world / > }.into_view(),
-                        Tab::Events => view! { <Events world / > }.into_view(),
+                        Tab::Events => view! { <Events world / ><filler::EventBatch world /> }.into_view(),
+                        Tab::Chains => view! { <EventChains world / > }.into_view(),
+                        Tab::Translations => view! { <Translations world / > }.into_view(),
+                        Tab::Find => view! { <Find world / > }.into_view(),
+                        Tab::Trash => view! { <Trash world tab / > }.into_view(),
+                        Tab::Checkpoints => view! { <Checkpoints world / > }.into_view(),
+                        Tab::Preview => view! { <Preview world / > }.into_view(),
+                        Tab::Playtest => view! { <Playtest world / > }.into_view(),
+                        Tab::Analysis => view! { <Analysis world / > }.into_view(),
                         Tab::Help => view! { <Help / > }.into_view(),
                     }
                 }}
@@ -143,6 +317,37 @@ async fn confirm(msg: &str) -> bool {
     window().confirm_with_message(msg).unwrap()
 }
 
+/// Only mount the children when they're scrolled into view.
+/// Otherwise render a placeholder with the last known height,
+/// so that the scroll position stays stable. This keeps long
+/// lists responsive by not mounting every item's inputs.
+#[component]
+pub fn LazyItem(children: ChildrenFn) -> impl IntoView {
+    const DEFAULT_HEIGHT: f64 = 400.;
+
+    let target = create_node_ref::<html::Div>();
+    let visible = use_element_visibility(target);
+    let size = use_element_size(target);
+    let height = create_rw_signal(DEFAULT_HEIGHT);
+    create_effect(move |_| {
+        let h = size.height.get();
+        if visible.get_untracked() && h > 0. {
+            height.set(h);
+        }
+    });
+
+    view! {
+        <div ref=target class="lazy-item">
+            <Show when=move || visible.get()
+                fallback=move || view! {
+                    <div class="lazy-placeholder" style:height=move || format!("{}px", height.get()) />
+                }>
+                {children()}
+            </Show>
+        </div>
+    }
+}
+
 #[macro_export]
 macro_rules! infinite_list {
     ($name:ident, $single:ident, $field:ident) => {
@@ -151,15 +356,39 @@ macro_rules! infinite_list {
             UseInfiniteScrollOptions,
         };
         use leptos_toaster::{Toasts, ToastId, ToastOptions, dismiss_toast};
+        use crate::{collab::Presence, LazyItem};
 
         #[component]
         pub fn $name(world: RwSignal<World>) -> impl IntoView {
             const PER_PAGE: usize = 20;
             let index_open = create_rw_signal(false);
+            let read_only = crate::inputs::read_only();
+
+            // Only show items with this tag, if set.
+            let tag_filter = create_rw_signal::<Option<String>>(None);
+            let all_tags = move || {
+                with!(|world| {
+                    let mut tags = world
+                        .$field
+                        .iter()
+                        .flat_map(|item| item.tags.iter().cloned())
+                        .collect::<Vec<_>>();
+                    tags.sort();
+                    tags.dedup();
+                    tags
+                })
+            };
+            let matches_tag = move |tags: &Vec<String>| {
+                with!(|tag_filter| tag_filter
+                    .as_ref()
+                    .map_or(true, |tag| tags.contains(tag)))
+            };
+
             let index = move || {
                 with!(|world| world
                     .$field
                     .iter()
+                    .filter(|item| matches_tag(&item.tags))
                     .map(|item| (item.id, item.name.clone()))
                     .collect::<Vec<_>>())
             };
@@ -167,7 +396,7 @@ macro_rules! infinite_list {
             create_effect(move |_| {
                 let id = go_to.get();
                 if let Some(id) = id {
-                    let target = document().get_element_by_id(&id.to_string());
+                    let target = document().get_element_by_id(&format!("item-{id}"));
                     target.unwrap().scroll_into_view();
                     go_to.set_untracked(None);
                 }
@@ -178,12 +407,39 @@ macro_rules! infinite_list {
                 with!(|world, max_idx| world
                     .$field
                     .iter()
+                    .filter(|item| matches_tag(&item.tags))
                     .map(|item| item.id)
                     .take(*max_idx)
                     .collect::<Vec<_>>())
             };
             let total = move || with!(|world| world.$field.len());
 
+            // Templates for new items, if any.
+            let templates = store_value(
+                <$single as crate::templates::Template>::templates()
+                    .into_iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>()
+            );
+            let templates_open = create_rw_signal(false);
+            let insert_new = move |template: Option<usize>| {
+                if read_only.get_untracked() {
+                    return;
+                }
+                let item = template
+                    .and_then(|idx| {
+                        <$single as crate::templates::Template>::templates()
+                            .into_iter()
+                            .nth(idx)
+                            .map(|(_, item)| item)
+                    })
+                    .unwrap_or_else($single::new);
+                update!(|world| {
+                    world.$field.push_front(item);
+                });
+                templates_open.set(false);
+            };
+
             let list_el = create_node_ref::<html::Div>();
             let _ = use_infinite_scroll_with_options(
                 list_el,
@@ -196,6 +452,7 @@ macro_rules! infinite_list {
                 UseInfiniteScrollOptions::default().distance(50.0),
             );
 
+            let trash = expect_context::<crate::trash::TrashBin>();
             let toast_context = expect_context::<Toasts>();
             let create_toast = move |name: String, refs: Vec<String>| {
                 let toast_id = ToastId::new();
@@ -226,6 +483,57 @@ macro_rules! infinite_list {
                 );
             };
 
+            let error_toast = move |title: String, errs: Vec<String>| {
+                let toast_id = ToastId::new();
+                toast_context.toast(
+                    view! {
+                        <div class="toast">
+                            <div class="toast-header">
+                                <div class="toast-remove" on:click=move |_| {
+                                    dismiss_toast(&toast_id);
+                                }>"✗"</div>
+                            </div>
+                            <div class="toast-body">
+                                <h3>{title}</h3>
+                                <div>{move || {
+                                    errs.iter().map(|err| {
+                                        view!{ <div>{err}</div> }
+                                    }).collect::<Vec<_>>()
+                                }}</div>
+                            </div>
+                        </div>
+                    },
+                    Some(toast_id),
+                    Some(ToastOptions {
+                        dismissible: true,
+                        duration: Some(std::time::Duration::from_millis(5000)),
+                        position: None,
+                    })
+                );
+            };
+
+            let paste_item = move || {
+                if read_only.get_untracked() {
+                    return;
+                }
+                spawn_local(async move {
+                    let res = match crate::clipboard::paste_text().await {
+                        Ok(data) => with!(|world| crate::clipboard::from_json::<$single>(&data, &world.$field, world)),
+                        Err(err) => Err(vec![err]),
+                    };
+                    match res {
+                        Ok(item) => {
+                            let id = item.id;
+                            update!(|world| {
+                                world.$field.push_front(item);
+                            });
+                            go_to.set(Some(id));
+                        }
+                        Err(errs) => error_toast("Couldn't paste:".into(), errs),
+                    }
+                });
+            };
+
             view! {
                 <div class="scroll-index">
                     <div class="scroll-index-open"
@@ -233,6 +541,20 @@ macro_rules! infinite_list {
                             update!(|index_open| *index_open = !*index_open);
                         }
                       >"≡"</div>
+                    <div class="tag-filter" class:hidden=move || !index_open.get()>
+                        <div class="tag"
+                            class:selected=move || with!(|tag_filter| tag_filter.is_none())
+                            on:click=move |_| tag_filter.set(None)>{t!("All")}</div>
+                        {move || all_tags().into_iter().map(|tag| {
+                            let value = tag.clone();
+                            let selected = tag.clone();
+                            view! {
+                                <div class="tag"
+                                    class:selected=move || with!(|tag_filter| tag_filter.as_ref() == Some(&selected))
+                                    on:click=move |_| tag_filter.set(Some(value.clone()))>{tag}</div>
+                            }
+                        }).collect::<Vec<_>>()}
+                    </div>
                     <ul class="scroll-index-list" class:hidden=move || !index_open.get()>
                         <For each=index
                             key=|(id, _)| *id
@@ -242,6 +564,7 @@ macro_rules! infinite_list {
                                         let idx = with!(|world| world
                                             .$field
                                             .iter()
+                                            .filter(|item| matches_tag(&item.tags))
                                             .position(|item| item.id == id)
                                             .unwrap());
                                         let new_max_idx = max_idx.get().max(idx + 1);
@@ -254,39 +577,92 @@ macro_rules! infinite_list {
                 </div>
 
                 <div ref=list_el class="scroll-list">
-                    <div class="insert-item" on:click=move |_| {
-                        update!(|world| {
-                            world.$field.push_front($single::new());
-                        });
+                    <div class="insert-item" class:hidden=read_only on:click=move |_| {
+                        if templates.with_value(|templates| templates.is_empty()) {
+                            insert_new(None);
+                        } else {
+                            update!(|templates_open| *templates_open = !*templates_open);
+                        }
                     }>+ New</div>
+                    <div class="paste-item"
+                        class:hidden=read_only
+                        title=t!("Paste an entity copied as JSON.")
+                        on:click=move |_| paste_item()>{t!("Paste")}</div>
+                    <Show when=move || templates_open.get() && !read_only.get()>
+                        <div class="insert-templates">
+                            <div on:click=move |_| insert_new(None)>{t!("Blank")}</div>
+                            {templates.get_value().into_iter().enumerate().map(|(i, name)| {
+                                view! {
+                                    <div on:click=move |_| insert_new(Some(i))>{name}</div>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    </Show>
                     <For each=list
                         key=|id| *id
                         children=move |id| {
                            view! {
-                                <div class="scroll-list-item">
-                                    <div class="remove-item tooltip-parent"
-                                        title="Ctrl-click to remove without confirmation."
-                                        on:click=move |ev| {
-                                            spawn_local(async move {
-                                                let msg = "Are you sure you want to delete this?";
-                                                let name = with!(|world| world.$field[&id].name.clone());
-                                                let refs = with!(|world| crate::validate::find_references(id, crate::validate::RefKind::$single, world));
-                                                if !refs.is_empty() {
-                                                    create_toast(name, refs);
-                                                } else if ev.ctrl_key() || crate::confirm(msg).await {
-                                                    update!(|world| {
-                                                        world.$field.remove(&id);
-                                                    });
+                                <div class="scroll-list-item" id=format!("item-{id}")>
+                                    <LazyItem>
+                                        <Presence id />
+                                        <div class="remove-item tooltip-parent"
+                                            class:hidden=read_only
+                                            title=t!("Ctrl-click to remove without confirmation.")
+                                            on:click=move |ev| {
+                                                if read_only.get_untracked() {
+                                                    return;
                                                 }
-                                            });
-                                        }>
-                                            "🞬 Delete"
-                                        </div>
-                                    <$single
-                                        signal=create_slice(world,
-                                            move |world| world.$field[&id].clone(),
-                                            move |world, val| world.$field[&id] = val
-                                        ) />
+                                                spawn_local(async move {
+                                                    let msg = "Move this to the trash? It can be restored from the Trash tab.";
+                                                    let name = with!(|world| world.$field[&id].name.clone());
+                                                    let refs = with!(|world| crate::validate::find_references(id, crate::validate::RefKind::$single, world));
+                                                    if !refs.is_empty() {
+                                                        create_toast(name, refs);
+                                                    } else if ev.ctrl_key() || crate::confirm(msg).await {
+                                                        let item = with!(|world| world.$field[&id].clone());
+                                                        trash.put(item);
+                                                        update!(|world| {
+                                                            world.$field.remove(&id);
+                                                        });
+                                                    }
+                                                });
+                                            }>
+                                                "🞬 Delete"
+                                            </div>
+                                        <div class="copy-item"
+                                            title=t!("Copy as JSON, to paste into another world.")
+                                            on:click=move |_| {
+                                                let data = with!(|world| crate::clipboard::to_json(&world.$field[&id]));
+                                                spawn_local(async move {
+                                                    if let Err(err) = crate::clipboard::copy_text(&data).await {
+                                                        error_toast("Couldn't copy:".into(), vec![err]);
+                                                    }
+                                                });
+                                            }>
+                                                "⧉ Copy"
+                                            </div>
+                                        <div class="share-item"
+                                            title=t!("Copy a link that opens this in the editor, e.g. to get feedback on it.")
+                                            on:click=move |_| {
+                                                let link = with!(|world| crate::share::share_link(&world.$field[&id].clone().into()));
+                                                spawn_local(async move {
+                                                    let res = match link {
+                                                        Ok(link) => crate::clipboard::copy_text(&link).await,
+                                                        Err(err) => Err(err.to_string()),
+                                                    };
+                                                    if let Err(err) = res {
+                                                        error_toast("Couldn't share:".into(), vec![err]);
+                                                    }
+                                                });
+                                            }>
+                                                "🔗 Share"
+                                            </div>
+                                        <$single
+                                            signal=create_slice(world,
+                                                move |world| world.$field[&id].clone(),
+                                                move |world, val| world.$field[&id] = val
+                                            ) />
+                                    </LazyItem>
                                 </div>
                             }
                         } />
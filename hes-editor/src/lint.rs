@@ -0,0 +1,214 @@
+use hes_engine::{
+    expr::{ConditionExpr, EffectsExpr},
+    scripting,
+};
+
+use crate::icons::icon_path;
+
+/// Variables the game fills in when displaying text.
+const KNOWN_VARS: &[&str] = &["region", "magnitude"];
+
+/// Roughly the most characters that fit
+/// in a line of the game's dialogue box.
+const DIALOGUE_LINE_LENGTH: usize = 280;
+
+/// Which checks to run on a text field.
+/// The default is to run none.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Lints {
+    /// Flag `{variables}` the game won't fill in.
+    pub vars: bool,
+
+    /// Flag double spaces and leading/trailing whitespace.
+    pub spacing: bool,
+
+    /// Flag unmatched `{}`, `[]`, and `()`.
+    pub brackets: bool,
+
+    /// Flag `[icons]` the game doesn't have.
+    pub icons: bool,
+
+    /// Flag lines longer than this many characters.
+    pub max_line_length: Option<usize>,
+
+    /// The whole text is a script, which
+    /// is flagged if it doesn't parse.
+    pub script: bool,
+
+    /// The whole text is an expression, which
+    /// is flagged if it doesn't parse.
+    pub expr: Option<ExprLint>,
+}
+
+/// What kind of expression a text field holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExprLint {
+    Condition,
+    Effects,
+}
+impl Lints {
+    /// Checks for text that's shown to the player.
+    pub const FLAVOR: Lints = Lints {
+        vars: true,
+        spacing: true,
+        brackets: true,
+        icons: true,
+        max_line_length: None,
+        script: false,
+        expr: None,
+    };
+
+    /// Checks for dialogue, which also
+    /// has to fit in the dialogue box.
+    pub const DIALOGUE: Lints = Lints {
+        max_line_length: Some(DIALOGUE_LINE_LENGTH),
+        ..Lints::FLAVOR
+    };
+
+    /// Checks for scripts.
+    pub const SCRIPT: Lints = Lints {
+        vars: false,
+        spacing: false,
+        brackets: false,
+        icons: false,
+        max_line_length: None,
+        script: true,
+        expr: None,
+    };
+
+    /// Checks for condition expressions.
+    pub const CONDITION_EXPR: Lints = Lints {
+        script: false,
+        expr: Some(ExprLint::Condition),
+        ..Lints::SCRIPT
+    };
+
+    /// Checks for effect expressions.
+    pub const EFFECTS_EXPR: Lints = Lints {
+        script: false,
+        expr: Some(ExprLint::Effects),
+        ..Lints::SCRIPT
+    };
+
+    /// Check the text, returning a description
+    /// of each problem found.
+    pub fn check(&self, text: &str) -> Vec<String> {
+        let mut problems = vec![];
+        if self.script {
+            if let Err(err) = scripting::check(text) {
+                problems.push(err.to_string());
+            }
+            return problems;
+        }
+        if let Some(kind) = self.expr {
+            let parsed = match kind {
+                ExprLint::Condition => {
+                    ConditionExpr::parse(text).err()
+                }
+                ExprLint::Effects => {
+                    EffectsExpr::parse(text).err()
+                }
+            };
+            problems.extend(parsed.map(|err| err.to_string()));
+            return problems;
+        }
+
+        // Any `{{ scripts }}` are checked on their own,
+        // as their code would trip up the other checks.
+        // They're replaced by a placeholder word so the
+        // spacing around them is still checked.
+        let mut rest = String::with_capacity(text.len());
+        let mut last = 0;
+        for (range, script) in scripting::text_scripts(text) {
+            if let Err(err) = scripting::check(script) {
+                problems.push(err.to_string());
+            }
+            rest.push_str(&text[last..range.start]);
+            rest.push_str("script");
+            last = range.end;
+        }
+        rest.push_str(&text[last..]);
+        let text = rest.as_str();
+
+        if self.vars {
+            problems.extend(unknown_vars(text).into_iter().map(
+                |var| format!("Unknown variable {{{var}}}."),
+            ));
+        }
+        if self.spacing {
+            if text.contains("  ") {
+                problems.push("Contains double spaces.".into());
+            }
+            if text.trim() != text {
+                problems.push(
+                    "Has leading or trailing whitespace.".into(),
+                );
+            }
+        }
+        if self.brackets {
+            if let Some(bracket) = unmatched_bracket(text) {
+                problems.push(format!("Unmatched \"{bracket}\"."));
+            }
+        }
+        if self.icons {
+            problems.extend(unknown_icons(text).into_iter().map(
+                |icon| format!("Unknown icon [{icon}]."),
+            ));
+        }
+        if let Some(max) = self.max_line_length {
+            let longest = text
+                .lines()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0);
+            if longest > max {
+                problems.push(format!(
+                    "Line is too long ({longest}/{max} characters)."
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Find `{variables}` that aren't in `KNOWN_VARS`.
+fn unknown_vars(text: &str) -> Vec<&str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(var, _)| var)
+        .filter(|var| !KNOWN_VARS.contains(var))
+        .collect()
+}
+
+/// Find `[icons]` that the game doesn't have.
+fn unknown_icons(text: &str) -> Vec<&str> {
+    text.split('[')
+        .skip(1)
+        .filter_map(|rest| rest.split_once(']'))
+        .map(|(icon, _)| icon)
+        .filter(|icon| icon_path(icon).is_none())
+        .collect()
+}
+
+/// Find the first bracket without a matching pair.
+fn unmatched_bracket(text: &str) -> Option<char> {
+    let mut stack = vec![];
+    for c in text.chars() {
+        match c {
+            '{' | '[' | '(' => stack.push(c),
+            '}' | ']' | ')' => {
+                let open = match c {
+                    '}' => '{',
+                    ']' => '[',
+                    _ => '(',
+                };
+                if stack.pop() != Some(open) {
+                    return Some(c);
+                }
+            }
+            _ => (),
+        }
+    }
+    stack.pop()
+}
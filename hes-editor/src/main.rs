@@ -1,4 +1,4 @@
-use hes_editor::App;
+use hes_editor::{i18n, App};
 use leptos::*;
 use tracing::Level;
 use tracing_wasm::WASMLayerConfigBuilder;
@@ -12,9 +12,15 @@ fn main() {
         .set_max_level(Level::DEBUG)
         .build();
     tracing_wasm::set_as_global_default_with_config(config);
-    mount_to_body(|| {
-        view! {
-            <App />
-        }
-    })
+
+    // Load the interface's translations before
+    // anything is rendered.
+    spawn_local(async {
+        i18n::load_language(i18n::preferred_language()).await;
+        mount_to_body(|| {
+            view! {
+                <App />
+            }
+        })
+    });
 }
@@ -0,0 +1,143 @@
+use hes_engine::World;
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+use crate::inputs::image_src;
+
+/// How many recent worlds to keep.
+const MAX_RECENT: u32 = 8;
+
+const THUMBNAIL_WIDTH: u32 = 96;
+const THUMBNAIL_HEIGHT: u32 = 64;
+
+#[wasm_bindgen(module = "/public/js/storage.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = putRecent, catch)]
+    async fn put_recent(
+        entry: &JsValue,
+        max: u32,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = listRecent, catch)]
+    async fn list_recent() -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = openRecent, catch)]
+    async fn open_recent(
+        name: &str,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = removeRecent, catch)]
+    async fn remove_recent(
+        name: &str,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = makeThumbnail)]
+    pub(crate) async fn make_thumbnail(
+        src: &str,
+        width: u32,
+        height: u32,
+    ) -> JsValue;
+}
+
+/// A recently opened or saved world.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentWorld {
+    pub name: String,
+
+    /// When the world was last opened or saved,
+    /// in milliseconds since the epoch.
+    pub opened: f64,
+
+    /// A data URL for a small preview image.
+    pub thumbnail: Option<String>,
+}
+
+fn get(obj: &JsValue, key: &str) -> JsValue {
+    Reflect::get(obj, &key.into()).unwrap_or(JsValue::NULL)
+}
+
+/// Remember a world as recently opened, along with
+/// the file it was opened from or saved to, if any.
+pub async fn remember(
+    name: &str,
+    world: &World,
+    handle: Option<JsValue>,
+) -> Result<(), JsValue> {
+    let thumbnail = match world.projects.first() {
+        Some(project) => {
+            make_thumbnail(
+                &image_src(&project.flavor.image),
+                THUMBNAIL_WIDTH,
+                THUMBNAIL_HEIGHT,
+            )
+            .await
+        }
+        None => JsValue::NULL,
+    };
+    let data = Uint8Array::from(world.to_binary().as_slice());
+
+    let entry = Object::new();
+    Reflect::set(&entry, &"name".into(), &name.into())?;
+    Reflect::set(
+        &entry,
+        &"opened".into(),
+        &js_sys::Date::now().into(),
+    )?;
+    Reflect::set(&entry, &"thumbnail".into(), &thumbnail)?;
+    Reflect::set(&entry, &"data".into(), &data.into())?;
+    Reflect::set(
+        &entry,
+        &"handle".into(),
+        &handle.unwrap_or(JsValue::NULL),
+    )?;
+    put_recent(&entry, MAX_RECENT).await?;
+    Ok(())
+}
+
+/// Recent worlds, most recent first.
+pub async fn recent_worlds() -> Vec<RecentWorld> {
+    let Ok(list) = list_recent().await else {
+        return vec![];
+    };
+    Array::from(&list)
+        .iter()
+        .map(|entry| RecentWorld {
+            name: get(&entry, "name")
+                .as_string()
+                .unwrap_or_default(),
+            opened: get(&entry, "opened")
+                .as_f64()
+                .unwrap_or(0.),
+            thumbnail: get(&entry, "thumbnail").as_string(),
+        })
+        .collect()
+}
+
+/// Load a recent world, and its file's handle if it has one
+/// and permission to access the file is granted.
+pub async fn reopen(
+    name: &str,
+) -> Result<(World, Option<JsValue>), String> {
+    let entry = open_recent(name)
+        .await
+        .map_err(|err| format!("{err:?}"))?;
+    if entry.is_null() {
+        return Err(format!("\"{name}\" is no longer saved."));
+    }
+    // Worlds remembered before the binary format was
+    // added are stored as JSON strings.
+    let data = get(&entry, "data");
+    let data = match data.as_string() {
+        Some(json) => json.into_bytes(),
+        None => Uint8Array::new(&data).to_vec(),
+    };
+    let world = World::from_bytes(&data)
+        .map_err(|err| err.to_string())?;
+    let handle = get(&entry, "handle");
+    Ok((world, (!handle.is_null()).then_some(handle)))
+}
+
+pub async fn forget(name: &str) -> Result<(), JsValue> {
+    remove_recent(name).await?;
+    Ok(())
+}
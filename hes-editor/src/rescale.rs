@@ -0,0 +1,335 @@
+use hes_engine::{
+    Bounds,
+    Byproduct,
+    Id,
+    Output,
+    Resource,
+    World,
+};
+use leptos::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    inputs::{read_only, SliderInput},
+    t,
+};
+
+const FACTOR: Bounds = Bounds::new(0., 10., 0.05);
+
+/// Which entities to rescale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Processes,
+    Industries,
+}
+
+/// The field to rescale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Resource(Resource),
+    Byproduct(Byproduct),
+    Feedstock,
+}
+impl Field {
+    fn all(target: Target) -> Vec<Field> {
+        let mut fields = vec![];
+        fields.extend(Byproduct::iter().map(Field::Byproduct));
+        fields.extend(Resource::iter().map(Field::Resource));
+        if target == Target::Processes {
+            fields.push(Field::Feedstock);
+        }
+        fields
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Field::Resource(res) => res.to_string(),
+            Field::Byproduct(byp) => byp.to_string(),
+            Field::Feedstock => "Feedstock".into(),
+        }
+    }
+
+    /// Get the field's value for the entity,
+    /// or `None` if it doesn't exist.
+    fn get(
+        &self,
+        world: &World,
+        target: Target,
+        id: &Id,
+    ) -> Option<f32> {
+        match target {
+            Target::Processes => {
+                let process = world.processes.try_get(id)?;
+                Some(match self {
+                    Field::Resource(res) => {
+                        process.resources[*res]
+                    }
+                    Field::Byproduct(byp) => {
+                        process.byproducts[*byp]
+                    }
+                    Field::Feedstock => process.feedstock.1,
+                })
+            }
+            Target::Industries => {
+                let industry = world.industries.try_get(id)?;
+                match self {
+                    Field::Resource(res) => {
+                        Some(industry.resources[*res])
+                    }
+                    Field::Byproduct(byp) => {
+                        Some(industry.byproducts[*byp])
+                    }
+                    Field::Feedstock => None,
+                }
+            }
+        }
+    }
+
+    fn get_mut<'a>(
+        &self,
+        world: &'a mut World,
+        target: Target,
+        id: &Id,
+    ) -> Option<&'a mut f32> {
+        match target {
+            Target::Processes => {
+                let process =
+                    world.processes.try_get_mut(id)?;
+                Some(match self {
+                    Field::Resource(res) => {
+                        &mut process.resources[*res]
+                    }
+                    Field::Byproduct(byp) => {
+                        &mut process.byproducts[*byp]
+                    }
+                    Field::Feedstock => {
+                        &mut process.feedstock.1
+                    }
+                })
+            }
+            Target::Industries => {
+                let industry =
+                    world.industries.try_get_mut(id)?;
+                match self {
+                    Field::Resource(res) => {
+                        Some(&mut industry.resources[*res])
+                    }
+                    Field::Byproduct(byp) => {
+                        Some(&mut industry.byproducts[*byp])
+                    }
+                    Field::Feedstock => None,
+                }
+            }
+        }
+    }
+}
+
+/// Which entities to rescale.
+#[derive(Debug, Clone, Default)]
+struct Filter {
+    name: String,
+    tag: String,
+    output: Option<Output>,
+}
+impl Filter {
+    fn matches(
+        &self,
+        name: &str,
+        tags: &[String],
+        output: Option<Output>,
+    ) -> bool {
+        let tag = self.tag.trim();
+        name.to_lowercase().contains(&self.name.to_lowercase())
+            && (tag.is_empty()
+                || tags
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(tag)))
+            && self.output.map_or(true, |o| output == Some(o))
+    }
+}
+
+/// The entities matching the filter, with their names.
+fn matching(
+    world: &World,
+    target: Target,
+    filter: &Filter,
+) -> Vec<(Id, String)> {
+    match target {
+        Target::Processes => world
+            .processes
+            .iter()
+            .filter(|p| {
+                filter.matches(&p.name, &p.tags, Some(p.output))
+            })
+            .map(|p| (p.id, p.name.clone()))
+            .collect(),
+        Target::Industries => world
+            .industries
+            .iter()
+            .filter(|i| filter.matches(&i.name, &i.tags, None))
+            .map(|i| (i.id, i.name.clone()))
+            .collect(),
+    }
+}
+
+/// Multiply a field by a factor across a filtered set
+/// of entities, e.g. to scale the CO2 emissions of all
+/// electricity processes by 0.8, previewing the changes
+/// before applying them.
+#[component]
+pub fn BatchScale(
+    world: RwSignal<World>,
+    target: Target,
+) -> impl IntoView {
+    let read_only = read_only();
+    let open = create_rw_signal(false);
+    let filter = create_rw_signal(Filter::default());
+    let field =
+        create_rw_signal(Field::Byproduct(Byproduct::Co2));
+    let factor = create_rw_signal(1.);
+
+    // Each matching entity's name and its
+    // current and rescaled values. Entities
+    // where the value is zero are skipped
+    // as they're unaffected.
+    let changes = move || {
+        with!(|world, filter| {
+            let field = field.get();
+            let factor = factor.get();
+            matching(world, target, filter)
+                .into_iter()
+                .filter_map(|(id, name)| {
+                    let value =
+                        field.get(world, target, &id)?;
+                    (value != 0.).then(|| {
+                        (id, name, value, value * factor)
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let apply = move |_| {
+        if read_only.get_untracked() {
+            return;
+        }
+        let ids = changes()
+            .into_iter()
+            .map(|(id, ..)| id)
+            .collect::<Vec<_>>();
+        let (field, factor) = (field.get(), factor.get());
+        update!(|world| {
+            for id in &ids {
+                if let Some(value) =
+                    field.get_mut(world, target, id)
+                {
+                    *value *= factor;
+                }
+            }
+        });
+        open.set(false);
+    };
+
+    let field_opts = move || {
+        Field::all(target)
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| {
+                view! {
+                    <option value=i selected=move || field.get() == f>
+                        {f.label()}
+                    </option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let output_opts = move || {
+        Output::iter()
+            .map(|output| {
+                let label: &'static str = output.into();
+                view! {
+                    <option value=label>{output.to_string()}</option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let preview = move || {
+        changes()
+            .into_iter()
+            .map(|(_, name, before, after)| {
+                view! {
+                    <tr>
+                        <td>{name}</td>
+                        <td>{before}</td>
+                        <td>{after}</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div class="batch-item"
+            class:hidden=read_only
+            title=t!("Multiply a field across many entities at once.")
+            on:click=move |_| update!(|open| *open = !*open)>
+            {t!("Rescale")}
+        </div>
+        <Show when=move || open.get() && !read_only.get()>
+            <div class="batch-panel rescale-panel">
+                <div class="table-filters">
+                    <input type="text"
+                        placeholder=t!("Filter by name")
+                        on:input=move |ev| {
+                            update!(|filter| filter.name = event_target_value(&ev));
+                        } />
+                    <input type="text"
+                        placeholder=t!("Filter by tag")
+                        on:input=move |ev| {
+                            update!(|filter| filter.tag = event_target_value(&ev));
+                        } />
+                    {(target == Target::Processes).then(|| view! {
+                        <select on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            update!(|filter| filter.output = value.parse().ok());
+                        }>
+                            <option value="">{t!("All Outputs")}</option>
+                            {output_opts}
+                        </select>
+                    })}
+                </div>
+                <select on:change=move |ev| {
+                    let fields = Field::all(target);
+                    let idx = event_target_value(&ev).parse::<usize>().ok();
+                    if let Some(f) = idx.and_then(|idx| fields.get(idx)) {
+                        field.set(*f);
+                    }
+                }>
+                    {field_opts}
+                </select>
+                <SliderInput
+                    label="Factor"
+                    help="What to multiply the field by."
+                    bounds=FACTOR
+                    signal=create_slice(factor, |n| *n, |n, val| *n = val) />
+                <div class="rescale-preview">
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>{t!("Name")}</th>
+                                <th>{t!("Before")}</th>
+                                <th>{t!("After")}</th>
+                            </tr>
+                        </thead>
+                        <tbody>{preview}</tbody>
+                    </table>
+                </div>
+                <button on:click=apply>
+                    {move || t!("Apply to {n}", n: changes().len())}
+                </button>
+            </div>
+        </Show>
+    }
+}
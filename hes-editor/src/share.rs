@@ -0,0 +1,95 @@
+use anyhow::{bail, Result};
+use base64::prelude::*;
+use hes_engine::{Event, Industry, Process, Project, World};
+use leptos::window;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+
+use crate::files::{compress, decompress};
+
+/// Links longer than this are unlikely to survive
+/// being pasted into chat apps and the like.
+const MAX_LINK_LEN: usize = 64_000;
+
+const PREFIX: &str = "#share=";
+
+/// A world or a single entity shared through a link.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Shared {
+    World(Box<World>),
+    Industry(Industry),
+    Process(Process),
+    Project(Project),
+    Event(Event),
+}
+impl From<Industry> for Shared {
+    fn from(item: Industry) -> Self {
+        Shared::Industry(item)
+    }
+}
+impl From<Process> for Shared {
+    fn from(item: Process) -> Self {
+        Shared::Process(item)
+    }
+}
+impl From<Project> for Shared {
+    fn from(item: Project) -> Self {
+        Shared::Project(item)
+    }
+}
+impl From<Event> for Shared {
+    fn from(item: Event) -> Self {
+        Shared::Event(item)
+    }
+}
+
+/// A link to the editor that opens what's shared,
+/// compressed into the URL fragment so it's never
+/// sent to the server.
+pub fn share_link(shared: &Shared) -> Result<String> {
+    let bytes = rmp_serde::to_vec_named(shared)?;
+    let data = BASE64_URL_SAFE_NO_PAD.encode(compress(&bytes)?);
+    let location = window().location();
+    let link = format!(
+        "{}{}{PREFIX}{data}",
+        location.origin().unwrap_or_default(),
+        location.pathname().unwrap_or_default(),
+    );
+    if link.len() > MAX_LINK_LEN {
+        bail!(
+            "This is too large to share as a link ({} KB), so export it as a file instead.",
+            link.len() / 1000
+        );
+    }
+    Ok(link)
+}
+
+/// Take what's shared in the current URL, if anything,
+/// removing it from the URL so it isn't opened again
+/// when reloading.
+pub fn take_shared() -> Option<Result<Shared>> {
+    let location = window().location();
+    let hash = location.hash().ok()?;
+    let data = hash.strip_prefix(PREFIX)?;
+    let shared = decode(data);
+
+    let url = format!(
+        "{}{}",
+        location.pathname().unwrap_or_default(),
+        location.search().unwrap_or_default(),
+    );
+    if let Ok(history) = window().history() {
+        let _ = history.replace_state_with_url(
+            &JsValue::NULL,
+            "",
+            Some(&url),
+        );
+    }
+    Some(shared)
+}
+
+fn decode(data: &str) -> Result<Shared> {
+    let bytes = BASE64_URL_SAFE_NO_PAD.decode(data)?;
+    let shared = rmp_serde::from_slice(&decompress(&bytes)?)?;
+    Ok(shared)
+}
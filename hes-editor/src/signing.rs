@@ -0,0 +1,65 @@
+use base64::prelude::*;
+use hes_engine::SigningKey;
+use leptos::window;
+
+const KEY_ITEM: &str = "signing-key";
+const SIGN_ITEM: &str = "sign-exports";
+
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+/// The author's key for signing worlds, created
+/// and kept in local storage the first time
+/// it's needed.
+pub fn signing_key() -> SigningKey {
+    let stored = storage()
+        .and_then(|storage| storage.get_item(KEY_ITEM).ok())
+        .flatten()
+        .and_then(|key| BASE64_STANDARD.decode(key).ok())
+        .and_then(|bytes| bytes.try_into().ok());
+    if let Some(bytes) = stored {
+        return SigningKey::from_bytes(&bytes);
+    }
+
+    let mut bytes = [0u8; 32];
+    window()
+        .crypto()
+        .expect("Crypto should be available")
+        .get_random_values_with_u8_array(&mut bytes)
+        .expect("Failed to generate a signing key");
+    if let Some(storage) = storage() {
+        let _ = storage
+            .set_item(KEY_ITEM, &BASE64_STANDARD.encode(bytes));
+    }
+    SigningKey::from_bytes(&bytes)
+}
+
+/// The author's public key, as shown for signed worlds.
+pub fn public_key() -> String {
+    signing_key()
+        .verifying_key()
+        .to_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Whether to sign worlds when saving or exporting them.
+pub fn sign_exports() -> bool {
+    storage()
+        .and_then(|storage| storage.get_item(SIGN_ITEM).ok())
+        .flatten()
+        .is_some_and(|value| value == "true")
+}
+
+pub fn set_sign_exports(sign: bool) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(SIGN_ITEM, &sign.to_string());
+    }
+}
+
+/// Abbreviate a hash or key for display.
+pub fn short(hex: &str) -> &str {
+    &hex[..hex.len().min(12)]
+}
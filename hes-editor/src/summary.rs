@@ -0,0 +1,400 @@
+use std::fmt::Write;
+
+use hes_engine::{
+    flavor::{Image, ImageData},
+    Byproduct,
+    ByproductMap,
+    Cost,
+    Effect,
+    Event,
+    Industry,
+    Process,
+    Project,
+    Resource,
+    ResourceMap,
+    World,
+};
+use leptos::window;
+use strum::IntoEnumIterator;
+
+use crate::{format, inputs::image_src};
+
+const STYLE: &str = "
+body { font-family: sans-serif; max-width: 860px; margin: 2em auto; padding: 0 1em; color: #222; }
+h1 { margin-bottom: 0; }
+h2 { border-bottom: 2px solid #222; margin-top: 2em; }
+.byline { color: #666; }
+.entity { border: 1px solid #ccc; padding: 1em; margin: 1em 0; page-break-inside: avoid; break-inside: avoid; }
+.entity h3 { margin-top: 0; }
+.entity img.flavor { max-width: 360px; display: block; margin-bottom: 0.5em; }
+.tags { font-size: 0.8em; color: #666; }
+.notes { font-size: 0.9em; color: #666; font-style: italic; }
+table { border-collapse: collapse; font-size: 0.9em; margin: 0.5em 0; }
+td, th { padding: 2px 8px; border-bottom: 1px solid #eee; text-align: left; vertical-align: top; }
+.text-icon { height: 1em; vertical-align: middle; }
+.text-var { font-family: monospace; background: #eee; }
+.dialogue p { margin: 0.25em 0; }
+@media print { body { margin: 0; max-width: none; } }
+";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A standalone HTML report of the world, with
+/// every entity's flavor text, images, and key
+/// numbers, for reviewing, printing, or posting
+/// as documentation.
+pub fn summary_html(world: &World, name: &str) -> String {
+    let meta = &world.metadata;
+    let title = if meta.title.is_empty() {
+        name
+    } else {
+        &meta.title
+    };
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title><style>{STYLE}</style></head><body>",
+        escape(title)
+    );
+    let _ = write!(html, "<h1>{}</h1>", escape(title));
+    let byline = [
+        (!meta.author.is_empty())
+            .then(|| format!("by {}", meta.author)),
+        (!meta.version.is_empty())
+            .then(|| format!("version {}", meta.version)),
+        (!meta.license.is_empty())
+            .then(|| meta.license.clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !byline.is_empty() {
+        let _ = write!(
+            html,
+            "<p class=\"byline\">{}</p>",
+            escape(&byline.join(" · "))
+        );
+    }
+    html.push_str(&format::render(&meta.description));
+
+    html.push_str("<h2>Overview</h2>");
+    html.push_str(&table(&[
+        ("Starting year", world.year.to_string()),
+        (
+            "Temperature anomaly",
+            format!("{}°C", world.temperature),
+        ),
+        (
+            "Sea level rise",
+            format!("{}m", world.sea_level_rise),
+        ),
+        ("Extinction rate", world.extinction_rate.to_string()),
+        ("Regions", world.regions.len().to_string()),
+        ("Industries", world.industries.len().to_string()),
+        ("Processes", world.processes.len().to_string()),
+        ("Projects", world.projects.len().to_string()),
+        ("Events", world.events.len().to_string()),
+    ]));
+
+    section(
+        &mut html,
+        "Industries",
+        world.industries.iter(),
+        industry,
+    );
+    section(
+        &mut html,
+        "Processes",
+        world.processes.iter(),
+        process,
+    );
+    section(
+        &mut html,
+        "Projects",
+        world.projects.iter(),
+        project,
+    );
+    section(&mut html, "Events", world.events.iter(), event);
+    html.push_str("</body></html>");
+
+    // Image and icon paths are relative to the editor,
+    // so point them to it for the report to stand alone.
+    let origin =
+        window().location().origin().unwrap_or_default();
+    html.replace("src=\"/", &format!("src=\"{origin}/"))
+}
+
+fn section<'a, T: 'a>(
+    html: &mut String,
+    title: &str,
+    items: impl Iterator<Item = &'a T>,
+    render: fn(&T) -> String,
+) {
+    let _ = write!(html, "<h2>{title}</h2>");
+    for item in items {
+        html.push_str(&render(item));
+    }
+}
+
+fn entity(
+    name: &str,
+    image: Option<&Image>,
+    description: &str,
+    rows: &[(&str, String)],
+    extra: &str,
+    tags: &[String],
+    notes: &str,
+) -> String {
+    let mut html = String::from("<div class=\"entity\">");
+    let _ = write!(html, "<h3>{}</h3>", escape(name));
+    if !tags.is_empty() {
+        let _ = write!(
+            html,
+            "<div class=\"tags\">{}</div>",
+            escape(&tags.join(", "))
+        );
+    }
+    if let Some(image) = image.filter(|image| has_image(image))
+    {
+        let _ = write!(
+            html,
+            "<img class=\"flavor\" src=\"{}\" alt=\"{}\">",
+            image_src(image),
+            escape(&image.alt)
+        );
+        let credit = image.credit();
+        if !credit.is_empty() {
+            let _ = write!(
+                html,
+                "<div class=\"tags\">Image: {}</div>",
+                escape(&credit)
+            );
+        }
+    }
+    html.push_str(&format::render(description));
+    html.push_str(&table(rows));
+    html.push_str(extra);
+    if !notes.is_empty() {
+        let _ = write!(
+            html,
+            "<p class=\"notes\">Notes: {}</p>",
+            escape(notes)
+        );
+    }
+    html.push_str("</div>");
+    html
+}
+
+fn has_image(image: &Image) -> bool {
+    match &image.data {
+        ImageData::File(fname) => !fname.is_empty(),
+        ImageData::Data { bytes, .. } => !bytes.is_empty(),
+    }
+}
+
+fn table(rows: &[(&str, String)]) -> String {
+    let mut html = String::from("<table>");
+    for (label, value) in rows {
+        if value.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            html,
+            "<tr><th>{}</th><td>{}</td></tr>",
+            escape(label),
+            escape(value)
+        );
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn resources(map: &ResourceMap) -> String {
+    Resource::iter()
+        .filter(|r| map[*r] != 0.)
+        .map(|r| format!("{r}: {}", map[r]))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn byproducts(map: &ByproductMap) -> String {
+    Byproduct::iter()
+        .filter(|b| map[*b] != 0.)
+        .map(|b| format!("{b}: {}", map[b]))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn effects(effects: &[Effect]) -> String {
+    effects
+        .iter()
+        .map(|effect| format!("{effect:?}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn industry(item: &Industry) -> String {
+    entity(
+        &item.name,
+        Some(&item.flavor.image),
+        &item.flavor.description,
+        &[
+            ("Resources", resources(&item.resources)),
+            ("Byproducts", byproducts(&item.byproducts)),
+        ],
+        "",
+        &item.tags,
+        &item.notes,
+    )
+}
+
+fn process(item: &Process) -> String {
+    let (feedstock, amount) = &item.feedstock;
+    entity(
+        &item.name,
+        Some(&item.flavor.image),
+        &item.flavor.description,
+        &[
+            ("Output", item.output.to_string()),
+            ("Mix share", item.mix_share.to_string()),
+            (
+                "Limit",
+                item.limit
+                    .map(|l| l.to_string())
+                    .unwrap_or_default(),
+            ),
+            ("Feedstock", format!("{feedstock}: {amount}")),
+            ("Resources", resources(&item.resources)),
+            ("Byproducts", byproducts(&item.byproducts)),
+            (
+                "Features",
+                item.features
+                    .iter()
+                    .map(|f| format!("{f:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("Locked", locked(item.locked)),
+        ],
+        "",
+        &item.tags,
+        &item.notes,
+    )
+}
+
+fn project(item: &Project) -> String {
+    let cost = match &item.base_cost {
+        Cost::Fixed(cost) => cost.to_string(),
+        Cost::Dynamic(factor, of) => {
+            format!("{factor} × {of:?}")
+        }
+    };
+    let outcomes = item
+        .outcomes
+        .iter()
+        .enumerate()
+        .map(|(i, outcome)| {
+            format!(
+                "{} ({}): {}",
+                i + 1,
+                outcome.probability.likelihood,
+                effects(&outcome.effects)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let upgrades = item
+        .upgrades
+        .iter()
+        .map(|upgrade| {
+            format!(
+                "{}: {}",
+                upgrade.cost,
+                effects(&upgrade.effects)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+    entity(
+        &item.name,
+        Some(&item.flavor.image),
+        &item.flavor.description,
+        &[
+            ("Type", format!("{:?}", item.kind)),
+            ("Group", format!("{:?}", item.group)),
+            ("Cost", cost),
+            (
+                "Required majority",
+                if item.required_majority > 0. {
+                    format!(
+                        "{}%",
+                        item.required_majority * 100.
+                    )
+                } else {
+                    String::new()
+                },
+            ),
+            ("Effects", effects(&item.effects)),
+            ("Outcomes", outcomes),
+            ("Upgrades", upgrades),
+            ("Locked", locked(item.locked)),
+        ],
+        "",
+        &item.tags,
+        &item.notes,
+    )
+}
+
+fn event(item: &Event) -> String {
+    let probabilities = item
+        .probabilities
+        .iter()
+        .map(|prob| {
+            format!(
+                "{} ({} conditions)",
+                prob.likelihood,
+                prob.conditions.len()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut dialogue = String::from("<div class=\"dialogue\">");
+    for line in &item.flavor.dialogue.lines {
+        let _ = write!(
+            dialogue,
+            "<p><b>{}:</b> {}</p>",
+            escape(&line.speaker.to_string()),
+            escape(&line.text)
+        );
+    }
+    dialogue.push_str("</div>");
+    entity(
+        &item.name,
+        item.flavor.image.as_ref(),
+        "",
+        &[
+            ("Phase", format!("{:?}", item.phase)),
+            ("Arc", item.flavor.arc.clone()),
+            ("Probabilities", probabilities),
+            ("Effects", effects(&item.effects)),
+            ("Locked", locked(item.locked)),
+        ],
+        &dialogue,
+        &item.tags,
+        &item.notes,
+    )
+}
+
+fn locked(locked: bool) -> String {
+    if locked {
+        "Yes".into()
+    } else {
+        String::new()
+    }
+}
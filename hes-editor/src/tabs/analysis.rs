@@ -0,0 +1,115 @@
+use crate::{
+    analyze::{analyze, stats},
+    t,
+};
+use hes_engine::World;
+use leptos::*;
+
+#[component]
+pub fn Analysis(world: RwSignal<World>) -> impl IntoView {
+    let outliers = move || {
+        with!(|world| analyze(world))
+            .into_iter()
+            .map(|outlier| {
+                let (lo, hi) = outlier.suggested;
+                view! {
+                    <tr>
+                        <td>{outlier.entity}</td>
+                        <td>{outlier.field}</td>
+                        <td class="numeric">{format!("{}", outlier.value)}</td>
+                        <td class="numeric">{format!("{}", outlier.median)}</td>
+                        <td class="numeric">{format!("{lo} – {hi}")}</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let dashboard = move || {
+        let stats = with!(|world| stats(world));
+        let processes = stats
+            .processes_per_output
+            .into_iter()
+            .map(|(output, n)| {
+                view! {
+                    <div class="stat-row"><label>{output.to_string()}</label><div>{n}</div></div>
+                }
+            })
+            .collect::<Vec<_>>();
+        let events = stats
+            .events_per_phase
+            .into_iter()
+            .map(|(phase, n)| {
+                view! {
+                    <div class="stat-row"><label>{phase.to_string()}</label><div>{n}</div></div>
+                }
+            })
+            .collect::<Vec<_>>();
+        let regions = stats
+            .region_populations
+            .into_iter()
+            .map(|(name, pop)| {
+                view! {
+                    <div class="stat-row"><label>{name}</label><div>{format!("{:.0}", pop)}</div></div>
+                }
+            })
+            .collect::<Vec<_>>();
+        let asset_kb = stats.asset_bytes as f32 / 1024.;
+
+        view! {
+            <div class="map-group-block stats">
+                <div class="map-group">
+                    <h2>{t!("Overview")}</h2>
+                    <div class="stat-row">
+                        <label>{t!("Baseline Emissions")}</label>
+                        <div>{format!("{:.2} Gt CO2eq", stats.baseline_emissions)}</div>
+                    </div>
+                    <div class="stat-row">
+                        <label>{t!("Embedded Images")}</label>
+                        <div>{format!("{:.1} KB", asset_kb)}</div>
+                    </div>
+                    <div class="stat-row">
+                        <label>{t!("Total Population")}</label>
+                        <div>{format!("{:.0}", stats.total_population)}</div>
+                    </div>
+                </div>
+                <div class="map-group">
+                    <h2>{t!("Processes per Output")}</h2>
+                    {processes}
+                </div>
+                <div class="map-group">
+                    <h2>{t!("Events per Phase")}</h2>
+                    {events}
+                </div>
+                <div class="map-group">
+                    <h2>{t!("Population by Region")}</h2>
+                    {regions}
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="analysis scroll-list">
+            {dashboard}
+            <div class="map-group table-group">
+                <h2 class="tooltip-parent">
+                    Possible Balance Issues
+                    <div class="tooltip">Values that are more than 100x larger or smaller than the median of their peers (e.g. other processes with the same output). These are often unit mistakes, like grams vs kilograms.</div>
+                </h2>
+                <table class="analysis-table">
+                    <thead>
+                        <tr>
+                            <th>{t!("Entity")}</th>
+                            <th>{t!("Field")}</th>
+                            <th>{t!("Value")}</th>
+                            <th>{t!("Median")}</th>
+                            <th>{t!("Suggested Range")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{outliers}</tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
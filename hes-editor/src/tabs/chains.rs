@@ -0,0 +1,351 @@
+use crate::{inputs::read_only, t};
+use hes_engine::{
+    flavor::DialogueNext,
+    Effect,
+    Event,
+    FollowUp,
+    Id,
+    World,
+};
+use leptos::*;
+use std::collections::HashMap;
+
+const NODE_WIDTH: f32 = 180.;
+const NODE_HEIGHT: f32 = 36.;
+const COL_GAP: f32 = 80.;
+const ROW_GAP: f32 = 16.;
+
+/// Longer names are truncated to fit in a node.
+const MAX_NAME_CHARS: usize = 24;
+
+/// Follow-ups created by connecting events fire right away.
+/// The delay can be changed in the source event's effects.
+const FOLLOW_UP_DELAY: usize = 0;
+
+/// How one event leads to another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Link {
+    /// Triggers the event after this many years.
+    Trigger(usize),
+
+    /// Unlocks the event so that it can occur.
+    Unlock,
+}
+impl Link {
+    fn label(&self) -> String {
+        match self {
+            Link::Trigger(years) => format!("+{years}y"),
+            Link::Unlock => "unlocks".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Edge {
+    from: Id,
+    to: Id,
+    link: Link,
+}
+
+/// All of an event's effects, including those
+/// of its dialogue responses.
+fn event_effects(event: &Event) -> Vec<&Effect> {
+    let mut effects: Vec<&Effect> =
+        event.effects.iter().collect();
+    for line in &event.flavor.dialogue.lines {
+        if let Some(DialogueNext::Responses(resps)) = &line.next
+        {
+            for resp in resps {
+                effects.extend(&resp.effects);
+            }
+        }
+    }
+    effects
+}
+
+/// The follow-ups of an event's dialogue responses.
+fn event_follow_ups(event: &Event) -> Vec<&FollowUp> {
+    let mut follow_ups = vec![];
+    for line in &event.flavor.dialogue.lines {
+        if let Some(DialogueNext::Responses(resps)) = &line.next
+        {
+            for resp in resps {
+                follow_ups.extend(&resp.follow_ups);
+            }
+        }
+    }
+    follow_ups
+}
+
+fn edges(world: &World) -> Vec<Edge> {
+    world
+        .events
+        .iter()
+        .flat_map(|event| {
+            let effects = event_effects(event)
+                .into_iter()
+                .filter_map(|effect| {
+                    let (to, link) = match effect {
+                        Effect::TriggerEvent(id, years) => {
+                            (*id, Link::Trigger(*years))
+                        }
+                        Effect::AddEvent(id) => {
+                            (*id, Link::Unlock)
+                        }
+                        _ => return None,
+                    };
+                    Some(Edge {
+                        from: event.id,
+                        to,
+                        link,
+                    })
+                });
+            let follow_ups = event_follow_ups(event)
+                .into_iter()
+                .map(|follow_up| Edge {
+                    from: event.id,
+                    to: follow_up.event,
+                    link: Link::Trigger(follow_up.delay),
+                });
+            effects.chain(follow_ups)
+        })
+        .collect()
+}
+
+/// Assign each event a column, by how far along a chain
+/// it is, and a row within that column.
+fn layout(
+    ids: &[Id],
+    edges: &[Edge],
+) -> HashMap<Id, (usize, usize)> {
+    let mut depth: HashMap<Id, usize> =
+        ids.iter().map(|id| (*id, 0)).collect();
+
+    // Push each event past the events that lead to it.
+    // Depth is capped so that cycles can't loop forever.
+    for _ in 0..ids.len() {
+        let mut changed = false;
+        for edge in edges {
+            let (Some(&from), Some(&to)) =
+                (depth.get(&edge.from), depth.get(&edge.to))
+            else {
+                continue;
+            };
+            if to <= from && from + 1 < ids.len() {
+                depth.insert(edge.to, from + 1);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut rows: HashMap<usize, usize> = HashMap::new();
+    ids.iter()
+        .map(|id| {
+            let col = depth[id];
+            let row = rows.entry(col).or_default();
+            let pos = (col, *row);
+            *row += 1;
+            (*id, pos)
+        })
+        .collect()
+}
+
+/// The top-left corner of a node.
+fn node_pos((col, row): (usize, usize)) -> (f32, f32) {
+    (
+        col as f32 * (NODE_WIDTH + COL_GAP),
+        row as f32 * (NODE_HEIGHT + ROW_GAP),
+    )
+}
+
+fn truncate(name: &str) -> String {
+    if name.chars().count() > MAX_NAME_CHARS {
+        let short: String =
+            name.chars().take(MAX_NAME_CHARS - 1).collect();
+        format!("{short}…")
+    } else {
+        name.to_string()
+    }
+}
+
+#[component]
+pub fn EventChains(world: RwSignal<World>) -> impl IntoView {
+    let read_only = read_only();
+    let only_chained = create_rw_signal(true);
+
+    // Only show events in this arc, if set.
+    let arc = create_rw_signal(String::new());
+
+    // The event to connect from, when connecting.
+    let source = create_rw_signal(None::<Id>);
+
+    let arcs = move || {
+        with!(|world| {
+            let mut arcs = world
+                .events
+                .iter()
+                .map(|ev| ev.flavor.arc.clone())
+                .filter(|arc| !arc.is_empty())
+                .collect::<Vec<_>>();
+            arcs.sort();
+            arcs.dedup();
+            arcs
+        })
+    };
+    let arc_opts = move || {
+        arcs()
+            .into_iter()
+            .map(|name| {
+                let value = name.clone();
+                view! {
+                    <option value=value.clone() selected=move || arc.get() == value>{name}</option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let graph = create_memo(move |_| {
+        with!(|world, only_chained, arc| {
+            let edges = edges(world);
+            let nodes = world
+                .events
+                .iter()
+                .filter(|ev| {
+                    arc.is_empty() || ev.flavor.arc == *arc
+                })
+                .filter(|ev| {
+                    !only_chained
+                        || edges.iter().any(|e| {
+                            e.from == ev.id || e.to == ev.id
+                        })
+                })
+                .map(|ev| (ev.id, ev.name.clone()))
+                .collect::<Vec<_>>();
+            let ids = nodes
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            let edges = edges
+                .into_iter()
+                .filter(|e| {
+                    ids.contains(&e.from) && ids.contains(&e.to)
+                })
+                .collect::<Vec<_>>();
+            let positions = layout(&ids, &edges);
+            (nodes, edges, positions)
+        })
+    });
+
+    let connect = move |id: Id| {
+        if read_only.get() {
+            return;
+        }
+        match source.get() {
+            None => source.set(Some(id)),
+            Some(from) if from == id => source.set(None),
+            Some(from) => {
+                update!(|world| {
+                    world.events[&from].effects.push(
+                        Effect::TriggerEvent(
+                            id,
+                            FOLLOW_UP_DELAY,
+                        ),
+                    );
+                });
+                source.set(None);
+            }
+        }
+    };
+
+    let svg = move || {
+        graph.with(|(nodes, edges, positions)| {
+            let (cols, rows) = positions.values().fold(
+                (0, 0),
+                |(cols, rows), (col, row)| {
+                    (cols.max(col + 1), rows.max(row + 1))
+                },
+            );
+            let width = cols as f32 * (NODE_WIDTH + COL_GAP);
+            let height = rows as f32 * (NODE_HEIGHT + ROW_GAP);
+
+            let edges = edges
+                .iter()
+                .map(|edge| {
+                    let (x1, y1) = node_pos(positions[&edge.from]);
+                    let (x2, y2) = node_pos(positions[&edge.to]);
+                    let (x1, y1) = (x1 + NODE_WIDTH, y1 + NODE_HEIGHT / 2.);
+                    let y2 = y2 + NODE_HEIGHT / 2.;
+                    view! {
+                        <g class="chain-edge" class:unlock=edge.link == Link::Unlock>
+                            <line x1=x1 y1=y1 x2=x2 y2=y2 marker-end="url(#chain-arrow)" />
+                            <text x=(x1 + x2) / 2. y=(y1 + y2) / 2. - 4.>{edge.link.label()}</text>
+                        </g>
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let nodes = nodes
+                .iter()
+                .map(|(id, name)| {
+                    let id = *id;
+                    let (x, y) = node_pos(positions[&id]);
+                    view! {
+                        <g class="chain-node"
+                            class:selected=move || source.get() == Some(id)
+                            on:click=move |_| connect(id)>
+                            <rect x=x y=y width=NODE_WIDTH height=NODE_HEIGHT rx=4 />
+                            <text x=x + 8. y=y + NODE_HEIGHT / 2. + 4.>{truncate(name)}</text>
+                        </g>
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            view! {
+                <svg class="chain-graph" width=width height=height>
+                    <defs>
+                        <marker id="chain-arrow" viewBox="0 0 10 10" refX=10 refY=5
+                            markerWidth=6 markerHeight=6 orient="auto">
+                            <path d="M0,0 L10,5 L0,10 z" />
+                        </marker>
+                    </defs>
+                    {edges}
+                    {nodes}
+                </svg>
+            }
+        })
+    };
+
+    view! {
+        <div class="chains scroll-list">
+            <div class="chains-controls">
+                <select on:change=move |ev| arc.set(event_target_value(&ev))>
+                    <option value="">{t!("All Arcs")}</option>
+                    {arc_opts}
+                </select>
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=only_chained
+                        on:change=move |ev| only_chained.set(event_target_checked(&ev)) />
+                    {t!("Only chained events")}
+                </label>
+            </div>
+            <div class="input-help">{move || {
+                if read_only.get() {
+                    t!("Arrows show which events trigger or unlock others.")
+                } else if source.get().is_some() {
+                    t!("Click another event to have the selected event trigger it, or click the selected event again to cancel.")
+                } else {
+                    t!("Arrows show which events trigger or unlock others. Click an event and then another to add a follow-up.")
+                }
+            }}</div>
+            <Show when=move || graph.with(|(nodes, _, _)| nodes.is_empty())>
+                <div class="empty">{t!("No events to show.")}</div>
+            </Show>
+            {svg}
+        </div>
+    }
+}
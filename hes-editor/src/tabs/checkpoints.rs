@@ -0,0 +1,272 @@
+use crate::{
+    baseline::Baseline,
+    checkpoints::{self, Checkpoint, Difference},
+    inputs::read_only,
+    t,
+};
+use hes_engine::World;
+use leptos::*;
+use wasm_bindgen::JsValue;
+
+/// Named snapshots of the world, to restore
+/// or compare against later.
+#[component]
+pub fn Checkpoints(world: RwSignal<World>) -> impl IntoView {
+    let baseline = expect_context::<Baseline>().0;
+    let read_only = read_only();
+    let name = create_rw_signal(String::new());
+    let error = create_rw_signal::<Option<String>>(None);
+
+    // The checkpoint currently being compared against,
+    // and the one whose changes are listed, if any.
+    let comparing = create_rw_signal::<Option<f64>>(None);
+    let changes = create_rw_signal::<
+        Option<(f64, Vec<Difference>)>,
+    >(None);
+
+    let list = create_local_resource(
+        || (),
+        |_| async move { checkpoints::checkpoints().await },
+    );
+
+    let create = move |_| {
+        let label = name.get_untracked().trim().to_string();
+        if label.is_empty() {
+            return;
+        }
+        let w = world.get_untracked();
+        spawn_local(async move {
+            match checkpoints::create(&label, &w).await {
+                Ok(()) => {
+                    name.set(String::new());
+                    error.set(None);
+                }
+                Err(err) => error.set(Some(format!(
+                    "Couldn't save the checkpoint: {err:?}"
+                ))),
+            }
+            list.refetch();
+        });
+    };
+
+    // Load a checkpoint's world and do something with it.
+    let with_loaded =
+        move |checkpoint: Checkpoint,
+              f: Box<dyn FnOnce(World)>| {
+            spawn_local(async move {
+                match checkpoints::load(&checkpoint).await {
+                    Ok(w) => {
+                        error.set(None);
+                        f(w);
+                    }
+                    Err(err) => error.set(Some(err)),
+                }
+            });
+        };
+
+    let restore = move |checkpoint: Checkpoint| {
+        let msg = "Replace the current world with this checkpoint? Any changes since it was created will be lost, unless you create a checkpoint of them first.";
+        if !window().confirm_with_message(msg).unwrap_or(false)
+        {
+            return;
+        }
+        with_loaded(
+            checkpoint,
+            Box::new(move |w| {
+                world.set(w);
+                changes.set(None);
+            }),
+        );
+    };
+
+    let compare = move |checkpoint: Checkpoint| {
+        let created = checkpoint.created;
+        if comparing.get_untracked() == Some(created) {
+            comparing.set(None);
+            baseline.set(None);
+            return;
+        }
+        with_loaded(
+            checkpoint,
+            Box::new(move |w| {
+                comparing.set(Some(created));
+                baseline.set(Some(w));
+            }),
+        );
+    };
+
+    let show_changes = move |checkpoint: Checkpoint| {
+        let created = checkpoint.created;
+        if with!(|changes| changes
+            .as_ref()
+            .is_some_and(|(shown, _)| *shown == created))
+        {
+            changes.set(None);
+            return;
+        }
+        with_loaded(
+            checkpoint,
+            Box::new(move |w| {
+                let diffs = world.with_untracked(|current| {
+                    checkpoints::diff(current, &w)
+                });
+                changes.set(Some((created, diffs)));
+            }),
+        );
+    };
+
+    let remove = move |checkpoint: Checkpoint| {
+        let msg =
+            "Delete this checkpoint? It can't be restored.";
+        if !window().confirm_with_message(msg).unwrap_or(false)
+        {
+            return;
+        }
+        spawn_local(async move {
+            if let Err(err) =
+                checkpoints::remove(&checkpoint).await
+            {
+                log::warn!(
+                    "Failed to remove checkpoint: {err:?}"
+                );
+            }
+            list.refetch();
+        });
+    };
+
+    // Stop marking the comparison if the baseline
+    // was changed elsewhere, e.g. from the menu.
+    create_effect(move |_| {
+        if with!(|baseline| baseline.is_none()) {
+            comparing.set(None);
+        }
+    });
+
+    let change_list = move |created: f64| {
+        with!(|changes| {
+            let (_, diffs) = changes
+                .as_ref()
+                .filter(|(shown, _)| *shown == created)?;
+            let view = if diffs.is_empty() {
+                view! {
+                    <div class="empty">{t!("No changes since this checkpoint.")}</div>
+                }
+                .into_view()
+            } else {
+                diffs
+                    .iter()
+                    .map(|diff| {
+                        view! {
+                            <div class="checkpoint-change">
+                                <span class="checkpoint-change-kind">
+                                    {t!(diff.change.label())}
+                                </span>
+                                <span class="checkpoint-change-entity">
+                                    {t!(diff.kind)}
+                                </span>
+                                {diff.name.clone()}
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            };
+            Some(view! {
+                <tr>
+                    <td colspan="3" class="checkpoint-changes">{view}</td>
+                </tr>
+            })
+        })
+    };
+
+    let rows = move || {
+        let checkpoints = list.get().unwrap_or_default();
+        if checkpoints.is_empty() {
+            return view! {
+                <div class="empty">{t!("No checkpoints yet.")}</div>
+            }
+            .into_view();
+        }
+        let rows = checkpoints
+            .into_iter()
+            .map(|checkpoint| {
+                let created = checkpoint.created;
+                let date = js_sys::Date::new(&created.into())
+                    .to_locale_string(
+                        "default",
+                        &JsValue::UNDEFINED,
+                    )
+                    .as_string()
+                    .unwrap_or_default();
+                let (c1, c2, c3, c4) = (
+                    checkpoint.clone(),
+                    checkpoint.clone(),
+                    checkpoint.clone(),
+                    checkpoint.clone(),
+                );
+                let is_compared =
+                    move || comparing.get() == Some(created);
+                view! {
+                    <tr>
+                        <td>{checkpoint.name}</td>
+                        <td class="checkpoint-created">{date}</td>
+                        <td class="checkpoint-actions">
+                            <button
+                                disabled=read_only
+                                on:click=move |_| restore(c1.clone())>
+                                {t!("Restore")}
+                            </button>
+                            <button
+                                title=t!("Mark fields that differ from this checkpoint.")
+                                on:click=move |_| compare(c2.clone())>
+                                {move || if is_compared() {
+                                    t!("Stop Comparing")
+                                } else {
+                                    t!("Compare")
+                                }}
+                            </button>
+                            <button on:click=move |_| show_changes(c3.clone())>
+                                {t!("Changes")}
+                            </button>
+                            <button on:click=move |_| remove(c4.clone())>
+                                {t!("Delete")}
+                            </button>
+                        </td>
+                    </tr>
+                    {move || change_list(created)}
+                }
+            })
+            .collect_view();
+        view! {
+            <table class="checkpoints-table">
+                <tbody>{rows}</tbody>
+            </table>
+        }
+        .into_view()
+    };
+
+    view! {
+        <div class="checkpoints scroll-list">
+            <div class="input-help">{t!("Save named snapshots of the world, e.g. before a big rebalance, to restore or compare against later. Checkpoints are stored in this browser rather than in the world.")}</div>
+            <div class="checkpoint-create">
+                <input type="text"
+                    placeholder=t!("Checkpoint name")
+                    prop:value=name
+                    on:input=move |ev| name.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            create(());
+                        }
+                    } />
+                <button
+                    disabled=move || with!(|name| name.trim().is_empty())
+                    on:click=move |_| create(())>
+                    {t!("Create Checkpoint")}
+                </button>
+            </div>
+            {move || error.get().map(|err| view! {
+                <div class="input-error">{err}</div>
+            })}
+            {rows}
+        </div>
+    }
+}
@@ -1,5 +1,15 @@
-use crate::{infinite_list, inputs::*, subsignal};
-use hes_engine::*;
+use crate::{
+    baseline,
+    baseline::use_baseline,
+    conflicts::FieldConflicts,
+    enum_slice,
+    infinite_list,
+    inputs::*,
+    lint::Lints,
+    subsignal,
+    t,
+};
+use hes_engine::{flavor::DialogueLine, *};
 use leptos::*;
 
 #[component]
@@ -7,33 +17,85 @@ fn Event(
     signal: (Signal<Event>, SignalSetter<Event>),
 ) -> impl IntoView {
     let event = signal;
+    let defaults = Event::new();
+    let id = event.0.with_untracked(|event| event.id);
+    let base = use_baseline(move |world| {
+        world.events.try_get(&id).cloned()
+    });
     view! {
         <div class="event" id={move || signal.0.with(|signal| signal.id.to_string())}>
             <div class="name">
-                <TextInput signal=subsignal!(event.name) />
+                <TextInput lints=Lints::FLAVOR commit=Commit::Debounce baseline=baseline!(base.name) signal=subsignal!(event.name) />
                 <div class="item-lock">
                     <ToggleInput
                         label="Locked"
                         tooltip=true
                         icons=("🔒Locked", "🔓Unlocked")
                         help="If this event is locked at the start."
+                        baseline=baseline!(base.locked)
                         signal=subsignal!(event.locked) />
                 </div>
             </div>
             <div class="item-form">
                 <div class="input-groups">
                     <OptionalImageInput signal=subsignal!(event.flavor.image) />
+                    <OptionalAudioInput signal=subsignal!(event.flavor.sound) />
+                    <GalleryInput
+                        label="Image Variants"
+                        help="Alternate images for this event. If it has an image, one of these or that image is shown at random."
+                        signal=subsignal!(event.flavor.variants) />
                 </div>
                 <div class="input-groups event-meta">
                     <div class="arc">
-                        <TextInput signal=subsignal!(event.flavor.arc) />
-                        <div class="input-help">Optional story arc name.</div>
+                        <TextInput lints=Lints::FLAVOR baseline=baseline!(base.flavor.arc) signal=subsignal!(event.flavor.arc) />
+                        <div class="input-help">{t!("Optional story arc name.")}</div>
                     </div>
                     <EnumInput
                         label="Phase"
                         help="What phase/screen the event can occur on."
+                        default=defaults.phase
+                        baseline=baseline!(base.phase)
                         signal=subsignal!(event.phase) />
                 </div>
+                <div class="input-groups event-meta">
+                    <NumericInput
+                        label="Cooldown"
+                        help="Years before this event can occur again. Events without a cooldown only occur once (except for icon events)."
+                        default=defaults.cooldown
+                        baseline=baseline!(base.cooldown)
+                        signal=subsignal!(event.cooldown) />
+                    <TextInput
+                        label="Pool"
+                        help="Optional pool to draw this event from. Only one event from a pool occurs at a time, and each is drawn before any repeat."
+                        commit=Commit::Debounce
+                        baseline=baseline!(base.pool)
+                        signal=subsignal!(event.pool) />
+                    <NumericInput
+                        label="Weight"
+                        help="How likely this event is to be drawn relative to the others in its pool."
+                        default=defaults.weight
+                        baseline=baseline!(base.weight)
+                        signal=subsignal!(event.weight) />
+                    <OptionalInput
+                        label="Scheduled"
+                        help="If this event occurs at a set time, whatever its probabilities."
+                        signal=subsignal!(event.schedule)
+                        editor=move |signal: (Signal<Schedule>, SignalSetter<Schedule>)| view! {
+                            <Schedule signal />
+                        } />
+                    <OptionalInput
+                        label="Magnitude"
+                        help="If this event occurs with a magnitude, rolled from this range (unless carried over from the event before it), which scales its effects and can be shown in its text as {magnitude}."
+                        signal=subsignal!(event.magnitude)
+                        editor=move |signal: (Signal<(f32, f32)>, SignalSetter<(f32, f32)>)| view! {
+                            <MagnitudeRange signal />
+                        } />
+                </div>
+            </div>
+
+            <div class="item-form dialogue-form">
+                <DialogueLines
+                    lines=subsignal!(event.flavor.dialogue.lines) />
             </div>
 
             <div class="item-form effects-form">
@@ -45,11 +107,123 @@ fn Event(
             <div class="item-form probabilities-form">
                 <Probabilities
                     probabilities=subsignal!(event.probabilities) />
+                <FieldConflicts item=event.0 fields=&["probabilities"] />
             </div>
 
              <div class="item-form notes-form">
-                 <TextArea label="Notes" help="Optional notes" signal=subsignal!(event.notes) />
+                 <TextArea label="Notes" help="Optional notes" commit=Commit::Blur signal=subsignal!(event.notes) />
              </div>
+             <div class="item-form tags-form">
+                 <TagsInput signal=subsignal!(event.tags) />
+             </div>
+        </div>
+    }
+}
+
+/// When a scheduled event occurs: in a given
+/// year, or some years after another event.
+#[component]
+fn Schedule(
+    signal: (Signal<Schedule>, SignalSetter<Schedule>),
+) -> impl IntoView {
+    let (read, write) = signal;
+    let events =
+        expect_context::<Signal<Collection<Ref<Event>>>>();
+
+    let kind = Signal::derive(move || {
+        with!(|read| ScheduleKind::from(read))
+    });
+    let set_kind = SignalSetter::map(move |kind| {
+        let default_event = with!(|events| events.first().id);
+        write.set(Schedule::from_kind(kind, default_event));
+    });
+
+    let input = move || match read.get() {
+        Schedule::Year(year) => view! {
+            <NumericInput
+                label="Year"
+                help="The year this event occurs in."
+                signal=enum_slice!(|write| Schedule::Year([year])) />
+        }.into_view(),
+        Schedule::After(id, years) => view! {
+            <EntityPicker
+                label="After Event"
+                opts=events
+                help="The event this one follows."
+                signal=enum_slice!(|write| Schedule::After([id], years)) />
+            <NumericInput
+                label="Years After"
+                help="How many years after that event this one occurs."
+                signal=enum_slice!(|write| Schedule::After(id, [years])) />
+        }.into_view(),
+    };
+
+    view! {
+        <EnumInput
+            label="Schedule"
+            help="If this event occurs in a set year or after another event."
+            signal=(kind, set_kind) />
+        {input}
+    }
+}
+
+/// The range an event's magnitude is rolled from.
+#[component]
+fn MagnitudeRange(
+    signal: (Signal<(f32, f32)>, SignalSetter<(f32, f32)>),
+) -> impl IntoView {
+    let (read, write) = signal;
+    let min = Signal::derive(move || read.get().0);
+    let set_min = SignalSetter::map(move |min| {
+        write.set((min, read.get_untracked().1));
+    });
+    let max = Signal::derive(move || read.get().1);
+    let set_max = SignalSetter::map(move |max| {
+        write.set((read.get_untracked().0, max));
+    });
+
+    view! {
+        <NumericInput
+            label="Min"
+            help="The smallest magnitude this event can occur with."
+            signal=(min, set_min) />
+        <NumericInput
+            label="Max"
+            help="The largest magnitude this event can occur with."
+            signal=(max, set_max) />
+    }
+}
+
+#[component]
+fn DialogueLines(
+    lines: (
+        Signal<Vec<DialogueLine>>,
+        SignalSetter<Vec<DialogueLine>>,
+    ),
+) -> impl IntoView {
+    let (read, _) = lines;
+    let n_lines = move || with!(|read| read.len());
+    view! {
+        <div class="dialogue-lines">
+            <h2>{t!("Dialogue")}</h2>
+            <div class="input-help">{t!("The lines shown when this event occurs.")}</div>
+            {move || {
+                 (0..n_lines()).map(|i| {
+                     let line = subsignal!(lines[i]);
+                     view! {
+                         <div class="dialogue-line">
+                             <SearchableEnumInput
+                                 label="Speaker"
+                                 help="Who says this line."
+                                 signal=subsignal!(line.speaker) />
+                             <MarkdownInput
+                                 label="Text"
+                                 lints=Lints::DIALOGUE
+                                 signal=subsignal!(line.text) />
+                         </div>
+                     }
+                 }).collect::<Vec<_>>()
+            }}
         </div>
     }
 }
@@ -71,7 +245,7 @@ where
                 label="Likelihood"
                 help="The likelihood when all conditions are met."
                 signal=subsignal!(probability.likelihood) />
-            <div class="mutable-list-item-remove" title="Ctrl-click to remove without confirmation." on:click=on_remove>"✗"</div>
+            <div class="mutable-list-item-remove" title=t!("Ctrl-click to remove without confirmation.") on:click=on_remove>"✗"</div>
         </div>
         <Conditions
             conditions=subsignal!(probability.conditions) />
@@ -90,7 +264,7 @@ fn Probabilities(
     view! {
         <div class="probabilities mutable-list mutable-list-double-col mutable-list-sortable">
             <div class="mutable-list-header">
-                <h2>Probabilities</h2>
+                <h2>{t!("Probabilities")}</h2>
                 <div class="mutable-list-add">
                     <div class="mutable-list-add-button" on:click=move |_| {
                         let probability = Probability::default();
@@ -100,12 +274,12 @@ fn Probabilities(
                     }>+Add</div>
                 </div>
             </div>
-            <div class="input-help">"Probabilities are checked in their defined order, and the first probability with all conditions satisfied is the one that is rolled."</div>
+            <div class="input-help">{t!("Probabilities are checked in their defined order, and the first probability with all conditions satisfied is the one that is rolled.")}</div>
             {move || {
                  let empty = with!(|read| read.is_empty());
                  if empty {
                      Some(view! {
-                         <div class="empty">No probabilities defined.</div>
+                         <div class="empty">{t!("No probabilities defined.")}</div>
                      })
                  } else {
                      None
@@ -0,0 +1,313 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{inputs::read_only, t};
+use hes_engine::World;
+use leptos::*;
+use regex_lite::Regex;
+
+/// The most matches to show at once.
+const MAX_SHOWN: usize = 200;
+
+/// How many characters of context
+/// to show around each match.
+const CONTEXT: usize = 30;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Search {
+    find: String,
+    replace: String,
+    regex: bool,
+    case_sensitive: bool,
+}
+impl Search {
+    /// The pattern to search with, or `None`
+    /// if there's nothing to search for.
+    fn pattern(&self) -> Result<Option<Regex>, String> {
+        if self.find.is_empty() {
+            return Ok(None);
+        }
+        let pattern = if self.regex {
+            self.find.clone()
+        } else {
+            regex_lite::escape(&self.find)
+        };
+        let pattern = if self.case_sensitive {
+            pattern
+        } else {
+            format!("(?i){pattern}")
+        };
+        Regex::new(&pattern)
+            .map(Some)
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// A match within one of the world's text fields.
+#[derive(Debug, Clone, PartialEq)]
+struct Match {
+    /// The index of the field in `World::text_fields`.
+    field: usize,
+    owner: String,
+    label: &'static str,
+    start: usize,
+    end: usize,
+    replacement: String,
+    before: String,
+    matched: String,
+    after: String,
+}
+impl Match {
+    /// Matches are identified by where they are, so
+    /// which are skipped survives re-running the search.
+    fn key(&self) -> (usize, usize) {
+        (self.field, self.start)
+    }
+}
+
+/// Up to `CONTEXT` characters before and after the
+/// span, marking where the text was cut off.
+fn context(
+    text: &str,
+    start: usize,
+    end: usize,
+) -> (String, String) {
+    let before = &text[..start];
+    let after = &text[end..];
+    let n_before = before.chars().count();
+    let before = if n_before > CONTEXT {
+        let skip = before
+            .char_indices()
+            .nth(n_before - CONTEXT)
+            .map_or(0, |(i, _)| i);
+        format!("…{}", &before[skip..])
+    } else {
+        before.to_string()
+    };
+    let after = match after.char_indices().nth(CONTEXT) {
+        Some((i, _)) => format!("{}…", &after[..i]),
+        None => after.to_string(),
+    };
+    (before, after)
+}
+
+fn find_matches(
+    world: &World,
+    search: &Search,
+    pattern: &Regex,
+) -> Vec<Match> {
+    let mut matches = vec![];
+    for (idx, (field, text)) in
+        world.text_fields().into_iter().enumerate()
+    {
+        for caps in pattern.captures_iter(text) {
+            let Some(m) = caps.get(0) else { continue };
+
+            // Empty matches, e.g. from `a*`,
+            // would only insert text everywhere.
+            if m.is_empty() {
+                continue;
+            }
+            let replacement = if search.regex {
+                let mut dst = String::new();
+                caps.expand(&search.replace, &mut dst);
+                dst
+            } else {
+                search.replace.clone()
+            };
+            let (before, after) =
+                context(text, m.start(), m.end());
+            matches.push(Match {
+                field: idx,
+                owner: field.owner.clone(),
+                label: field.field,
+                start: m.start(),
+                end: m.end(),
+                replacement,
+                before,
+                matched: m.as_str().to_string(),
+                after,
+            });
+        }
+    }
+    matches
+}
+
+/// Apply the matches, which must all be
+/// from the world's current text.
+fn replace_matches(world: &mut World, matches: &[Match]) {
+    let mut by_field: BTreeMap<usize, Vec<&Match>> =
+        BTreeMap::new();
+    for m in matches {
+        by_field.entry(m.field).or_default().push(m);
+    }
+    for (idx, (_, text)) in
+        world.text_fields_mut().into_iter().enumerate()
+    {
+        let Some(matches) = by_field.get(&idx) else {
+            continue;
+        };
+        let mut replaced = String::new();
+        let mut last = 0;
+        for m in matches {
+            replaced.push_str(&text[last..m.start]);
+            replaced.push_str(&m.replacement);
+            last = m.end;
+        }
+        replaced.push_str(&text[last..]);
+        *text = replaced;
+    }
+}
+
+/// Find and replace across the world's names and flavor
+/// text, previewing each match so it can be skipped.
+#[component]
+pub fn Find(world: RwSignal<World>) -> impl IntoView {
+    let search = create_rw_signal(Search::default());
+    let skipped =
+        create_rw_signal(HashSet::<(usize, usize)>::new());
+    let read_only = read_only();
+
+    // Any change to the search starts over.
+    create_effect(move |_| {
+        search.track();
+        skipped.set(HashSet::new());
+    });
+
+    let pattern =
+        create_memo(move |_| with!(|search| search.pattern()));
+    let matches = create_memo(move |_| {
+        pattern.with(|pattern| match pattern {
+            Ok(Some(pattern)) => with!(|world, search| {
+                find_matches(world, search, pattern)
+            }),
+            _ => vec![],
+        })
+    });
+    let accepted = move || {
+        with!(|matches, skipped| {
+            matches
+                .iter()
+                .filter(|m| !skipped.contains(&m.key()))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let replace = move |_| {
+        let accepted = accepted();
+        update!(|world| replace_matches(world, &accepted));
+        skipped.set(HashSet::new());
+    };
+
+    let set_all = move |accept: bool| {
+        let keys = with!(|matches| {
+            matches
+                .iter()
+                .map(Match::key)
+                .collect::<HashSet<_>>()
+        });
+        skipped.set(if accept { HashSet::new() } else { keys });
+    };
+
+    let rows = move || {
+        with!(|matches| {
+            matches
+                .iter()
+                .take(MAX_SHOWN)
+                .map(|m| {
+                    let key = m.key();
+                    let m = m.clone();
+                    view! {
+                        <tr class:skipped=move || with!(|skipped| skipped.contains(&key))>
+                            <td>
+                                <input type="checkbox"
+                                    prop:checked=move || with!(|skipped| !skipped.contains(&key))
+                                    on:change=move |ev| {
+                                        let accept = event_target_checked(&ev);
+                                        update!(|skipped| {
+                                            if accept {
+                                                skipped.remove(&key);
+                                            } else {
+                                                skipped.insert(key);
+                                            }
+                                        });
+                                    } />
+                            </td>
+                            <td class="find-owner">
+                                {m.owner}
+                                <div class="find-field">{m.label}</div>
+                            </td>
+                            <td class="find-context">
+                                {m.before}
+                                <del>{m.matched}</del>
+                                <ins>{m.replacement}</ins>
+                                {m.after}
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div class="find scroll-list">
+            <div class="find-controls">
+                <input
+                    class="text-input"
+                    placeholder=t!("Find")
+                    on:input=move |ev| {
+                        update!(|search| search.find = event_target_value(&ev));
+                    } />
+                <input
+                    class="text-input"
+                    placeholder=t!("Replace with")
+                    on:input=move |ev| {
+                        update!(|search| search.replace = event_target_value(&ev));
+                    } />
+                <label>
+                    <input
+                        type="checkbox"
+                        on:change=move |ev| {
+                            update!(|search| search.regex = event_target_checked(&ev));
+                        } />
+                    {t!("Regex")}
+                </label>
+                <label>
+                    <input
+                        type="checkbox"
+                        on:change=move |ev| {
+                            update!(|search| search.case_sensitive = event_target_checked(&ev));
+                        } />
+                    {t!("Match case")}
+                </label>
+            </div>
+            <div class="input-help">{t!("Searches the names and flavor text of regions, industries, processes, projects and events. With regex enabled, the replacement can refer to groups, e.g. $1. Translations are keyed by their source text, so replaced text will need to be translated again.")}</div>
+            {move || pattern.with(|pattern| pattern.as_ref().err().map(|err| view! {
+                <div class="input-error">{t!("Invalid pattern: {err}", err: err)}</div>
+            }))}
+            <Show when=move || matches.with(|m| !m.is_empty())>
+                <div class="find-summary">
+                    <div>
+                        {move || t!("{accepted} of {n} matches accepted", accepted: accepted().len(), n: matches.with(|m| m.len()))}
+                    </div>
+                    <div class="button" on:click=move |_| set_all(true)>{t!("Accept All")}</div>
+                    <div class="button" on:click=move |_| set_all(false)>{t!("Skip All")}</div>
+                    <button
+                        disabled=move || read_only.get() || accepted().is_empty()
+                        on:click=replace>
+                        {t!("Replace Accepted")}
+                    </button>
+                </div>
+                <Show when=move || matches.with(|m| m.len() > MAX_SHOWN)>
+                    <div class="input-help">
+                        {t!("Showing the first {n} matches. The rest are replaced too unless skipped with \"Skip All\".", n: MAX_SHOWN)}
+                    </div>
+                </Show>
+                <table class="find-table">
+                    <tbody>{rows}</tbody>
+                </table>
+            </Show>
+        </div>
+    }
+}
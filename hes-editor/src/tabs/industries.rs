@@ -1,4 +1,14 @@
-use crate::{infinite_list, inputs::*, subsignal};
+use crate::{
+    baseline,
+    baseline::use_baseline,
+    conflicts::FieldConflicts,
+    field_doc,
+    infinite_list,
+    inputs::*,
+    lint::Lints,
+    subsignal,
+    t,
+};
 use hes_engine::{Industry, World};
 use leptos::*;
 
@@ -7,34 +17,42 @@ fn Industry(
     signal: (Signal<Industry>, SignalSetter<Industry>),
 ) -> impl IntoView {
     let industry = signal;
+    let id = industry.0.with_untracked(|industry| industry.id);
+    let base = use_baseline(move |world| {
+        world.industries.try_get(&id).cloned()
+    });
 
     view! {
         <div class="industry" id={move || signal.0.with(|signal| signal.id.to_string())}>
             <div class="name">
-                <TextInput signal=subsignal!(industry.name) />
+                <TextInput lints=Lints::FLAVOR commit=Commit::Debounce baseline=baseline!(base.name) signal=subsignal!(industry.name) />
             </div>
             <div class="item-form">
                 <ImageInput signal=subsignal!(industry.flavor.image) />
                 <div class="input-groups">
                     <ByproductMapInput
                         label="Byproducts"
-                        help="Byproducts produced, per low-income-capita (LIC) per year."
+                        help=field_doc!(Industry.byproducts)
                         signal=subsignal!(industry.byproducts) />
                     <ResourceMapInput
                         label="Resources"
-                        help="Resources used, per low-income-capita (LIC) per year."
+                        help=field_doc!(Industry.resources)
                         signal=subsignal!(industry.resources)
                      />
-                     <div class="input-help">"Note that an industry's *direct* emissions (including due to land use) should be represented as byproducts, but for many industries their principle byproducts are due to energy use, which should be represented as fuel/electricity resource use."</div>
+                     <FieldConflicts item=industry.0 fields=&["resources"] />
+                     <div class="input-help">{t!("Note that an industry's *direct* emissions (including due to land use) should be represented as byproducts, but for many industries their principle byproducts are due to energy use, which should be represented as fuel/electricity resource use.")}</div>
                  </div>
              </div>
 
              <div class="item-form desc-form">
-                 <TextArea label="Description" help="Describe the industry." signal=subsignal!(industry.flavor.description) />
+                 <MarkdownInput lints=Lints::FLAVOR commit=Commit::Debounce label="Description" help="Describe the industry." signal=subsignal!(industry.flavor.description) />
              </div>
 
              <div class="item-form notes-form">
-                 <TextArea label="Notes" help="Optional notes" signal=subsignal!(industry.notes) />
+                 <TextArea label="Notes" help=field_doc!(Industry.notes) commit=Commit::Blur signal=subsignal!(industry.notes) />
+             </div>
+             <div class="item-form tags-form">
+                 <TagsInput signal=subsignal!(industry.tags) />
              </div>
         </div>
     }
@@ -1,13 +1,29 @@
+mod analysis;
+mod chains;
+mod checkpoints;
 mod events;
+mod find;
 mod help;
 mod industries;
+mod playtest;
+mod preview;
 mod processes;
 mod projects;
+mod translations;
+mod trash;
 mod world;
 
+pub use analysis::Analysis;
+pub use chains::EventChains;
+pub use checkpoints::Checkpoints;
 pub use events::Events;
+pub use find::Find;
 pub use help::Help;
 pub use industries::Industries;
+pub use playtest::Playtest;
+pub use preview::Preview;
 pub use processes::Processes;
 pub use projects::Projects;
+pub use translations::Translations;
+pub use trash::Trash;
 pub use world::World;
@@ -0,0 +1,342 @@
+use crate::t;
+use hes_engine::{Simulation, World, YearReport};
+use leptos::*;
+
+/// The most years a single jump can simulate.
+const MAX_JUMP: usize = 100;
+
+const HELP: &str = "Commands:
+  step                     Simulate one year.
+  jump <years>             Simulate several years.
+  set temperature <value>  Set the temperature anomaly (C).
+  set pc <value>           Set political capital.
+  set outlook <value>      Set contentedness.
+  set extinction <value>   Set the extinction rate.
+  event <name> [@ region]  Apply an event's effects now.
+  start <project>          Start building a project.
+  reset                    Start over from the edited world.";
+
+#[derive(Clone)]
+enum Line {
+    Command(String),
+    Info(String),
+    Error(String),
+}
+
+/// What a console command did.
+struct Executed {
+    message: String,
+    years: Vec<YearReport>,
+}
+impl Executed {
+    fn message(message: impl Into<String>) -> Self {
+        Executed {
+            message: message.into(),
+            years: vec![],
+        }
+    }
+}
+
+fn parse_value(value: &str) -> Result<f32, String> {
+    value
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("Not a number: \"{value}\""))
+}
+
+/// Run a console command against the simulation.
+fn execute(
+    sim: &mut Simulation,
+    command: &str,
+) -> Result<Executed, String> {
+    let (name, args) =
+        command.split_once(' ').unwrap_or((command, ""));
+    let args = args.trim();
+    match name.to_lowercase().as_str() {
+        "help" => Ok(Executed::message(HELP)),
+        "step" => jump(sim, 1),
+        "jump" => {
+            let years = args.parse::<usize>().map_err(|_| {
+                format!("Not a number of years: \"{args}\"")
+            })?;
+            jump(sim, years.min(MAX_JUMP))
+        }
+        "set" => {
+            let (var, value) = args
+                .split_once(' ')
+                .ok_or("Usage: set <variable> <value>")?;
+            let value = parse_value(value)?;
+            match var.to_lowercase().as_str() {
+                "temperature" | "temp" => {
+                    sim.set_temperature(value)
+                }
+                "pc" | "political_capital" => {
+                    sim.state.political_capital = value as isize
+                }
+                "outlook" | "contentedness" => {
+                    sim.state.world.base_outlook +=
+                        value - sim.state.outlook();
+                }
+                "extinction" => {
+                    sim.state.world.extinction_rate = value
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown variable: \"{var}\""
+                    ))
+                }
+            }
+            Ok(Executed::message(format!("Set {var} to {value}.")))
+        }
+        "event" => {
+            let (name, region) = match args.split_once('@') {
+                Some((name, region)) => {
+                    (name.trim(), Some(region.trim()))
+                }
+                None => (args, None),
+            };
+            let state = &mut sim.state;
+            let event = state
+                .event_pool
+                .events
+                .iter()
+                .find(|ev| ev.name.eq_ignore_ascii_case(name))
+                .ok_or(format!("No event named \"{name}\""))?;
+            let (id, name) = (event.id, event.name.clone());
+            let region_id = match region {
+                Some(region) => Some(
+                    state
+                        .world
+                        .regions
+                        .iter()
+                        .find(|r| {
+                            r.name.eq_ignore_ascii_case(region)
+                        })
+                        .ok_or(format!(
+                            "No region named \"{region}\""
+                        ))?
+                        .id,
+                ),
+                None => None,
+            };
+            state.apply_event(id, region_id);
+            Ok(Executed::message(format!("Applied \"{name}\".")))
+        }
+        "start" => {
+            let state = &mut sim.state;
+            let project = state
+                .world
+                .projects
+                .iter()
+                .find(|p| p.name.eq_ignore_ascii_case(args))
+                .ok_or(format!("No project named \"{args}\""))?;
+            let (id, name) = (project.id, project.name.clone());
+            state.start_project(&id);
+            Ok(Executed::message(format!("Started \"{name}\".")))
+        }
+        _ => Err(format!(
+            "Unknown command: \"{name}\". Type \"help\" for commands."
+        )),
+    }
+}
+
+fn jump(
+    sim: &mut Simulation,
+    years: usize,
+) -> Result<Executed, String> {
+    if sim.state.game_over {
+        return Err(
+            "The game is over. Type \"reset\" to start over."
+                .into(),
+        );
+    }
+    let years = sim.run(years);
+    let mut message =
+        format!("Now in {}.", sim.state.world.year);
+    if sim.state.game_over {
+        message.push_str(" The game is over.");
+    }
+    Ok(Executed { message, years })
+}
+
+/// Play the edited world with a debug console for
+/// skipping ahead, so late-game content can be reached
+/// without playing through the whole game.
+#[component]
+pub fn Playtest(world: RwSignal<World>) -> impl IntoView {
+    let sim = store_value::<Option<Simulation>>(None);
+    let current = create_rw_signal::<Option<YearReport>>(None);
+    let history = create_rw_signal::<Vec<YearReport>>(vec![]);
+    let log = create_rw_signal::<Vec<Line>>(vec![]);
+
+    let reset = move || {
+        let new = Simulation::new(world.get_untracked());
+        current.set(Some(new.report()));
+        sim.set_value(Some(new));
+        history.set(vec![]);
+        log.set(vec![Line::Info(
+            "Started from the edited world. Type \"help\" for commands."
+                .into(),
+        )]);
+    };
+
+    let submit = move |command: String| {
+        let command = command.trim().to_string();
+        if command.is_empty() {
+            return;
+        }
+        let is_reset = command.eq_ignore_ascii_case("reset");
+        if is_reset || sim.with_value(Option::is_none) {
+            reset();
+            if is_reset {
+                return;
+            }
+        }
+        update!(|log| log.push(Line::Command(command.clone())));
+
+        sim.update_value(|sim| {
+            let Some(sim) = sim else { return };
+            let result = execute(sim, &command);
+            current.set(Some(sim.report()));
+            match result {
+                Ok(executed) => {
+                    update!(|history| history
+                        .extend(executed.years));
+                    update!(|log| log
+                        .push(Line::Info(executed.message)));
+                }
+                Err(err) => {
+                    update!(|log| log.push(Line::Error(err)))
+                }
+            }
+        });
+    };
+
+    let summary = move || {
+        with!(|current| {
+            current.as_ref().map(|report| {
+            view! {
+                <div class="preview-summary">
+                    <div>
+                        <label>{t!("Year")}</label>
+                        <div>{report.year}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Temperature")}</label>
+                        <div>{format!("{:.2}°C", report.temperature)}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Political Capital")}</label>
+                        <div>{report.political_capital}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Contentedness")}</label>
+                        <div>{format!("{:.1}", report.outlook)}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Extinction Rate")}</label>
+                        <div>{format!("{:.1}", report.extinction_rate)}</div>
+                    </div>
+                </div>
+            }
+        })
+        })
+    };
+
+    // Keep the latest output in view.
+    let log_ref = create_node_ref::<html::Div>();
+    create_effect(move |_| {
+        log.track();
+        request_animation_frame(move || {
+            if let Some(el) = log_ref.get_untracked() {
+                el.set_scroll_top(el.scroll_height());
+            }
+        });
+    });
+
+    let lines = move || {
+        with!(|log| {
+            log
+            .iter()
+            .map(|line| match line {
+                Line::Command(text) => view! {
+                    <div class="console-command">"> "{text.clone()}</div>
+                },
+                Line::Info(text) => view! {
+                    <div class="console-info">{text.clone()}</div>
+                },
+                Line::Error(text) => view! {
+                    <div class="console-error">{text.clone()}</div>
+                },
+            })
+            .collect::<Vec<_>>()
+        })
+    };
+
+    let events = move || {
+        with!(|history| history
+            .iter()
+            .rev()
+            .filter(|report| !report.events.is_empty())
+            .map(|report| {
+                view! {
+                    <tr>
+                        <td>{report.year}</td>
+                        <td>{report.events.join(", ")}</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>())
+    };
+
+    view! {
+        <div class="playtest scroll-list">
+            <div class="preview-controls">
+                <div class="button" on:click=move |_| reset()>
+                    {move || if current.with(Option::is_none) {
+                        t!("Start Playtest")
+                    } else {
+                        t!("Restart")
+                    }}
+                </div>
+                <Show when=move || current.with(Option::is_some)>
+                    <div class="button" on:click=move |_| submit("step".into())>
+                        {t!("Next Year")}
+                    </div>
+                    <div class="button" on:click=move |_| submit("jump 10".into())>
+                        {t!("Skip 10 Years")}
+                    </div>
+                </Show>
+            </div>
+            <div class="input-help">
+                {t!("Plays the edited world without player input, as the preview does. Changes to the world take effect on restart.")}
+            </div>
+            {summary}
+            <Show when=move || current.with(Option::is_some)>
+                <div class="console">
+                    <div ref=log_ref class="console-log">{lines}</div>
+                    <input
+                        class="console-input"
+                        placeholder=t!("Type a command, e.g. \"jump 20\" or \"help\"")
+                        on:keydown=move |ev| {
+                            if ev.key() == "Enter" {
+                                submit(event_target_value(&ev));
+                                event_target::<web_sys::HtmlInputElement>(&ev).set_value("");
+                            }
+                        } />
+                </div>
+                <table class="preview-table">
+                    <thead>
+                        <tr>
+                            <th>{t!("Year")}</th>
+                            <th>{t!("Events")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{events}</tbody>
+                </table>
+            </Show>
+        </div>
+    }
+}
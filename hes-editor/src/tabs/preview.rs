@@ -0,0 +1,362 @@
+use crate::{
+    inputs::*,
+    t,
+    worker::{PreviewRun, Progress},
+};
+use hes_engine::{Output, World, YearReport};
+use leptos::*;
+use strum::IntoEnumIterator;
+
+const CHART_WIDTH: f32 = 360.;
+const CHART_HEIGHT: f32 = 160.;
+const COLORS: [&str; 5] =
+    ["#FBBC04", "#10AB78", "#4C7CFF", "#E1383A", "#B56CFF"];
+
+/// A single line in a chart.
+#[derive(Clone)]
+struct Series {
+    label: String,
+    values: Vec<f32>,
+}
+impl Series {
+    fn new(
+        label: impl Into<String>,
+        reports: &[YearReport],
+        f: impl Fn(&YearReport) -> f32,
+    ) -> Self {
+        Series {
+            label: label.into(),
+            values: reports.iter().map(f).collect(),
+        }
+    }
+}
+
+/// Render a line chart of the given series. If baseline
+/// series are provided they're drawn dashed underneath,
+/// for comparing two preview runs.
+#[component]
+fn LineChart(
+    #[prop(into)] title: String,
+    series: Vec<Series>,
+    baseline: Vec<Series>,
+) -> impl IntoView {
+    let all_values = series
+        .iter()
+        .chain(baseline.iter())
+        .flat_map(|s| s.values.iter().copied());
+    let (min, max) = all_values.fold(
+        (f32::INFINITY, f32::NEG_INFINITY),
+        |(min, max), v| (min.min(v), max.max(v)),
+    );
+    let (min, max) = if min.is_finite() && max > min {
+        (min, max)
+    } else {
+        (min.min(0.), min.max(0.) + 1.)
+    };
+    let n = series
+        .iter()
+        .chain(baseline.iter())
+        .map(|s| s.values.len())
+        .max()
+        .unwrap_or(0)
+        .max(2);
+
+    let points = move |values: &[f32]| {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let x = i as f32 / (n - 1) as f32 * CHART_WIDTH;
+                let y = CHART_HEIGHT
+                    - (v - min) / (max - min) * CHART_HEIGHT;
+                format!("{x:.1},{y:.1}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let lines = series
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let color = COLORS[i % COLORS.len()];
+            view! {
+                <polyline class="chart-line" points=points(&s.values) stroke=color />
+            }
+        })
+        .collect::<Vec<_>>();
+    let baseline_lines = baseline
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let color = COLORS[i % COLORS.len()];
+            view! {
+                <polyline class="chart-line baseline" points=points(&s.values) stroke=color />
+            }
+        })
+        .collect::<Vec<_>>();
+    let legend = series
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let color = COLORS[i % COLORS.len()];
+            view! {
+                <div class="chart-legend-item">
+                    <span style:background=color></span>
+                    {s.label.clone()}
+                </div>
+            }
+        })
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="chart">
+            <h3>{title}</h3>
+            <div class="chart-body">
+                <div class="chart-axis">
+                    <div>{format!("{max:.2}")}</div>
+                    <div>{format!("{min:.2}")}</div>
+                </div>
+                <svg
+                    viewBox=format!("0 0 {CHART_WIDTH} {CHART_HEIGHT}")
+                    preserveAspectRatio="none">
+                    {baseline_lines}
+                    {lines}
+                </svg>
+            </div>
+            <div class="chart-legend">{legend}</div>
+        </div>
+    }
+}
+
+/// Build the charts for a preview run, optionally
+/// overlaid with a baseline run.
+fn charts(
+    reports: &[YearReport],
+    baseline: &[YearReport],
+) -> impl IntoView {
+    type Extract = fn(&[YearReport]) -> Vec<Series>;
+    let charts: Vec<(&str, Extract)> = vec![
+        ("Temperature (C)", |r| {
+            vec![Series::new("Temperature", r, |r| r.temperature)]
+        }),
+        ("Emissions by Sector (Gt CO2eq)", |r| {
+            let mut series: Vec<_> = Output::iter()
+                .map(|output| {
+                    Series::new(output.to_string(), r, move |r| {
+                        r.emissions_by_output[output]
+                    })
+                })
+                .collect();
+            series.push(Series::new("Industry", r, |r| {
+                r.industry_emissions
+            }));
+            series.push(Series::new("Other", r, |r| {
+                r.other_emissions
+            }));
+            series
+        }),
+        ("Extinction Rate", |r| {
+            vec![Series::new("Extinction Rate", r, |r| {
+                r.extinction_rate
+            })]
+        }),
+        ("Contentedness", |r| {
+            vec![Series::new("Contentedness", r, |r| r.outlook)]
+        }),
+        ("Political Capital", |r| {
+            vec![Series::new("Political Capital", r, |r| {
+                r.political_capital as f32
+            })]
+        }),
+    ];
+
+    let output_charts = Output::iter().map(|output| {
+        let extract = move |r: &[YearReport]| {
+            vec![
+                Series::new("Demand", r, move |r| r.demand[output]),
+                Series::new("Production", r, move |r| {
+                    r.produced[output]
+                }),
+            ]
+        };
+        view! {
+            <LineChart
+                title=format!("{output} Demand vs Production")
+                series=extract(reports)
+                baseline=extract(baseline) />
+        }
+    });
+
+    charts
+        .into_iter()
+        .map(|(title, extract)| {
+            view! {
+                <LineChart
+                    title=title
+                    series=extract(reports)
+                    baseline=extract(baseline) />
+            }
+        })
+        .chain(output_charts)
+        .collect::<Vec<_>>()
+}
+
+#[component]
+pub fn Preview(world: RwSignal<World>) -> impl IntoView {
+    let years = create_rw_signal(20usize);
+    let reports = create_rw_signal::<Vec<YearReport>>(vec![]);
+
+    // A previous run to compare against.
+    let baseline = create_rw_signal::<Vec<YearReport>>(vec![]);
+
+    // The run in progress, if any, and how many
+    // years it's simulated so far. Years are collected
+    // until the run finishes, to avoid redrawing
+    // the charts every year.
+    let running = store_value::<Option<PreviewRun>>(None);
+    let progress = create_rw_signal::<Option<usize>>(None);
+    let pending = store_value::<Vec<YearReport>>(vec![]);
+
+    let cancel = move || {
+        running.set_value(None);
+        progress.set(None);
+    };
+    let run = move |_| {
+        cancel();
+        pending.set_value(vec![]);
+        let on_progress = move |update: Progress| match update {
+            Progress::Year(report) => {
+                pending.update_value(|p| p.push(report));
+                progress
+                    .set(Some(pending.with_value(Vec::len)));
+            }
+            Progress::Done => {
+                reports.set(pending.with_value(|p| p.clone()));
+                cancel();
+            }
+        };
+        let run = world.with_untracked(|world| {
+            PreviewRun::start(
+                world,
+                years.get_untracked(),
+                on_progress,
+            )
+        });
+        match run {
+            Ok(run) => {
+                running.set_value(Some(run));
+                progress.set(Some(0));
+            }
+            Err(err) => {
+                log::warn!("Failed to start preview: {err:?}");
+            }
+        }
+    };
+
+    let summary = move || {
+        with!(|reports| reports.last().map(|last| {
+            view! {
+                <div class="preview-summary">
+                    <div>
+                        <label>{t!("Year")}</label>
+                        <div>{last.year}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Temperature")}</label>
+                        <div>{format!("{:.2}°C", last.temperature)}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Emissions")}</label>
+                        <div>{format!("{:.1} Gt CO2eq", last.emissions)}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Extinction Rate")}</label>
+                        <div>{format!("{:.1}", last.extinction_rate)}</div>
+                    </div>
+                    <div>
+                        <label>{t!("Contentedness")}</label>
+                        <div>{format!("{:.1}", last.outlook)}</div>
+                    </div>
+                </div>
+            }
+        }))
+    };
+
+    let rows = move || {
+        with!(|reports| reports
+            .iter()
+            .map(|report| {
+                view! {
+                    <tr>
+                        <td>{report.year}</td>
+                        <td>{format!("{:.2}", report.temperature)}</td>
+                        <td>{format!("{:.1}", report.emissions)}</td>
+                        <td>{format!("{:.1}", report.extinction_rate)}</td>
+                        <td>{format!("{:.1}", report.outlook)}</td>
+                        <td>{report.events.join(", ")}</td>
+                    </tr>
+                }
+            })
+            .collect::<Vec<_>>())
+    };
+
+    view! {
+        <div class="preview scroll-list">
+            <div class="preview-controls">
+                <NumericInput
+                    inline=true
+                    label="Years"
+                    help="How many years to simulate. The simulation assumes no player input, and approximates warming from cumulative emissions rather than running the full climate model."
+                    signal=create_slice(years, |years| *years, |years, val| *years = val) />
+                <Show
+                    when=move || progress.get().is_some()
+                    fallback=move || view! {
+                        <div class="button" on:click=run>{t!("Run Preview")}</div>
+                    }>
+                    <div class="preview-progress">
+                        {move || t!(
+                            "Year {year} of {years}",
+                            year: progress.get().unwrap_or(0),
+                            years: years.get()
+                        )}
+                    </div>
+                    <div class="button" on:click=move |_| cancel()>
+                        {t!("Cancel")}
+                    </div>
+                </Show>
+                <div class="button"
+                    title=t!("Keep this run to compare against the next one.")
+                    on:click=move |_| baseline.set(reports.get())>
+                    {t!("Pin as Baseline")}
+                </div>
+                <Show when=move || with!(|baseline| !baseline.is_empty())>
+                    <div class="button" on:click=move |_| baseline.set(vec![])>
+                        {t!("Clear Baseline")}
+                    </div>
+                </Show>
+            </div>
+            {summary}
+            <div class="charts">
+                {move || with!(|reports, baseline| {
+                    (!reports.is_empty()).then(|| charts(reports, baseline))
+                })}
+            </div>
+            <Show when=move || with!(|reports| !reports.is_empty())>
+                <table class="preview-table">
+                    <thead>
+                        <tr>
+                            <th>{t!("Year")}</th>
+                            <th>{t!("Temperature (C)")}</th>
+                            <th>{t!("Emissions (Gt CO2eq)")}</th>
+                            <th>{t!("Extinction Rate")}</th>
+                            <th>{t!("Contentedness")}</th>
+                            <th>{t!("Events")}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            </Show>
+        </div>
+    }
+}
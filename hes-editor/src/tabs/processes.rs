@@ -1,13 +1,29 @@
-use crate::{infinite_list, inputs::*, subsignal};
+use crate::{
+    baseline,
+    baseline::use_baseline,
+    conflicts::FieldConflicts,
+    field_doc,
+    infinite_list,
+    inputs::*,
+    lint::Lints,
+    subsignal,
+    t,
+};
 use hes_engine::{
+    Bounds,
+    Byproduct,
     Collection,
     Feedstock,
+    Output,
     Process,
     ProcessFeature,
+    Resource,
     World,
     NPC,
 };
 use leptos::*;
+use std::cmp::Ordering;
+use strum::IntoEnumIterator;
 
 impl Describe for ProcessFeature {
     fn describe(&self) -> &'static str {
@@ -24,6 +40,8 @@ impl Describe for ProcessFeature {
             Self::IsFossil => "If this process uses fossil fuels",
             Self::UsesOil => "If this process uses oil",
             Self::IsLaborIntensive => "If this process is especially labor intensive",
+            Self::IsMarine => "If this process relies on the oceans, e.g. fisheries. Marine food production suffers from ocean acidification",
+            Self::IsRegenerative => "If this process builds up the health of the soil it farms, rather than wearing it down",
         }
     }
 }
@@ -33,6 +51,11 @@ fn Process(
     signal: (Signal<Process>, SignalSetter<Process>),
 ) -> impl IntoView {
     let process = signal;
+    let defaults = Process::default();
+    let id = process.0.with_untracked(|process| process.id);
+    let base = use_baseline(move |world| {
+        world.processes.try_get(&id).cloned()
+    });
 
     let npcs = expect_context::<Signal<Collection<Ref<NPC>>>>();
 
@@ -57,13 +80,14 @@ fn Process(
     view! {
         <div class="process" id={move || signal.0.with(|signal| signal.id.to_string())}>
             <div class="name">
-                <TextInput signal=subsignal!(process.name) />
+                <TextInput lints=Lints::FLAVOR commit=Commit::Debounce baseline=baseline!(base.name) signal=subsignal!(process.name) />
                 <div class="item-lock">
                     <ToggleInput
                         label="Locked"
                         tooltip=true
                         icons=("🔒Locked", "🔓Unlocked")
-                        help="If this process is locked at the start."
+                        help=field_doc!(Process.locked)
+                        baseline=baseline!(base.locked)
                         signal=subsignal!(process.locked) />
                 </div>
             </div>
@@ -73,22 +97,31 @@ fn Process(
                     <NumericInput
                         inline=true
                         label="Mix Share"
-                        help="What percent of total output production this process represents at the start. Note that 1 mix share = 5% of total output."
+                        help=field_doc!(Process.mix_share)
+                        default=defaults.mix_share
+                        baseline=baseline!(base.mix_share)
+                        bounds=Some(Process::MIX_SHARE_BOUNDS)
                         signal=subsignal!(process.mix_share) />
                     <OptionalNumericInput
                         label="Output Limit"
-                        help="(Optional) This process can never produce more than this much output, effectively setting a limit on its mix share. This may be because, for example, of a finite availability, e.g. with geothermal."
+                        help=field_doc!(Process.limit)
+                        bounds=Some(Bounds::NON_NEGATIVE)
                         signal=subsignal!(process.limit)
                         />
+                    <FieldConflicts item=process.0 fields=&["locked", "limit"] />
                 </div>
                 <div class="input-groups">
                     <EnumInput
                         label="Output Type"
-                        help="What this process produces."
+                        help=field_doc!(Process.output)
+                        default=defaults.output
+                        baseline=baseline!(base.output)
                         signal=subsignal!(process.output) />
                     <EnumInput
                         label="Feedstock Type"
                         help=r#"What this feedstock this process requires. If no particular feedstock, just set to "Other". Note that "Soil" is ignored."#
+                        default=defaults.feedstock.0
+                        baseline=baseline!(base.feedstock.0)
                         signal=subsignal!(process.feedstock.0) />
                     <Show when=move || process.0.with(|process| process.feedstock.0 != Feedstock::Other)>
                         <div class="feedstock-amount">
@@ -96,51 +129,376 @@ fn Process(
                                 inline=true
                                 label="Feedstock"
                                 help=format!("Feedstock required per unit output, in {} of {}.", feedstock_units(), feedstock_name())
+                                default=defaults.feedstock.1
+                                baseline=baseline!(base.feedstock.1)
+                                bounds=Some(Process::FEEDSTOCK_BOUNDS)
+                                scalable=true
                                 signal=subsignal!(process.feedstock.1) />
                         </div>
                     </Show>
                     <ByproductMapInput
                         label="Byproducts"
-                        help="Byproducts produced, per unit output."
+                        help=field_doc!(Process.byproducts)
                         signal=subsignal!(process.byproducts) />
                     <ResourceMapInput
                         label="Resources"
-                        help="Resources used, per unit output."
+                        help=field_doc!(Process.resources)
                         signal=subsignal!(process.resources) />
+                    <FieldConflicts item=process.0 fields=&["resources", "feedstock"] />
                 </div>
             </div>
 
             <div class="item-form desc-form">
-                <TextArea label="Description" help="Describe the process." signal=subsignal!(process.flavor.description) />
+                <MarkdownInput lints=Lints::FLAVOR commit=Commit::Debounce label="Description" help="Describe the process." signal=subsignal!(process.flavor.description) />
             </div>
 
             <div class="item-form">
                 <MultiEnumInput
                     label="Features"
-                    help="Special properties associated with this process."
+                    help=field_doc!(Process.features)
                     signal=subsignal!(process.features)
                     />
             </div>
             <div class="item-form">
                 <MultiEntitySelect
                     label="Supporters"
-                    help="NPCs that support this process."
+                    help=field_doc!(Process.supporters)
                     signal=subsignal!(process.supporters)
                     opts=npcs
                     />
                 <MultiEntitySelect
                     label="Opposers"
-                    help="NPCs that oppose this process."
+                    help=field_doc!(Process.opposers)
                     signal=subsignal!(process.opposers)
                     opts=npcs
                     />
             </div>
 
             <div class="item-form notes-form">
-                <TextArea label="Notes" help="Optional notes" signal=subsignal!(process.notes) />
+                <TextArea label="Notes" help=field_doc!(Process.notes) commit=Commit::Blur signal=subsignal!(process.notes) />
+            </div>
+            <div class="item-form tags-form">
+                <TagsInput signal=subsignal!(process.tags) />
             </div>
         </div>
     }
 }
 
-infinite_list!(Processes, Process, processes);
+infinite_list!(ProcessList, Process, processes);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Cards,
+    Table,
+    Mix,
+}
+
+#[component]
+pub fn Processes(world: RwSignal<World>) -> impl IntoView {
+    let mode = create_rw_signal(Mode::Cards);
+
+    view! {
+        <div class="view-toggle">
+            <span class:selected=move || mode.get() == Mode::Cards
+                on:click=move |_| mode.set(Mode::Cards)>{t!("Cards")}</span>
+            <span class:selected=move || mode.get() == Mode::Table
+                on:click=move |_| mode.set(Mode::Table)>{t!("Table")}</span>
+            <span class:selected=move || mode.get() == Mode::Mix
+                on:click=move |_| mode.set(Mode::Mix)>{t!("Mix")}</span>
+        </div>
+        {move || match mode.get() {
+            Mode::Cards => view! { <ProcessList world /> }.into_view(),
+            Mode::Table => view! { <ProcessTable world /> }.into_view(),
+            Mode::Mix => view! { <ProcessMix world /> }.into_view(),
+        }}
+    }
+}
+
+/// Mix shares are in units of 5%,
+/// so a full mix is 20 shares.
+const FULL_MIX: f32 = 20.;
+
+/// The starting mix shares of each output's processes,
+/// which should add up to the full output.
+#[component]
+fn ProcessMix(world: RwSignal<World>) -> impl IntoView {
+    let mixes = Output::iter()
+        .map(|output| {
+            let labels = Signal::derive(move || {
+                with!(|world| world
+                    .processes
+                    .iter()
+                    .filter(|p| p.output == output)
+                    .map(|p| p.name.clone())
+                    .collect::<Vec<_>>())
+            });
+            let shares = Signal::derive(move || {
+                with!(|world| world
+                    .processes
+                    .iter()
+                    .filter(|p| p.output == output)
+                    .map(|p| p.mix_share as f32)
+                    .collect::<Vec<_>>())
+            });
+            let set_shares =
+                SignalSetter::map(move |shares: Vec<f32>| {
+                    update!(|world| {
+                        let processes = world
+                            .processes
+                            .iter_mut()
+                            .filter(|p| p.output == output);
+                        for (process, share) in
+                            processes.zip(shares)
+                        {
+                            process.mix_share =
+                                share.max(0.).round() as usize;
+                        }
+                    });
+                });
+            view! {
+                <WeightsInput
+                    label=output.to_string()
+                    help="Each process's starting share of this output."
+                    total=FULL_MIX
+                    whole=true
+                    labels
+                    signal=(shares, set_shares) />
+            }
+        })
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="process-mix scroll-list">
+            {mixes}
+        </div>
+    }
+}
+
+/// A column in the process table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Name,
+    Output,
+    MixShare,
+    Feedstock,
+    Resource(Resource),
+    Byproduct(Byproduct),
+}
+impl Column {
+    fn all() -> Vec<Column> {
+        let mut cols = vec![
+            Column::Name,
+            Column::Output,
+            Column::MixShare,
+            Column::Feedstock,
+        ];
+        cols.extend(Resource::iter().map(Column::Resource));
+        cols.extend(Byproduct::iter().map(Column::Byproduct));
+        cols
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Column::Name => "Name".into(),
+            Column::Output => "Output".into(),
+            Column::MixShare => "Mix Share".into(),
+            Column::Feedstock => "Feedstock".into(),
+            Column::Resource(res) => res.to_string(),
+            Column::Byproduct(byp) => byp.to_string(),
+        }
+    }
+
+    fn compare(&self, a: &Process, b: &Process) -> Ordering {
+        match self {
+            Column::Name => a.name.cmp(&b.name),
+            Column::Output => {
+                a.output.to_string().cmp(&b.output.to_string())
+            }
+            Column::MixShare => a.mix_share.cmp(&b.mix_share),
+            Column::Feedstock => a
+                .feedstock
+                .1
+                .partial_cmp(&b.feedstock.1)
+                .unwrap_or(Ordering::Equal),
+            Column::Resource(res) => a.resources[*res]
+                .partial_cmp(&b.resources[*res])
+                .unwrap_or(Ordering::Equal),
+            Column::Byproduct(byp) => a.byproducts[*byp]
+                .partial_cmp(&b.byproducts[*byp])
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+/// A spreadsheet-like view of all processes,
+/// for comparing values across processes.
+#[component]
+fn ProcessTable(world: RwSignal<World>) -> impl IntoView {
+    // The column to sort by, and whether it's ascending.
+    let sort = create_rw_signal((Column::Name, true));
+    let filter = create_rw_signal(String::new());
+    let output_filter = create_rw_signal::<Option<Output>>(None);
+
+    let rows = move || {
+        with!(|world, sort, filter, output_filter| {
+            let filter = filter.to_lowercase();
+            let mut processes = world
+                .processes
+                .iter()
+                .filter(|p| {
+                    p.name.to_lowercase().contains(&filter)
+                        && output_filter
+                            .map_or(true, |output| p.output == output)
+                })
+                .collect::<Vec<_>>();
+            let (col, asc) = sort;
+            processes.sort_by(|a, b| {
+                let ord = col.compare(a, b);
+                if *asc {
+                    ord
+                } else {
+                    ord.reverse()
+                }
+            });
+            processes.iter().map(|p| p.id).collect::<Vec<_>>()
+        })
+    };
+
+    let headers = move || {
+        Column::all()
+            .into_iter()
+            .map(|col| {
+                let indicator = move || {
+                    with!(|sort| if sort.0 == col {
+                        if sort.1 {
+                            " ⯅"
+                        } else {
+                            " ⯆"
+                        }
+                    } else {
+                        ""
+                    })
+                };
+                view! {
+                    <th on:click=move |_| {
+                        update!(|sort| {
+                            if sort.0 == col {
+                                sort.1 = !sort.1;
+                            } else {
+                                *sort = (col, true);
+                            }
+                        });
+                    }>{col.label()}{indicator}</th>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let output_opts = move || {
+        Output::iter()
+            .map(|output| {
+                let label: &'static str = output.into();
+                view! {
+                    <option value=label>{output.to_string()}</option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div class="process-table scroll-list">
+            <div class="table-filters">
+                <input type="text"
+                    placeholder=t!("Filter by name")
+                    on:input=move |ev| {
+                        filter.set(event_target_value(&ev));
+                    } />
+                <select on:change=move |ev| {
+                    let value = event_target_value(&ev);
+                    output_filter.set(value.parse().ok());
+                }>
+                    <option value="">{t!("All Outputs")}</option>
+                    {output_opts}
+                </select>
+            </div>
+            <div class="table-help">{t!("Use the arrow keys or Tab to move between cells. Press Enter or start typing to edit a cell, Enter or Tab to finish, and Escape to cancel.")}</div>
+            <KeyboardGrid>
+                <table>
+                    <thead>
+                        <tr>{headers}</tr>
+                    </thead>
+                    <tbody>
+                        <For each=rows
+                            key=|id| *id
+                            children=move |id| {
+                                let cells = Column::all().into_iter().map(|col| {
+                                    let cell = match col {
+                                        Column::Name => view! {
+                                            <TextInput
+                                                signal=create_slice(world,
+                                                    move |world| world.processes[&id].name.clone(),
+                                                    move |world, val| world.processes[&id].name = val
+                                                ) />
+                                        }.into_view(),
+                                        Column::Output => view! {
+                                            <EnumInput
+                                                label=""
+                                                help=""
+                                                signal=create_slice(world,
+                                                    move |world| world.processes[&id].output,
+                                                    move |world, val| world.processes[&id].output = val
+                                                ) />
+                                        }.into_view(),
+                                        Column::MixShare => view! {
+                                            <NumericInput
+                                                label=""
+                                                help=""
+                                                bounds=Some(Process::MIX_SHARE_BOUNDS)
+                                                signal=create_slice(world,
+                                                    move |world| world.processes[&id].mix_share,
+                                                    move |world, val| world.processes[&id].mix_share = val
+                                                ) />
+                                        }.into_view(),
+                                        Column::Feedstock => view! {
+                                            <NumericInput
+                                                label=""
+                                                help="Feedstock required per unit output."
+                                                bounds=Some(Process::FEEDSTOCK_BOUNDS)
+                                                scalable=true
+                                                signal=create_slice(world,
+                                                    move |world| world.processes[&id].feedstock.1,
+                                                    move |world, val| world.processes[&id].feedstock.1 = val
+                                                ) />
+                                        }.into_view(),
+                                        Column::Resource(res) => view! {
+                                            <NumericInput
+                                                label=""
+                                                help=""
+                                                bounds=Some(Bounds::NON_NEGATIVE)
+                                                scalable=true
+                                                signal=create_slice(world,
+                                                    move |world| world.processes[&id].resources[res],
+                                                    move |world, val| world.processes[&id].resources[res] = val
+                                                ) />
+                                        }.into_view(),
+                                        Column::Byproduct(byp) => view! {
+                                            <NumericInput
+                                                label=""
+                                                help=""
+                                                scalable=true
+                                                signal=create_slice(world,
+                                                    move |world| world.processes[&id].byproducts[byp],
+                                                    move |world, val| world.processes[&id].byproducts[byp] = val
+                                                ) />
+                                        }.into_view(),
+                                    };
+                                    view! { <td tabindex="-1">{cell}</td> }
+                                }).collect::<Vec<_>>();
+                                view! { <tr>{cells}</tr> }
+                            } />
+                    </tbody>
+                </table>
+            </KeyboardGrid>
+        </div>
+    }
+}
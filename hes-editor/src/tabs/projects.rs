@@ -1,4 +1,14 @@
-use crate::{infinite_list, inputs::*, subsignal};
+use crate::{
+    baseline,
+    baseline::use_baseline,
+    conflicts::FieldConflicts,
+    field_doc,
+    infinite_list,
+    inputs::*,
+    lint::Lints,
+    subsignal,
+    t,
+};
 use hes_engine::*;
 use leptos::*;
 
@@ -7,19 +17,25 @@ fn Project(
     signal: (Signal<Project>, SignalSetter<Project>),
 ) -> impl IntoView {
     let project = signal;
+    let defaults = Project::new();
+    let id = project.0.with_untracked(|project| project.id);
+    let base = use_baseline(move |world| {
+        world.projects.try_get(&id).cloned()
+    });
 
     let npcs = expect_context::<Signal<Collection<Ref<NPC>>>>();
 
     view! {
         <div class="project" id={move || signal.0.with(|signal| signal.id.to_string())}>
             <div class="name">
-                <TextInput signal=subsignal!(project.name) />
+                <TextInput lints=Lints::FLAVOR commit=Commit::Debounce baseline=baseline!(base.name) signal=subsignal!(project.name) />
                 <div class="item-lock">
                     <ToggleInput
                         label="Locked"
                         tooltip=true
                         icons=("🔒Locked", "🔓Unlocked")
-                        help="If this project is locked at the start."
+                        help=field_doc!(Project.locked)
+                        baseline=baseline!(base.locked)
                         signal=subsignal!(project.locked) />
                 </div>
             </div>
@@ -30,40 +46,47 @@ fn Project(
                 <div class="input-groups">
                     <EnumInput
                         label="Type"
-                        help="The type of project."
+                        help=field_doc!(Project.kind)
+                        default=defaults.kind
+                        baseline=baseline!(base.kind)
                         signal=subsignal!(project.kind) />
                     <EnumInput
                         label="Category"
-                        help="The project's category."
+                        help=field_doc!(Project.group)
+                        default=defaults.group
+                        baseline=baseline!(base.group)
                         signal=subsignal!(project.group) />
                     <ToggleInput
                         label="Ongoing"
-                        help="Is this a one-and-done project, or does it need continued maintenance?"
+                        help=field_doc!(Project.ongoing)
+                        baseline=baseline!(base.ongoing)
                         signal=subsignal!(project.ongoing) />
                     <Show when=move || project.0.with(|project| project.kind == ProjectType::Initiative)>
                         <ToggleInput
                             label="Gradual"
-                            help="Does this project have to be 100% finished before the effects occur, or do they develop as the project is developed?"
+                            help=field_doc!(Project.gradual)
+                            baseline=baseline!(base.gradual)
                             signal=subsignal!(project.gradual) />
                     </Show>
                     <Cost project />
+                    <FieldConflicts item=project.0 fields=&["kind"] />
                 </div>
             </div>
 
             <div class="item-form desc-form">
-                <TextArea label="Description" help="Describe the project." signal=subsignal!(project.flavor.description) />
+                <MarkdownInput lints=Lints::FLAVOR commit=Commit::Debounce label="Description" help="Describe the project." signal=subsignal!(project.flavor.description) />
             </div>
 
             <div class="item-form">
                 <MultiEntitySelect
                     label="Supporters"
-                    help="NPCs that support this project."
+                    help=field_doc!(Project.supporters)
                     signal=subsignal!(project.supporters)
                     opts=npcs
                     />
                 <MultiEntitySelect
                     label="Opposers"
-                    help="NPCs that oppose this project."
+                    help=field_doc!(Project.opposers)
                     signal=subsignal!(project.opposers)
                     opts=npcs
                     />
@@ -73,6 +96,7 @@ fn Project(
                 <Effects
                     double_col=true
                     effects=subsignal!(project.effects) />
+                <FieldConflicts item=project.0 fields=&["effects"] />
             </div>
 
             <div class="item-form upgrades-form">
@@ -84,7 +108,10 @@ fn Project(
             </div>
 
             <div class="item-form notes-form">
-                <TextArea label="Notes" help="Optional notes" signal=subsignal!(project.notes) />
+                <TextArea label="Notes" help=field_doc!(Project.notes) commit=Commit::Blur signal=subsignal!(project.notes) />
+            </div>
+            <div class="item-form tags-form">
+                <TagsInput signal=subsignal!(project.tags) />
             </div>
         </div>
     }
@@ -246,16 +273,16 @@ fn Cost(
                         project.base_cost = Cost::Fixed(fixed_cost.get());
                         write.set(project);
                     }
-                }>Static</span>
+                }>{t!("Static")}</span>
                 <span class:selected=is_dynamic on:click=move |_| {
                     if !is_dynamic() {
                         let mut project = read.get();
                         project.base_cost = Cost::Dynamic(multiplier_.get(), factor_.get());
                         write.set(project);
                     }
-                }>Dynamic</span>Cost
+                }>{t!("Dynamic")}</span>Cost
             </div>
-            <div class="input-help">If this project uses a dynamically-calculated cost.</div>
+            <div class="input-help">{t!("If this project uses a dynamically-calculated cost.")}</div>
             {cost_view}
         </div>
     }
@@ -271,11 +298,8 @@ where
 {
     view! {
         <div class="mutable-list-item-header">
-            <NumericInput
-                label="Cost"
-                help="The upgrade cost."
-                signal=subsignal!(upgrade.cost) />
-            <div class="mutable-list-item-remove" title="Ctrl-click to remove without confirmation." on:click=on_remove>"✗"</div>
+            <Form signal=upgrade />
+            <div class="mutable-list-item-remove" title=t!("Ctrl-click to remove without confirmation.") on:click=on_remove>"✗"</div>
         </div>
         <Effects
             effects=subsignal!(upgrade.effects) />
@@ -294,7 +318,7 @@ fn Upgrades(
     view! {
         <div class="upgrades mutable-list mutable-list-double-col mutable-list-sortable">
             <div class="mutable-list-header">
-                <h2>Upgrades</h2>
+                <h2>{t!("Upgrades")}</h2>
                 <div class="mutable-list-add">
                     <div class="mutable-list-add-button" on:click=move |_| {
                         let upgrade = Upgrade::default();
@@ -308,7 +332,7 @@ fn Upgrades(
                  let empty = with!(|read| read.is_empty());
                  if empty {
                      Some(view! {
-                         <div class="empty">No upgrades defined.</div>
+                         <div class="empty">{t!("No upgrades defined.")}</div>
                      })
                  } else {
                      None
@@ -367,7 +391,7 @@ where
                 label="Likelihood"
                 help="The likelihood when all conditions are met."
                 signal=subsignal!(outcome.probability.likelihood) />
-            <div class="mutable-list-item-remove" title="Ctrl-click to remove without confirmation." on:click=on_remove>"✗"</div>
+            <div class="mutable-list-item-remove" title=t!("Ctrl-click to remove without confirmation.") on:click=on_remove>"✗"</div>
         </div>
         <Conditions
             conditions=subsignal!(outcome.probability.conditions) />
@@ -388,7 +412,7 @@ fn Outcomes(
     view! {
         <div class="outcomes mutable-list mutable-list-double-col mutable-list-sortable">
             <div class="mutable-list-header">
-                <h2>Outcomes</h2>
+                <h2>{t!("Outcomes")}</h2>
                 <div class="mutable-list-add">
                     <div class="mutable-list-add-button" on:click=move |_| {
                         let outcome = Outcome::default();
@@ -398,12 +422,12 @@ fn Outcomes(
                     }>+Add</div>
                 </div>
             </div>
-            <div class="input-help">"Outcomes are checked in their defined order, so you should order them from least likely to most likely. For example, if you have a guaranteed outcome first, then that's the one that will always trigger. You should move it to the end so other outcomes can be checked before it."</div>
+            <div class="input-help">{t!("Outcomes are checked in their defined order, so you should order them from least likely to most likely. For example, if you have a guaranteed outcome first, then that's the one that will always trigger. You should move it to the end so other outcomes can be checked before it.")}</div>
             {move || {
                  let empty = with!(|read| read.is_empty());
                  if empty {
                      Some(view! {
-                         <div class="empty">No outcomes defined.</div>
+                         <div class="empty">{t!("No outcomes defined.")}</div>
                      })
                  } else {
                      None
@@ -0,0 +1,171 @@
+use crate::{inputs::read_only, t};
+use hes_engine::World;
+use leptos::*;
+
+/// Locales the game has translations for,
+/// matching `AVAILABLE_LANGUAGES` in `hes-game`
+/// (excluding English, the source language).
+const LOCALES: &[&str] = &[
+    "pt", "pt-br", "pt-pt", "es", "de-de", "jp", "fr-fr", "th",
+    "tr-tr",
+];
+
+/// How many strings to show at a time.
+const PER_PAGE: usize = 50;
+
+#[component]
+pub fn Translations(world: RwSignal<World>) -> impl IntoView {
+    let locale = create_rw_signal(LOCALES[0].to_string());
+    let only_missing = create_rw_signal(false);
+    let query = create_rw_signal(String::new());
+    let limit = create_rw_signal(PER_PAGE);
+    let read_only = read_only();
+
+    // Only recompute the strings when the world's
+    // text changes, not when translations are edited.
+    let strings = create_memo(move |_| {
+        with!(|world| world.translatable_strings())
+    });
+
+    let missing = move |locale: &str| {
+        with!(|world| world.missing_translations(locale))
+    };
+
+    let locale_opts = move || {
+        LOCALES
+            .iter()
+            .map(|loc| {
+                let label =
+                    format!("{loc} ({} missing)", missing(loc));
+                view! {
+                    <option
+                        value=*loc
+                        selected=move || locale.get() == *loc>
+                        {label}
+                    </option>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The strings to show, with their current translations.
+    // Translations are read untracked so that editing one
+    // doesn't re-render the whole table.
+    let rows = move || {
+        let locale = locale.get();
+        let only_missing = only_missing.get();
+        let query = query.get().to_lowercase();
+        let translations = world.with_untracked(|world| {
+            world
+                .translations
+                .get(&locale)
+                .cloned()
+                .unwrap_or_default()
+        });
+        strings.with(|strings| {
+            strings
+                .iter()
+                .filter(|s| {
+                    query.is_empty()
+                        || s.to_lowercase().contains(&query)
+                })
+                .map(|s| {
+                    let translated = translations
+                        .get(s)
+                        .cloned()
+                        .unwrap_or_default();
+                    (s.clone(), translated)
+                })
+                .filter(|(_, translated)| {
+                    !only_missing || translated.trim().is_empty()
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+    let n_rows = move || rows().len();
+
+    let set_translation = move |source: String, value: String| {
+        let locale = locale.get_untracked();
+        world.update(|world| {
+            let phrases =
+                world.translations.entry(locale).or_default();
+            if value.is_empty() {
+                phrases.remove(&source);
+            } else {
+                phrases.insert(source, value);
+            }
+        });
+    };
+
+    view! {
+        <div class="translations scroll-list">
+            <div class="translations-controls">
+                <div class="input-group">
+                    <label>{t!("Locale")}</label>
+                    <select on:change=move |ev| {
+                        locale.set(event_target_value(&ev));
+                        limit.set(PER_PAGE);
+                    }>
+                        {locale_opts}
+                    </select>
+                </div>
+                <input
+                    class="text-input"
+                    placeholder=t!("Search source text")
+                    on:input=move |ev| {
+                        query.set(event_target_value(&ev));
+                        limit.set(PER_PAGE);
+                    } />
+                <label class="translations-missing-toggle">
+                    <input
+                        type="checkbox"
+                        on:change=move |ev| {
+                            only_missing.set(event_target_checked(&ev));
+                            limit.set(PER_PAGE);
+                        } />
+                    {t!("Only missing")}
+                </label>
+                <div class="translations-count">
+                    {move || format!("{} missing of {}", missing(&locale.get()), strings.with(|s| s.len()))}
+                </div>
+            </div>
+            <div class="input-help">{t!("Translations are keyed by their source text, so changing the source text means it will need to be translated again. Translations are included when the world is exported.")}</div>
+            <table class="translations-table">
+                <thead>
+                    <tr>
+                        <th>{t!("Source")}</th>
+                        <th>{t!("Translation")}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {move || {
+                        rows()
+                            .into_iter()
+                            .take(limit.get())
+                            .map(|(source, translated)| {
+                                let key = source.clone();
+                                view! {
+                                    <tr class:missing=translated.trim().is_empty()>
+                                        <td class="translation-source">{source}</td>
+                                        <td>
+                                            <textarea
+                                                disabled=read_only
+                                                on:change=move |ev| {
+                                                    set_translation(key.clone(), event_target_value(&ev));
+                                                }>{translated}</textarea>
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    }}
+                </tbody>
+            </table>
+            <Show when=move || limit.get() < n_rows()>
+                <div class="button" on:click=move |_| limit.update(|limit| *limit += PER_PAGE)>
+                    {t!("Show More")}
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    add_shared,
+    inputs::read_only,
+    t,
+    trash::TrashBin,
+    Tab,
+};
+use hes_engine::World;
+use leptos::*;
+use wasm_bindgen::JsValue;
+
+/// Deleted entities, which can be restored
+/// until they're deleted permanently.
+#[component]
+pub fn Trash(
+    world: RwSignal<World>,
+    tab: RwSignal<Tab>,
+) -> impl IntoView {
+    let trash = expect_context::<TrashBin>();
+    let errors = create_rw_signal::<Vec<String>>(vec![]);
+    let read_only = read_only();
+
+    // Only take it out of the trash once it's
+    // restored, so it isn't lost if it can't be.
+    let restore = move |idx: usize| {
+        let item = trash.0.with_untracked(|items| {
+            items.get(idx).map(|trashed| trashed.item.clone())
+        });
+        let Some(item) = item else { return };
+        match add_shared(item, world, tab) {
+            Ok(()) => {
+                trash.take(idx);
+                errors.set(vec![]);
+            }
+            Err(errs) => errors.set(errs),
+        }
+    };
+
+    let delete = move |idx: usize| {
+        let msg =
+            "Permanently delete this? It can't be restored.";
+        if window().confirm_with_message(msg).unwrap_or(false) {
+            trash.take(idx);
+        }
+    };
+
+    let empty = move |_| {
+        let msg = "Permanently delete everything in the trash?";
+        if window().confirm_with_message(msg).unwrap_or(false) {
+            trash.empty();
+        }
+    };
+
+    let rows = move || {
+        trash.0.with(|items| {
+            items
+                .iter()
+                .enumerate()
+                .map(|(idx, trashed)| {
+                    let deleted =
+                        js_sys::Date::new(&trashed.deleted.into())
+                            .to_locale_string(
+                                "default",
+                                &JsValue::UNDEFINED,
+                            )
+                            .as_string()
+                            .unwrap_or_default();
+                    view! {
+                        <tr>
+                            <td class="trash-kind">{t!(trashed.kind())}</td>
+                            <td>{trashed.name().to_string()}</td>
+                            <td class="trash-deleted">{deleted}</td>
+                            <td class="trash-actions">
+                                <button
+                                    disabled=read_only
+                                    on:click=move |_| restore(idx)>
+                                    {t!("Restore")}
+                                </button>
+                                <button on:click=move |_| delete(idx)>
+                                    {t!("Delete Forever")}
+                                </button>
+                            </td>
+                        </tr>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div class="trash scroll-list">
+            <div class="input-help">{t!("Deleted industries, processes, projects and events are kept here so they can be restored. The trash is stored in this browser rather than in the world, so it isn't included when the world is exported.")}</div>
+            {move || with!(|errors| (!errors.is_empty()).then(|| view! {
+                <div class="input-error">
+                    <div>{t!("Couldn't restore:")}</div>
+                    {errors.iter().map(|err| view! { <div>{err.clone()}</div> }).collect::<Vec<_>>()}
+                </div>
+            }))}
+            <Show
+                when=move || trash.0.with(|items| !items.is_empty())
+                fallback=move || view! { <div class="empty">{t!("The trash is empty.")}</div> }>
+                <table class="trash-table">
+                    <tbody>{rows}</tbody>
+                </table>
+                <button on:click=empty>{t!("Empty Trash")}</button>
+            </Show>
+        </div>
+    }
+}
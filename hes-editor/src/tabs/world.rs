@@ -1,5 +1,22 @@
-use crate::inputs::*;
-use hes_engine::{Income, Output, World};
+use crate::{
+    baseline::use_baseline,
+    field_doc,
+    inputs::*,
+    t,
+    validate::{find_references, RefKind},
+};
+use hes_engine::{
+    Bounds,
+    Cohort,
+    Income,
+    Integrity,
+    Latitude,
+    Output,
+    Region,
+    TippingPoint,
+    Variable,
+    World,
+};
 use leptos::*;
 use strum::IntoEnumIterator;
 
@@ -7,68 +24,153 @@ use strum::IntoEnumIterator;
 pub fn World(world: RwSignal<World>) -> impl IntoView {
     view! {
         <div class="world scroll-list">
+            <div class="map-group metadata">
+                <h2 class="tooltip-parent">
+                    About
+                    <div class="tooltip">{t!("Shown to players when they pick this world.")}</div>
+                </h2>
+                <div class="map-inputs">
+                    <Form signal=slice!(world.metadata) />
+                </div>
+                <TextArea
+                    label="Description"
+                    help="What sets the world apart."
+                    signal=slice!(world.metadata.description) />
+                <div class="world-integrity">{move || with!(|world| match &world.integrity {
+                    Some(Integrity { hash, signature }) => {
+                        let signer = signature.as_ref().map(|sig| {
+                            format!(" Signed by {}.", sig.public_key)
+                        }).unwrap_or_default();
+                        format!("Content hash as of the last export: {hash}.{signer}")
+                    }
+                    None => "Not exported yet, so there's no content hash.".into(),
+                })}</div>
+            </div>
             <div class="map-group-block">
                 <div class="map-group">
-                    <h2>Initial Values</h2>
+                    <h2>{t!("Initial Values")}</h2>
                     <div class="map-inputs">
                         <NumericInput
                             label="Contentedness"
-                            help="The starting world contentedness."
+                            help=field_doc!(World.base_outlook)
+                            baseline=use_baseline(|world| Some(world.base_outlook))
                             signal=slice!(world.base_outlook) />
                         <NumericInput
                             label="Extinction Pressure"
-                            help="The starting extinction pressure."
+                            help=field_doc!(World.extinction_rate)
+                            baseline=use_baseline(|world| Some(world.extinction_rate))
+                            bounds=Some(World::EXTINCTION_RATE_BOUNDS)
                             signal=slice!(world.extinction_rate) />
                         <NumericInput
                             label="Warming"
-                            help="The starting temperature anomaly (C)."
+                            help=field_doc!(World.temperature)
+                            baseline=use_baseline(|world| Some(world.temperature))
                             signal=slice!(world.temperature) />
                         <NumericInput
                             label="Sea Level Rise"
-                            help="The starting sea level rise (meters)."
+                            help=field_doc!(World.sea_level_rise)
+                            baseline=use_baseline(|world| Some(world.sea_level_rise))
+                            bounds=Some(World::SEA_LEVEL_RISE_BOUNDS)
                             signal=slice!(world.sea_level_rise) />
+                        <NumericInput
+                            label="Ocean Acidification"
+                            help=field_doc!(World.acidification)
+                            baseline=use_baseline(|world| Some(world.acidification))
+                            bounds=Some(World::ACIDIFICATION_BOUNDS)
+                            signal=slice!(world.acidification) />
+                        <NumericInput
+                            label="Disaster Rate"
+                            help=field_doc!(World.disaster_rate)
+                            baseline=use_baseline(|world| Some(world.disaster_rate))
+                            bounds=Some(World::DISASTER_RATE_BOUNDS)
+                            signal=slice!(world.disaster_rate) />
+                        <NumericInput
+                            label="Outbreak Rate"
+                            help=field_doc!(World.outbreak_rate)
+                            baseline=use_baseline(|world| Some(world.outbreak_rate))
+                            bounds=Some(World::OUTBREAK_RATE_BOUNDS)
+                            signal=slice!(world.outbreak_rate) />
+                        <NumericInput
+                            label="Preparedness"
+                            help=field_doc!(World.preparedness)
+                            baseline=use_baseline(|world| Some(world.preparedness))
+                            bounds=Some(World::PREPAREDNESS_BOUNDS)
+                            signal=slice!(world.preparedness) />
+                        <NumericInput
+                            label="Water Efficiency"
+                            help=field_doc!(World.water_efficiency)
+                            baseline=use_baseline(|world| Some(world.water_efficiency))
+                            bounds=Some(World::WATER_EFFICIENCY_BOUNDS)
+                            signal=slice!(world.water_efficiency) />
+                        <NumericInput
+                            label="Soil Health"
+                            help=field_doc!(World.soil_health)
+                            baseline=use_baseline(|world| Some(world.soil_health))
+                            bounds=Some(World::SOIL_HEALTH_BOUNDS)
+                            signal=slice!(world.soil_health) />
+                        <NumericInput
+                            label="Plastic"
+                            help=field_doc!(World.plastic)
+                            baseline=use_baseline(|world| Some(world.plastic))
+                            bounds=Some(World::PLASTIC_BOUNDS)
+                            signal=slice!(world.plastic) />
                     </div>
                 </div>
                 <ResourceMapInput
                     label="Starting Resources"
-                    help="The starting resource availability."
+                    help=field_doc!(World.starting_resources)
                     signal=slice!(world.starting_resources)
                 />
                 <FeedstockMapInput
                     label="Feedstocks Reserves"
-                    help="The starting feedstock reserves."
+                    help=field_doc!(World.feedstock_reserves)
                     signal=slice!(world.feedstock_reserves)
                 />
 
                 <div class="map-group-block">
                     <div class="map-group table-group">
                         <h2 class="tooltip-parent">
-                            Annual Population Growth Coefficients
-                            <div class="tooltip">The coefficients for cubic annual population growth model, with one for each income level.</div>
+                            Demographics By Income
+                            <div class="tooltip">{t!("The annual birth and death rates for each income level. Births are per adult and deaths are the share of each age cohort that dies.")}</div>
                         </h2>
                         <div class="map-table">
                             <div class="label-column input-column">
                                 <div>-</div>
-                                <label>Low</label>
-                                <label>Lower-Middle</label>
-                                <label>Upper-Middle</label>
-                                <label>High</label>
+                                <label>{t!("Low")}</label>
+                                <label>{t!("Lower-Middle")}</label>
+                                <label>{t!("Upper-Middle")}</label>
+                                <label>{t!("High")}</label>
+                            </div>
+                            <div class="input-column">
+                                <label>{t!("Fertility")}</label>
+                                {move || {
+                                     Income::iter().enumerate().map(|(j, income)| {
+                                         view! {
+                                             <NumericInput
+                                                 label=format!("{}-Fertility", income)
+                                                 help=format!("Children born each year per adult at the {} income level.", income)
+                                                 signal=create_slice(world,
+                                                     move |world| world.demographics_by_income[j].fertility,
+                                                     move |world, val| world.demographics_by_income[j].fertility = val
+                                                 ) />
+                                         }
+                                     }).collect::<Vec<_>>()
+                                }}
                             </div>
                             {move || {
-                                 ["β₀", "β₁", "β₂", "β₃"].iter().enumerate().map(|(i, label)| {
-                                     let label = *label;
+                                 Cohort::iter().map(|cohort| {
                                      view! {
                                          <div class="input-column">
-                                             <label>{label}</label>
+                                             <label>{format!("{} {}", cohort, t!("Mortality"))}</label>
                                              {move || {
                                                   Income::iter().enumerate().map(|(j, income)| {
                                                       view! {
                                                           <NumericInput
-                                                              label=format!("{}-{}", income, label)
-                                                              help=format!("{} parameter for {} income annual growth equation.", label, income)
+                                                              label=format!("{}-{}", income, cohort)
+                                                              help=format!("The share of {} that dies each year at the {} income level.", cohort.to_string().to_lowercase(), income)
                                                               signal=create_slice(world,
-                                                                  move |world| world.income_pop_coefs[j][i],
-                                                                  move |world, val| world.income_pop_coefs[j][i] = val
+                                                                  move |world| world.demographics_by_income[j].mortality[cohort],
+                                                                  move |world, val| world.demographics_by_income[j].mortality[cohort] = val
                                                               ) />
                                                       }
                                                   }).collect::<Vec<_>>()
@@ -84,15 +186,15 @@ pub fn World(world: RwSignal<World>) -> impl IntoView {
                 <div class="map-group table-group">
                     <h2 class="tooltip-parent">
                         Per-Capita Demand/Intensity By Income
-                        <div class="tooltip">The per-capita demand for outputs and resources for each income level.</div>
+                        <div class="tooltip">{t!("The per-capita demand for outputs and resources for each income level.")}</div>
                     </h2>
                     <div class="map-table">
                         <div class="label-column input-column">
                             <div>-</div>
-                            <label>Low</label>
-                            <label>Lower-Middle</label>
-                            <label>Upper-Middle</label>
-                            <label>High</label>
+                            <label>{t!("Low")}</label>
+                            <label>{t!("Lower-Middle")}</label>
+                            <label>{t!("Upper-Middle")}</label>
+                            <label>{t!("High")}</label>
                         </div>
                         {move || {
                              Output::iter().map(|output| {
@@ -110,6 +212,8 @@ pub fn World(world: RwSignal<World>) -> impl IntoView {
                                                       <NumericInput
                                                           label=format!("{}-{}", income, label)
                                                           help=format!("{} income per-capita demand for {}, in {}.", income, label, units)
+                                                          scalable=true
+                                                          bounds=Some(Bounds::NON_NEGATIVE)
                                                           signal=create_slice(world,
                                                               move |world| world.per_capita_demand[j].base[output],
                                                               move |world, val| world.per_capita_demand[j].base[output] = val
@@ -122,16 +226,18 @@ pub fn World(world: RwSignal<World>) -> impl IntoView {
                              }).collect::<Vec<_>>()
                          }}
                          <div class="input-column">
-                             <label>"Water"</label>
+                             <label>{t!("Water")}</label>
                              {move || {
                                   Income::iter().enumerate().map(|(j, income)| {
                                       view! {
                                           <NumericInput
                                               label=format!("{}-Water Demand", income)
-                                              help="Per-capita municipal/household water demand by income level, in L/month."
+                                              help=field_doc!(World.water_by_income)
+                                              scalable=true
+                                              bounds=Some(Bounds::NON_NEGATIVE)
                                               signal=create_slice(world,
-                                                  move |world| world.materials_by_income[j],
-                                                  move |world, val| world.materials_by_income[j] = val
+                                                  move |world| world.water_by_income[j],
+                                                  move |world, val| world.water_by_income[j] = val
                                               ) />
                                       }
                                   }).collect::<Vec<_>>()
@@ -139,16 +245,18 @@ pub fn World(world: RwSignal<World>) -> impl IntoView {
                          </div>
 
                          <div class="input-column">
-                             <label>"Material"</label>
+                             <label>{t!("Material")}</label>
                              {move || {
                                   Income::iter().enumerate().map(|(j, income)| {
                                       view! {
                                           <NumericInput
                                               label=format!("{}-Material Intensity", income)
-                                              help="Per-capita material intensity by income level in metric tons/year, though the units are less important as these values are used for scaling."
+                                              help=field_doc!(World.materials_by_income)
+                                              scalable=true
+                                              bounds=Some(Bounds::NON_NEGATIVE)
                                               signal=create_slice(world,
-                                                  move |world| world.water_by_income[j],
-                                                  move |world, val| world.water_by_income[j] = val
+                                                  move |world| world.materials_by_income[j],
+                                                  move |world, val| world.materials_by_income[j] = val
                                               ) />
                                       }
                                   }).collect::<Vec<_>>()
@@ -158,6 +266,8 @@ pub fn World(world: RwSignal<World>) -> impl IntoView {
                 </div>
 
                 <Regions world />
+                <Variables world />
+                <TippingPoints world />
             </div>
         </div>
     }
@@ -181,7 +291,7 @@ fn Regions(world: RwSignal<World>) -> impl IntoView {
             <div class="map-group table-group">
                 <h2 class="tooltip-parent">
                     Regions
-                    <div class="tooltip">"Parameters for the world's regions."</div>
+                    <div class="tooltip">{t!("Parameters for the world's regions.")}</div>
                 </h2>
                 <div class="map-table">
                     <div class="label-column input-column">
@@ -195,13 +305,15 @@ fn Regions(world: RwSignal<World>) -> impl IntoView {
                         }}
                     </div>
                      <div class="input-column">
-                         <label>"Population"</label>
+                         <label>{t!("Population")}</label>
                         {move || {
                              (0..n_regions).map(|i| {
                                  view! {
                                   <NumericInput
+                                      separators=true
                                       label="Population"
-                                      help="The region's starting population."
+                                      help=field_doc!(Region.population)
+                                      bounds=Some(Region::POPULATION_BOUNDS)
                                       signal=create_slice(world,
                                           move |world| world.regions.by_idx(i).population,
                                           move |world, val| world.regions.by_idx_mut(i).population = val
@@ -211,13 +323,14 @@ fn Regions(world: RwSignal<World>) -> impl IntoView {
                          }}
                      </div>
                      <div class="input-column">
-                         <label>"Development"</label>
+                         <label>{t!("Development")}</label>
                         {move || {
                              (0..n_regions).map(|i| {
                                  view! {
-                                  <NumericInput
+                                  <SliderInput
                                       label="Development"
-                                      help="The region's starting progress to the next income level, from 0.0 to 1.0."
+                                      help=field_doc!(Region.development)
+                                      bounds=Region::DEVELOPMENT_BOUNDS
                                       signal=create_slice(world,
                                           move |world| world.regions.by_idx(i).development,
                                           move |world, val| world.regions.by_idx_mut(i).development = val
@@ -227,14 +340,99 @@ fn Regions(world: RwSignal<World>) -> impl IntoView {
                          }}
                      </div>
                      <div class="input-column">
-                         <label>"Income Level"</label>
+                         <label>{t!("Coastal Exposure")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <SliderInput
+                                      label="Coastal Exposure"
+                                      help=field_doc!(Region.coastal_exposure)
+                                      bounds=Region::COASTAL_EXPOSURE_BOUNDS
+                                      signal=create_slice(world,
+                                          move |world| world.regions.by_idx(i).coastal_exposure,
+                                          move |world, val| world.regions.by_idx_mut(i).coastal_exposure = val
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Continentality")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <SliderInput
+                                      label="Continentality"
+                                      help=field_doc!(Region.continentality)
+                                      bounds=Region::CONTINENTALITY_BOUNDS
+                                      signal=create_slice(world,
+                                          move |world| world.regions.by_idx(i).continentality,
+                                          move |world, val| world.regions.by_idx_mut(i).continentality = val
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Forest")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <SliderInput
+                                      label="Forest"
+                                      help=field_doc!(Region.land_use)
+                                      bounds=Region::LAND_USE_BOUNDS
+                                      signal=create_slice(world,
+                                          move |world| world.regions.by_idx(i).land_use.forest,
+                                          move |world, val| world.regions.by_idx_mut(i).land_use.forest = val
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Urban Land")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <SliderInput
+                                      label="Urban Land"
+                                      help=field_doc!(Region.land_use)
+                                      bounds=Region::LAND_USE_BOUNDS
+                                      signal=create_slice(world,
+                                          move |world| world.regions.by_idx(i).land_use.urban,
+                                          move |world, val| world.regions.by_idx_mut(i).land_use.urban = val
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Vulnerability")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <NumericInput
+                                      label="Vulnerability"
+                                      help=field_doc!(Region.vulnerability)
+                                      bounds=Some(Region::VULNERABILITY_BOUNDS)
+                                      signal=create_slice(world,
+                                          move |world| world.regions.by_idx(i).vulnerability,
+                                          move |world, val| world.regions.by_idx_mut(i).vulnerability = val
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Income Level")}</label>
                         {move || {
                              (0..n_regions).map(|i| {
                                  view! {
                                     <EnumInput
                                         tooltip=true
                                         label="Income Level"
-                                        help="The region's starting income level."
+                                        help=field_doc!(Region.income)
                                         signal=create_slice(world,
                                             move |world| world.regions.by_idx(i).income,
                                             move |world, val| world.regions.by_idx_mut(i).income = val
@@ -243,8 +441,276 @@ fn Regions(world: RwSignal<World>) -> impl IntoView {
                              }).collect::<Vec<_>>()
                          }}
                      </div>
+                     <div class="input-column">
+                         <label>{t!("Temperature")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <RangeInput
+                                      label="Temperature"
+                                      help=field_doc!(Region.temp_lo)
+                                      signal=create_slice(world,
+                                          move |world| {
+                                              let region = world.regions.by_idx(i);
+                                              (region.temp_lo, region.temp_hi)
+                                          },
+                                          move |world, (lo, hi)| {
+                                              let region = world.regions.by_idx_mut(i);
+                                              region.temp_lo = lo;
+                                              region.temp_hi = hi;
+                                          }
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Precipitation")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 view! {
+                                  <RangeInput
+                                      label="Precipitation"
+                                      help=field_doc!(Region.precip_lo)
+                                      signal=create_slice(world,
+                                          move |world| {
+                                              let region = world.regions.by_idx(i);
+                                              (region.precip_lo, region.precip_hi)
+                                          },
+                                          move |world, (lo, hi)| {
+                                              let region = world.regions.by_idx_mut(i);
+                                              region.precip_lo = lo;
+                                              region.precip_hi = hi;
+                                          }
+                                      ) />
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
+                     <div class="input-column">
+                         <label>{t!("Location")}</label>
+                        {move || {
+                             (0..n_regions).map(|i| {
+                                 let band_mismatch = move || {
+                                     with!(|world| {
+                                         let region = world.regions.by_idx(i);
+                                         region.flavor.location.and_then(|(lat, _)| {
+                                             let band = Latitude::of_degrees(lat);
+                                             (band != region.latitude).then(|| format!(
+                                                 "This location is in the {band} band, but the region's latitude is {}.",
+                                                 region.latitude
+                                             ))
+                                         })
+                                     })
+                                 };
+                                 view! {
+                                  <OptionalInput
+                                      label="Set Location"
+                                      signal=create_slice(world,
+                                          move |world| world.regions.by_idx(i).flavor.location,
+                                          move |world, val| world.regions.by_idx_mut(i).flavor.location = val
+                                      )
+                                      editor=move |signal: (Signal<(f32, f32)>, SignalSetter<(f32, f32)>)| view! {
+                                          <CoordinateInput
+                                              map=true
+                                              label="Location"
+                                              help="A representative location for the region, as latitude and longitude in degrees."
+                                              signal />
+                                      } />
+                                  {move || band_mismatch().map(|err| view! {
+                                      <div class="input-error">{err}</div>
+                                  })}
+                                 }
+                             }).collect::<Vec<_>>()
+                         }}
+                     </div>
                 </div>
             </div>
         </div>
     }
 }
+
+#[component]
+fn Variables(world: RwSignal<World>) -> impl IntoView {
+    let n_variables =
+        move || with!(|world| world.variables.len());
+    view! {
+        <div class="variables map-group mutable-list">
+            <div class="mutable-list-header">
+                <h2 class="tooltip-parent">
+                    Variables
+                    <div class="tooltip">{t!("The world's own variables, e.g. \"Public Trust\", which effects can change and conditions can check.")}</div>
+                </h2>
+                <div class="mutable-list-add">
+                    <div class="mutable-list-add-button" on:click=move |_| {
+                        update!(|world| world.variables.push(Variable::new()));
+                    }>+Add</div>
+                </div>
+            </div>
+            {move || (n_variables() == 0).then(|| view! {
+                <div class="empty">{t!("No variables defined.")}</div>
+            })}
+            <div class="mutable-list-items">
+                {move || {
+                     (0..n_variables()).map(|i| {
+                         let id = with!(|world| world.variables.by_idx(i).id);
+                         let used_by = move || {
+                             with!(|world| find_references(id, RefKind::Variable, world))
+                         };
+                         view! {
+                             <div class="variable mutable-list-item">
+                                 <div class="mutable-list-item-header">
+                                     <TextInput
+                                         label="Name"
+                                         help="The variable's name, as shown to players."
+                                         signal=create_slice(world,
+                                             move |world| world.variables.by_idx(i).name.clone(),
+                                             move |world, val| world.variables.by_idx_mut(i).name = val
+                                         ) />
+                                     <div class="mutable-list-item-remove"
+                                         title=t!("Variables that are used can't be removed.")
+                                         on:click=move |_| {
+                                             let msg = "Are you sure you want to remove this variable?";
+                                             if used_by().is_empty() && window().confirm_with_message(msg).unwrap() {
+                                                 update!(|world| world.variables.remove(&id));
+                                             }
+                                         }>"✗"</div>
+                                 </div>
+                                 <EnumInput
+                                     label="Kind"
+                                     help=field_doc!(Variable.kind)
+                                     signal=create_slice(world,
+                                         move |world| world.variables.by_idx(i).kind,
+                                         move |world, val| {
+                                             let var = world.variables.by_idx_mut(i);
+                                             var.kind = val;
+                                             var.set(var.value);
+                                         }
+                                     ) />
+                                 <NumericInput
+                                     label="Starting Value"
+                                     help=field_doc!(Variable.value)
+                                     signal=create_slice(world,
+                                         move |world| world.variables.by_idx(i).value,
+                                         move |world, val| world.variables.by_idx_mut(i).set(val)
+                                     ) />
+                                 <TextArea
+                                     label="Description"
+                                     help=field_doc!(Variable.description)
+                                     signal=create_slice(world,
+                                         move |world| world.variables.by_idx(i).description.clone(),
+                                         move |world, val| world.variables.by_idx_mut(i).description = val
+                                     ) />
+                                 {move || {
+                                      let refs = used_by();
+                                      (!refs.is_empty()).then(|| view! {
+                                          <div class="input-help">
+                                              {t!("Used by {names}.", names: refs.join(", "))}
+                                          </div>
+                                      })
+                                 }}
+                             </div>
+                         }
+                     }).collect::<Vec<_>>()
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn TippingPoints(world: RwSignal<World>) -> impl IntoView {
+    let n_points =
+        move || with!(|world| world.tipping_points.len());
+    view! {
+        <div class="tipping-points map-group mutable-list">
+            <div class="mutable-list-header">
+                <h2 class="tooltip-parent">
+                    Tipping Points
+                    <div class="tooltip">{t!("Thresholds in the climate system, e.g. permafrost thaw, which may be crossed once the temperature is past them. Events can check if they've been crossed.")}</div>
+                </h2>
+                <div class="mutable-list-add">
+                    <div class="mutable-list-add-button" on:click=move |_| {
+                        update!(|world| world.tipping_points.push(TippingPoint::new()));
+                    }>+Add</div>
+                </div>
+            </div>
+            {move || (n_points() == 0).then(|| view! {
+                <div class="empty">{t!("No tipping points defined.")}</div>
+            })}
+            <div class="mutable-list-items">
+                {move || {
+                     (0..n_points()).map(|i| {
+                         let id = with!(|world| world.tipping_points.by_idx(i).id);
+                         let used_by = move || {
+                             with!(|world| find_references(id, RefKind::TippingPoint, world))
+                         };
+                         view! {
+                             <div class="tipping-point mutable-list-item">
+                                 <div class="mutable-list-item-header">
+                                     <TextInput
+                                         label="Name"
+                                         help="The tipping point's name, as shown to players."
+                                         signal=create_slice(world,
+                                             move |world| world.tipping_points.by_idx(i).name.clone(),
+                                             move |world, val| world.tipping_points.by_idx_mut(i).name = val
+                                         ) />
+                                     <div class="mutable-list-item-remove"
+                                         title=t!("Tipping points that are used can't be removed.")
+                                         on:click=move |_| {
+                                             let msg = "Are you sure you want to remove this tipping point?";
+                                             if used_by().is_empty() && window().confirm_with_message(msg).unwrap() {
+                                                 update!(|world| world.tipping_points.remove(&id));
+                                             }
+                                         }>"✗"</div>
+                                 </div>
+                                 <NumericInput
+                                     label="Threshold"
+                                     help=field_doc!(TippingPoint.threshold)
+                                     signal=create_slice(world,
+                                         move |world| world.tipping_points.by_idx(i).threshold,
+                                         move |world, val| world.tipping_points.by_idx_mut(i).threshold = val
+                                     ) />
+                                 <NumericInput
+                                     label="Probability"
+                                     help=field_doc!(TippingPoint.probability)
+                                     bounds=Some(Bounds::NON_NEGATIVE)
+                                     signal=create_slice(world,
+                                         move |world| world.tipping_points.by_idx(i).probability,
+                                         move |world, val| world.tipping_points.by_idx_mut(i).probability = val
+                                     ) />
+                                 <ToggleInput
+                                     label="Already Crossed"
+                                     help="If it's crossed from the start, in which case its effects aren't applied again."
+                                     signal=create_slice(world,
+                                         move |world| world.tipping_points.by_idx(i).crossed,
+                                         move |world, val| world.tipping_points.by_idx_mut(i).crossed = val
+                                     ) />
+                                 <TextArea
+                                     label="Description"
+                                     help=field_doc!(TippingPoint.description)
+                                     signal=create_slice(world,
+                                         move |world| world.tipping_points.by_idx(i).description.clone(),
+                                         move |world, val| world.tipping_points.by_idx_mut(i).description = val
+                                     ) />
+                                 <Effects
+                                     effects=create_slice(world,
+                                         move |world| world.tipping_points.by_idx(i).effects.clone(),
+                                         move |world, val| world.tipping_points.by_idx_mut(i).effects = val
+                                     ) />
+                                 {move || {
+                                      let refs = used_by();
+                                      (!refs.is_empty()).then(|| view! {
+                                          <div class="input-help">
+                                              {t!("Used by {names}.", names: refs.join(", "))}
+                                          </div>
+                                      })
+                                 }}
+                             </div>
+                         }
+                     }).collect::<Vec<_>>()
+                }}
+            </div>
+        </div>
+    }
+}
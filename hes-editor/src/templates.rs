@@ -0,0 +1,271 @@
+use hes_engine::{
+    flavor::{Dialogue, DialogueLine, DialogueNext},
+    Comparator,
+    Condition,
+    Cost,
+    Effect,
+    Event,
+    Feedstock,
+    Group,
+    Industry,
+    Likelihood,
+    Output,
+    Probability,
+    Process,
+    ProcessFeature,
+    Project,
+    ProjectType,
+    WorldVariable,
+};
+
+/// Starter templates offered when creating a new entity,
+/// so that common kinds of entities don't have to be
+/// filled in from scratch.
+///
+/// Each template is created fresh (with a new id)
+/// every time this is called.
+pub trait Template: Sized {
+    fn templates() -> Vec<(&'static str, Self)>;
+}
+
+impl Template for Industry {
+    fn templates() -> Vec<(&'static str, Self)> {
+        vec![]
+    }
+}
+
+impl Template for Process {
+    fn templates() -> Vec<(&'static str, Self)> {
+        let fossil_power = {
+            let mut process = Process::new();
+            process.name = "Fossil Power Plant".into();
+            process.output = Output::Electricity;
+            process.feedstock = (Feedstock::Coal, 500.);
+            process.resources.land = 0.00017;
+            process.resources.water = 2.;
+            process.byproducts.co2 = 950.;
+            process.byproducts.ch4 = 2.;
+            process.byproducts.biodiversity = 1.;
+            process.features = vec![
+                ProcessFeature::IsCombustion,
+                ProcessFeature::IsFossil,
+            ];
+            process.flavor.description = "Burns fossil fuels to generate electricity.".into();
+            process
+        };
+
+        let renewable_power = {
+            let mut process = Process::new();
+            process.name = "Renewable Power".into();
+            process.output = Output::Electricity;
+            process.feedstock = (Feedstock::Other, 1.);
+            process.resources.land = 0.005;
+            process.resources.water = 0.1;
+            process.byproducts.co2 = 30.;
+            process.features = vec![ProcessFeature::IsIntermittent];
+            process.flavor.description = "Generates electricity from a renewable source.".into();
+            process
+        };
+
+        let fossil_fuel = {
+            let mut process = Process::new();
+            process.name = "Fossil Fuel".into();
+            process.output = Output::Fuel;
+            process.feedstock = (Feedstock::Oil, 0.3);
+            process.resources.land = 0.0005;
+            process.resources.water = 3.5;
+            process.byproducts.co2 = 250.;
+            process.byproducts.ch4 = 0.6;
+            process.byproducts.biodiversity = 2.;
+            process.features = vec![
+                ProcessFeature::IsCombustion,
+                ProcessFeature::IsFossil,
+                ProcessFeature::UsesOil,
+            ];
+            process.flavor.description = "Extracts and refines fossil fuels.".into();
+            process
+        };
+
+        let crop_agriculture = {
+            let mut process = Process::new();
+            process.name = "Crop Agriculture".into();
+            process.output = Output::PlantCalories;
+            process.feedstock = (Feedstock::Soil, 1.);
+            process.resources.land = 0.002;
+            process.resources.water = 1.3;
+            process.resources.fuel = 0.0001;
+            process.byproducts.co2 = 0.2;
+            process.byproducts.n2o = 0.0004;
+            process.byproducts.biodiversity = 2.;
+            process.features = vec![
+                ProcessFeature::IsSolar,
+                ProcessFeature::UsesSynFertilizer,
+            ];
+            process.flavor.description = "Grows crops for plant calories.".into();
+            process
+        };
+
+        let livestock_agriculture = {
+            let mut process = Process::new();
+            process.name = "Livestock Agriculture".into();
+            process.output = Output::AnimalCalories;
+            process.feedstock = (Feedstock::Soil, 1.);
+            process.resources.land = 0.025;
+            process.resources.water = 3.5;
+            process.resources.fuel = 0.0008;
+            process.byproducts.co2 = 1.75;
+            process.byproducts.ch4 = 0.026;
+            process.byproducts.n2o = 0.0034;
+            process.byproducts.biodiversity = 3.;
+            process.features = vec![ProcessFeature::UsesLivestock];
+            process.flavor.description = "Raises livestock for animal calories.".into();
+            process
+        };
+
+        vec![
+            ("Fossil Power Plant", fossil_power),
+            ("Renewable Power", renewable_power),
+            ("Fossil Fuel", fossil_fuel),
+            ("Crop Agriculture", crop_agriculture),
+            ("Livestock Agriculture", livestock_agriculture),
+        ]
+    }
+}
+
+impl Template for Project {
+    fn templates() -> Vec<(&'static str, Self)> {
+        let policy = {
+            let mut project = Project::new();
+            project.name = "New Policy".into();
+            project.kind = ProjectType::Policy;
+            project.group = Group::Behavior;
+            project.base_cost = Cost::Fixed(10);
+            project.effects = vec![Effect::WorldVariable(
+                WorldVariable::Outlook,
+                1.,
+            )];
+            project.flavor.description = "Describe what this policy does and why.".into();
+            project
+        };
+
+        let research = {
+            let mut project = Project::new();
+            project.name = "New Research".into();
+            project.kind = ProjectType::Research;
+            project.group = Group::Energy;
+            project.base_cost = Cost::Fixed(10);
+            project.flavor.description = "Describe what this research is for.".into();
+            project
+        };
+
+        let initiative = {
+            let mut project = Project::new();
+            project.name = "New Initiative".into();
+            project.kind = ProjectType::Initiative;
+            project.group = Group::Restoration;
+            project.base_cost = Cost::Fixed(20);
+            project.gradual = true;
+            project.effects = vec![Effect::WorldVariable(
+                WorldVariable::ExtinctionRate,
+                -1.,
+            )];
+            project.flavor.description = "Describe what this initiative builds or does.".into();
+            project
+        };
+
+        vec![
+            ("Policy", policy),
+            ("Research", research),
+            ("Initiative", initiative),
+        ]
+    }
+}
+
+/// A two-line dialogue, with the first line
+/// leading into the second.
+pub fn two_line_dialogue(first: &str, second: &str) -> Dialogue {
+    Dialogue {
+        root: 0,
+        lines: vec![
+            DialogueLine {
+                id: 0,
+                next: Some(DialogueNext::Line { id: 1 }),
+                text: first.into(),
+                ..Default::default()
+            },
+            DialogueLine {
+                id: 1,
+                next: None,
+                text: second.into(),
+                ..Default::default()
+            },
+        ],
+    }
+}
+
+impl Template for Event {
+    fn templates() -> Vec<(&'static str, Self)> {
+        let after_start = Condition::WorldVariable(
+            WorldVariable::Year,
+            Comparator::Greater,
+            2025.,
+        );
+
+        let crisis = {
+            let mut event = Event::new();
+            event.name = "New Crisis".into();
+            event.probabilities = vec![Probability {
+                likelihood: Likelihood::Random,
+                conditions: vec![
+                    Condition::WorldVariable(
+                        WorldVariable::Temperature,
+                        Comparator::GreaterEqual,
+                        1.5,
+                    ),
+                    after_start.clone(),
+                ],
+            }];
+            event.effects = vec![
+                Effect::WorldVariable(WorldVariable::Outlook, -2.),
+                Effect::WorldVariable(
+                    WorldVariable::ExtinctionRate,
+                    1.,
+                ),
+            ];
+            event.flavor.arc = "A Dying Planet".into();
+            event.flavor.dialogue = two_line_dialogue(
+                "Describe what's happening.",
+                "Describe what it means for the world.",
+            );
+            event
+        };
+
+        let good_news = {
+            let mut event = Event::new();
+            event.name = "New Good News".into();
+            event.probabilities = vec![Probability {
+                likelihood: Likelihood::Likely,
+                conditions: vec![
+                    Condition::WorldVariable(
+                        WorldVariable::Emissions,
+                        Comparator::LessEqual,
+                        10.,
+                    ),
+                    after_start,
+                ],
+            }];
+            event.effects = vec![Effect::WorldVariable(
+                WorldVariable::Outlook,
+                2.,
+            )];
+            event.flavor.arc = "Eden Earth".into();
+            event.flavor.dialogue = two_line_dialogue(
+                "Describe what's happening.",
+                "Describe why it matters.",
+            );
+            event
+        };
+
+        vec![("Crisis", crisis), ("Good News", good_news)]
+    }
+}
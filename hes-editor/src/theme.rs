@@ -0,0 +1,67 @@
+use leptos::*;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+const THEME_ITEM: &str = "theme";
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Display, EnumIter,
+)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    #[strum(serialize = "High Contrast")]
+    HighContrast,
+}
+impl Theme {
+    /// The value for the root's `data-theme`,
+    /// which the stylesheet keys off of.
+    fn key(&self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+
+    /// The theme after this one, for cycling through them.
+    pub fn next(&self) -> Theme {
+        let themes = Theme::iter().collect::<Vec<_>>();
+        let idx =
+            themes.iter().position(|t| t == self).unwrap_or(0);
+        themes[(idx + 1) % themes.len()]
+    }
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+fn stored_theme() -> Theme {
+    let key = storage()
+        .and_then(|storage| storage.get_item(THEME_ITEM).ok())
+        .flatten();
+    Theme::iter()
+        .find(|theme| Some(theme.key()) == key.as_deref())
+        .unwrap_or_default()
+}
+
+/// The editor's theme, kept in local storage
+/// so it carries across sessions.
+#[derive(Clone, Copy)]
+pub struct ThemeSetting(pub RwSignal<Theme>);
+impl ThemeSetting {
+    pub fn provide() {
+        let theme = create_rw_signal(stored_theme());
+        create_effect(move |_| {
+            let key = theme.get().key();
+            if let Some(root) = document().document_element() {
+                let _ = root.set_attribute("data-theme", key);
+            }
+            if let Some(storage) = storage() {
+                let _ = storage.set_item(THEME_ITEM, key);
+            }
+        });
+        provide_context(ThemeSetting(theme));
+    }
+}
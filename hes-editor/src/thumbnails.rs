@@ -0,0 +1,154 @@
+//! Small previews of embedded images, so that lists of
+//! entities don't have to decode every full-size image
+//! just to render.
+
+use hes_engine::{Image, ImageData};
+use js_sys::{Array, Uint8Array};
+use leptos::*;
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+use web_sys::{Blob, BlobPropertyBag, Url};
+
+use crate::{inputs::image_src, recent::make_thumbnail, t};
+
+/// The size images are shown at in the editor's forms.
+const WIDTH: u32 = 280;
+const HEIGHT: u32 = 187;
+
+thread_local! {
+    /// Thumbnail data URLs, by image key.
+    static THUMBNAILS: RefCell<HashMap<u64, String>> =
+        RefCell::new(HashMap::new());
+
+    /// Object URLs for full-size images, by image key.
+    static FULL_SIZE: RefCell<HashMap<u64, String>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Identifies an embedded image by its contents,
+/// or `None` if it's one of the bundled files.
+fn key(image: &Image) -> Option<u64> {
+    match &image.data {
+        ImageData::File(_) => None,
+        ImageData::Data { bytes, .. } => {
+            let mut hasher = DefaultHasher::new();
+            bytes.hash(&mut hasher);
+            Some(hasher.finish())
+        }
+    }
+}
+
+fn object_url(bytes: &[u8], mime: &str) -> Option<String> {
+    let parts = Array::of1(&Uint8Array::from(bytes));
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &parts,
+        BlobPropertyBag::new().type_(mime),
+    )
+    .ok()?;
+    Url::create_object_url_with_blob(&blob).ok()
+}
+
+/// The URL for the full-size image. Embedded images are
+/// given object URLs, which are much cheaper than
+/// encoding the image as a data URL each time.
+pub fn full_src(image: &Image) -> String {
+    let (Some(key), ImageData::Data { bytes, mime }) =
+        (key(image), &image.data)
+    else {
+        return image_src(image);
+    };
+    FULL_SIZE.with_borrow_mut(|urls| {
+        urls.entry(key)
+            .or_insert_with(|| {
+                object_url(bytes, mime)
+                    .unwrap_or_else(|| image_src(image))
+            })
+            .clone()
+    })
+}
+
+/// A small preview of an image, generated in the
+/// background the first time an image is shown.
+/// Click it to show the full-size image.
+#[component]
+pub fn Thumbnail(
+    #[prop(into)] image: Signal<Image>,
+) -> impl IntoView {
+    let src = create_rw_signal::<Option<String>>(None);
+    let full_size = create_rw_signal(false);
+
+    // Only reload when the image itself has changed,
+    // not whenever the entity it belongs to does.
+    create_effect(move |prev: Option<Option<u64>>| {
+        let key = image.with(key);
+        if prev == Some(key)
+            && src.with_untracked(Option::is_some)
+        {
+            return key;
+        }
+        full_size.set(false);
+        let Some(key) = key else {
+            src.set(Some(image.with_untracked(image_src)));
+            return key;
+        };
+        let cached = THUMBNAILS
+            .with_borrow(|thumbs| thumbs.get(&key).cloned());
+        if cached.is_some() {
+            src.set(cached);
+            return Some(key);
+        }
+
+        src.set(None);
+        let full = image.with_untracked(full_src);
+        spawn_local(async move {
+            let thumb = make_thumbnail(&full, WIDTH, HEIGHT)
+                .await
+                .as_string();
+            if let Some(thumb) = &thumb {
+                THUMBNAILS.with_borrow_mut(|thumbs| {
+                    thumbs.insert(key, thumb.clone())
+                });
+            }
+
+            // Skip if the image was changed meanwhile,
+            // and fall back to the full-size image if
+            // the thumbnail couldn't be made.
+            if image.with_untracked(self::key) == Some(key) {
+                src.set(Some(thumb.unwrap_or(full)));
+            }
+        });
+        Some(key)
+    });
+
+    let shown = move || {
+        if full_size.get() {
+            Some(image.with(full_src))
+        } else {
+            src.get()
+        }
+    };
+
+    view! {
+        {move || match shown() {
+            Some(src) => view! {
+                <img
+                    class="thumbnail"
+                    class:full-size=full_size
+                    src=src
+                    alt=t!("Preview of the current image")
+                    title=move || if full_size.get() {
+                        t!("Click to show less.")
+                    } else {
+                        t!("Click to show full size.")
+                    }
+                    on:click=move |_| update!(|full_size| *full_size = !*full_size) />
+            }
+            .into_view(),
+            None => view! { <div class="image-placeholder" /> }
+                .into_view(),
+        }}
+    }
+}
@@ -0,0 +1,129 @@
+//! Deleted entities, kept in the browser's storage rather
+//! than in the world so that they can be restored but
+//! aren't included when the world is exported.
+
+use anyhow::Result;
+use base64::prelude::*;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    files::{compress, decompress},
+    share::Shared,
+};
+
+const TRASH_ITEM: &str = "editor-trash";
+
+/// Browser storage is limited, so the
+/// oldest items are dropped beyond this.
+const MAX_ITEMS: usize = 50;
+
+/// An entity that was deleted.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Trashed {
+    /// When it was deleted,
+    /// in milliseconds since the epoch.
+    pub deleted: f64,
+    pub item: Shared,
+}
+impl Trashed {
+    pub fn name(&self) -> &str {
+        match &self.item {
+            Shared::World(world) => &world.metadata.title,
+            Shared::Industry(item) => &item.name,
+            Shared::Process(item) => &item.name,
+            Shared::Project(item) => &item.name,
+            Shared::Event(item) => &item.name,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match &self.item {
+            Shared::World(_) => "World",
+            Shared::Industry(_) => "Industry",
+            Shared::Process(_) => "Process",
+            Shared::Project(_) => "Project",
+            Shared::Event(_) => "Event",
+        }
+    }
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+fn serialize(items: &[Trashed]) -> Result<String> {
+    let bytes = rmp_serde::to_vec_named(items)?;
+    Ok(BASE64_STANDARD.encode(compress(&bytes)?))
+}
+
+fn deserialize(data: &str) -> Result<Vec<Trashed>> {
+    let bytes = decompress(&BASE64_STANDARD.decode(data)?)?;
+    Ok(rmp_serde::from_slice(&bytes)?)
+}
+
+fn load() -> Vec<Trashed> {
+    storage()
+        .and_then(|storage| storage.get_item(TRASH_ITEM).ok())
+        .flatten()
+        .and_then(|data| deserialize(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(items: &[Trashed]) {
+    let Some(storage) = storage() else { return };
+    let saved = serialize(items).and_then(|data| {
+        storage
+            .set_item(TRASH_ITEM, &data)
+            .map_err(|_| anyhow::anyhow!("Storage is full"))
+    });
+    if let Err(err) = saved {
+        log::warn!("Couldn't save the trash: {err}");
+    }
+}
+
+/// The trash bin, most recently deleted first.
+#[derive(Clone, Copy)]
+pub struct TrashBin(pub RwSignal<Vec<Trashed>>);
+impl TrashBin {
+    pub fn provide() -> TrashBin {
+        let trash = TrashBin(create_rw_signal(load()));
+        provide_context(trash);
+        trash
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Vec<Trashed>)) {
+        self.0.update(|items| {
+            f(items);
+            save(items);
+        });
+    }
+
+    /// Move a deleted entity into the trash.
+    pub fn put(&self, item: impl Into<Shared>) {
+        let trashed = Trashed {
+            deleted: js_sys::Date::now(),
+            item: item.into(),
+        };
+        self.update(|items| {
+            items.insert(0, trashed);
+            items.truncate(MAX_ITEMS);
+        });
+    }
+
+    /// Take an item out of the trash, e.g. to restore it.
+    pub fn take(&self, idx: usize) -> Option<Trashed> {
+        let mut taken = None;
+        self.update(|items| {
+            if idx < items.len() {
+                taken = Some(items.remove(idx));
+            }
+        });
+        taken
+    }
+
+    /// Permanently delete everything in the trash.
+    pub fn empty(&self) {
+        self.update(|items| items.clear());
+    }
+}
@@ -1,26 +1,41 @@
 use std::collections::HashSet;
 
 use hes_engine::{
+    expr::{ConditionExpr, EffectsExpr},
     flavor::DialogueNext,
     Condition,
     Effect,
+    Event,
+    Flag,
     Id,
+    Process,
+    Project,
+    TippingPoint,
     World,
+    WorldVariable,
+    NPC,
 };
 
 /// Errors:
 /// - Effect refers to entity that doesn't exist.
 /// - Condition refers to entity that doesn't exist.
+/// - Process or project is supported or opposed by an NPC
+///   that doesn't exist.
 
-struct IdTracker {
+struct IdTracker<'a> {
+    world: &'a World,
     projects: Vec<Id>,
     processes: Vec<Id>,
     industries: Vec<Id>,
     events: Vec<Id>,
+    variables: Vec<Id>,
+    tipping_points: Vec<Id>,
+    npcs: Vec<Id>,
 }
-impl IdTracker {
-    fn new(world: &World) -> Self {
+impl<'a> IdTracker<'a> {
+    fn new(world: &'a World) -> Self {
         Self {
+            world,
             projects: world
                 .projects
                 .iter()
@@ -41,11 +56,29 @@ impl IdTracker {
                 .iter()
                 .map(|item| item.id)
                 .collect(),
+            variables: world
+                .variables
+                .iter()
+                .map(|item| item.id)
+                .collect(),
+            tipping_points: world
+                .tipping_points
+                .iter()
+                .map(|item| item.id)
+                .collect(),
+            npcs: NPC::load().iter().map(|npc| npc.id).collect(),
         }
     }
 
     fn check_effect(&self, effect: &Effect) -> bool {
-        if let Some(id) = effect.project_id() {
+        // Expressions refer to things by name. Ones that
+        // don't parse are flagged by their lints instead.
+        if let Effect::Expr(src) = effect {
+            match EffectsExpr::parse(src) {
+                Ok(expr) => expr.check(self.world).is_ok(),
+                Err(_) => true,
+            }
+        } else if let Some(id) = effect.project_id() {
             self.projects.contains(&id)
         } else if let Some(id) = effect.process_id() {
             self.processes.contains(&id)
@@ -53,16 +86,27 @@ impl IdTracker {
             self.industries.contains(&id)
         } else if let Some(id) = effect.event_id() {
             self.events.contains(&id)
+        } else if let Some(id) = effect.variable_id() {
+            self.variables.contains(&id)
         } else {
             true
         }
     }
 
     fn check_condition(&self, condition: &Condition) -> bool {
-        if let Some(id) = condition.project_id() {
+        if let Condition::Expr(src) = condition {
+            match ConditionExpr::parse(src) {
+                Ok(expr) => expr.check(self.world).is_ok(),
+                Err(_) => true,
+            }
+        } else if let Some(id) = condition.project_id() {
             self.projects.contains(&id)
         } else if let Some(id) = condition.process_id() {
             self.processes.contains(&id)
+        } else if let Some(id) = condition.variable_id() {
+            self.variables.contains(&id)
+        } else if let Some(id) = condition.tipping_point_id() {
+            self.tipping_points.contains(&id)
         } else {
             true
         }
@@ -75,6 +119,8 @@ pub enum RefKind {
     Process,
     Industry,
     Event,
+    Variable,
+    TippingPoint,
 }
 
 /// Find all references to this id, returning the names
@@ -92,13 +138,20 @@ pub fn find_references(
             RefKind::Process => effect.process_id(),
             RefKind::Industry => effect.industry_id(),
             RefKind::Event => effect.event_id(),
+            RefKind::Variable => effect.variable_id(),
+            RefKind::TippingPoint => None,
         };
         id_ == Some(id)
     };
 
+    let is_event = matches!(kind, RefKind::Event);
     let check_condition = move |cond: &Condition| match kind {
         RefKind::Project => cond.project_id() == Some(id),
         RefKind::Process => cond.process_id() == Some(id),
+        RefKind::Variable => cond.variable_id() == Some(id),
+        RefKind::TippingPoint => {
+            cond.tipping_point_id() == Some(id)
+        }
         _ => false,
     };
 
@@ -158,6 +211,13 @@ pub fn find_references(
                 referenced_by.insert(item.name.clone());
             }
         }
+        if let Some(after) =
+            item.schedule.and_then(|s| s.event_id())
+        {
+            if is_event && after == id {
+                referenced_by.insert(item.name.clone());
+            }
+        }
         for prob in &item.probabilities {
             for cond in &prob.conditions {
                 if check_condition(cond) {
@@ -183,58 +243,268 @@ pub fn find_references(
                                 .insert(item.name.clone());
                         }
                     }
+                    let follows_up = resp
+                        .follow_ups
+                        .iter()
+                        .any(|follow_up| follow_up.event == id);
+                    if is_event && follows_up {
+                        referenced_by.insert(item.name.clone());
+                    }
                 }
             }
         }
     }
 
+    for item in world.tipping_points.iter() {
+        for effect in &item.effects {
+            if check_effect(effect) {
+                referenced_by.insert(item.name.clone());
+            }
+        }
+    }
+
     referenced_by.into_iter().collect()
 }
 
-pub fn validate(world: &World) -> Vec<String> {
-    let mut errors = vec![];
-    let tracker = IdTracker::new(world);
-
+/// All the effects in the world, including
+/// those in outcomes, upgrades, and dialogue.
+fn all_effects(world: &World) -> Vec<&Effect> {
+    let mut effects = vec![];
     for item in world.projects.iter() {
-        for effect in &item.effects {
-            if !tracker.check_effect(effect) {
-                errors.push(format!("Project effects of {:?} refers to a non-existent entity.", item.name));
-            }
-        }
+        effects.extend(&item.effects);
         for outcome in &item.outcomes {
-            for effect in &outcome.effects {
-                if !tracker.check_effect(effect) {
-                    errors.push(format!("Project outcomes of {:?} refers to a non-existent entity.", item.name));
+            effects.extend(&outcome.effects);
+        }
+        for upgrade in &item.upgrades {
+            effects.extend(&upgrade.effects);
+        }
+        for dialogue in &item.flavor.outcomes {
+            for line in &dialogue.lines {
+                if let Some(DialogueNext::Responses(resps)) =
+                    &line.next
+                {
+                    for resp in resps {
+                        effects.extend(&resp.effects);
+                    }
                 }
             }
-            for cond in &outcome.probability.conditions {
-                if !tracker.check_condition(cond) {
-                    errors.push(format!("Project outcome conditions for {:?} refers to a non-existent entity.", item.name));
+        }
+    }
+    for item in world.events.iter() {
+        effects.extend(&item.effects);
+        for line in &item.flavor.dialogue.lines {
+            if let Some(DialogueNext::Responses(resps)) = &line.next
+            {
+                for resp in resps {
+                    effects.extend(&resp.effects);
                 }
             }
         }
-        for upgrade in &item.upgrades {
-            for effect in &upgrade.effects {
-                if !tracker.check_effect(effect) {
-                    errors.push(format!("Project upgrades of {:?} refers to a non-existent entity.", item.name));
+    }
+    for item in world.tipping_points.iter() {
+        effects.extend(&item.effects);
+    }
+    effects
+}
+
+/// Flags which some effect in the world sets.
+/// A condition on any other flag can never be met
+/// (unless the game sets it, e.g. tutorial flags).
+pub fn flags_set(world: &World) -> Vec<Flag> {
+    let mut flags = vec![];
+    for effect in all_effects(world) {
+        if let Effect::AddFlag(flag) = effect {
+            if !flags.contains(flag) {
+                flags.push(*flag);
+            }
+        }
+    }
+    flags
+}
+
+/// Years that events are conditioned on, with the name
+/// of the event, to show when events fire relative to
+/// each other.
+pub fn year_markers(world: &World) -> Vec<(f32, String)> {
+    let mut markers = vec![];
+    for event in world.events.iter() {
+        for prob in &event.probabilities {
+            for cond in &prob.conditions {
+                if let Condition::WorldVariable(
+                    WorldVariable::Year,
+                    _,
+                    year,
+                ) = cond
+                {
+                    markers.push((*year, event.name.clone()));
                 }
             }
         }
     }
+    markers
+}
 
+pub fn validate(world: &World) -> Vec<String> {
+    let mut errors = vec![];
+    let tracker = IdTracker::new(world);
+    for item in world.processes.iter() {
+        check_process(&tracker, item, &mut errors);
+    }
+    for item in world.projects.iter() {
+        check_project(&tracker, item, &mut errors);
+    }
     for item in world.events.iter() {
-        for effect in &item.effects {
+        check_event(&tracker, item, &mut errors);
+    }
+    for item in world.tipping_points.iter() {
+        check_tipping_point(&tracker, item, &mut errors);
+    }
+    errors
+}
+
+/// Validate a single process against the given world,
+/// e.g. before inserting it into that world.
+pub fn validate_process(item: &Process, world: &World) -> Vec<String> {
+    let mut errors = vec![];
+    check_process(&IdTracker::new(world), item, &mut errors);
+    errors
+}
+
+/// Validate a single project against the given world,
+/// e.g. before inserting it into that world. It can
+/// refer to itself, as it's in the world once inserted.
+pub fn validate_project(item: &Project, world: &World) -> Vec<String> {
+    let mut errors = vec![];
+    let mut tracker = IdTracker::new(world);
+    tracker.projects.push(item.id);
+    check_project(&tracker, item, &mut errors);
+    errors
+}
+
+/// Validate a single event against the given world,
+/// e.g. before inserting it into that world. It can
+/// refer to itself, as it's in the world once inserted.
+pub fn validate_event(item: &Event, world: &World) -> Vec<String> {
+    let mut errors = vec![];
+    let mut tracker = IdTracker::new(world);
+    tracker.events.push(item.id);
+    check_event(&tracker, item, &mut errors);
+    errors
+}
+
+fn check_npcs(
+    tracker: &IdTracker,
+    name: &str,
+    supporters: &[Id],
+    opposers: &[Id],
+    errors: &mut Vec<String>,
+) {
+    if supporters
+        .iter()
+        .chain(opposers)
+        .any(|id| !tracker.npcs.contains(id))
+    {
+        errors.push(format!("{name} is supported or opposed by a non-existent NPC."));
+    }
+}
+
+fn check_process(
+    tracker: &IdTracker,
+    item: &Process,
+    errors: &mut Vec<String>,
+) {
+    check_npcs(
+        tracker,
+        &format!("Process {:?}", item.name),
+        &item.supporters,
+        &item.opposers,
+        errors,
+    );
+}
+
+fn check_project(
+    tracker: &IdTracker,
+    item: &Project,
+    errors: &mut Vec<String>,
+) {
+    check_npcs(
+        tracker,
+        &format!("Project {:?}", item.name),
+        &item.supporters,
+        &item.opposers,
+        errors,
+    );
+    for effect in &item.effects {
+        if !tracker.check_effect(effect) {
+            errors.push(format!("Project effects of {:?} refers to a non-existent entity.", item.name));
+        }
+    }
+    for outcome in &item.outcomes {
+        for effect in &outcome.effects {
             if !tracker.check_effect(effect) {
-                errors.push(format!("Event {:?} refers to a non-existent entity.", item.name));
+                errors.push(format!("Project outcomes of {:?} refers to a non-existent entity.", item.name));
             }
         }
-        for prob in &item.probabilities {
-            for cond in &prob.conditions {
-                if !tracker.check_condition(cond) {
-                    errors.push(format!("Event conditions for {:?} refers to a non-existent entity.", item.name));
+        for cond in &outcome.probability.conditions {
+            if !tracker.check_condition(cond) {
+                errors.push(format!("Project outcome conditions for {:?} refers to a non-existent entity.", item.name));
+            }
+        }
+    }
+    for upgrade in &item.upgrades {
+        for effect in &upgrade.effects {
+            if !tracker.check_effect(effect) {
+                errors.push(format!("Project upgrades of {:?} refers to a non-existent entity.", item.name));
+            }
+        }
+    }
+}
+
+fn check_event(
+    tracker: &IdTracker,
+    item: &Event,
+    errors: &mut Vec<String>,
+) {
+    for effect in &item.effects {
+        if !tracker.check_effect(effect) {
+            errors.push(format!("Event {:?} refers to a non-existent entity.", item.name));
+        }
+    }
+    if let Some(id) = item.schedule.and_then(|s| s.event_id()) {
+        if !tracker.events.contains(&id) {
+            errors.push(format!("Event {:?} is scheduled after a non-existent event.", item.name));
+        }
+    }
+    for prob in &item.probabilities {
+        for cond in &prob.conditions {
+            if !tracker.check_condition(cond) {
+                errors.push(format!("Event conditions for {:?} refers to a non-existent entity.", item.name));
+            }
+        }
+    }
+    for line in &item.flavor.dialogue.lines {
+        if let Some(DialogueNext::Responses(resps)) = &line.next
+        {
+            for resp in resps {
+                for follow_up in &resp.follow_ups {
+                    let event = follow_up.event;
+                    if !tracker.events.contains(&event) {
+                        errors.push(format!("Event {:?} has a follow-up to a non-existent event.", item.name));
+                    }
                 }
             }
         }
     }
-    errors
+}
+
+fn check_tipping_point(
+    tracker: &IdTracker,
+    item: &TippingPoint,
+    errors: &mut Vec<String>,
+) {
+    for effect in &item.effects {
+        if !tracker.check_effect(effect) {
+            errors.push(format!("Tipping point {:?} refers to a non-existent entity.", item.name));
+        }
+    }
 }
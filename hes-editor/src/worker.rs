@@ -0,0 +1,118 @@
+//! Runs preview simulations in a Web Worker so that
+//! long runs don't freeze the editor.
+//!
+//! The worker is built from `src/bin/preview_worker.rs`,
+//! which just calls [`serve`].
+
+use hes_engine::{Simulation, World, YearReport};
+use js_sys::Uint8Array;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    DedicatedWorkerGlobalScope,
+    MessageEvent,
+    Worker,
+};
+
+/// Where Trunk puts the worker's loader script.
+const WORKER_URL: &str = "./preview_worker.js";
+
+#[derive(Serialize, Deserialize)]
+struct Request {
+    world: World,
+    years: usize,
+}
+
+/// What the worker sends back as the run goes.
+#[derive(Serialize, Deserialize)]
+pub enum Progress {
+    Year(YearReport),
+    Done,
+}
+
+fn encode<T: Serialize>(msg: &T) -> Option<JsValue> {
+    rmp_serde::encode::to_vec_named(msg)
+        .ok()
+        .map(|bytes| Uint8Array::from(bytes.as_slice()).into())
+}
+
+fn decode<T: DeserializeOwned>(ev: &MessageEvent) -> Option<T> {
+    let bytes = Uint8Array::new(&ev.data()).to_vec();
+    rmp_serde::from_slice(&bytes).ok()
+}
+
+/// Handle simulation requests. This runs in the worker.
+pub fn serve() {
+    let scope: DedicatedWorkerGlobalScope =
+        js_sys::global().unchecked_into();
+    let post = {
+        let scope = scope.clone();
+        move |progress: &Progress| {
+            if let Some(msg) = encode(progress) {
+                let _ = scope.post_message(&msg);
+            }
+        }
+    };
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(
+        move |ev: MessageEvent| {
+            let Some(request) = decode::<Request>(&ev) else {
+                return;
+            };
+            let mut sim = Simulation::new(request.world);
+            for _ in 0..request.years {
+                post(&Progress::Year(sim.step()));
+                if sim.state.game_over {
+                    break;
+                }
+            }
+            post(&Progress::Done);
+        },
+    );
+    scope.set_onmessage(Some(
+        on_message.into_js_value().unchecked_ref(),
+    ));
+}
+
+/// A simulation running in a worker. Dropping
+/// it cancels the run.
+pub struct PreviewRun {
+    worker: Worker,
+}
+impl PreviewRun {
+    pub fn start(
+        world: &World,
+        years: usize,
+        mut on_progress: impl FnMut(Progress) + 'static,
+    ) -> Result<Self, JsValue> {
+        let worker = Worker::new(WORKER_URL)?;
+        let on_message =
+            Closure::<dyn FnMut(MessageEvent)>::new(
+                move |ev: MessageEvent| {
+                    if let Some(progress) = decode(&ev) {
+                        on_progress(progress);
+                    }
+                },
+            );
+
+        // Owned by JS from here on, so it lives
+        // as long as the worker does.
+        worker.set_onmessage(Some(
+            on_message.into_js_value().unchecked_ref(),
+        ));
+
+        let request = Request {
+            world: world.clone(),
+            years,
+        };
+        let msg = encode(&request).ok_or_else(|| {
+            JsValue::from_str("Couldn't encode the world.")
+        })?;
+        worker.post_message(&msg)?;
+        Ok(PreviewRun { worker })
+    }
+}
+impl Drop for PreviewRun {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
@@ -1,6 +1,11 @@
 use std::path::PathBuf;
 
-use hes_engine::World;
+use hes_engine::{
+    LoadError,
+    Verification,
+    World,
+    WORLD_VERSION,
+};
 use leptos::*;
 use leptos_toaster::*;
 use leptos_use::{
@@ -9,20 +14,80 @@ use leptos_use::{
     use_event_listener,
 };
 
+use js_sys::Uint8Array;
 use wasm_bindgen::{prelude::*, JsCast};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
     Blob,
     File,
-    FileReader,
     HtmlAnchorElement,
     HtmlInputElement,
     Url,
 };
 
-use crate::{files, validate::validate};
+use crate::{
+    baseline::Baseline,
+    clipboard,
+    files,
+    generate::Generator,
+    i18n,
+    inputs::ReadOnly,
+    recent::{self, RecentWorld},
+    share::{self, Shared},
+    signing,
+    summary::summary_html,
+    t,
+    theme::ThemeSetting,
+    validate::validate,
+};
+
+#[wasm_bindgen(module = "/public/js/files.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = hasFileSystemAccess)]
+    fn has_file_system_access() -> bool;
+
+    #[wasm_bindgen(js_name = pickSaveFile, catch)]
+    async fn pick_save_file(
+        suggested_name: &str,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = pickOpenFile, catch)]
+    async fn pick_open_file() -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = writeFile, catch)]
+    async fn write_file(
+        handle: &JsValue,
+        data: &[u8],
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = readFile, catch)]
+    async fn read_file(
+        handle: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+}
+
+fn js_error(err: JsValue) -> Error {
+    Error::IO(
+        err.as_string().unwrap_or_else(|| format!("{err:?}")),
+    )
+}
+
+/// The file name of a file handle.
+fn handle_name(handle: &JsValue) -> String {
+    js_sys::Reflect::get(handle, &"name".into())
+        .ok()
+        .and_then(|name| name.as_string())
+        .unwrap_or_default()
+}
+
+/// The world's file name without the extension.
+fn file_stem(name: &str) -> Option<String> {
+    PathBuf::from(name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+}
 
-pub async fn pick_and_load_file() -> Option<(String, String)> {
+pub async fn pick_and_load_file() -> Option<(String, Vec<u8>)> {
     let document = window().document().unwrap();
 
     // Create an input element of type 'file'
@@ -80,44 +145,25 @@ pub async fn pick_and_load_file() -> Option<(String, String)> {
     }
 
     let file = result.dyn_into::<File>().unwrap();
-    let file_content = read_file_as_text(&file).await;
+    let file_content = read_file_bytes(&file).await;
 
     Some((file.name(), file_content))
 }
 
-async fn read_file_as_text(file: &File) -> String {
-    let reader = FileReader::new().unwrap();
-
-    let promise = js_sys::Promise::new(&mut |resolve, _| {
-        let reader_clone = reader.clone();
-        let resolve_clone = resolve.clone();
-
-        let closure = Closure::wrap(Box::new(move || {
-            let result = reader_clone.result().unwrap();
-            resolve_clone
-                .call1(&JsValue::NULL, &result)
-                .unwrap();
-        })
-            as Box<dyn FnMut()>);
-
-        reader.set_onloadend(Some(
-            closure.as_ref().unchecked_ref(),
-        ));
-        closure.forget();
-
-        reader.read_as_text(file).unwrap();
-    });
-
-    let result = JsFuture::from(promise).await.unwrap();
-
-    result.as_string().unwrap()
+/// Read a file's contents. Worlds may be JSON or binary,
+/// so they're read as bytes either way.
+async fn read_file_bytes(file: &File) -> Vec<u8> {
+    let buffer = JsFuture::from(file.array_buffer())
+        .await
+        .expect("Failed to read file");
+    Uint8Array::new(&buffer).to_vec()
 }
 
-async fn download(data: &str, filename: &str) {
-    let parts = js_sys::Array::of1(&JsValue::from_str(&data));
-    let blob = Blob::new_with_str_sequence_and_options(
+async fn download(data: &[u8], filename: &str, mime: &str) {
+    let parts = js_sys::Array::of1(&Uint8Array::from(data));
+    let blob = Blob::new_with_u8_array_sequence_and_options(
         &parts,
-        web_sys::BlobPropertyBag::new().type_("text/plain"),
+        web_sys::BlobPropertyBag::new().type_(mime),
     )
     .expect("Failed to create Blob");
     let url = Url::create_object_url_with_blob(&blob)
@@ -150,12 +196,14 @@ async fn download(data: &str, filename: &str) {
 async fn export(
     default_name: &str,
     world: World,
+    binary: bool,
 ) -> Result<Option<String>, Error> {
-    let errors = validate(&world);
-    if !errors.is_empty() {
-        return Err(Error::Validation(errors));
-    }
-
+    let data = world_data(&world, binary)?;
+    let mime = if binary {
+        "application/octet-stream"
+    } else {
+        "text/plain"
+    };
     let result = window()
         .prompt_with_message_and_default(
             "Name Your World",
@@ -164,16 +212,101 @@ async fn export(
         .unwrap();
 
     if let Some(name) = result {
-        let data =
-            serde_json::to_string_pretty(&world).unwrap();
         let name = format!("{name}.world");
-        download(&data, &name).await;
+        download(&data, &name, mime).await;
         Ok(Some(name))
     } else {
         Ok(None)
     }
 }
 
+/// Serialize the world for saving, if it's valid,
+/// either as JSON or in the compact binary format.
+fn world_data(
+    world: &World,
+    binary: bool,
+) -> Result<Vec<u8>, Error> {
+    let errors = validate(world);
+    if !errors.is_empty() {
+        return Err(Error::Validation(errors));
+    }
+    let mut world = world.clone();
+    if signing::sign_exports() {
+        world.sign(&signing::signing_key());
+    }
+    if binary {
+        Ok(world.to_binary())
+    } else {
+        Ok(world.to_export_json().into_bytes())
+    }
+}
+
+/// Overwrite the file the world was opened from or saved to.
+async fn save_to(
+    handle: &JsValue,
+    world: &World,
+) -> Result<(), Error> {
+    let data = world_data(world, false)?;
+    write_file(handle, &data).await.map_err(js_error)?;
+    Ok(())
+}
+
+/// Pick a file to save the world to, returning its name
+/// and handle. If the browser doesn't support the File
+/// System Access API this falls back to a download,
+/// in which case there's no handle.
+async fn save_as(
+    default_name: &str,
+    world: World,
+) -> Result<Option<(String, Option<JsValue>)>, Error> {
+    if !has_file_system_access() {
+        let name = export(default_name, world, false).await?;
+        return Ok(name.map(|name| (name, None)));
+    }
+
+    let data = world_data(&world, false)?;
+    let handle =
+        pick_save_file(&format!("{default_name}.world"))
+            .await
+            .map_err(js_error)?;
+    if handle.is_null() {
+        return Ok(None);
+    }
+    write_file(&handle, &data).await.map_err(js_error)?;
+    Ok(Some((handle_name(&handle), Some(handle))))
+}
+
+/// Pick a world file to open, returning its name, contents,
+/// and handle, if the browser supports the File System
+/// Access API. Otherwise there's no handle.
+async fn open_file(
+) -> Result<Option<(String, Vec<u8>, Option<JsValue>)>, Error> {
+    if !has_file_system_access() {
+        return Ok(pick_and_load_file()
+            .await
+            .map(|(name, data)| (name, data, None)));
+    }
+
+    let handle = pick_open_file().await.map_err(js_error)?;
+    if handle.is_null() {
+        return Ok(None);
+    }
+    let data = read_file(&handle).await.map_err(js_error)?;
+    Ok(Some((
+        handle_name(&handle),
+        Uint8Array::new(&data).to_vec(),
+        Some(handle),
+    )))
+}
+
+fn load_error(err: LoadError) -> Error {
+    let msg = match err {
+        LoadError::Version(_) => err.to_string(),
+        _ => "Failed to parse world from file. Are you sure it's valid?".into(),
+    };
+    Error::IO(msg)
+}
+
 async fn confirm_lose_changes() -> bool {
     let msg = "Any unsaved changes to the current world will be lost. Continue?";
     crate::confirm(msg).await
@@ -236,7 +369,7 @@ pub fn WorldsMenu(world: RwSignal<World>) -> impl IntoView {
                         }>"✗"</div>
                     </div>
                     <div class="toast-body">
-                        <h3>Error</h3>
+                        <h3>{t!("Error")}</h3>
                         <div>{inner}</div>
                     </div>
                 </div>
@@ -251,6 +384,11 @@ pub fn WorldsMenu(world: RwSignal<World>) -> impl IntoView {
     };
 
     let open = create_rw_signal(false);
+    let sign_exports = create_rw_signal(signing::sign_exports());
+    let read_only = expect_context::<ReadOnly>().0;
+    let baseline = expect_context::<Baseline>().0;
+    let theme = expect_context::<ThemeSetting>().0;
+    let comparing = move || with!(|baseline| baseline.is_some());
 
     let target = create_node_ref::<html::Div>();
     let _ = on_click_outside(target, move |_| {
@@ -259,13 +397,201 @@ pub fn WorldsMenu(world: RwSignal<World>) -> impl IntoView {
         }
     });
 
+    let default_name = format!(
+        "world-{}",
+        (js_sys::Math::random() * usize::MAX as f64).round()
+            as usize
+    );
+    let last_name = create_rw_signal(default_name);
+
+    // The file the world was opened from or last saved to,
+    // if the browser supports the File System Access API.
+    let file_handle = store_value(None::<JsValue>);
+
+    // The world as of when it was last opened or saved,
+    // to tell if there are unsaved changes.
+    let saved = store_value(world.get_untracked());
+    let unsaved = move || {
+        saved.with_value(|saved| {
+            world.with_untracked(|world| world != saved)
+        })
+    };
+
+    // Add the world to the recent worlds, under its
+    // current name.
+    let remember = move |w: World, handle: Option<JsValue>| {
+        let name = last_name.get_untracked();
+        spawn_local(async move {
+            let result =
+                recent::remember(&name, &w, handle).await;
+            if let Err(err) = result {
+                log::warn!("Failed to remember world: {err:?}");
+            }
+        });
+    };
+
+    // Switch to a world opened from a file.
+    let open_world =
+        move |w: World, name: &str, handle: Option<JsValue>| {
+            if let Some(stem) = file_stem(name) {
+                last_name.set(stem);
+            }
+            file_handle.set_value(handle.clone());
+            saved.set_value(w.clone());
+            remember(w.clone(), handle);
+            world.set(w);
+        };
+
+    // Let the user know if an older world was upgraded,
+    // or otherwise if it doesn't match its hash. Upgraded
+    // worlds can't be checked against their hash as
+    // upgrading changes them.
+    let report_loaded = move |w: &World, notes: Vec<String>| {
+        if !notes.is_empty() {
+            notice_toast(
+                format!("Upgraded to version {WORLD_VERSION}"),
+                notes.join(" "),
+            );
+            return;
+        }
+        match w.verify() {
+            Verification::Unhashed => (),
+            Verification::Valid { hash, signer } => {
+                let details = match signer {
+                    Some(key) => format!(
+                        "Hash {}, signed by {}.",
+                        signing::short(&hash),
+                        signing::short(&key)
+                    ),
+                    None => format!(
+                        "Hash {}.",
+                        signing::short(&hash)
+                    ),
+                };
+                notice_toast("Verified".into(), details);
+            }
+            Verification::Corrupted { expected, actual } => {
+                error_toast(Error::IO(format!(
+                    "This world doesn't match its hash, so it may be corrupted or have been edited outside of the editor. Expected {}, got {}.",
+                    signing::short(&expected),
+                    signing::short(&actual)
+                )));
+            }
+            Verification::BadSignature { .. } => {
+                error_toast(Error::IO(
+                    "This world's signature is invalid, so it may have been tampered with.".into(),
+                ));
+            }
+        }
+    };
+
+    let save = move |new_file: bool| {
+        spawn_local(async move {
+            let world = world.get_untracked();
+            let snapshot = world.clone();
+            let result = match file_handle.get_value() {
+                Some(handle) if !new_file => {
+                    save_to(&handle, &world).await.map(|_| {
+                        Some((handle_name(&handle), Some(handle)))
+                    })
+                }
+                _ => {
+                    save_as(&last_name.get_untracked(), world)
+                        .await
+                }
+            };
+            match result {
+                Ok(None) => (),
+                Ok(Some((name, handle))) => {
+                    if let Some(stem) = file_stem(&name) {
+                        last_name.set(stem);
+                    }
+                    remember(snapshot.clone(), handle.clone());
+                    saved.set_value(snapshot);
+                    if handle.is_some() {
+                        file_handle.set_value(handle);
+                        notice_toast(name, "Saved.".into());
+                    } else {
+                        notice_toast(
+                            name,
+                            "Successfully exported.".into(),
+                        );
+                    }
+                }
+                Err(err) => error_toast(err),
+            }
+        });
+    };
+
+    let export_as = move |binary: bool| {
+        spawn_local(async move {
+            let snapshot = world.get_untracked();
+            let name = last_name.get_untracked();
+            match export(&name, snapshot.clone(), binary).await {
+                Ok(None) => (),
+                Ok(Some(name)) => {
+                    if let Some(stem) = file_stem(&name) {
+                        last_name.set(stem);
+                    }
+                    remember(snapshot.clone(), None);
+                    saved.set_value(snapshot);
+                    notice_toast(
+                        name,
+                        "Successfully exported.".into(),
+                    );
+                }
+                Err(err) => error_toast(err),
+            }
+        });
+        open.set(false);
+    };
+
+    let generating = create_rw_signal(false);
+    let generate = move |generated: World| {
+        spawn_local(async move {
+            if confirm_lose_changes().await {
+                file_handle.set_value(None);
+                saved.set_value(World::default());
+                world.set(generated);
+            }
+        });
+    };
+
+    let copy_link = move || {
+        let shared = Shared::World(Box::new(world.get_untracked()));
+        match share::share_link(&shared) {
+            Ok(link) => spawn_local(async move {
+                match clipboard::copy_text(&link).await {
+                    Ok(_) => notice_toast(
+                        "Link copied".into(),
+                        "Anyone with the link can open this world in the editor.".into(),
+                    ),
+                    Err(err) => error_toast(Error::IO(err)),
+                }
+            }),
+            Err(err) => error_toast(Error::IO(err.to_string())),
+        }
+        open.set(false);
+    };
+
     let _ = use_event_listener(
         use_document(),
         ev::keydown,
         move |ev| {
-            if ev.key() == "s" && ev.ctrl_key() {
+            if ev.key().eq_ignore_ascii_case("s")
+                && ev.ctrl_key()
+            {
                 ev.prevent_default();
-                match files::save_session(&world.get()) {
+                let saved = files::save_session(&world.get());
+
+                // Save to the world's file if possible,
+                // rather than downloading it every time.
+                if has_file_system_access() {
+                    save(ev.shift_key());
+                    return;
+                }
+
+                match saved {
                     Ok(_) => {
                         notice_toast(
                             "Session saved".into(),
@@ -284,42 +610,180 @@ pub fn WorldsMenu(world: RwSignal<World>) -> impl IntoView {
         },
     );
 
-    let default_name = format!(
-        "world-{}",
-        (js_sys::Math::random() * usize::MAX as f64).round()
-            as usize
-    );
-    let last_name = create_rw_signal(default_name);
-
     let load_action = create_action(move |_: &()| async move {
-        if let Some((name, data)) = pick_and_load_file().await {
-            if let Ok(w) = serde_json::from_str::<World>(&data)
-            {
-                let as_path = PathBuf::from(name);
-                if let Some(name) = as_path.file_stem() {
-                    last_name.set(
-                        name.to_string_lossy().to_string(),
-                    );
+        match open_file().await {
+            Ok(Some((name, data, handle))) => {
+                match World::load(&data) {
+                    Ok((w, notes)) => {
+                        report_loaded(&w, notes);
+                        return Some((w, name, handle));
+                    }
+                    Err(err) => error_toast(load_error(err)),
                 }
-                return Some(w);
-            } else {
-                error_toast(Error::IO("Failed to parse world from file. Are you sure it's valid?".to_string()));
             }
-        } else {
-            error_toast(Error::IO(
-                "Failed to read file.".to_string(),
-            ));
+            Ok(None) => (),
+            Err(err) => error_toast(err),
         }
         None
     });
     let value = load_action.value();
     create_effect(move |_| {
-        if let Some(w) = value.get().flatten() {
-            world.set(w);
+        if let Some((w, name, handle)) = value.get().flatten() {
+            open_world(w, &name, handle);
         }
     });
 
+    // Open worlds dropped anywhere on the editor.
+    let dragging = create_rw_signal(false);
+    let _ = use_event_listener(
+        use_document(),
+        ev::dragover,
+        move |ev| {
+            ev.prevent_default();
+            dragging.set(true);
+        },
+    );
+    let _ = use_event_listener(
+        use_document(),
+        ev::dragleave,
+        move |ev| {
+            // Only when leaving the window.
+            if ev.related_target().is_none() {
+                dragging.set(false);
+            }
+        },
+    );
+    let _ = use_event_listener(use_document(), ev::drop, move |ev| {
+        ev.prevent_default();
+        dragging.set(false);
+        let Some(file) = ev
+            .data_transfer()
+            .and_then(|dt| dt.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+        if !file.name().ends_with(".world") {
+            error_toast(Error::IO(
+                "Only .world files can be opened.".to_string(),
+            ));
+            return;
+        }
+        spawn_local(async move {
+            if unsaved() && !confirm_lose_changes().await {
+                return;
+            }
+            let data = read_file_bytes(&file).await;
+            match World::load(&data) {
+                Ok((w, notes)) => {
+                    report_loaded(&w, notes);
+                    open_world(w, &file.name(), None);
+                }
+                Err(err) => error_toast(load_error(err)),
+            }
+        });
+    });
+
+    // Recent worlds are shown when the editor starts,
+    // if there are any, or when picked from the menu.
+    let show_recent = create_rw_signal(true);
+    let recent_from_menu = create_rw_signal(false);
+    let recent_worlds =
+        create_local_resource(show_recent, |show| async move {
+            if show {
+                recent::recent_worlds().await
+            } else {
+                vec![]
+            }
+        });
+    let recent_visible = move || {
+        let any = recent_worlds
+            .with(|list| !list.is_empty())
+            .unwrap_or(false);
+        show_recent.get() && (recent_from_menu.get() || any)
+    };
+    let close_recent = move || {
+        show_recent.set(false);
+        recent_from_menu.set(false);
+    };
+    let reopen = move |name: String| {
+        spawn_local(async move {
+            if unsaved() && !confirm_lose_changes().await {
+                return;
+            }
+            match recent::reopen(&name).await {
+                Ok((w, handle)) => {
+                    open_world(w, &name, handle);
+                    close_recent();
+                }
+                Err(err) => error_toast(Error::IO(err)),
+            }
+        });
+    };
+    let forget = move |name: String| {
+        spawn_local(async move {
+            if let Err(err) = recent::forget(&name).await {
+                log::warn!("Failed to forget world: {err:?}");
+            }
+            recent_worlds.refetch();
+        });
+    };
+    let recent_list = move || {
+        let list = recent_worlds.get().unwrap_or_default();
+        if list.is_empty() {
+            return view! {
+                <div class="empty">{t!("No recent worlds.")}</div>
+            }
+            .into_view();
+        }
+        list.into_iter()
+            .map(|RecentWorld { name, opened, thumbnail }| {
+                let opened = js_sys::Date::new(&opened.into())
+                    .to_locale_string("default", &JsValue::UNDEFINED)
+                    .as_string()
+                    .unwrap_or_default();
+                let to_open = name.clone();
+                let to_forget = name.clone();
+                view! {
+                    <div class="recent-world" on:click=move |_| reopen(to_open.clone())>
+                        {match thumbnail {
+                            Some(src) => view! { <img src=src /> }.into_view(),
+                            None => view! { <div class="image-placeholder" /> }.into_view(),
+                        }}
+                        <div class="recent-world-info">
+                            <div class="recent-world-name">{name}</div>
+                            <div class="recent-world-opened">{opened}</div>
+                        </div>
+                        <div class="recent-world-remove"
+                            title=t!("Remove from recent worlds.")
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                forget(to_forget.clone());
+                            }>"✗"</div>
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
     view! {
+        <Show when=recent_visible>
+            <div class="recent-worlds-overlay">
+                <div class="recent-worlds">
+                    <h2>{t!("Recent Worlds")}</h2>
+                    <div class="recent-worlds-list">{recent_list}</div>
+                    <button on:click=move |_| close_recent()>
+                        {t!("Continue with Current World")}
+                    </button>
+                </div>
+            </div>
+        </Show>
+        <Show when=move || generating.get()>
+            <Generator open=generating on_generate=generate />
+        </Show>
+        <Show when=move || dragging.get()>
+            <div class="drop-overlay">{t!("Drop a .world file to open it")}</div>
+        </Show>
         <div class="worlds-menu" ref=target>
             <div class="worlds-menu-open"
                 on:click=move |_| {
@@ -330,35 +794,128 @@ pub fn WorldsMenu(world: RwSignal<World>) -> impl IntoView {
                 <div on:click=move |_| {
                     spawn_local(async move {
                         if confirm_lose_changes().await {
+                            file_handle.set_value(None);
+                            saved.set_value(World::default());
                             world.set(World::default());
                         }
                     });
                     open.set(false);
-                }>"New"</div>
+                }>{t!("New")}</div>
+
+                <div
+                    title=t!("Scaffold a new world from a few settings.")
+                    on:click=move |_| {
+                        generating.set(true);
+                        open.set(false);
+                    }>{t!("Generate")}</div>
 
                 <div on:click=move |_| {
                     load_action.dispatch(());
                     open.set(false);
-                }>"Import"</div>
-
-                 <div on:click=move |_| {
-                     spawn_local(async move {
-                         match export(&last_name.get_untracked(), world.get_untracked()).await {
-                             Ok(None) => (),
-                             Ok(Some(name)) => {
-                                 let as_path = PathBuf::from(name.clone());
-                                 if let Some(name) = as_path.file_stem() {
-                                     last_name.set(
-                                         name.to_string_lossy().to_string(),
-                                     );
-                                 }
-                                 notice_toast(name, "Successfully exported.".into());
-                             }
-                             Err(err) => error_toast(err),
-                         }
-                     });
-                     open.set(false);
-                 }>"Export"</div>
+                }>{t!("Open")}</div>
+
+                <div on:click=move |_| {
+                    recent_from_menu.set(true);
+                    show_recent.set(true);
+                    open.set(false);
+                }>{t!("Recent Worlds")}</div>
+
+                <div
+                    title="Ctrl+S"
+                    on:click=move |_| {
+                        save(false);
+                        open.set(false);
+                    }>{t!("Save")}</div>
+
+                <div
+                    title="Ctrl+Shift+S"
+                    on:click=move |_| {
+                        save(true);
+                        open.set(false);
+                    }>{t!("Save As")}</div>
+
+                <div on:click=move |_| export_as(false)>{t!("Export")}</div>
+
+                <div
+                    title=t!("A standalone page with every entity's text, images, and key numbers, for reviewing or printing.")
+                    on:click=move |_| {
+                        let name = last_name.get_untracked();
+                        let html = with!(|world| summary_html(world, &name));
+                        spawn_local(async move {
+                            download(html.as_bytes(), &format!("{name}.html"), "text/html").await;
+                        });
+                        open.set(false);
+                    }>{t!("Export Summary")}</div>
+
+                <div
+                    title=t!("Copy a link that opens this world in the editor. Only for small worlds.")
+                    on:click=move |_| copy_link()>{t!("Copy Shareable Link")}</div>
+
+                <div
+                    title=t!("A smaller file that's faster to load, but can't be read or diffed as text.")
+                    on:click=move |_| export_as(true)>{t!("Export Binary")}</div>
+
+                <div
+                    title=move || format!("Sign saved and exported worlds with your key ({}), so players can tell they're from you.", signing::short(&signing::public_key()))
+                    on:click=move |_| {
+                        sign_exports.set(!sign_exports.get());
+                        signing::set_sign_exports(sign_exports.get());
+                        open.set(false);
+                    }>{move || if sign_exports.get() {
+                    t!("Stop Signing Exports")
+                } else {
+                    t!("Sign Exports")
+                }}</div>
+
+
+                <div on:click=move |_| {
+                    update!(|read_only| *read_only = !*read_only);
+                    open.set(false);
+                }>{move || if read_only.get() {
+                    t!("Exit Review Mode")
+                } else {
+                    t!("Review Mode")
+                }}</div>
+
+                <div
+                    title=t!("Mark fields that differ from the base world.")
+                    on:click=move |_| {
+                        if comparing() {
+                            baseline.set(None);
+                        } else {
+                            baseline.set(Some(World::default()));
+                        }
+                        open.set(false);
+                    }>{move || if comparing() {
+                    t!("Stop Comparing")
+                } else {
+                    t!("Compare to Base World")
+                }}</div>
+
+                <div class="menu-language"
+                    title=t!("The language of the editor itself. Changing it reloads the editor, keeping your session.")>
+                    {t!("Language")}
+                    <select on:change=move |ev| {
+                        i18n::set_language(&event_target_value(&ev));
+                        if let Err(err) = files::save_session(&world.get_untracked()) {
+                            error_toast(Error::IO(err.to_string()));
+                            return;
+                        }
+                        let _ = window().location().reload();
+                    }>
+                        {i18n::AVAILABLE_LANGUAGES.iter().map(|lang| view! {
+                            <option value=*lang selected=*lang == i18n::preferred_language()>
+                                {lang.to_uppercase()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+
+                <div
+                    title=t!("Switch between the dark, light, and high-contrast themes.")
+                    on:click=move |_| update!(|theme| *theme = theme.next())>
+                    {move || t!("Theme: {theme}", theme: t!(&theme.get().to_string()))}
+                </div>
             </div>
         </div>
     }
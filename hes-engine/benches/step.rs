@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use hes_engine::{Batch, Simulation, State, World};
+
+/// Seeded so that every iteration simulates the same years.
+const SEED: u64 = 0;
+
+fn step_year(c: &mut Criterion) {
+    let state = State::with_seed(World::default(), SEED);
+    c.bench_function("step_year", |b| {
+        b.iter_batched_ref(
+            || state.clone(),
+            |state| state.step_year(state.world.temperature),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn simulate(c: &mut Criterion) {
+    let sim = || Simulation::with_seed(World::default(), SEED);
+    c.bench_function("simulate_10_years", |b| {
+        b.iter_batched_ref(
+            sim,
+            |sim| sim.run(10),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn new_game(c: &mut Criterion) {
+    let world = World::default();
+    c.bench_function("new_game", |b| {
+        b.iter(|| State::with_seed(world.clone(), SEED))
+    });
+}
+
+fn batch(c: &mut Criterion) {
+    let batch = Batch::new(World::default(), 10);
+    let mut group = c.benchmark_group("batch");
+    group.sample_size(10);
+    group.bench_function("batch_8_runs", |b| b.iter(|| batch.run(0..8)));
+    group.finish();
+}
+
+criterion_group!(benches, step_year, simulate, new_game, batch);
+criterion_main!(benches);
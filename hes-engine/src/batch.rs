@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    par,
+    replay::{Decision, Replay},
+    world::World,
+};
+
+/// Runs a world many times with different seeds, to see how
+/// much its outcomes depend on luck (i.e. which events
+/// happen and how projects' outcomes roll).
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub world: World,
+
+    /// Decisions to make in every run,
+    /// each with the year to make it in.
+    pub decisions: Vec<(usize, Decision)>,
+
+    /// How many years to run each run for,
+    /// unless the game ends first.
+    pub years: usize,
+}
+impl Batch {
+    pub fn new(world: World, years: usize) -> Self {
+        Batch {
+            world,
+            decisions: vec![],
+            years,
+        }
+    }
+
+    /// Run once per seed and summarize the outcomes.
+    pub fn run(
+        &self,
+        seeds: impl IntoIterator<Item = u64>,
+    ) -> BatchSummary {
+        let seeds: Vec<u64> = seeds.into_iter().collect();
+        let runs =
+            par::map(&seeds, |seed| self.run_seed(*seed));
+        BatchSummary::new(runs)
+    }
+
+    fn run_seed(&self, seed: u64) -> RunOutcome {
+        let replay = Replay {
+            world: self.world.clone(),
+            seed,
+            decisions: self.decisions.clone(),
+            tgavs: vec![],
+        };
+        let (state, _) = replay.run(self.years);
+        RunOutcome {
+            seed,
+            year: state.world.year,
+            temperature: state.world.temperature,
+            extinction_rate: state.world.extinction_rate,
+            emissions: state.emissions.as_gtco2eq(),
+            outlook: state.outlook(),
+
+            // The game also ends when the player's
+            // time is up, which isn't a collapse.
+            collapsed: state.game_over
+                && state.world.year < state.death_year,
+        }
+    }
+}
+
+/// How a single run ended up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunOutcome {
+    pub seed: u64,
+
+    /// The year the run ended in.
+    pub year: usize,
+
+    /// Temperature anomaly, in C.
+    pub temperature: f32,
+    pub extinction_rate: f32,
+
+    /// Emissions, in GtCO2eq.
+    pub emissions: f32,
+
+    /// Contentedness.
+    pub outlook: f32,
+
+    /// If the player was ousted before their time was up.
+    pub collapsed: bool,
+}
+
+/// The spread of an outcome across runs.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+pub struct Distribution {
+    pub mean: f32,
+    pub min: f32,
+    pub p10: f32,
+    pub median: f32,
+    pub p90: f32,
+    pub max: f32,
+}
+impl Distribution {
+    pub fn of(values: impl IntoIterator<Item = f32>) -> Self {
+        let mut values: Vec<f32> = values.into_iter().collect();
+        if values.is_empty() {
+            return Distribution::default();
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mean =
+            values.iter().sum::<f32>() / values.len() as f32;
+        Distribution {
+            mean,
+            min: values[0],
+            p10: percentile(&values, 0.1),
+            median: percentile(&values, 0.5),
+            p90: percentile(&values, 0.9),
+            max: values[values.len() - 1],
+        }
+    }
+}
+
+/// The value at the given percentile (0-1) of sorted
+/// values, interpolating between the closest two.
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let rank = p * (sorted.len() - 1) as f32;
+    let (lower, upper) =
+        (rank.floor() as usize, rank.ceil() as usize);
+    let t = rank - lower as f32;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * t
+}
+
+/// Outcomes across all of a batch's runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub temperature: Distribution,
+    pub extinction_rate: Distribution,
+    pub emissions: Distribution,
+    pub outlook: Distribution,
+
+    /// The fraction of runs that collapsed.
+    pub collapse_rate: f32,
+
+    pub runs: Vec<RunOutcome>,
+}
+impl BatchSummary {
+    fn new(runs: Vec<RunOutcome>) -> Self {
+        let dist = |f: fn(&RunOutcome) -> f32| {
+            Distribution::of(runs.iter().map(f))
+        };
+        let collapsed =
+            runs.iter().filter(|run| run.collapsed).count();
+        BatchSummary {
+            temperature: dist(|run| run.temperature),
+            extinction_rate: dist(|run| run.extinction_rate),
+            emissions: dist(|run| run.emissions),
+            outlook: dist(|run| run.outlook),
+            collapse_rate: collapsed as f32
+                / runs.len().max(1) as f32,
+            runs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_distribution() {
+        let dist = Distribution::of([
+            5., 1., 4., 2., 3., 6., 7., 8., 9., 10., 11.,
+        ]);
+        assert_eq!(dist.min, 1.);
+        assert_eq!(dist.max, 11.);
+        assert_eq!(dist.mean, 6.);
+        assert_eq!(dist.median, 6.);
+        assert_eq!(dist.p10, 2.);
+        assert_eq!(dist.p90, 10.);
+        assert_eq!(
+            Distribution::of([]),
+            Distribution::default()
+        );
+    }
+
+    #[test]
+    fn test_batch() {
+        let batch = Batch::new(World::default(), 10);
+        let summary = batch.run(0..4);
+        assert_eq!(summary.runs.len(), 4);
+        assert!(summary.temperature.min > 0.);
+        assert!(
+            summary.temperature.min
+                <= summary.temperature.median
+        );
+        assert!(
+            summary.temperature.median
+                <= summary.temperature.max
+        );
+        assert!((0. ..=1.).contains(&summary.collapse_rate));
+
+        // Runs are reproducible by their seeds.
+        assert_eq!(batch.run([2]).runs[0], summary.runs[2]);
+    }
+}
@@ -0,0 +1,29 @@
+/// The valid range of a bounded quantity, along with
+/// a sensible step size for adjusting it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+impl Bounds {
+    pub const fn new(min: f32, max: f32, step: f32) -> Self {
+        Bounds { min, max, step }
+    }
+
+    /// A fraction from 0 to 1, e.g. a probability or a share.
+    pub const FRACTION: Bounds = Bounds::new(0., 1., 0.01);
+
+    /// An amount that can't be negative, e.g.
+    /// a resource amount or a population.
+    pub const NON_NEGATIVE: Bounds =
+        Bounds::new(0., f32::INFINITY, 1.);
+
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.min && value <= self.max
+    }
+
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.clamp(self.min, self.max)
+    }
+}
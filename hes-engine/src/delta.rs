@@ -0,0 +1,357 @@
+//! Compact, structured changes between steps, e.g. so that
+//! the UI or a networked client only has to update what
+//! changed rather than cloning and comparing whole states.
+//! Unlike [`crate::Diff`], which describes changes for
+//! people to read, deltas are meant to be consumed by code.
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{
+    events::{
+        Flag,
+        LocalVariable,
+        PlayerVariable,
+        RegionFlag,
+        WorldVariable,
+    },
+    projects::Status,
+    regions::Income,
+    state::State,
+    Id,
+};
+
+/// The values a [`Delta`] is taken against, captured
+/// before a step. Much smaller than the state itself,
+/// as it only has the values that change between steps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    game_over: bool,
+    world: Vec<f32>,
+    player: Vec<f32>,
+    flags: Vec<Flag>,
+
+    /// How many events had been applied.
+    events: usize,
+
+    regions: Vec<RegionValues>,
+    projects: Vec<ProjectValues>,
+    processes: Vec<ProcessValues>,
+    npcs: Vec<(Id, f32)>,
+    variables: Vec<(Id, f32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RegionValues {
+    id: Id,
+    variables: Vec<f32>,
+    income: Income,
+    development: f32,
+    seceded: bool,
+    flags: Vec<RegionFlag>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ProjectValues {
+    id: Id,
+    status: Status,
+    level: usize,
+    progress: f32,
+    locked: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ProcessValues {
+    id: Id,
+    mix_share: usize,
+    locked: bool,
+}
+
+/// What changed between a [`Checkpoint`] and the state.
+/// Values are the new ones, and are only included
+/// if they changed.
+#[derive(
+    Debug, Clone, PartialEq, Default, Serialize, Deserialize,
+)]
+pub struct Delta {
+    /// If the game ended since the checkpoint.
+    pub game_over: bool,
+
+    pub world: Vec<(WorldVariable, f32)>,
+    pub player: Vec<(PlayerVariable, f32)>,
+    pub added_flags: Vec<Flag>,
+    pub removed_flags: Vec<Flag>,
+
+    /// Events that were applied since the
+    /// checkpoint, in the order they were applied.
+    pub events: Vec<Id>,
+
+    pub regions: Vec<RegionDelta>,
+    pub projects: Vec<ProjectDelta>,
+    pub processes: Vec<ProcessDelta>,
+
+    /// NPCs' new relationships with the player.
+    pub npcs: Vec<(Id, f32)>,
+
+    /// The world's variables' new values.
+    pub variables: Vec<(Id, f32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionDelta {
+    pub id: Id,
+    pub variables: Vec<(LocalVariable, f32)>,
+    pub income: Option<Income>,
+    pub development: Option<f32>,
+    pub seceded: Option<bool>,
+    pub added_flags: Vec<RegionFlag>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectDelta {
+    pub id: Id,
+    pub status: Option<Status>,
+    pub level: Option<usize>,
+    pub progress: Option<f32>,
+    pub locked: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessDelta {
+    pub id: Id,
+    pub mix_share: Option<usize>,
+    pub locked: Option<bool>,
+}
+
+impl Delta {
+    pub fn is_empty(&self) -> bool {
+        *self == Delta::default()
+    }
+}
+
+impl State {
+    /// Capture the values to take a [`Delta`] against,
+    /// e.g. before stepping a year.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            game_over: self.game_over,
+            world: WorldVariable::iter()
+                .map(|var| var.value(self))
+                .collect(),
+            player: PlayerVariable::iter()
+                .map(|var| var.value(self))
+                .collect(),
+            flags: self.flags.clone(),
+            events: self.events.len(),
+            regions: self
+                .world
+                .regions
+                .iter()
+                .map(|region| RegionValues {
+                    id: region.id,
+                    variables: LocalVariable::iter()
+                        .map(|var| var.value(region))
+                        .collect(),
+                    income: region.income,
+                    development: region.development,
+                    seceded: region.seceded,
+                    flags: region.flags.clone(),
+                })
+                .collect(),
+            projects: self
+                .world
+                .projects
+                .iter()
+                .map(|project| ProjectValues {
+                    id: project.id,
+                    status: project.status,
+                    level: project.level,
+                    progress: project.progress,
+                    locked: project.locked,
+                })
+                .collect(),
+            processes: self
+                .world
+                .processes
+                .iter()
+                .map(|process| ProcessValues {
+                    id: process.id,
+                    mix_share: process.mix_share,
+                    locked: process.locked,
+                })
+                .collect(),
+            npcs: self
+                .npcs
+                .iter()
+                .map(|npc| (npc.id, npc.relationship))
+                .collect(),
+            variables: self
+                .world
+                .variables
+                .iter()
+                .map(|var| (var.id, var.value))
+                .collect(),
+        }
+    }
+
+    /// What's changed since the checkpoint.
+    pub fn delta(&self, since: &Checkpoint) -> Delta {
+        let now = self.checkpoint();
+        Delta {
+            game_over: now.game_over && !since.game_over,
+            world: changed_vars(
+                WorldVariable::iter(),
+                &since.world,
+                &now.world,
+            ),
+            player: changed_vars(
+                PlayerVariable::iter(),
+                &since.player,
+                &now.player,
+            ),
+            added_flags: missing(&now.flags, &since.flags),
+            removed_flags: missing(&since.flags, &now.flags),
+            events: self
+                .events
+                .iter()
+                .skip(since.events)
+                .map(|event| event.id)
+                .collect(),
+            regions: since
+                .regions
+                .iter()
+                .zip(&now.regions)
+                .filter(|(a, b)| a != b)
+                .map(|(a, b)| RegionDelta {
+                    id: b.id,
+                    variables: changed_vars(
+                        LocalVariable::iter(),
+                        &a.variables,
+                        &b.variables,
+                    ),
+                    income: changed(a.income, b.income),
+                    development: changed(
+                        a.development,
+                        b.development,
+                    ),
+                    seceded: changed(a.seceded, b.seceded),
+                    added_flags: missing(&b.flags, &a.flags),
+                })
+                .collect(),
+            projects: since
+                .projects
+                .iter()
+                .zip(&now.projects)
+                .filter(|(a, b)| a != b)
+                .map(|(a, b)| ProjectDelta {
+                    id: b.id,
+                    status: changed(a.status, b.status),
+                    level: changed(a.level, b.level),
+                    progress: changed(a.progress, b.progress),
+                    locked: changed(a.locked, b.locked),
+                })
+                .collect(),
+            processes: since
+                .processes
+                .iter()
+                .zip(&now.processes)
+                .filter(|(a, b)| a != b)
+                .map(|(a, b)| ProcessDelta {
+                    id: b.id,
+                    mix_share: changed(
+                        a.mix_share,
+                        b.mix_share,
+                    ),
+                    locked: changed(a.locked, b.locked),
+                })
+                .collect(),
+            npcs: since
+                .npcs
+                .iter()
+                .zip(&now.npcs)
+                .filter(|(a, b)| a != b)
+                .map(|(_, b)| *b)
+                .collect(),
+            variables: since
+                .variables
+                .iter()
+                .zip(&now.variables)
+                .filter(|(a, b)| a != b)
+                .map(|(_, b)| *b)
+                .collect(),
+        }
+    }
+}
+
+fn changed<T: PartialEq>(before: T, after: T) -> Option<T> {
+    (before != after).then_some(after)
+}
+
+/// The variables whose values changed, with their new values.
+fn changed_vars<V>(
+    vars: impl Iterator<Item = V>,
+    before: &[f32],
+    after: &[f32],
+) -> Vec<(V, f32)> {
+    vars.zip(before.iter().zip(after))
+        .filter(|(_, (a, b))| a != b)
+        .map(|(var, (_, b))| (var, *b))
+        .collect()
+}
+
+/// Items in `a` that aren't in `b`.
+fn missing<T: PartialEq + Copy>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().filter(|item| !b.contains(item)).copied().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::world::World;
+
+    #[test]
+    fn test_delta() {
+        let mut state = State::with_seed(World::default(), 0);
+        let checkpoint = state.checkpoint();
+        assert!(state.delta(&checkpoint).is_empty());
+
+        let process = state.world.processes.first().id;
+        state.change_process_mix_share(&process, 1);
+        state.flags.push(Flag::EcosystemModeling);
+        state.political_capital += 10;
+        let delta = state.delta(&checkpoint);
+        assert_eq!(
+            delta.added_flags,
+            vec![Flag::EcosystemModeling]
+        );
+        assert_eq!(
+            delta.player,
+            vec![(
+                PlayerVariable::PoliticalCapital,
+                state.political_capital as f32
+            )]
+        );
+        assert_eq!(delta.processes.len(), 1);
+        assert_eq!(delta.processes[0].id, process);
+        assert_eq!(delta.processes[0].locked, None);
+        assert!(delta.regions.is_empty());
+        assert!(delta.events.is_empty());
+
+        let checkpoint = state.checkpoint();
+        state.step_year(state.world.temperature);
+        let event = state.event_pool.events.first().id;
+        state.apply_event(event, None);
+        let delta = state.delta(&checkpoint);
+        assert!(delta.world.contains(&(
+            WorldVariable::Year,
+            state.world.year as f32
+        )));
+        assert!(!delta.regions.is_empty());
+        assert_eq!(delta.events, vec![event]);
+
+        // Deltas are much smaller than the state.
+        let delta = serde_json::to_vec(&delta).unwrap();
+        let state = serde_json::to_vec(&state).unwrap();
+        assert!(delta.len() * 10 < state.len());
+    }
+}
@@ -0,0 +1,348 @@
+//! Population demographics. Each region's population is
+//! split into children, adults and the elderly. Adults have
+//! children, children grow up and adults grow old, and each
+//! cohort dies at its own rate. Birth and death rates fall
+//! as regions develop, and dirty air kills the young and
+//! old more than adults. Since only adults have children,
+//! changes to births, e.g. from family planning, take a
+//! generation to fully show in the population. Children
+//! consume less than adults, so demand depends on a
+//! region's age structure as well as its size.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    kinds::{Cohort, CohortMap, KindMap},
+    regions::{Income, Region},
+};
+
+/// How many years people are children for.
+const CHILDHOOD: f32 = 15.;
+
+/// How many years people are adults for.
+const ADULTHOOD: f32 = 50.;
+
+/// Birth and death rates for an income level.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct Demographics {
+    /// Children born each year per adult.
+    pub fertility: f32,
+
+    /// The share of each cohort that dies each year.
+    pub mortality: CohortMap,
+}
+impl Demographics {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            fertility: (other.fertility - self.fertility) * t
+                + self.fertility,
+            mortality: (other.mortality - self.mortality) * t
+                + self.mortality,
+        }
+    }
+}
+
+pub(crate) fn default_demographics() -> [Demographics; 4] {
+    let demographics =
+        |fertility, children, adults, elderly| Demographics {
+            fertility,
+            mortality: CohortMap {
+                children,
+                adults,
+                elderly,
+            },
+        };
+    [
+        demographics(0.05, 0.004, 0.007, 0.05),
+        demographics(0.034, 0.004, 0.005, 0.045),
+        demographics(0.022, 0.003, 0.0035, 0.042),
+        demographics(0.022, 0.0015, 0.004, 0.04),
+    ]
+}
+
+/// How much each cohort consumes, relative to adults.
+const CONSUMPTION: CohortMap = CohortMap {
+    children: 0.6,
+    adults: 1.,
+    elderly: 0.9,
+};
+
+/// The usual share of the population in each
+/// cohort for an income level.
+fn usual_age_structure(income: Income) -> CohortMap {
+    let (children, adults, elderly) = match income {
+        Income::Low => (0.42, 0.55, 0.03),
+        Income::LowerMiddle => (0.3, 0.64, 0.06),
+        Income::UpperMiddle => (0.2, 0.69, 0.11),
+        Income::High => (0.16, 0.64, 0.2),
+    };
+    CohortMap {
+        children,
+        adults,
+        elderly,
+    }
+}
+
+impl Region {
+    /// The usual share of the population in each cohort
+    /// for the region's income, as it develops.
+    fn usual_age_structure(&self) -> CohortMap {
+        let usual = usual_age_structure(self.income);
+        if self.income == Income::High {
+            usual
+        } else {
+            let next = usual_age_structure(self.income.next());
+            (next - usual) * self.development + usual
+        }
+    }
+
+    /// The share of the region's population in each cohort,
+    /// or the usual shares for its income if it has none.
+    pub fn age_structure(&self) -> CohortMap {
+        if self.age_structure.sum() > 0. {
+            self.age_structure
+        } else {
+            self.usual_age_structure()
+        }
+    }
+
+    /// The region's population weighed by how much each
+    /// cohort consumes, relative to the usual shares for
+    /// its income, which per-capita demand is based on.
+    pub fn consumers(&self) -> f32 {
+        let usual =
+            (self.usual_age_structure() * CONSUMPTION).sum();
+        let actual = (self.age_structure() * CONSUMPTION).sum();
+        self.population * actual / usual
+    }
+
+    /// The number of people in each cohort.
+    pub fn cohorts(&self) -> CohortMap {
+        self.age_structure() * self.population
+    }
+
+    fn set_cohorts(&mut self, cohorts: CohortMap) {
+        let population = cohorts.sum();
+        if population > 0. {
+            self.age_structure = cohorts / population;
+        }
+        self.population = population.max(0.);
+    }
+
+    /// The region's birth and death rates, between its income
+    /// level's and the next one's as it develops.
+    pub fn demographics(
+        &self,
+        by_income: &[Demographics; 4],
+    ) -> Demographics {
+        let idx = self.income.level();
+        if idx < 3 {
+            by_income[idx]
+                .lerp(&by_income[idx + 1], self.development)
+        } else {
+            by_income[idx]
+        }
+    }
+
+    /// Run a year of births, aging and deaths,
+    /// with births scaled by the modifier.
+    pub fn update_demographics(
+        &mut self,
+        by_income: &[Demographics; 4],
+        birth_modifier: f32,
+    ) {
+        let rates = self.demographics(by_income);
+        let mut cohorts = self.cohorts();
+        let births =
+            cohorts.adults * rates.fertility * birth_modifier;
+        let grown_up = cohorts.children / CHILDHOOD;
+        let grown_old = cohorts.adults / ADULTHOOD;
+        cohorts.children += births.max(0.) - grown_up;
+        cohorts.adults += grown_up - grown_old;
+        cohorts.elderly += grown_old;
+        cohorts -= cohorts * rates.mortality;
+        self.set_cohorts(cohorts);
+    }
+
+    /// Kill a share of the region's population, weighing
+    /// each cohort by how vulnerable it is, or bring
+    /// people back if the share is negative.
+    pub fn kill(
+        &mut self,
+        share: f32,
+        vulnerability: CohortMap,
+    ) {
+        let mut cohorts = self.cohorts();
+        for (cohort, count) in cohorts.items_mut() {
+            *count *=
+                1. - (share * vulnerability[cohort]).min(1.);
+        }
+        self.set_cohorts(cohorts);
+    }
+
+    /// The share of the region's population that's elderly.
+    pub fn elderly_share(&self) -> f32 {
+        self.age_structure()[Cohort::Elderly]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::State;
+
+    #[test]
+    fn test_demographics() {
+        let state = State::default();
+        let demographics = &state.world.demographics_by_income;
+        let region = state
+            .world
+            .regions
+            .iter()
+            .find(|r| r.income == Income::Low)
+            .unwrap()
+            .clone();
+        assert!(
+            (region.age_structure().sum() - 1.).abs() < 1e-4
+        );
+        assert!(
+            (region.cohorts().sum() / region.population - 1.)
+                .abs()
+                < 1e-4
+        );
+
+        // Poorer regions grow faster.
+        let growth = |mut region: Region| {
+            let population = region.population;
+            region.update_demographics(demographics, 1.);
+            region.population / population
+        };
+        let high = state
+            .world
+            .regions
+            .iter()
+            .find(|r| r.income == Income::High)
+            .unwrap()
+            .clone();
+        assert!(growth(region.clone()) > growth(high));
+
+        // Fewer births shrink the population only slowly,
+        // as it takes a generation for fewer children to
+        // mean fewer adults.
+        let mut usual = region.clone();
+        let mut fewer = region.clone();
+        usual.update_demographics(demographics, 1.);
+        fewer.update_demographics(demographics, 0.5);
+        assert!(
+            fewer.cohorts().children < usual.cohorts().children
+        );
+        assert!(
+            (fewer.cohorts().adults / usual.cohorts().adults
+                - 1.)
+                .abs()
+                < 1e-4
+        );
+        let gap =
+            usual.cohorts().adults - fewer.cohorts().adults;
+        for _ in 0..20 {
+            usual.update_demographics(demographics, 1.);
+            fewer.update_demographics(demographics, 0.5);
+        }
+        assert!(
+            usual.cohorts().adults - fewer.cohorts().adults
+                > gap
+        );
+
+        // An older population consumes more than
+        // a younger one of the same size.
+        assert!(
+            (region.consumers() / region.population - 1.).abs()
+                < 1e-4
+        );
+        let mut aged = region.clone();
+        for _ in 0..20 {
+            aged.update_demographics(demographics, 0.);
+        }
+        let per_capita = aged.consumers() / aged.population;
+        assert!(per_capita > 1.);
+
+        // Deaths fall on the most vulnerable.
+        let mut region = region;
+        let elderly = region.elderly_share();
+        let population = region.population;
+        region.kill(
+            0.01,
+            CohortMap {
+                children: 1.,
+                adults: 0.,
+                elderly: 0.,
+            },
+        );
+        assert!(region.population < population);
+        assert!(region.elderly_share() > elderly);
+    }
+
+    /// The coefficients population growth was projected
+    /// with by income level, before there were cohorts.
+    const PROJECTION_COEFS: [[f32; 4]; 4] = [
+        [-137.09105, 0.20175901, -9.881497e-05, 1.610786e-08],
+        [-31.64509, 0.04905328, -2.514448e-05, 4.267315e-09],
+        [-73.97073, 0.11030435, -5.4715376e-05, 9.029389e-09],
+        [193.77744, -0.2777678, 0.00013271414, -2.1135532e-08],
+    ];
+
+    #[test]
+    fn test_default_demographics() {
+        let state = State::default();
+        assert_eq!(
+            state.world.demographics_by_income,
+            default_demographics()
+        );
+
+        // Without changes in income, the default rates keep
+        // each income level's population close to how it
+        // used to be projected, through to the end of the
+        // century.
+        let demographics = default_demographics();
+        for (level, coefs) in
+            PROJECTION_COEFS.iter().enumerate()
+        {
+            let mut region = state
+                .world
+                .regions
+                .iter()
+                .find(|r| r.income.level() == level)
+                .unwrap()
+                .clone();
+            region.development = 0.;
+            region.age_structure = CohortMap::default();
+            region.population = 1.;
+
+            let mut projected = 1.;
+            for year in state.world.year..2100 {
+                let year = year as f32;
+                let change = coefs[0]
+                    + (coefs[1] * year)
+                    + (coefs[2] * year.powf(2.0))
+                    + (coefs[3] * year.powf(3.0));
+                projected *= 1. + change;
+                region.update_demographics(&demographics, 1.);
+                assert!(
+                    (region.population / projected - 1.).abs()
+                        < 0.15,
+                    "{:?} in {year}: {} vs {projected}",
+                    region.income,
+                    region.population,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Extreme weather. Each year every region can be struck by
+//! heatwaves, floods, storms and wildfires, more often and
+//! more severely the warmer the world gets and the more
+//! exposed the region is to each. They occur as the
+//! world's icon events for them, alongside any that occur
+//! from those events' own probabilities.
+
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    regions::{Latitude, Region},
+    state::State,
+    Id,
+};
+
+/// The chance of a disaster each year in a fully exposed
+/// region, for each degree of warming.
+const BASE_CHANCE: f32 = 0.05;
+
+/// Warming (C) before which there's no extreme weather
+/// beyond what events' own probabilities have.
+const BASELINE_TEMPERATURE: f32 = 1.;
+
+pub(crate) fn default_disaster_rate() -> f32 {
+    1.
+}
+
+pub(crate) fn default_vulnerability() -> f32 {
+    1.
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Hazard {
+    Heatwave,
+    Flood,
+    Storm,
+    Wildfire,
+}
+impl Hazard {
+    /// The icon events for this hazard, by severity.
+    fn events(&self) -> [Id; 3] {
+        let id = Id::from_u128;
+        match self {
+            Hazard::Heatwave => [
+                id(0xf26afb3d_8983_4da6_a972_3463dfff4f64),
+                id(0x9a071e16_712e_4497_89c5_dea1ae121c89),
+                id(0x9a071e16_712e_4497_89c5_dea1ae121c89),
+            ],
+            Hazard::Flood => [
+                id(0xccdd92d2_c2dc_4ab5_8602_49399f069956),
+                id(0xe47c32c2_fb48_433f_b3ef_9578c29daf25),
+                id(0x39bb56d1_fb19_47dd_bfcf_187b348666a9),
+            ],
+            Hazard::Storm => [
+                id(0x904ddf15_f296_496f_b3ef_5056f3f92d33),
+                id(0x422d9a58_8f1f_4912_b31a_fdf0f91108e1),
+                id(0x422d9a58_8f1f_4912_b31a_fdf0f91108e1),
+            ],
+            Hazard::Wildfire => [
+                id(0x9cdc23d5_170b_4432_9968_f25b7ad8d0c3),
+                id(0x85dd8e47_57f5_4b8e_bab2_abb4cb4eb664),
+                id(0xd5139a29_9d43_4ec1_b11c_69b1ca7df6c4),
+            ],
+        }
+    }
+}
+
+impl Region {
+    /// How exposed the region is to a hazard given
+    /// its climate and geography, from 0 to 1.
+    pub fn exposure(&self, hazard: Hazard) -> f32 {
+        let exposure = match hazard {
+            Hazard::Heatwave => (self.temp_hi - 30.) / 10.,
+            Hazard::Flood => {
+                self.precip_hi / 300.
+                    + self.coastal_exposure * 5.
+            }
+            Hazard::Storm => {
+                let latitude = match self.latitude {
+                    Latitude::Tropic => 1.,
+                    Latitude::Subtropic => 0.7,
+                    Latitude::Temperate => 0.3,
+                    Latitude::Frigid => 0.,
+                };
+                latitude * (0.5 + self.coastal_exposure * 10.)
+            }
+            Hazard::Wildfire => {
+                let heat = (self.temp_hi - 25.) / 15.;
+                let dryness = 1. - self.precip_lo / 100.;
+                heat.clamp(0., 1.) * dryness.clamp(0., 1.)
+            }
+        };
+        exposure.clamp(0., 1.)
+    }
+}
+
+impl State {
+    /// Roll for extreme weather in each region, returning
+    /// the ids of the events for any disasters that strike
+    /// and the regions they strike.
+    pub(crate) fn roll_disasters(&mut self) -> Vec<(Id, Id)> {
+        let warming = (self.world.temperature
+            - BASELINE_TEMPERATURE)
+            .max(0.);
+        let rate = self.world.disaster_rate * warming;
+        if rate <= 0. {
+            return vec![];
+        }
+
+        let mut disasters = vec![];
+        for region in self.world.regions.iter() {
+            if region.seceded {
+                continue;
+            }
+            for hazard in Hazard::iter() {
+                let chance = (BASE_CHANCE
+                    * rate
+                    * region.vulnerability
+                    * region.exposure(hazard))
+                .min(1.);
+                if chance <= 0. || self.rng.f32() >= chance {
+                    continue;
+                }
+
+                // Severity also scales with warming: with a
+                // degree of it disasters are mild or severe,
+                // with three they're often extreme.
+                let severity =
+                    (self.rng.f32() * (warming + 1.)) as usize;
+                let event = hazard.events()[severity.min(2)];
+
+                // Worlds don't have to have these events.
+                if self
+                    .event_pool
+                    .events
+                    .try_get(&event)
+                    .is_some()
+                {
+                    disasters.push((event, region.id));
+                }
+            }
+        }
+        disasters
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{events::Phase, world::World};
+
+    #[test]
+    fn test_disasters_scale_with_warming() {
+        let world = World::default();
+        let mut state = State::with_seed(world, 0);
+        for hazard in Hazard::iter() {
+            for event in hazard.events() {
+                assert!(state
+                    .event_pool
+                    .events
+                    .try_get(&event)
+                    .is_some());
+            }
+        }
+
+        let count = |state: &mut State| {
+            (0..20)
+                .map(|_| state.roll_disasters().len())
+                .sum::<usize>()
+        };
+        state.world.temperature = 0.5;
+        assert_eq!(count(&mut state), 0);
+        state.world.temperature = 2.;
+        let mild = count(&mut state);
+        state.world.temperature = 4.;
+        let severe = count(&mut state);
+        assert!(severe > mild);
+
+        // No disasters where there's no vulnerability.
+        for region in state.world.regions.iter_mut() {
+            region.vulnerability = 0.;
+        }
+        assert_eq!(count(&mut state), 0);
+
+        // They come through as icon events for the region.
+        for region in state.world.regions.iter_mut() {
+            region.vulnerability = 100.;
+        }
+        let events = state.roll_events(Phase::Icon);
+        assert!(events.iter().any(|ev| ev.region.is_some()
+            && Hazard::iter().any(|hazard| hazard
+                .events()
+                .contains(&ev.id))));
+    }
+
+    #[test]
+    fn test_disasters_resolve_magnitudes() {
+        let world = World::default();
+        let mut state = State::with_seed(world, 0);
+        state.world.temperature = 4.;
+        for region in state.world.regions.iter_mut() {
+            region.vulnerability = 100.;
+        }
+
+        // Magnitudes left over from past occurrences.
+        let regions: Vec<Id> = state
+            .world
+            .regions
+            .iter()
+            .map(|region| region.id)
+            .collect();
+        for hazard in Hazard::iter() {
+            for event in hazard.events() {
+                for region in &regions {
+                    state
+                        .event_pool
+                        .magnitudes
+                        .insert((event, Some(*region)), 5.);
+                }
+            }
+        }
+
+        let events = state.roll_events(Phase::Icon);
+        assert!(!events.is_empty());
+        for ev in &events {
+            let key = (ev.id, ev.region.as_ref().map(|r| r.0));
+            let pool = &state.event_pool;
+            assert_eq!(
+                pool.magnitudes.get(&key).copied(),
+                ev.magnitude
+            );
+            if ev.magnitude.is_none() {
+                assert_eq!(
+                    pool.scaled_effects(
+                        &key.0,
+                        key.1,
+                        &ev.effects
+                    ),
+                    ev.effects
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Disease outbreaks. Each year every region can suffer an
+//! outbreak, more often the more of its natural habitat has
+//! been destroyed, bringing people into contact with
+//! wildlife, and the warmer the world gets, spreading
+//! disease-carrying insects. Outbreaks last a few years,
+//! killing people and holding back production while they
+//! do. Preparedness, e.g. from health projects, makes them
+//! rarer, milder and shorter.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{kinds::CohortMap, state::State, Id};
+
+/// The chance of an outbreak each year in
+/// a region with its habitat intact.
+const BASE_CHANCE: f32 = 0.005;
+
+/// How much more likely outbreaks are for each
+/// share of a region's habitat destroyed.
+const HABITAT_RISK: f32 = 4.;
+
+/// How much more likely outbreaks
+/// are for each degree of warming.
+const WARMING_RISK: f32 = 0.5;
+
+/// The longest an outbreak lasts (years).
+const MAX_DURATION: f32 = 3.;
+
+/// The share of a region's population that
+/// dies each year of a severe outbreak.
+const MORTALITY: f32 = 0.005;
+
+/// How vulnerable each cohort is to outbreaks,
+/// relative to the population as a whole.
+const VULNERABILITY: CohortMap = CohortMap {
+    children: 1.5,
+    adults: 0.6,
+    elderly: 3.,
+};
+
+/// The share of production lost while the whole
+/// world suffers from a severe outbreak.
+const OUTPUT_LOSS: f32 = 0.2;
+
+const DISEASE_OUTBREAK: Id =
+    Id::from_u128(0x90fb50b4_077a_4325_845a_abf2c1bb6f52);
+
+pub(crate) fn default_outbreak_rate() -> f32 {
+    1.
+}
+
+/// A disease outbreak in a region.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Outbreak {
+    pub region: Id,
+
+    /// How severe the outbreak is, from 0 to 1.
+    pub severity: f32,
+
+    /// How many more years the outbreak lasts.
+    pub years: usize,
+
+    /// The share of production the outbreak holds back.
+    pub output_loss: f32,
+}
+
+impl State {
+    /// The share of production lost to outbreaks.
+    pub fn outbreak_output_loss(&self) -> f32 {
+        self.outbreaks.iter().map(|o| o.output_loss).sum()
+    }
+
+    /// Start an outbreak in a region, holding back production
+    /// by the region's share of the world's population.
+    pub fn start_outbreak(
+        &mut self,
+        region: Id,
+        severity: f32,
+        years: usize,
+    ) {
+        let population = self.world.regions.population();
+        let share = if population > 0. {
+            self.world.regions[&region].population / population
+        } else {
+            0.
+        };
+        self.outbreaks.push(Outbreak {
+            region,
+            severity,
+            years,
+            output_loss: severity * share * OUTPUT_LOSS,
+        });
+    }
+
+    /// Roll for outbreaks in each region without one, returning
+    /// the ids of the events for any that start and the
+    /// regions they start in.
+    pub(crate) fn roll_outbreaks(&mut self) -> Vec<(Id, Id)> {
+        let rate = self.world.outbreak_rate
+            * (1. - self.world.preparedness).max(0.);
+        if rate <= 0. {
+            return vec![];
+        }
+
+        let warming = self.world.temperature.max(0.);
+        let mut started = vec![];
+        for idx in 0..self.world.regions.len() {
+            let region = self.world.regions.by_idx(idx);
+            let id = region.id;
+            if region.seceded
+                || self
+                    .outbreaks
+                    .iter()
+                    .any(|outbreak| outbreak.region == id)
+            {
+                continue;
+            }
+            let chance = BASE_CHANCE
+                * rate
+                * (1.
+                    + region.habitat_loss() * HABITAT_RISK
+                    + warming * WARMING_RISK);
+            if self.rng.f32() >= chance.min(1.) {
+                continue;
+            }
+
+            // Preparedness also makes them milder and shorter.
+            let mildness = 1. - self.world.preparedness / 2.;
+            let severity = self.rng.f32() * mildness;
+            let years =
+                (self.rng.f32() * MAX_DURATION * mildness)
+                    .ceil()
+                    .max(1.) as usize;
+            self.start_outbreak(id, severity, years);
+
+            // Worlds don't have to have this event.
+            if self
+                .event_pool
+                .events
+                .try_get(&DISEASE_OUTBREAK)
+                .is_some()
+            {
+                started.push((DISEASE_OUTBREAK, id));
+            }
+        }
+        started
+    }
+
+    /// Run the year of each outbreak, killing people in
+    /// their regions, and end any that have run their course.
+    pub(crate) fn update_outbreaks(&mut self) {
+        for outbreak in &mut self.outbreaks {
+            let region =
+                &mut self.world.regions[&outbreak.region];
+            region.kill(
+                outbreak.severity * MORTALITY,
+                VULNERABILITY,
+            );
+            outbreak.years = outbreak.years.saturating_sub(1);
+        }
+
+        self.outbreaks.retain(|outbreak| outbreak.years > 0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        events::{Effect, Phase, WorldVariable},
+        kinds::LandUse,
+        world::World,
+    };
+
+    #[test]
+    fn test_outbreaks() {
+        let world = World::default();
+        let mut state = State::with_seed(world, 0);
+        assert!(state.outbreaks.is_empty());
+
+        let count = |state: &mut State| {
+            let mut count = 0;
+            for _ in 0..50 {
+                count += state.roll_outbreaks().len();
+                for outbreak in &mut state.outbreaks {
+                    outbreak.years = 1;
+                }
+                state.update_outbreaks();
+            }
+            count
+        };
+        state.world.temperature = 0.;
+        let baseline = count(&mut state);
+
+        // Habitat destruction and warming make them likelier.
+        for region in state.world.regions.iter_mut() {
+            region.land_use[LandUse::Forest] = 0.;
+            region.land_use[LandUse::Protected] = 0.;
+        }
+        state.world.temperature = 3.;
+        let risky = count(&mut state);
+        assert!(risky > baseline);
+
+        // Preparedness makes them rarer.
+        state.apply_effects(
+            &[Effect::WorldVariable(
+                WorldVariable::Preparedness,
+                1.,
+            )],
+            None,
+        );
+        assert_eq!(count(&mut state), 0);
+
+        // They kill people and hold back production
+        // until they've run their course.
+        let region = state.world.regions.first().id;
+        let population =
+            state.world.regions[&region].population;
+        let mut healthy = state.clone();
+        state.start_outbreak(region, 1., 2);
+        assert!(state.outbreak_output_loss() > 0.);
+        let mut sick = state.clone();
+        healthy.step_year(healthy.world.temperature);
+        sick.step_year(sick.world.temperature);
+        assert!(
+            sick.produced.total().sum()
+                < healthy.produced.total().sum()
+        );
+        state.update_outbreaks();
+        assert!(
+            state.world.regions[&region].population
+                < population
+        );
+        state.update_outbreaks();
+        assert!(state.outbreaks.is_empty());
+        assert_eq!(state.outbreak_output_loss(), 0.);
+
+        // They come through as icon events for the region.
+        state.world.preparedness = 0.;
+        state.world.outbreak_rate = 1000.;
+        let events = state.roll_events(Phase::Icon);
+        assert!(events.iter().any(|ev| ev.region.is_some()
+            && ev.id == DISEASE_OUTBREAK));
+    }
+}
@@ -0,0 +1,205 @@
+use std::{fmt::Display, io::Read};
+
+use serde::Deserialize;
+
+use ruzstd::{
+    decoding::StreamingDecoder,
+    encoding::{compress_to_vec, CompressionLevel},
+};
+
+use crate::{
+    migrate::{migrate, WORLD_VERSION},
+    World,
+};
+
+/// Zstandard frames start with this, which is how
+/// binary worlds are told apart from JSON ones.
+pub(crate) const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+#[derive(Debug)]
+pub enum LoadError {
+    Json(serde_json::Error),
+    Decompress(String),
+    Binary(rmp_serde::decode::Error),
+    Version(u32),
+}
+impl Display for LoadError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Json(err) => {
+                write!(f, "Invalid world JSON: {err}")
+            }
+            Self::Decompress(err) => {
+                write!(f, "Couldn't decompress world: {err}")
+            }
+            Self::Binary(err) => {
+                write!(f, "Invalid binary world: {err}")
+            }
+            Self::Version(version) => {
+                write!(
+                    f,
+                    "This world is from a newer version ({version}) than is supported ({WORLD_VERSION})."
+                )
+            }
+        }
+    }
+}
+
+impl World {
+    /// Serialize the world in a compact binary format,
+    /// MessagePack compressed with zstd. This is much
+    /// smaller than JSON for worlds with a lot of images.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut world = self.canonical();
+        world.seal();
+        let bytes = rmp_serde::to_vec_named(&world)
+            .expect("Worlds are always serializable");
+        compress_to_vec(
+            bytes.as_slice(),
+            CompressionLevel::Fastest,
+        )
+    }
+
+    pub fn from_binary(
+        data: &[u8],
+    ) -> Result<World, LoadError> {
+        World::decode_binary(data).map(|(world, _)| world)
+    }
+
+    /// Decode a binary world, upgrading it if it's from an
+    /// older version. Also returns notes on what was changed.
+    fn decode_binary(
+        data: &[u8],
+    ) -> Result<(World, Vec<String>), LoadError> {
+        let mut decoder =
+            StreamingDecoder::new(data).map_err(|err| {
+                LoadError::Decompress(err.to_string())
+            })?;
+        let mut bytes = vec![];
+        decoder.read_to_end(&mut bytes).map_err(|err| {
+            LoadError::Decompress(err.to_string())
+        })?;
+
+        let Versioned { version } =
+            rmp_serde::from_slice(&bytes)
+                .map_err(LoadError::Binary)?;
+        if version == WORLD_VERSION {
+            let world = rmp_serde::from_slice(&bytes)
+                .map_err(LoadError::Binary)?;
+            return Ok((world, vec![]));
+        }
+
+        // Other versions go through JSON to be migrated,
+        // the same as JSON worlds are.
+        let json = rmp_serde::from_slice(&bytes)
+            .map_err(LoadError::Binary)?;
+        World::from_json_value(json)
+    }
+
+    /// Load a world from JSON, upgrading it if it's from
+    /// an older version. Also returns notes on what was
+    /// changed.
+    fn from_json_value(
+        mut json: serde_json::Value,
+    ) -> Result<(World, Vec<String>), LoadError> {
+        let notes =
+            migrate(&mut json).map_err(LoadError::Version)?;
+        let world = serde_json::from_value(json)
+            .map_err(LoadError::Json)?;
+        Ok((world, notes))
+    }
+
+    /// Load a world from either a binary or a JSON file,
+    /// upgrading it if it's from an older version and
+    /// replacing invalid numbers. Also returns notes on
+    /// what was changed, if anything.
+    pub fn load(
+        data: &[u8],
+    ) -> Result<(World, Vec<String>), LoadError> {
+        let (mut world, mut notes) =
+            if data.starts_with(&ZSTD_MAGIC) {
+                World::decode_binary(data)?
+            } else {
+                let json = serde_json::from_slice(data)
+                    .map_err(LoadError::Json)?;
+                World::from_json_value(json)?
+            };
+        let replaced = world.sanitize();
+        if replaced > 0 {
+            notes.push(format!(
+                "Replaced {replaced} invalid numbers (NaN or infinity) with 0."
+            ));
+        }
+        Ok((world, notes))
+    }
+
+    /// Load a world from either a binary or a JSON file.
+    pub fn from_bytes(data: &[u8]) -> Result<World, LoadError> {
+        World::load(data).map(|(world, _)| world)
+    }
+}
+
+/// Just enough of a world to check its version.
+#[derive(Deserialize)]
+struct Versioned {
+    #[serde(default)]
+    version: u32,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let world = World::default();
+        let binary = world.to_binary();
+        let json = world.to_export_json();
+        assert!(binary.len() < json.len());
+
+        // Exports are sorted, so compare them as exported.
+        let loaded = World::from_bytes(&binary).unwrap();
+        assert_eq!(loaded.to_export_json(), json);
+        let loaded =
+            World::from_bytes(json.as_bytes()).unwrap();
+        assert_eq!(loaded.to_export_json(), json);
+    }
+
+    #[test]
+    fn test_binary_migration() {
+        let mut json =
+            serde_json::to_value(World::default()).unwrap();
+        let obj = json.as_object_mut().unwrap();
+        obj.insert("version".into(), 0.into());
+        obj.remove("translations");
+        let bytes = rmp_serde::to_vec_named(&json).unwrap();
+        let binary = compress_to_vec(
+            bytes.as_slice(),
+            CompressionLevel::Fastest,
+        );
+
+        // Older binary worlds are upgraded like JSON ones.
+        let (world, notes) = World::load(&binary).unwrap();
+        assert_eq!(world.version, WORLD_VERSION);
+        assert!(!notes.is_empty());
+
+        // Newer ones can't be loaded.
+        let obj = json.as_object_mut().unwrap();
+        obj.insert(
+            "version".into(),
+            (WORLD_VERSION + 1).into(),
+        );
+        let bytes = rmp_serde::to_vec_named(&json).unwrap();
+        let binary = compress_to_vec(
+            bytes.as_slice(),
+            CompressionLevel::Fastest,
+        );
+        assert!(matches!(
+            World::load(&binary),
+            Err(LoadError::Version(_))
+        ));
+    }
+}
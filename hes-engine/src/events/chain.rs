@@ -0,0 +1,254 @@
+//! Event chains: a response in an event's dialogue can
+//! queue follow-up events, which carry on the event's
+//! region and magnitude, so story arcs don't have to keep
+//! track of what happened with flags.
+//!
+//! An event with a magnitude range rolls its magnitude when
+//! it occurs, unless one was carried over from the event
+//! before it. Its effects (and its responses' effects) are
+//! scaled by the magnitude, and its text can show it as
+//! `{magnitude}`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Effect, Event, EventPool};
+use crate::{rng::Rng, Id};
+
+/// Magnitudes keyed by event and region, as the same event
+/// can occur in several regions at once. Serialized as a
+/// list of entries, as JSON keys have to be strings.
+pub(super) mod by_occurrence {
+    use super::*;
+
+    type Magnitudes = BTreeMap<(Id, Option<Id>), f32>;
+
+    pub fn serialize<S: Serializer>(
+        magnitudes: &Magnitudes,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(magnitudes.iter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Magnitudes, D::Error> {
+        let entries: Vec<((Id, Option<Id>), f32)> =
+            Vec::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// An event to queue when a response is chosen.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize,
+)]
+pub struct FollowUp {
+    pub event: Id,
+
+    /// How many times the event's phase has to come up
+    /// before it occurs, i.e. years for world events.
+    pub delay: usize,
+}
+
+impl EventPool {
+    /// Queue a follow-up to an event, in the same region
+    /// and carrying on the event's magnitude, if it has one.
+    pub fn queue_follow_up(
+        &mut self,
+        from: Id,
+        follow_up: &FollowUp,
+        region_id: Option<Id>,
+    ) {
+        let key = (follow_up.event, region_id);
+        match self.magnitudes.get(&(from, region_id)).copied() {
+            Some(magnitude) => {
+                self.carried.insert(key, magnitude);
+            }
+            None => {
+                self.carried.remove(&key);
+            }
+        }
+        self.queue_event(
+            follow_up.event,
+            region_id,
+            follow_up.delay,
+        );
+    }
+
+    /// The magnitude an event occurs with: the one carried
+    /// over from the event before it in its chain, or else
+    /// one rolled from its range, if it has one.
+    pub(crate) fn resolve_magnitude(
+        &mut self,
+        ev: &Event,
+        region_id: Option<Id>,
+        rng: &mut Rng,
+    ) -> Option<f32> {
+        let key = (ev.id, region_id);
+        let magnitude =
+            self.carried.remove(&key).or_else(|| {
+                ev.magnitude.map(|(min, max)| {
+                    min + rng.f32() * (max - min)
+                })
+            });
+        match magnitude {
+            Some(magnitude) => {
+                self.magnitudes.insert(key, magnitude);
+            }
+            None => {
+                self.magnitudes.remove(&key);
+            }
+        }
+        magnitude
+    }
+
+    /// Effects of an event (or its responses), scaled by
+    /// the magnitude it occurred with in the region.
+    pub fn scaled_effects(
+        &self,
+        ev_id: &Id,
+        region_id: Option<Id>,
+        effects: &[Effect],
+    ) -> Vec<Effect> {
+        match self.magnitudes.get(&(*ev_id, region_id)) {
+            Some(magnitude) => effects
+                .iter()
+                .map(|effect| effect.clone() * *magnitude)
+                .collect(),
+            None => effects.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        events::{Phase, WorldVariable},
+        flavor::{DialogueLine, DialogueNext, Response},
+        replay::Decision,
+        state::State,
+        world::World,
+    };
+
+    #[test]
+    fn test_event_chain() {
+        let mut world = World::default();
+        let (storm, aftermath) = (Id::new_v4(), Id::new_v4());
+        let mut first = Event {
+            id: storm,
+            name: "Storm".into(),
+            magnitude: Some((2., 2.)),
+            effects: vec![Effect::WorldVariable(
+                WorldVariable::Outlook,
+                -1.,
+            )],
+            ..Default::default()
+        };
+        first.flavor.dialogue.lines = vec![DialogueLine {
+            next: Some(DialogueNext::Responses(vec![
+                Response {
+                    id: 0,
+                    next_line: None,
+                    text: "Rebuild".into(),
+                    conditions: vec![],
+                    effects: vec![],
+                    follow_ups: vec![FollowUp {
+                        event: aftermath,
+                        delay: 2,
+                    }],
+                },
+            ])),
+            ..Default::default()
+        }];
+        world.events = vec![
+            first,
+            Event {
+                id: aftermath,
+                name: "Aftermath".into(),
+                locked: true,
+                magnitude: Some((5., 5.)),
+                ..Default::default()
+            },
+        ]
+        .into();
+        let region = world.regions.first().id;
+
+        let mut state = State::with_seed(world, 0);
+        state.event_pool.events[&storm].phase = Phase::Icon;
+        let outlook = state.world.base_outlook;
+        let events = state.roll_events(Phase::Icon);
+        let event = events
+            .iter()
+            .find(|ev| {
+                ev.region.as_ref().map(|r| r.0) == Some(region)
+            })
+            .unwrap();
+        assert_eq!(event.magnitude, Some(2.));
+        state.apply_event(storm, Some(region));
+        assert_eq!(state.world.base_outlook, outlook - 2.);
+
+        state.decide(Decision::Respond {
+            event: storm,
+            region: Some(region),
+            line: 0,
+            response: 0,
+        });
+        assert!(state.roll_events(Phase::WorldMain).is_empty());
+        let events = state.roll_events(Phase::WorldMain);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "Aftermath");
+        assert_eq!(
+            events[0].region.as_ref().unwrap().0,
+            region
+        );
+
+        // The storm's magnitude is carried on,
+        // rather than the aftermath's own.
+        assert_eq!(events[0].magnitude, Some(2.));
+    }
+
+    #[test]
+    fn test_magnitudes_by_region() {
+        let ev = Event {
+            id: Id::new_v4(),
+            magnitude: Some((1., 3.)),
+            effects: vec![Effect::WorldVariable(
+                WorldVariable::Outlook,
+                -1.,
+            )],
+            ..Default::default()
+        };
+        let (a, b) = (Id::new_v4(), Id::new_v4());
+        let mut pool = EventPool::default();
+        let mut rng = Rng::with_seed(0);
+        let mag_a =
+            pool.resolve_magnitude(&ev, Some(a), &mut rng);
+        let mag_b =
+            pool.resolve_magnitude(&ev, Some(b), &mut rng);
+        assert_ne!(mag_a, mag_b);
+
+        // Each region keeps the magnitude
+        // the event occurred with there.
+        for (region, magnitude) in [(a, mag_a), (b, mag_b)] {
+            assert_eq!(
+                pool.scaled_effects(
+                    &ev.id,
+                    Some(region),
+                    &ev.effects
+                ),
+                vec![
+                    ev.effects[0].clone() * magnitude.unwrap()
+                ]
+            );
+        }
+
+        // And they survive saving.
+        let json = serde_json::to_string(&pool).unwrap();
+        let loaded: EventPool =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.magnitudes, pool.magnitudes);
+    }
+}
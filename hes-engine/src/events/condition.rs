@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::{
+    expr::ConditionExpr,
     Flag,
     LocalVariable,
     PlayerVariable,
@@ -67,6 +68,18 @@ pub enum Condition {
     HeavyProjects(Comparator, usize),
     ProtectLand(Comparator, f32),
     WaterStress(Comparator, f32),
+
+    /// Compare against one of the world's variables.
+    Variable(Id, Comparator, f32),
+
+    /// If a climate tipping point has been crossed, or not.
+    TippingPointCrossed(Id),
+    TippingPointNotCrossed(Id),
+
+    /// A condition written as an expression, e.g.
+    /// `temperature > 2 && flag(Vegan)`.
+    /// See [`crate::expr`] for what it can do.
+    Expr(String),
 }
 
 impl Condition {
@@ -75,6 +88,8 @@ impl Condition {
         default_process: Id,
         default_project: Id,
         default_npc: Id,
+        default_variable: Id,
+        default_tipping_point: Id,
     ) -> Self {
         let comp = Comparator::GreaterEqual;
         match kind {
@@ -171,6 +186,20 @@ impl Condition {
             ConditionKind::WaterStress => {
                 Self::WaterStress(comp, 0.)
             }
+            ConditionKind::Variable => {
+                Self::Variable(default_variable, comp, 0.)
+            }
+            ConditionKind::TippingPointCrossed => {
+                Self::TippingPointCrossed(default_tipping_point)
+            }
+            ConditionKind::TippingPointNotCrossed => {
+                Self::TippingPointNotCrossed(
+                    default_tipping_point,
+                )
+            }
+            ConditionKind::Expr => {
+                Self::Expr("temperature > 2".into())
+            }
         }
     }
 
@@ -191,15 +220,36 @@ impl Condition {
             _ => None,
         }
     }
+
+    pub fn variable_id(&self) -> Option<Id> {
+        match self {
+            Condition::Variable(id, ..) => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn tipping_point_id(&self) -> Option<Id> {
+        match self {
+            Condition::TippingPointCrossed(id)
+            | Condition::TippingPointNotCrossed(id) => {
+                Some(*id)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Condition {
     /// If this condition has any regional conditions.
     pub fn is_regional(&self) -> bool {
-        matches!(
-            self,
-            Self::LocalVariable(..) | Self::RegionFlag(..)
-        )
+        match self {
+            Self::LocalVariable(..) | Self::RegionFlag(..) => {
+                true
+            }
+            Self::Expr(src) => ConditionExpr::parse(src)
+                .is_ok_and(|expr| expr.is_regional()),
+            _ => false,
+        }
     }
 
     pub fn eval(
@@ -211,69 +261,16 @@ impl Condition {
             Condition::LocalVariable(var, comp, other_val) => {
                 if let Some(id) = &region_id {
                     let region = &state.world.regions[id];
-                    let val = match var {
-                        LocalVariable::Population => {
-                            region.population
-                        }
-                        LocalVariable::Outlook => {
-                            region.outlook
-                        }
-                        LocalVariable::Habitability => {
-                            region.habitability()
-                        }
-                    };
-                    comp.eval(val, *other_val)
+                    comp.eval(var.value(region), *other_val)
                 } else {
                     false
                 }
             }
             Condition::WorldVariable(var, comp, other_val) => {
-                let val = match var {
-                    WorldVariable::Year => {
-                        state.world.year as f32
-                    }
-                    WorldVariable::Population => {
-                        state.world.regions.population()
-                    }
-                    WorldVariable::PopulationGrowth => {
-                        state.world.population_growth_modifier
-                    }
-                    WorldVariable::Emissions => {
-                        state.emissions.as_co2eq()
-                    }
-                    WorldVariable::ExtinctionRate => {
-                        state.world.extinction_rate
-                    }
-                    WorldVariable::Outlook => state.outlook(),
-                    WorldVariable::Temperature => {
-                        state.world.temperature
-                    }
-                    WorldVariable::SeaLevelRise => {
-                        state.world.sea_level_rise
-                    }
-                    WorldVariable::SeaLevelRiseRate => {
-                        state.world.sea_level_rise_rate()
-                    }
-                    WorldVariable::Precipitation => {
-                        state.world.precipitation
-                    }
-                };
-                comp.eval(val, *other_val)
+                comp.eval(var.value(state), *other_val)
             }
             Condition::PlayerVariable(var, comp, other_val) => {
-                let val = match var {
-                    PlayerVariable::PoliticalCapital => {
-                        state.political_capital as f32
-                    }
-                    PlayerVariable::ResearchPoints => {
-                        state.research_points as f32
-                    }
-                    PlayerVariable::YearsToDeath => {
-                        state.death_year as f32
-                            - state.world.year as f32
-                    }
-                };
-                comp.eval(val, *other_val)
+                comp.eval(var.value(state), *other_val)
             }
             Condition::ProcessOutput(id, comp, other_val) => {
                 if let Some(val) =
@@ -418,6 +415,27 @@ impl Condition {
                         / state.resources.available.water;
                 comp.eval(water_stress, *n)
             }
+            Condition::Variable(id, comp, other_val) => {
+                state.world.variables.try_get(id).is_some_and(
+                    |var| comp.eval(var.value, *other_val),
+                )
+            }
+            Condition::TippingPointCrossed(id) => state
+                .world
+                .tipping_points
+                .try_get(id)
+                .is_some_and(|point| point.crossed),
+            Condition::TippingPointNotCrossed(id) => state
+                .world
+                .tipping_points
+                .try_get(id)
+                .is_some_and(|point| !point.crossed),
+            Condition::Expr(src) => ConditionExpr::parse(src)
+                .and_then(|expr| expr.eval(state, region_id))
+                .unwrap_or_else(|err| {
+                    tracing::warn!("{err}");
+                    false
+                }),
         }
     }
 }
@@ -443,7 +461,7 @@ pub enum Comparator {
 }
 
 impl Comparator {
-    fn eval(&self, a: f32, b: f32) -> bool {
+    pub(crate) fn eval(&self, a: f32, b: f32) -> bool {
         match self {
             Comparator::Less => a < b,
             Comparator::LessEqual => a <= b,
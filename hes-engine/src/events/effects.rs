@@ -172,8 +172,31 @@ pub enum Effect {
 
     ProtectLand(f32),
 
+    /// Plant forest on a share of every region's unused land.
+    Afforest(f32),
+
+    /// Set one of the world's variables to a value.
+    SetVariable(Id, f32),
+
+    /// Change one of the world's variables by an amount.
+    ChangeVariable(Id, f32),
+
     BailOut(usize),
     GameOver,
+
+    /// A script that decides which effects to apply.
+    /// See [`crate::scripting`] for what scripts can do.
+    /// When unapplied, the script is run again and its
+    /// effects are unapplied, so scripts for projects
+    /// shouldn't depend on values that change.
+    Script(String),
+
+    /// Effects written as expressions, e.g.
+    /// `outlook -= temperature * 2`.
+    /// See [`crate::expr`] for what they can do.
+    /// As with scripts, they're evaluated again when
+    /// they're unapplied.
+    Expr(String),
 }
 impl AsRef<Effect> for Effect {
     fn as_ref(&self) -> &Effect {
@@ -207,6 +230,7 @@ impl Effect {
         default_industry: Id,
         default_event: Id,
         default_npc: Id,
+        default_variable: Id,
     ) -> Self {
         match kind {
             EffectKind::WorldVariable => Effect::WorldVariable(
@@ -352,8 +376,22 @@ impl Effect {
                 Effect::ProjectCostModifier(default_project, 0.)
             }
             EffectKind::ProtectLand => Effect::ProtectLand(0.1),
+            EffectKind::Afforest => Effect::Afforest(0.01),
+            EffectKind::SetVariable => {
+                Effect::SetVariable(default_variable, 1.)
+            }
+            EffectKind::ChangeVariable => {
+                Effect::ChangeVariable(default_variable, 1.)
+            }
             EffectKind::BailOut => Effect::BailOut(20),
             EffectKind::GameOver => Effect::GameOver,
+            EffectKind::Script => Effect::Script(
+                "effects.push(#{ WorldVariable: [\"Outlook\", 1.] });"
+                    .into(),
+            ),
+            EffectKind::Expr => {
+                Effect::Expr("political_capital += 10".into())
+            }
         }
     }
 
@@ -400,6 +438,14 @@ impl Effect {
             _ => None,
         }
     }
+
+    pub fn variable_id(&self) -> Option<Id> {
+        match self {
+            Effect::SetVariable(id, _)
+            | Effect::ChangeVariable(id, _) => Some(*id),
+            _ => None,
+        }
+    }
 }
 
 impl Effect {
@@ -446,6 +492,26 @@ impl Effect {
         region_id: Option<Id>,
     ) {
         match self {
+            Effect::Script(script) => {
+                match state.script_effects(script, region_id) {
+                    Ok(effects) => {
+                        for effect in effects {
+                            effect.apply(state, region_id);
+                        }
+                    }
+                    Err(err) => tracing::warn!("{err}"),
+                }
+            }
+            Effect::Expr(src) => {
+                match state.expr_effects(src, region_id) {
+                    Ok(effects) => {
+                        for effect in effects {
+                            effect.apply(state, region_id);
+                        }
+                    }
+                    Err(err) => tracing::warn!("{err}"),
+                }
+            }
             Effect::GameOver => {
                 if !state.flags.contains(&Flag::LifeGoesOn) {
                     state.game_over = true;
@@ -501,6 +567,18 @@ impl Effect {
                     WorldVariable::Precipitation => {
                         state.world.precipitation += *change
                     }
+                    WorldVariable::Acidification => {
+                        state.world.acidification += *change
+                    }
+                    WorldVariable::WaterEfficiency => {
+                        state.world.water_efficiency += *change
+                    }
+                    WorldVariable::SoilHealth => {
+                        state.change_soil_health(*change);
+                    }
+                    WorldVariable::Preparedness => {
+                        state.world.preparedness += *change
+                    }
                 }
             }
             Effect::PlayerVariable(var, change) => match var {
@@ -783,15 +861,47 @@ impl Effect {
             Effect::ProtectLand(percent) => {
                 state.protected_land += percent;
             }
+            Effect::Afforest(percent) => {
+                for region in state.world.regions.iter_mut() {
+                    region.afforest(*percent);
+                }
+            }
+            Effect::SetVariable(id, value) => {
+                state.world.variables[id].set(*value);
+            }
+            Effect::ChangeVariable(id, change) => {
+                let var = &mut state.world.variables[id];
+                var.set(var.value + change);
+            }
         }
     }
 
     pub fn unapply(
         &self,
         state: &mut State,
-        _region_id: Option<Id>,
+        region_id: Option<Id>,
     ) {
         match self {
+            Effect::Script(script) => {
+                match state.script_effects(script, region_id) {
+                    Ok(effects) => {
+                        for effect in effects {
+                            effect.unapply(state, region_id);
+                        }
+                    }
+                    Err(err) => tracing::warn!("{err}"),
+                }
+            }
+            Effect::Expr(src) => {
+                match state.expr_effects(src, region_id) {
+                    Ok(effects) => {
+                        for effect in effects {
+                            effect.unapply(state, region_id);
+                        }
+                    }
+                    Err(err) => tracing::warn!("{err}"),
+                }
+            }
             Effect::WorldVariable(var, change) => {
                 match var {
                     WorldVariable::Year => {
@@ -835,6 +945,18 @@ impl Effect {
                     WorldVariable::Precipitation => {
                         state.world.precipitation -= *change
                     }
+                    WorldVariable::Acidification => {
+                        state.world.acidification -= *change
+                    }
+                    WorldVariable::WaterEfficiency => {
+                        state.world.water_efficiency -= *change
+                    }
+                    WorldVariable::SoilHealth => {
+                        state.change_soil_health(-*change);
+                    }
+                    WorldVariable::Preparedness => {
+                        state.world.preparedness -= *change
+                    }
                 }
             }
             Effect::PlayerVariable(var, change) => match var {
@@ -1027,6 +1149,15 @@ impl Effect {
             Effect::ProtectLand(percent) => {
                 state.protected_land -= percent;
             }
+            Effect::Afforest(percent) => {
+                for region in state.world.regions.iter_mut() {
+                    region.afforest(-percent);
+                }
+            }
+            Effect::ChangeVariable(id, change) => {
+                let var = &mut state.world.variables[id];
+                var.set(var.value - change);
+            }
             Effect::AddFlag(flag) => {
                 if let Some(idx) =
                     state.flags.iter().position(|x| x == flag)
@@ -1131,6 +1262,10 @@ impl Mul<f32> for Effect {
             Effect::ProtectLand(val) => {
                 Effect::ProtectLand(val * rhs)
             }
+            Effect::Afforest(val) => Effect::Afforest(val * rhs),
+            Effect::ChangeVariable(id, val) => {
+                Effect::ChangeVariable(id, val * rhs)
+            }
             _ => self,
         }
     }
@@ -1,14 +1,24 @@
 use super::{Effect, Likelihood, Probability};
 use crate::{
     flavor::EventFlavor,
+    rng::Rng,
     state::State,
     Collection,
     HasId,
     Id,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, fmt::Display};
-use strum::{Display, EnumIter, EnumString, IntoStaticStr};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
+use strum::{
+    Display,
+    EnumDiscriminants,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+};
 
 #[derive(
     Clone, Debug, Default, Serialize, Deserialize, PartialEq,
@@ -19,6 +29,26 @@ pub struct EventPool {
     // (phase, event id, region id, countdown)
     pub queue: Vec<(Phase, Id, Option<Id>, usize)>,
     pub triggered: Vec<(Phase, Id, Option<Id>)>,
+
+    /// The year each event last occurred, for
+    /// cooldowns and events scheduled after others.
+    #[serde(default)]
+    pub last_occurred: BTreeMap<Id, usize>,
+
+    /// The events drawn from each pool
+    /// since it was last refilled.
+    #[serde(default)]
+    pub drawn: BTreeMap<String, Vec<Id>>,
+
+    /// Magnitudes carried over to queued follow-ups,
+    /// by event and region.
+    #[serde(default, with = "super::chain::by_occurrence")]
+    pub carried: BTreeMap<(Id, Option<Id>), f32>,
+
+    /// The magnitude each event last occurred with in each
+    /// region, for those that have one.
+    #[serde(default, with = "super::chain::by_occurrence")]
+    pub magnitudes: BTreeMap<(Id, Option<Id>), f32>,
 }
 
 impl EventPool {
@@ -27,9 +57,60 @@ impl EventPool {
             events,
             queue: Vec::new(),
             triggered: Vec::new(),
+            last_occurred: BTreeMap::new(),
+            drawn: BTreeMap::new(),
+            carried: BTreeMap::new(),
+            magnitudes: BTreeMap::new(),
         }
     }
 
+    /// If the event occurred too recently to occur again.
+    fn is_cooling_down(&self, ev: &Event, year: usize) -> bool {
+        self.last_occurred
+            .get(&ev.id)
+            .is_some_and(|last| year < last + ev.cooldown)
+    }
+
+    /// If the event is scheduled for this year or
+    /// earlier and hasn't occurred since.
+    fn is_due(&self, ev: &Event, year: usize) -> bool {
+        let due = match ev.schedule {
+            None => return false,
+            Some(Schedule::Year(due)) => due,
+            Some(Schedule::After(id, years)) => {
+                match self.last_occurred.get(&id) {
+                    Some(occurred) => occurred + years,
+                    None => return false,
+                }
+            }
+        };
+        year >= due
+            && self
+                .last_occurred
+                .get(&ev.id)
+                .is_none_or(|last| *last < due)
+    }
+
+    fn is_drawn(&self, ev: &Event) -> bool {
+        self.drawn
+            .get(&ev.pool)
+            .is_some_and(|drawn| drawn.contains(&ev.id))
+    }
+
+    /// Refill any pools which have no events left to draw.
+    fn refill_pools(&mut self) {
+        let events = &self.events;
+        self.drawn.retain(|pool, drawn| {
+            events.iter().any(|ev| {
+                ev.pool == *pool
+                    && ev.weight > 0.
+                    && !ev.occurred
+                    && !ev.locked
+                    && !drawn.contains(&ev.id)
+            })
+        });
+    }
+
     pub fn queue_event(
         &mut self,
         id: Id,
@@ -44,7 +125,11 @@ impl EventPool {
         &mut self,
         phase: Phase,
         state: &State,
+        rng: &mut Rng,
     ) -> Vec<(Event, Option<Id>)> {
+        let year = state.world.year;
+        self.refill_pools();
+
         // Prevent duplicate events
         let mut existing: HashSet<&Id> = HashSet::new();
         for (_, ev_id, _, _) in &self.queue {
@@ -63,10 +148,22 @@ impl EventPool {
                     && !ev.occurred
                     && !ev.locked
                     && !existing.contains(&ev.id)
+                    && !self.is_cooling_down(ev, year)
+                    && !self.is_drawn(ev)
             })
             .map(|ev| ev.id)
             .collect();
-        fastrand::shuffle(&mut valid_ids);
+        rng.shuffle(&mut valid_ids);
+
+        // Scheduled events happen once they're due,
+        // whatever their probabilities.
+        valid_ids.retain(|ev_id| {
+            let due = self.is_due(&self.events[ev_id], year);
+            if due {
+                self.triggered.push((phase, *ev_id, None));
+            }
+            !due
+        });
 
         // Tick queued countdowns
         let mut i = 0;
@@ -75,8 +172,8 @@ impl EventPool {
                 let (_, ev_id, _, countdown) =
                     &mut self.queue[i];
                 if self.events[&*ev_id].phase == phase {
-                    *countdown -= 1;
-                    *countdown <= 0
+                    *countdown = countdown.saturating_sub(1);
+                    *countdown == 0
                 } else {
                     false
                 }
@@ -84,7 +181,7 @@ impl EventPool {
             if try_trigger {
                 let (_, ev_id, region_id, _) = self.queue[i];
                 let ev = &mut self.events[&ev_id];
-                if ev.roll(state, region_id) {
+                if ev.roll(state, region_id, rng) {
                     self.triggered
                         .push((ev.phase, ev_id, region_id));
                 }
@@ -97,51 +194,64 @@ impl EventPool {
         // Roll for additional events
         // These events start with countdown 0;
         // i.e. we immediately trigger them if possible.
+        // Events in pools are set aside so that
+        // only one is drawn from each.
+        let mut pooled: BTreeMap<
+            String,
+            Vec<(Id, Option<Id>)>,
+        > = BTreeMap::new();
         for ev_id in valid_ids {
-            let ev = &mut self.events[&ev_id];
-            // Icon-type events are always local
-            if ev.phase == Phase::Icon {
-                for region in state.world.regions.iter() {
-                    if ev.roll(state, Some(region.id)) {
-                        self.triggered.push((
-                            ev.phase,
-                            ev_id,
-                            Some(region.id),
-                        ));
-                    }
-                }
+            let ev = &self.events[&ev_id];
+            let regions = ev.roll_regions(state, rng);
+            if ev.pool.is_empty() {
+                self.triggered.extend(
+                    regions.into_iter().map(|region| {
+                        (ev.phase, ev_id, region)
+                    }),
+                );
             } else {
-                if ev.is_regional() {
-                    for region in state.world.regions.iter() {
-                        if ev.roll(state, Some(region.id)) {
-                            self.triggered.push((
-                                ev.phase,
-                                ev_id,
-                                Some(region.id),
-                            ));
-                        }
-                    }
-                } else if ev.roll(state, None) {
-                    self.triggered
-                        .push((ev.phase, ev_id, None));
-                }
+                pooled
+                    .entry(ev.pool.clone())
+                    .or_default()
+                    .extend(
+                        regions
+                            .into_iter()
+                            .map(|region| (ev_id, region)),
+                    );
+            }
+        }
+        for (pool, candidates) in pooled {
+            if let Some((ev_id, region_id)) =
+                self.draw(&candidates, rng)
+            {
+                self.triggered.push((phase, ev_id, region_id));
+                self.drawn.entry(pool).or_default().push(ev_id);
             }
         }
 
         // Get the first MAX_EVENTS_PER_TURN triggered events
         let mut happening = Vec::new();
-        fastrand::shuffle(&mut self.triggered);
+        rng.shuffle(&mut self.triggered);
 
         let mut i = 0;
         while i < self.triggered.len() {
             let (p, ev_id, region_id) = self.triggered[i];
             if p == phase {
+                let cooling_down = self.is_cooling_down(
+                    &self.events[&ev_id],
+                    year,
+                );
                 let ev = &mut self.events[&ev_id];
-                if !ev.occurred {
+                if !ev.occurred && !cooling_down {
                     happening.push((ev_id, region_id));
-                    // All events except
+                    self.last_occurred.insert(ev_id, year);
+
+                    // Events with a cooldown can repeat after it,
+                    // and otherwise all events except
                     // for Icon events don't repeat
-                    if ev.phase != Phase::Icon {
+                    if ev.cooldown == 0
+                        && ev.phase != Phase::Icon
+                    {
                         ev.occurred = true;
                     }
                 }
@@ -158,6 +268,34 @@ impl EventPool {
         }
         results
     }
+
+    /// Draw one of the candidates, weighted by
+    /// their events' weights.
+    fn draw(
+        &self,
+        candidates: &[(Id, Option<Id>)],
+        rng: &mut Rng,
+    ) -> Option<(Id, Option<Id>)> {
+        let weight =
+            |ev_id: &Id| self.events[ev_id].weight.max(0.);
+        let total: f32 =
+            candidates.iter().map(|(id, _)| weight(id)).sum();
+        if total <= 0. {
+            return None;
+        }
+        let mut roll = rng.f32() * total;
+        for (ev_id, region_id) in candidates {
+            roll -= weight(ev_id);
+            if roll < 0. {
+                return Some((*ev_id, *region_id));
+            }
+        }
+        candidates
+            .iter()
+            .rev()
+            .find(|(id, _)| weight(id) > 0.)
+            .copied()
+    }
 }
 
 /// The game phase in which an event can be rolled.
@@ -225,8 +363,40 @@ pub struct Event {
     /// Icon event intensity
     pub intensity: usize,
 
+    /// Years before this event can occur again.
+    /// Events without one only occur once,
+    /// except for icon events.
+    #[serde(default)]
+    pub cooldown: usize,
+
+    /// The pool this event is drawn from, if any.
+    /// Only one event from a pool occurs at a time,
+    /// and each of a pool's events are drawn before
+    /// any are drawn again.
+    #[serde(default)]
+    pub pool: String,
+
+    /// How likely this event is to be drawn
+    /// relative to the others in its pool.
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+
+    /// When this event occurs, if it's scheduled,
+    /// in addition to any of its probabilities.
+    /// Scheduled events aren't regional.
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+
+    /// The range of magnitudes this event can occur with.
+    /// See [`super::chain`] for how they're used.
+    #[serde(default)]
+    pub magnitude: Option<(f32, f32)>,
+
     pub flavor: EventFlavor,
     pub notes: String,
+    /// Free-form tags for organizing entities in the editor.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 impl Default for Event {
     fn default() -> Self {
@@ -238,6 +408,11 @@ impl Default for Event {
             phase: Phase::WorldMain,
             prob_modifier: 1.,
             intensity: 0,
+            cooldown: 0,
+            pool: String::new(),
+            weight: 1.,
+            schedule: None,
+            magnitude: None,
             effects: vec![],
             probabilities: vec![Probability {
                 likelihood: Likelihood::Guaranteed,
@@ -245,10 +420,66 @@ impl Default for Event {
             }],
             flavor: EventFlavor::default(),
             notes: "".into(),
+            tags: vec![],
         }
     }
 }
 
+/// When a scheduled event occurs.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    EnumDiscriminants,
+)]
+#[strum_discriminants(derive(
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Display
+))]
+#[strum_discriminants(name(ScheduleKind))]
+pub enum Schedule {
+    /// In this year, or the first year
+    /// after it that its phase comes up.
+    Year(usize),
+
+    /// This many years after another event occurs.
+    After(Id, usize),
+}
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule::Year(0)
+    }
+}
+impl Schedule {
+    pub fn from_kind(
+        kind: ScheduleKind,
+        default_event: Id,
+    ) -> Self {
+        match kind {
+            ScheduleKind::Year => Schedule::Year(0),
+            ScheduleKind::After => {
+                Schedule::After(default_event, 1)
+            }
+        }
+    }
+
+    pub fn event_id(&self) -> Option<Id> {
+        match self {
+            Schedule::After(id, _) => Some(*id),
+            Schedule::Year(_) => None,
+        }
+    }
+}
+
+fn default_weight() -> f32 {
+    1.
+}
+
 impl Display for Event {
     fn fmt(
         &self,
@@ -294,16 +525,42 @@ impl Event {
         res
     }
 
+    /// Roll to see which regions the event occurs in,
+    /// or `None` if it isn't regional.
+    fn roll_regions(
+        &self,
+        state: &State,
+        rng: &mut Rng,
+    ) -> Vec<Option<Id>> {
+        // Icon-type events are always local
+        if self.phase == Phase::Icon || self.is_regional() {
+            state
+                .world
+                .regions
+                .iter()
+                .filter(|region| {
+                    self.roll(state, Some(region.id), rng)
+                })
+                .map(|region| Some(region.id))
+                .collect()
+        } else if self.roll(state, None, rng) {
+            vec![None]
+        } else {
+            vec![]
+        }
+    }
+
     /// Roll to see if the event occurs.
     fn roll(
         &self,
         state: &State,
         region_id: Option<Id>,
+        rng: &mut Rng,
     ) -> bool {
         match self.eval(state, region_id) {
             Some(likelihood) => {
                 let prob = likelihood.p();
-                fastrand::f32() <= (prob * self.prob_modifier)
+                rng.f32() <= (prob * self.prob_modifier)
             }
             None => false,
         }
@@ -362,17 +619,21 @@ mod test {
 
     #[test]
     fn test_event_pool() {
-        fastrand::seed(0);
+        let mut rng = Rng::with_seed(0);
         let events = gen_events();
         let mut pool = EventPool {
             events,
             queue: vec![],
             triggered: vec![],
+            ..Default::default()
         };
 
         let mut state = State::default();
-        let events =
-            pool.roll_for_phase(Phase::WorldMain, &state);
+        let events = pool.roll_for_phase(
+            Phase::WorldMain,
+            &state,
+            &mut rng,
+        );
 
         // Only event B should happen
         assert_eq!(events.len(), 1);
@@ -381,15 +642,18 @@ mod test {
         // But if we set it so that event A's first condition
         // is met, it should happen
         state.world.year = 10;
-        let events =
-            pool.roll_for_phase(Phase::WorldMain, &state);
+        let events = pool.roll_for_phase(
+            Phase::WorldMain,
+            &state,
+            &mut rng,
+        );
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].0.name, "Test Event A");
     }
 
     #[test]
     fn test_event_pool_local() {
-        fastrand::seed(0);
+        let mut rng = Rng::with_seed(0);
         let events = vec![Event {
             id: Id::new_v4(),
             name: "Test Event A".into(),
@@ -415,6 +679,7 @@ mod test {
             events,
             queue: vec![],
             triggered: vec![],
+            ..Default::default()
         };
 
         let mut state = State::default();
@@ -431,7 +696,11 @@ mod test {
             },
         ]
         .into();
-        let events = pool.roll_for_phase(Phase::Icon, &state);
+        let events = pool.roll_for_phase(
+            Phase::Icon,
+            &state,
+            &mut rng,
+        );
 
         // No events should happen
         assert_eq!(events.len(), 0);
@@ -440,7 +709,11 @@ mod test {
         let region = state.world.regions.by_idx_mut(1);
         region.population = 10.;
         let id = region.id;
-        let events = pool.roll_for_phase(Phase::Icon, &state);
+        let events = pool.roll_for_phase(
+            Phase::Icon,
+            &state,
+            &mut rng,
+        );
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].0.name, "Test Event A");
         assert_eq!(events[0].1, Some(id));
@@ -448,7 +721,7 @@ mod test {
 
     #[test]
     fn test_event_pool_countdown() {
-        fastrand::seed(0);
+        let mut rng = Rng::with_seed(0);
         let id = Id::new_v4();
         let events = vec![Event {
             id,
@@ -463,24 +736,31 @@ mod test {
             events,
             queue: vec![(Phase::WorldMain, id, None, 2)],
             triggered: vec![],
+            ..Default::default()
         };
 
         let state = State::default();
 
         // No events should happen
-        let events =
-            pool.roll_for_phase(Phase::WorldMain, &state);
+        let events = pool.roll_for_phase(
+            Phase::WorldMain,
+            &state,
+            &mut rng,
+        );
         assert_eq!(events.len(), 0);
 
         // Countdown finished
-        let events =
-            pool.roll_for_phase(Phase::WorldMain, &state);
+        let events = pool.roll_for_phase(
+            Phase::WorldMain,
+            &state,
+            &mut rng,
+        );
         assert_eq!(events.len(), 1);
     }
 
     #[test]
     fn test_event_pool_no_dupes() {
-        fastrand::seed(0);
+        let mut rng = Rng::with_seed(0);
         let id = Id::new_v4();
         let mut pool = EventPool {
             events: vec![Event {
@@ -498,20 +778,144 @@ mod test {
                 (Phase::WorldMain, id, None),
                 (Phase::WorldMain, id, None),
             ],
+            ..Default::default()
         };
 
         let state = State::default();
-        let events =
-            pool.roll_for_phase(Phase::WorldMain, &state);
+        let events = pool.roll_for_phase(
+            Phase::WorldMain,
+            &state,
+            &mut rng,
+        );
 
         // Only 1 event should happen
         assert_eq!(events.len(), 1);
 
         // Shouldn't happen again, even though they're pre-triggered
         for _ in 0..4 {
-            let events =
-                pool.roll_for_phase(Phase::WorldMain, &state);
+            let events = pool.roll_for_phase(
+                Phase::WorldMain,
+                &state,
+                &mut rng,
+            );
             assert_eq!(events.len(), 0);
         }
     }
+
+    #[test]
+    fn test_event_cooldown() {
+        let mut rng = Rng::with_seed(0);
+        let id = Id::new_v4();
+        let mut pool = EventPool::new(
+            vec![Event {
+                id,
+                cooldown: 2,
+                ..Default::default()
+            }]
+            .into(),
+        );
+
+        let mut state = State::default();
+        let mut occurred = vec![];
+        for year in 0..6 {
+            state.world.year = year;
+            let events = pool.roll_for_phase(
+                Phase::WorldMain,
+                &state,
+                &mut rng,
+            );
+            if !events.is_empty() {
+                occurred.push(year);
+            }
+        }
+        assert_eq!(occurred, vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn test_weighted_pool() {
+        let mut rng = Rng::with_seed(0);
+        let events: Vec<Event> = ["Common", "Uncommon", "Rare"]
+            .into_iter()
+            .zip([10., 1., 0.])
+            .map(|(name, weight)| Event {
+                id: Id::new_v4(),
+                name: name.into(),
+                cooldown: 1,
+                pool: "Crises".into(),
+                weight,
+                ..Default::default()
+            })
+            .collect();
+        let mut pool = EventPool::new(events.into());
+
+        let mut state = State::default();
+        let mut names = vec![];
+        for year in 0..6 {
+            state.world.year = year;
+            let events = pool.roll_for_phase(
+                Phase::WorldMain,
+                &state,
+                &mut rng,
+            );
+
+            // Only one event from the pool at a time.
+            assert_eq!(events.len(), 1);
+            names.push(events[0].0.name.clone());
+        }
+
+        // Each event is drawn before any repeats, and
+        // events without any weight are never drawn.
+        for pair in names.chunks(2) {
+            let mut pair = pair.to_vec();
+            pair.sort();
+            assert_eq!(pair, vec!["Common", "Uncommon"]);
+        }
+    }
+
+    #[test]
+    fn test_scheduled_events() {
+        let mut rng = Rng::with_seed(0);
+        let (first, second) = (Id::new_v4(), Id::new_v4());
+        let impossible = vec![Probability {
+            likelihood: Likelihood::Impossible,
+            conditions: vec![],
+        }];
+        let mut pool = EventPool::new(
+            vec![
+                Event {
+                    id: first,
+                    name: "First".into(),
+                    probabilities: impossible.clone(),
+                    schedule: Some(Schedule::Year(2)),
+                    ..Default::default()
+                },
+                Event {
+                    id: second,
+                    name: "Second".into(),
+                    probabilities: impossible,
+                    schedule: Some(Schedule::After(first, 3)),
+                    ..Default::default()
+                },
+            ]
+            .into(),
+        );
+
+        let mut state = State::default();
+        let mut occurred = vec![];
+        for year in 0..8 {
+            state.world.year = year;
+            let events = pool.roll_for_phase(
+                Phase::WorldMain,
+                &state,
+                &mut rng,
+            );
+            for (ev, _) in events {
+                occurred.push((ev.name, year));
+            }
+        }
+        assert_eq!(
+            occurred,
+            vec![("First".into(), 2), ("Second".into(), 5)]
+        );
+    }
 }
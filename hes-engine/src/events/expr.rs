@@ -0,0 +1,1539 @@
+//! A small expression language for conditions and effects,
+//! for triggers the built-in conditions can't express, e.g.
+//! `temperature > 2.0 && project(SolarRadiationManagement).active`.
+//!
+//! Expressions are numbers and `true`/`false`, combined with
+//! `||`, `&&`, `!`, comparisons (`<`, `<=`, `==`, `!=`, `>=`,
+//! `>`), arithmetic (`+`, `-`, `*`, `/`) and parentheses.
+//! They can read:
+//!
+//! - the world's variables in snake case, e.g. `temperature`,
+//!   `sea_level_rise` or `year`
+//! - the player's variables, e.g. `political_capital`
+//! - `runs`, how many runs the player has played
+//! - `flag(Vegan)`, if the flag is set
+//! - `var(PublicTrust)`, one of the world's own variables
+//! - `project(Name).active` (or `finished`, `building`,
+//!   `level` and `progress`)
+//! - `process(Name).mix_share` (a percent) and `.output`
+//! - `npc(Name).relationship`
+//! - `tipping_point(Name)`, if the tipping point is crossed
+//! - `region.outlook` (or `population`, `habitability`,
+//!   `water_stress` and `air_pollution`), for regional
+//!   events
+//!
+//! Names can be written without their spaces and in any case,
+//! e.g. `project(solarradiationmanagement)`, or quoted, e.g.
+//! `project("Solar Radiation Management")`.
+//!
+//! Effects are lines (or `;`-separated statements) that
+//! change a value, e.g.:
+//!
+//! ```text
+//! political_capital += 10
+//! outlook -= temperature * 2
+//! var(PublicTrust) = 0
+//! flag(Vegan) = true
+//! npc(TheEcofeminist).relationship += 1
+//! ```
+//!
+//! The world's and player's variables and relationships can
+//! only be changed with `+=` and `-=`, and flags can only be
+//! set, not cleared. Values are read when the effects are
+//! applied, and again when they're unapplied.
+
+use std::{fmt::Display, ops::Range};
+
+use strum::IntoEnumIterator;
+
+use super::{
+    Comparator,
+    Effect,
+    Flag,
+    LocalVariable,
+    PlayerVariable,
+    WorldVariable,
+};
+use crate::{
+    npcs::NPC,
+    projects::Status,
+    state::State,
+    world::World,
+    Id,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprError {
+    pub message: String,
+
+    /// Where in the source the error is, in bytes.
+    pub span: Range<usize>,
+
+    source: String,
+}
+impl Display for ExprError {
+    /// The message and the line the error is on,
+    /// with the error underlined.
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        let src = self.source.as_str();
+        let start = self.span.start.min(src.len());
+        let line_start =
+            src[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = src[start..]
+            .find('\n')
+            .map_or(src.len(), |i| start + i);
+        let end = self.span.end.clamp(start, line_end);
+        let col = src[line_start..start].chars().count();
+        let width = src[start..end].chars().count().max(1);
+        write!(
+            f,
+            "{}\n{}\n{}{}",
+            self.message,
+            &src[line_start..line_end],
+            " ".repeat(col),
+            "^".repeat(width)
+        )
+    }
+}
+
+/// Names are matched regardless of case,
+/// spaces and punctuation.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Ident(String),
+    Quoted(String),
+    Symbol(&'static str),
+    Newline,
+}
+impl Display for Token {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "`{n}`"),
+            Token::Ident(ident) => write!(f, "`{ident}`"),
+            Token::Quoted(name) => write!(f, "\"{name}\""),
+            Token::Symbol(sym) => write!(f, "`{sym}`"),
+            Token::Newline => write!(f, "the end of the line"),
+        }
+    }
+}
+
+/// Longer symbols first, so they're matched first.
+const SYMBOLS: [&str; 20] = [
+    "||", "&&", "==", "!=", "<=", ">=", "+=", "-=", "!", "<",
+    ">", "+", "-", "*", "/", "=", "(", ")", ".", ";",
+];
+
+fn lex(
+    src: &str,
+) -> Result<Vec<(Token, Range<usize>)>, ExprError> {
+    let error =
+        |message: String, span: Range<usize>| ExprError {
+            message,
+            span,
+            source: src.to_string(),
+        };
+    let scan = |start: usize, f: fn(char) -> bool| {
+        src[start..]
+            .find(|c: char| !f(c))
+            .map_or(src.len(), |i| start + i)
+    };
+
+    let mut tokens = vec![];
+    let mut i = 0;
+    while let Some(c) = src[i..].chars().next() {
+        let start = i;
+        if c == '\n' {
+            i += 1;
+            tokens.push((Token::Newline, start..i));
+        } else if c.is_whitespace() {
+            i += c.len_utf8();
+        } else if c.is_ascii_digit() {
+            i = scan(start, |c| c.is_ascii_digit() || c == '_');
+            if src[i..].starts_with('.')
+                && src[i + 1..]
+                    .starts_with(|c: char| c.is_ascii_digit())
+            {
+                i = scan(i + 1, |c| {
+                    c.is_ascii_digit() || c == '_'
+                });
+            }
+            let number = src[start..i].replace('_', "");
+            let number = number.parse().map_err(|_| {
+                error(
+                    format!("`{number}` isn't a number."),
+                    start..i,
+                )
+            })?;
+            tokens.push((Token::Number(number), start..i));
+        } else if c.is_alphabetic() || c == '_' {
+            i = scan(start, |c| {
+                c.is_alphanumeric() || c == '_'
+            });
+            tokens.push((
+                Token::Ident(src[start..i].to_string()),
+                start..i,
+            ));
+        } else if c == '"' {
+            let Some(len) = src[start + 1..].find('"') else {
+                return Err(error(
+                    "This name is missing its closing `\"`."
+                        .into(),
+                    start..src.len(),
+                ));
+            };
+            i = start + 1 + len + 1;
+            tokens.push((
+                Token::Quoted(
+                    src[start + 1..i - 1].to_string(),
+                ),
+                start..i,
+            ));
+        } else if let Some(sym) = SYMBOLS
+            .iter()
+            .find(|sym| src[i..].starts_with(**sym))
+        {
+            i += sym.len();
+            tokens.push((Token::Symbol(sym), start..i));
+        } else {
+            return Err(error(
+                format!("Unexpected `{c}`."),
+                start..start + c.len_utf8(),
+            ));
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Type {
+    Number,
+    Bool,
+}
+impl Display for Type {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Type::Number => write!(f, "a number"),
+            Type::Bool => write!(f, "true or false"),
+        }
+    }
+}
+
+/// A name of a project, process, etc.,
+/// which is looked up when it's needed.
+#[derive(Debug, Clone, PartialEq)]
+struct Name {
+    name: String,
+    span: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProjectField {
+    Active,
+    Finished,
+    Building,
+    Level,
+    Progress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProcessField {
+    MixShare,
+    Output,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Read {
+    World(WorldVariable),
+    Player(PlayerVariable),
+    Local(LocalVariable, Range<usize>),
+    Runs,
+    Flag(Flag),
+    Variable(Name),
+    Project(Name, ProjectField),
+    Process(Name, ProcessField),
+    Relationship(Name),
+    TippingPoint(Name),
+}
+impl Read {
+    fn ty(&self) -> Type {
+        match self {
+            Read::Flag(_)
+            | Read::TippingPoint(_)
+            | Read::Project(
+                _,
+                ProjectField::Active
+                | ProjectField::Finished
+                | ProjectField::Building,
+            ) => Type::Bool,
+            _ => Type::Number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Arith {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Booleans are evaluated as `1.` and `0.`,
+/// which the types keep from being mixed up
+/// with numbers.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f32),
+    Read(Read),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Arith(Box<Expr>, Arith, Box<Expr>),
+    Compare(Box<Expr>, Comparator, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A parsed expression with its type and
+/// where it is in the source.
+struct Parsed {
+    expr: Expr,
+    ty: Type,
+    span: Range<usize>,
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<(Token, Range<usize>)>,
+    pos: usize,
+}
+impl Parser<'_> {
+    fn error(
+        &self,
+        message: impl Into<String>,
+        span: Range<usize>,
+    ) -> ExprError {
+        ExprError {
+            message: message.into(),
+            span,
+            source: self.src.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    /// Where the next token is, or the end of the source.
+    fn here(&self) -> Range<usize> {
+        self.tokens.get(self.pos).map_or(
+            self.src.len()..self.src.len(),
+            |(_, span)| span.clone(),
+        )
+    }
+
+    fn next(&mut self) -> Option<(Token, Range<usize>)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, sym: &str) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol(sym))) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(
+        &mut self,
+        sym: &str,
+        what: &str,
+    ) -> Result<Range<usize>, ExprError> {
+        let span = self.here();
+        if self.eat(sym) {
+            Ok(span)
+        } else {
+            Err(self.unexpected(&format!("`{sym}` {what}")))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ExprError {
+        let found = match self.peek() {
+            Some(token) => token.to_string(),
+            None => "the end".into(),
+        };
+        self.error(
+            format!("Expected {expected}, but found {found}."),
+            self.here(),
+        )
+    }
+
+    fn check_type(
+        &self,
+        parsed: &Parsed,
+        ty: Type,
+        what: &str,
+    ) -> Result<(), ExprError> {
+        if parsed.ty == ty {
+            Ok(())
+        } else {
+            Err(self.error(
+                format!(
+                    "{what} should be {ty}, but this is {}.",
+                    parsed.ty
+                ),
+                parsed.span.clone(),
+            ))
+        }
+    }
+
+    fn or(&mut self) -> Result<Parsed, ExprError> {
+        let mut left = self.and()?;
+        while self.eat("||") {
+            let right = self.and()?;
+            for side in [&left, &right] {
+                self.check_type(
+                    side,
+                    Type::Bool,
+                    "`||`'s sides",
+                )?;
+            }
+            left = Parsed {
+                span: left.span.start..right.span.end,
+                expr: Expr::Or(
+                    Box::new(left.expr),
+                    Box::new(right.expr),
+                ),
+                ty: Type::Bool,
+            };
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Parsed, ExprError> {
+        let mut left = self.comparison()?;
+        while self.eat("&&") {
+            let right = self.comparison()?;
+            for side in [&left, &right] {
+                self.check_type(
+                    side,
+                    Type::Bool,
+                    "`&&`'s sides",
+                )?;
+            }
+            left = Parsed {
+                span: left.span.start..right.span.end,
+                expr: Expr::And(
+                    Box::new(left.expr),
+                    Box::new(right.expr),
+                ),
+                ty: Type::Bool,
+            };
+        }
+        Ok(left)
+    }
+
+    fn comparison(&mut self) -> Result<Parsed, ExprError> {
+        let left = self.sum()?;
+        let comp = match self.peek() {
+            Some(Token::Symbol(sym)) => match *sym {
+                "<" => Comparator::Less,
+                "<=" => Comparator::LessEqual,
+                "==" => Comparator::Equal,
+                "!=" => Comparator::NotEqual,
+                ">=" => Comparator::GreaterEqual,
+                ">" => Comparator::Greater,
+                "=" => {
+                    return Err(self.error(
+                        "Use `==` to compare values.",
+                        self.here(),
+                    ))
+                }
+                _ => return Ok(left),
+            },
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.sum()?;
+        let equality = matches!(
+            comp,
+            Comparator::Equal | Comparator::NotEqual
+        );
+        if equality {
+            self.check_type(&right, left.ty, "Both sides")?;
+        } else {
+            for side in [&left, &right] {
+                self.check_type(
+                    side,
+                    Type::Number,
+                    "Compared values",
+                )?;
+            }
+        }
+        Ok(Parsed {
+            span: left.span.start..right.span.end,
+            expr: Expr::Compare(
+                Box::new(left.expr),
+                comp,
+                Box::new(right.expr),
+            ),
+            ty: Type::Bool,
+        })
+    }
+
+    fn sum(&mut self) -> Result<Parsed, ExprError> {
+        let mut left = self.product()?;
+        loop {
+            let op = if self.eat("+") {
+                Arith::Add
+            } else if self.eat("-") {
+                Arith::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.product()?;
+            left = self.arith(left, op, right)?;
+        }
+    }
+
+    fn product(&mut self) -> Result<Parsed, ExprError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat("*") {
+                Arith::Mul
+            } else if self.eat("/") {
+                Arith::Div
+            } else {
+                return Ok(left);
+            };
+            let right = self.unary()?;
+            left = self.arith(left, op, right)?;
+        }
+    }
+
+    fn arith(
+        &self,
+        left: Parsed,
+        op: Arith,
+        right: Parsed,
+    ) -> Result<Parsed, ExprError> {
+        for side in [&left, &right] {
+            self.check_type(side, Type::Number, "Arithmetic")?;
+        }
+        Ok(Parsed {
+            span: left.span.start..right.span.end,
+            expr: Expr::Arith(
+                Box::new(left.expr),
+                op,
+                Box::new(right.expr),
+            ),
+            ty: Type::Number,
+        })
+    }
+
+    fn unary(&mut self) -> Result<Parsed, ExprError> {
+        let start = self.here().start;
+        if self.eat("!") {
+            let inner = self.unary()?;
+            self.check_type(&inner, Type::Bool, "`!`'s value")?;
+            Ok(Parsed {
+                span: start..inner.span.end,
+                expr: Expr::Not(Box::new(inner.expr)),
+                ty: Type::Bool,
+            })
+        } else if self.eat("-") {
+            let inner = self.unary()?;
+            self.check_type(
+                &inner,
+                Type::Number,
+                "`-`'s value",
+            )?;
+            Ok(Parsed {
+                span: start..inner.span.end,
+                expr: Expr::Neg(Box::new(inner.expr)),
+                ty: Type::Number,
+            })
+        } else {
+            self.atom()
+        }
+    }
+
+    fn atom(&mut self) -> Result<Parsed, ExprError> {
+        let span = self.here();
+        match self.peek().cloned() {
+            Some(Token::Number(n)) => {
+                self.pos += 1;
+                Ok(Parsed {
+                    expr: Expr::Number(n),
+                    ty: Type::Number,
+                    span,
+                })
+            }
+            Some(Token::Symbol("(")) => {
+                self.pos += 1;
+                let inner = self.or()?;
+                let end =
+                    self.expect(")", "to close the `(`")?;
+                Ok(Parsed {
+                    span: span.start..end.end,
+                    ..inner
+                })
+            }
+            Some(Token::Ident(ident)) => {
+                let bool = match ident.as_str() {
+                    "true" => Some(1.),
+                    "false" => Some(0.),
+                    _ => None,
+                };
+                if let Some(value) = bool {
+                    self.pos += 1;
+                    return Ok(Parsed {
+                        expr: Expr::Number(value),
+                        ty: Type::Bool,
+                        span,
+                    });
+                }
+                let (read, span) = self.read()?;
+                Ok(Parsed {
+                    ty: read.ty(),
+                    expr: Expr::Read(read),
+                    span,
+                })
+            }
+            _ => Err(self.unexpected("a value")),
+        }
+    }
+
+    /// The name in `kind(name)`.
+    fn name(&mut self, kind: &str) -> Result<Name, ExprError> {
+        self.expect("(", &format!("after `{kind}`"))?;
+        let name = match self.next() {
+            Some((
+                Token::Ident(name) | Token::Quoted(name),
+                span,
+            )) => Name { name, span },
+            _ => {
+                self.pos -= 1;
+                return Err(self.unexpected(&format!(
+                    "the name of a {kind}"
+                )));
+            }
+        };
+        self.expect(")", "after the name")?;
+        Ok(name)
+    }
+
+    /// The `field` in `kind(name).field`.
+    fn field(
+        &mut self,
+        kind: &str,
+        fields: &[&str],
+    ) -> Result<(usize, Range<usize>), ExprError> {
+        let options = fields
+            .iter()
+            .map(|field| format!("`{field}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.expect(
+            ".",
+            &format!("and one of {options} after the {kind}"),
+        )?;
+        match self.next() {
+            Some((Token::Ident(field), span)) => fields
+                .iter()
+                .position(|f| *f == field)
+                .map(|idx| (idx, span.clone()))
+                .ok_or_else(|| {
+                    self.error(
+                        format!(
+                            "A {kind} doesn't have `{field}`; it has {options}."
+                        ),
+                        span,
+                    )
+                }),
+            _ => {
+                self.pos -= 1;
+                Err(self.unexpected(&options))
+            }
+        }
+    }
+
+    /// Something to read, e.g. `temperature`
+    /// or `project(Name).active`.
+    fn read(
+        &mut self,
+    ) -> Result<(Read, Range<usize>), ExprError> {
+        let Some((Token::Ident(ident), span)) = self.next()
+        else {
+            unreachable!("Only called for identifiers");
+        };
+        let start = span.start;
+        let read = match ident.as_str() {
+            "runs" => Read::Runs,
+            "flag" => {
+                let name = self.name("flag")?;
+                let flag = Flag::iter()
+                    .find(|flag| {
+                        let key: &'static str = flag.into();
+                        normalize(key) == normalize(&name.name)
+                    })
+                    .ok_or_else(|| {
+                        self.error(
+                            format!(
+                                "There's no flag named \"{}\".",
+                                name.name
+                            ),
+                            name.span,
+                        )
+                    })?;
+                Read::Flag(flag)
+            }
+            "var" | "variable" => {
+                Read::Variable(self.name("variable")?)
+            }
+            "npc" => {
+                let name = self.name("npc")?;
+                self.field("NPC", &["relationship"])?;
+                Read::Relationship(name)
+            }
+            "tipping_point" => {
+                Read::TippingPoint(self.name("tipping point")?)
+            }
+            "project" => {
+                let name = self.name("project")?;
+                let (field, _) = self.field(
+                    "project",
+                    &[
+                        "active", "finished", "building",
+                        "level", "progress",
+                    ],
+                )?;
+                let field = [
+                    ProjectField::Active,
+                    ProjectField::Finished,
+                    ProjectField::Building,
+                    ProjectField::Level,
+                    ProjectField::Progress,
+                ][field];
+                Read::Project(name, field)
+            }
+            "process" => {
+                let name = self.name("process")?;
+                let (field, _) = self.field(
+                    "process",
+                    &["mix_share", "output"],
+                )?;
+                let field = [
+                    ProcessField::MixShare,
+                    ProcessField::Output,
+                ][field];
+                Read::Process(name, field)
+            }
+            "region" => {
+                let fields: Vec<String> = LocalVariable::iter()
+                    .map(|var| snake_case(var.into()))
+                    .collect();
+                let fields: Vec<&str> =
+                    fields.iter().map(String::as_str).collect();
+                let (field, _) =
+                    self.field("region", &fields)?;
+                let var = LocalVariable::iter()
+                    .nth(field)
+                    .expect("Fields are the local variables");
+                Read::Local(var, span.clone())
+            }
+            _ => {
+                let world = WorldVariable::iter().find(|var| {
+                    snake_case(var.into()) == ident
+                });
+                let player =
+                    PlayerVariable::iter().find(|var| {
+                        snake_case(var.into()) == ident
+                    });
+                match (world, player) {
+                    (Some(var), _) => Read::World(var),
+                    (_, Some(var)) => Read::Player(var),
+                    _ => {
+                        return Err(self.error(
+                            format!(
+                            "There's no value named `{ident}`."
+                        ),
+                            span,
+                        ))
+                    }
+                }
+            }
+        };
+        let end = self.tokens[self.pos - 1].1.end;
+        Ok((read, start..end))
+    }
+}
+
+fn symbol(sym: &str) -> &'static str {
+    SYMBOLS
+        .iter()
+        .find(|s| **s == sym)
+        .expect("Only known symbols are used")
+}
+
+/// E.g. `SeaLevelRise` to `sea_level_rise`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Look up something by its name.
+fn find<'a, T>(
+    items: impl IntoIterator<Item = &'a T>,
+    name: &str,
+    item_name: impl Fn(&T) -> &str,
+) -> Option<&'a T>
+where
+    T: 'a,
+{
+    let name = normalize(name);
+    items
+        .into_iter()
+        .find(|item| normalize(item_name(item)) == name)
+}
+
+/// Evaluates parsed expressions, with the
+/// source to point any errors at, since names
+/// are only looked up when they're evaluated.
+struct Source<'a> {
+    src: &'a str,
+}
+impl Source<'_> {
+    fn error(
+        &self,
+        message: String,
+        span: &Range<usize>,
+    ) -> ExprError {
+        ExprError {
+            message,
+            span: span.clone(),
+            source: self.src.to_string(),
+        }
+    }
+
+    fn not_found(&self, kind: &str, name: &Name) -> ExprError {
+        self.error(
+            format!(
+                "There's no {kind} named \"{}\".",
+                name.name
+            ),
+            &name.span,
+        )
+    }
+
+    fn eval(
+        &self,
+        expr: &Expr,
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Result<f32, ExprError> {
+        let eval = |expr| self.eval(expr, state, region_id);
+        let bool = |value: bool| if value { 1. } else { 0. };
+        Ok(match expr {
+            Expr::Number(n) => *n,
+            Expr::Read(read) => {
+                self.read(read, state, region_id)?
+            }
+            Expr::Not(inner) => bool(eval(inner)? == 0.),
+            Expr::Neg(inner) => -eval(inner)?,
+            Expr::Arith(a, op, b) => {
+                let (a, b) = (eval(a)?, eval(b)?);
+                match op {
+                    Arith::Add => a + b,
+                    Arith::Sub => a - b,
+                    Arith::Mul => a * b,
+                    Arith::Div => a / b,
+                }
+            }
+            Expr::Compare(a, comp, b) => {
+                bool(comp.eval(eval(a)?, eval(b)?))
+            }
+            Expr::And(a, b) => {
+                bool(eval(a)? != 0. && eval(b)? != 0.)
+            }
+            Expr::Or(a, b) => {
+                bool(eval(a)? != 0. || eval(b)? != 0.)
+            }
+        })
+    }
+
+    fn read(
+        &self,
+        read: &Read,
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Result<f32, ExprError> {
+        let bool = |value: bool| if value { 1. } else { 0. };
+        Ok(match read {
+            Read::World(var) => var.value(state),
+            Read::Player(var) => var.value(state),
+            Read::Runs => state.runs as f32,
+            Read::Flag(flag) => {
+                bool(state.flags.contains(flag))
+            }
+            Read::Local(var, span) => {
+                let region = region_id
+                    .and_then(|id| state.world.regions.try_get(&id))
+                    .ok_or_else(|| {
+                        self.error(
+                            "`region` is only available for regional events.".into(),
+                            span,
+                        )
+                    })?;
+                var.value(region)
+            }
+            Read::Variable(name) => {
+                find(
+                    state.world.variables.iter(),
+                    &name.name,
+                    |var| &var.name,
+                )
+                .ok_or_else(|| {
+                    self.not_found("variable", name)
+                })?
+                .value
+            }
+            Read::Relationship(name) => {
+                find(state.npcs.iter(), &name.name, |npc| {
+                    &npc.name
+                })
+                .ok_or_else(|| self.not_found("NPC", name))?
+                .relationship
+            }
+            Read::TippingPoint(name) => bool(
+                find(
+                    state.world.tipping_points.iter(),
+                    &name.name,
+                    |point| &point.name,
+                )
+                .ok_or_else(|| {
+                    self.not_found("tipping point", name)
+                })?
+                .crossed,
+            ),
+            Read::Project(name, field) => {
+                let project = find(
+                    state.world.projects.iter(),
+                    &name.name,
+                    |project| &project.name,
+                )
+                .ok_or_else(|| {
+                    self.not_found("project", name)
+                })?;
+                match field {
+                    // As with `Condition::ProjectStatus`,
+                    // finished projects count as active.
+                    ProjectField::Active => bool(matches!(
+                        project.status,
+                        Status::Active | Status::Finished
+                    )),
+                    ProjectField::Finished => {
+                        bool(project.status == Status::Finished)
+                    }
+                    ProjectField::Building => {
+                        bool(project.status == Status::Building)
+                    }
+                    ProjectField::Level => project.level as f32,
+                    ProjectField::Progress => project.progress,
+                }
+            }
+            Read::Process(name, field) => {
+                let process = find(
+                    state.world.processes.iter(),
+                    &name.name,
+                    |process| &process.name,
+                )
+                .ok_or_else(|| {
+                    self.not_found("process", name)
+                })?;
+                match field {
+                    ProcessField::MixShare => {
+                        process.mix_percent()
+                    }
+                    ProcessField::Output => state
+                        .produced
+                        .by_process
+                        .get(&process.id)
+                        .copied()
+                        .unwrap_or_default(),
+                }
+            }
+        })
+    }
+
+    /// Check that the names refer to something in the world.
+    fn check(
+        &self,
+        expr: &Expr,
+        world: &World,
+    ) -> Result<(), ExprError> {
+        match expr {
+            Expr::Number(_) => Ok(()),
+            Expr::Read(read) => self.check_read(read, world),
+            Expr::Not(inner) | Expr::Neg(inner) => {
+                self.check(inner, world)
+            }
+            Expr::Arith(a, _, b)
+            | Expr::Compare(a, _, b)
+            | Expr::And(a, b)
+            | Expr::Or(a, b) => {
+                self.check(a, world)?;
+                self.check(b, world)
+            }
+        }
+    }
+
+    fn check_read(
+        &self,
+        read: &Read,
+        world: &World,
+    ) -> Result<(), ExprError> {
+        let (kind, name, found) = match read {
+            Read::Variable(name) => (
+                "variable",
+                name,
+                find(
+                    world.variables.iter(),
+                    &name.name,
+                    |var| &var.name,
+                )
+                .is_some(),
+            ),
+            Read::Relationship(name) => (
+                "NPC",
+                name,
+                find(NPC::load().iter(), &name.name, |npc| {
+                    &npc.name
+                })
+                .is_some(),
+            ),
+            Read::TippingPoint(name) => (
+                "tipping point",
+                name,
+                find(
+                    world.tipping_points.iter(),
+                    &name.name,
+                    |point| &point.name,
+                )
+                .is_some(),
+            ),
+            Read::Project(name, _) => (
+                "project",
+                name,
+                find(world.projects.iter(), &name.name, |p| {
+                    &p.name
+                })
+                .is_some(),
+            ),
+            Read::Process(name, _) => (
+                "process",
+                name,
+                find(world.processes.iter(), &name.name, |p| {
+                    &p.name
+                })
+                .is_some(),
+            ),
+            _ => return Ok(()),
+        };
+        if found {
+            Ok(())
+        } else {
+            Err(self.not_found(kind, name))
+        }
+    }
+}
+
+fn uses_region(expr: &Expr) -> bool {
+    match expr {
+        Expr::Number(_) => false,
+        Expr::Read(read) => matches!(read, Read::Local(..)),
+        Expr::Not(inner) | Expr::Neg(inner) => {
+            uses_region(inner)
+        }
+        Expr::Arith(a, _, b)
+        | Expr::Compare(a, _, b)
+        | Expr::And(a, b)
+        | Expr::Or(a, b) => uses_region(a) || uses_region(b),
+    }
+}
+
+/// A condition, e.g. `temperature > 2 && flag(Vegan)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionExpr {
+    source: String,
+    expr: Expr,
+}
+impl ConditionExpr {
+    pub fn parse(src: &str) -> Result<Self, ExprError> {
+        let mut tokens = lex(src)?;
+        tokens.retain(|(token, _)| *token != Token::Newline);
+        let mut parser = Parser {
+            src,
+            tokens,
+            pos: 0,
+        };
+        if parser.peek().is_none() {
+            return Err(
+                parser.error("The condition is empty.", 0..0)
+            );
+        }
+        let parsed = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(
+                parser.unexpected("the end of the condition")
+            );
+        }
+        parser.check_type(
+            &parsed,
+            Type::Bool,
+            "The condition",
+        )?;
+        Ok(ConditionExpr {
+            source: src.to_string(),
+            expr: parsed.expr,
+        })
+    }
+
+    fn source(&self) -> Source<'_> {
+        Source { src: &self.source }
+    }
+
+    pub fn eval(
+        &self,
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Result<bool, ExprError> {
+        let value =
+            self.source().eval(&self.expr, state, region_id)?;
+        Ok(value != 0.)
+    }
+
+    /// If the condition reads any of the region's values.
+    pub fn is_regional(&self) -> bool {
+        uses_region(&self.expr)
+    }
+
+    /// Check that the names it uses are in the world.
+    pub fn check(
+        &self,
+        world: &World,
+    ) -> Result<(), ExprError> {
+        self.source().check(&self.expr, world)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    World(WorldVariable),
+    Player(PlayerVariable),
+    Variable(Name),
+    Flag(Flag),
+    Relationship(Name),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Assign {
+    Set,
+    Add,
+    Sub,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Statement {
+    target: Target,
+    assign: Assign,
+    value: Expr,
+}
+
+/// Effects, e.g. `outlook -= temperature * 2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectsExpr {
+    source: String,
+    statements: Vec<Statement>,
+}
+impl EffectsExpr {
+    pub fn parse(src: &str) -> Result<Self, ExprError> {
+        let mut parser = Parser {
+            src,
+            tokens: lex(src)?,
+            pos: 0,
+        };
+        let mut statements = vec![];
+        loop {
+            // Skip any blank lines and extra `;`s.
+            while matches!(
+                parser.peek(),
+                Some(Token::Newline | Token::Symbol(";"))
+            ) {
+                parser.pos += 1;
+            }
+            if parser.peek().is_none() {
+                break;
+            }
+            statements.push(Self::statement(&mut parser)?);
+            match parser.peek() {
+                None
+                | Some(Token::Newline)
+                | Some(Token::Symbol(";")) => {}
+                _ => {
+                    return Err(parser.unexpected(
+                        "a new line or `;` after the effect",
+                    ))
+                }
+            }
+        }
+        if statements.is_empty() {
+            return Err(
+                parser.error("There are no effects.", 0..0)
+            );
+        }
+        Ok(EffectsExpr {
+            source: src.to_string(),
+            statements,
+        })
+    }
+
+    fn statement(
+        parser: &mut Parser,
+    ) -> Result<Statement, ExprError> {
+        let start = parser.here();
+        if !matches!(parser.peek(), Some(Token::Ident(_))) {
+            return Err(parser.unexpected("a value to change"));
+        }
+        let (read, span) = parser.read()?;
+        let target = match read {
+            Read::World(var) => Target::World(var),
+            Read::Player(var) => Target::Player(var),
+            Read::Variable(name) => Target::Variable(name),
+            Read::Flag(flag) => Target::Flag(flag),
+            Read::Relationship(name) => {
+                Target::Relationship(name)
+            }
+            _ => {
+                return Err(parser.error(
+                    format!(
+                        "`{}` can't be changed.",
+                        &parser.src[span.clone()]
+                    ),
+                    start.start..span.end,
+                ))
+            }
+        };
+
+        let assign_span = parser.here();
+        let assign = if parser.eat("=") {
+            Assign::Set
+        } else if parser.eat("+=") {
+            Assign::Add
+        } else if parser.eat("-=") {
+            Assign::Sub
+        } else {
+            return Err(parser
+                .unexpected("`=`, `+=` or `-=` to change it"));
+        };
+        let settable = matches!(
+            target,
+            Target::Variable(_) | Target::Flag(_)
+        );
+        let changeable = !matches!(target, Target::Flag(_));
+        let allowed = match assign {
+            Assign::Set => settable,
+            Assign::Add | Assign::Sub => changeable,
+        };
+        if !allowed {
+            let how = if settable {
+                "can only be set with `=`"
+            } else {
+                "can only be changed with `+=` or `-=`"
+            };
+            return Err(parser.error(
+                format!("`{}` {how}.", &parser.src[span]),
+                assign_span,
+            ));
+        }
+
+        let value = parser.or()?;
+        let ty = match target {
+            Target::Flag(_) => Type::Bool,
+            _ => Type::Number,
+        };
+        parser.check_type(&value, ty, "The new value")?;
+        Ok(Statement {
+            target,
+            assign,
+            value: value.expr,
+        })
+    }
+
+    fn source(&self) -> Source<'_> {
+        Source { src: &self.source }
+    }
+
+    /// The effects to apply, with their
+    /// values read from the state.
+    pub fn effects(
+        &self,
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Result<Vec<Effect>, ExprError> {
+        let source = self.source();
+        let mut effects = vec![];
+        for statement in &self.statements {
+            let value = source.eval(
+                &statement.value,
+                state,
+                region_id,
+            )?;
+            let change = match statement.assign {
+                Assign::Sub => -value,
+                _ => value,
+            };
+            let effect = match &statement.target {
+                Target::World(var) => {
+                    Effect::WorldVariable(*var, change)
+                }
+                Target::Player(var) => {
+                    Effect::PlayerVariable(*var, change)
+                }
+                Target::Flag(flag) => {
+                    if value == 0. {
+                        continue;
+                    }
+                    Effect::AddFlag(*flag)
+                }
+                Target::Variable(name) => {
+                    let var = find(
+                        state.world.variables.iter(),
+                        &name.name,
+                        |var| &var.name,
+                    )
+                    .ok_or_else(|| {
+                        source.not_found("variable", name)
+                    })?;
+                    match statement.assign {
+                        Assign::Set => {
+                            Effect::SetVariable(var.id, value)
+                        }
+                        _ => Effect::ChangeVariable(
+                            var.id, change,
+                        ),
+                    }
+                }
+                Target::Relationship(name) => {
+                    let npc = find(
+                        state.npcs.iter(),
+                        &name.name,
+                        |npc| &npc.name,
+                    )
+                    .ok_or_else(|| {
+                        source.not_found("NPC", name)
+                    })?;
+                    Effect::NPCRelationship(npc.id, change)
+                }
+            };
+            effects.push(effect);
+        }
+        Ok(effects)
+    }
+
+    /// Check that the names it uses are in the world.
+    pub fn check(
+        &self,
+        world: &World,
+    ) -> Result<(), ExprError> {
+        let source = self.source();
+        for statement in &self.statements {
+            match &statement.target {
+                Target::Variable(name) => source.check_read(
+                    &Read::Variable(name.clone()),
+                    world,
+                )?,
+                Target::Relationship(name) => source
+                    .check_read(
+                        &Read::Relationship(name.clone()),
+                        world,
+                    )?,
+                _ => {}
+            }
+            source.check(&statement.value, world)?;
+        }
+        Ok(())
+    }
+}
+
+impl State {
+    /// Evaluate effect expressions,
+    /// returning the effects they add.
+    pub fn expr_effects(
+        &self,
+        src: &str,
+        region_id: Option<Id>,
+    ) -> Result<Vec<Effect>, ExprError> {
+        EffectsExpr::parse(src)?.effects(self, region_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::variables::Variable;
+
+    #[test]
+    fn test_condition_expr() {
+        let mut state = State::with_seed(World::default(), 0);
+        let project = state.world.projects.first().clone();
+        let eval = |src: &str, state: &State| {
+            ConditionExpr::parse(src)
+                .unwrap()
+                .eval(state, None)
+                .unwrap()
+        };
+
+        state.world.temperature = 2.5;
+        assert!(eval("temperature > 2.0", &state));
+        assert!(eval(
+            "-(temperature * 2) < -4 || false",
+            &state
+        ));
+        assert!(!eval("!(temperature >= 2) && true", &state));
+        let active = format!(
+            "temperature > 2 && project(\"{}\").active",
+            project.name
+        );
+        assert!(!eval(&active, &state));
+        state.world.projects[&project.id].status =
+            Status::Active;
+        assert!(eval(&active, &state));
+
+        // Names don't need their spaces or case.
+        let unquoted = format!(
+            "project({}).active == true",
+            normalize(&project.name)
+        );
+        assert!(eval(&unquoted, &state));
+
+        state.flags.push(Flag::Vegan);
+        assert!(eval("flag(vegan) && runs >= 0", &state));
+
+        let mut trust = Variable::new();
+        trust.name = "Public Trust".into();
+        trust.value = 3.;
+        state.world.variables.push(trust);
+        assert!(eval("var(PublicTrust) + 1 == 4", &state));
+
+        let regional =
+            ConditionExpr::parse("region.outlook > 0").unwrap();
+        assert!(regional.is_regional());
+        assert!(regional.eval(&state, None).is_err());
+        let region = state.world.regions.first().id;
+        assert!(regional.eval(&state, Some(region)).is_ok());
+
+        let missing =
+            ConditionExpr::parse("project(Nothing).active")
+                .unwrap();
+        assert!(missing.check(&state.world).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error =
+            |src: &str| ConditionExpr::parse(src).unwrap_err();
+
+        let err = error("temperature > 2 &&");
+        assert_eq!(
+            err.to_string(),
+            "Expected a value, but found the end.\ntemperature > 2 &&\n                  ^"
+        );
+        let err = error("temperature && true");
+        assert_eq!(err.span, 0..11);
+        assert!(err.message.contains("true or false"));
+        assert_eq!(
+            error("temperature = 2").message,
+            "Use `==` to compare values."
+        );
+        assert_eq!(
+            error("temprature > 2").message,
+            "There's no value named `temprature`."
+        );
+        assert!(error("project(X).cost > 1")
+            .message
+            .contains("`active`"));
+        assert!(error("temperature")
+            .message
+            .contains("condition"));
+        assert!(error("flag(Nope)")
+            .message
+            .contains("no flag"));
+    }
+
+    #[test]
+    fn test_effects_expr() {
+        let mut state = State::with_seed(World::default(), 0);
+        let mut trust = Variable::new();
+        trust.name = "Public Trust".into();
+        let trust_id = trust.id;
+        state.world.variables.push(trust);
+        state.world.temperature = 2.;
+
+        let effects = EffectsExpr::parse(
+            "political_capital += 10\noutlook -= temperature * 2; var(PublicTrust) = 5\n\nflag(Vegan) = temperature > 3",
+        )
+        .unwrap();
+        assert_eq!(
+            effects.effects(&state, None).unwrap(),
+            vec![
+                Effect::PlayerVariable(
+                    PlayerVariable::PoliticalCapital,
+                    10.
+                ),
+                Effect::WorldVariable(
+                    WorldVariable::Outlook,
+                    -4.
+                ),
+                Effect::SetVariable(trust_id, 5.),
+            ]
+        );
+
+        let error = |src: &str| {
+            EffectsExpr::parse(src).unwrap_err().message
+        };
+        assert!(
+            error("temperature = 1").contains("`+=` or `-=`")
+        );
+        assert!(error("flag(Vegan) += 1").contains("`=`"));
+        assert!(error("runs += 1").contains("can't be changed"));
+        assert!(error("outlook += 1 outlook += 2")
+            .contains("new line"));
+        assert!(error("").contains("no effects"));
+    }
+}
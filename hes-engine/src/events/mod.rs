@@ -1,12 +1,15 @@
+mod chain;
 mod condition;
 mod effects;
 mod events;
+pub mod expr;
 mod icons;
 mod probability;
 mod vars;
 
 pub use self::{
-    condition::{Condition, ConditionKind},
+    chain::FollowUp,
+    condition::{Comparator, Condition, ConditionKind},
     effects::{
         mean_demand_outlook_change,
         mean_income_outlook_change,
@@ -16,7 +19,7 @@ pub use self::{
         RegionFlag,
         Request,
     },
-    events::{Event, EventPool, Phase},
+    events::{Event, EventPool, Phase, Schedule, ScheduleKind},
     icons::{IconEvent, ICON_EVENTS},
     probability::{Likelihood, Probability},
     vars::{LocalVariable, PlayerVariable, WorldVariable},
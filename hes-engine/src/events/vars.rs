@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, EnumString, IntoStaticStr};
 
+use crate::{regions::Region, state::State};
+
 #[derive(
     Debug,
     Copy,
@@ -23,7 +25,22 @@ pub enum WorldVariable {
     SeaLevelRise,
     SeaLevelRiseRate,
     Precipitation,
+
+    /// Scales births, so changes take a
+    /// generation to fully show.
     PopulationGrowth,
+
+    /// The drop in the oceans' pH.
+    Acidification,
+
+    /// The share of water demand saved by efficiency.
+    WaterEfficiency,
+
+    /// The health of farmed soils, relative to the start.
+    SoilHealth,
+
+    /// How prepared the world is for disease outbreaks.
+    Preparedness,
 }
 
 #[derive(
@@ -42,6 +59,8 @@ pub enum LocalVariable {
     Population,
     Outlook,
     Habitability,
+    WaterStress,
+    AirPollution,
 }
 
 #[derive(
@@ -61,3 +80,79 @@ pub enum PlayerVariable {
     ResearchPoints,
     YearsToDeath,
 }
+
+impl WorldVariable {
+    pub fn value(&self, state: &State) -> f32 {
+        match self {
+            WorldVariable::Year => state.world.year as f32,
+            WorldVariable::Population => {
+                state.world.regions.population()
+            }
+            WorldVariable::PopulationGrowth => {
+                state.world.population_growth_modifier
+            }
+            WorldVariable::Emissions => {
+                state.emissions.as_co2eq()
+            }
+            WorldVariable::ExtinctionRate => {
+                state.world.extinction_rate
+            }
+            WorldVariable::Outlook => state.outlook(),
+            WorldVariable::Temperature => {
+                state.world.temperature
+            }
+            WorldVariable::SeaLevelRise => {
+                state.world.sea_level_rise
+            }
+            WorldVariable::SeaLevelRiseRate => {
+                state.world.sea_level_rise_rate()
+            }
+            WorldVariable::Precipitation => {
+                state.world.precipitation
+            }
+            WorldVariable::Acidification => {
+                state.world.acidification
+            }
+            WorldVariable::WaterEfficiency => {
+                state.world.water_efficiency
+            }
+            WorldVariable::SoilHealth => {
+                state.world.soil_health
+            }
+            WorldVariable::Preparedness => {
+                state.world.preparedness
+            }
+        }
+    }
+}
+
+impl LocalVariable {
+    pub fn value(&self, region: &Region) -> f32 {
+        match self {
+            LocalVariable::Population => region.population,
+            LocalVariable::Outlook => region.outlook,
+            LocalVariable::Habitability => {
+                region.habitability()
+            }
+            LocalVariable::WaterStress => region.water_stress(),
+            LocalVariable::AirPollution => region.air_pollution,
+        }
+    }
+}
+
+impl PlayerVariable {
+    pub fn value(&self, state: &State) -> f32 {
+        match self {
+            PlayerVariable::PoliticalCapital => {
+                state.political_capital as f32
+            }
+            PlayerVariable::ResearchPoints => {
+                state.research_points as f32
+            }
+            PlayerVariable::YearsToDeath => {
+                state.death_year as f32
+                    - state.world.year as f32
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use serde_bytes;
 use std::collections::BTreeMap;
 use strum::{EnumIter, EnumString, IntoStaticStr};
 
-use crate::events::{Condition, Effect};
+use crate::events::{Condition, Effect, FollowUp};
 
 pub type ProjectLockers = BTreeMap<usize, usize>;
 
@@ -28,6 +28,41 @@ impl Default for ImageData {
 pub struct Image {
     pub data: ImageData,
     pub attribution: String,
+
+    /// A description of the image for
+    /// those who can't see it.
+    #[serde(default)]
+    pub alt: String,
+
+    /// The license the image is used under,
+    /// e.g. "CC BY 4.0".
+    #[serde(default)]
+    pub license: String,
+}
+impl Image {
+    /// Who the image is by and under what license,
+    /// e.g. "Jane Doe (CC BY 4.0)".
+    pub fn credit(&self) -> String {
+        let attribution = self.attribution.trim();
+        let license = self.license.trim();
+        match (attribution.is_empty(), license.is_empty()) {
+            (_, true) => attribution.to_string(),
+            (true, false) => license.to_string(),
+            (false, false) => {
+                format!("{attribution} ({license})")
+            }
+        }
+    }
+}
+
+/// A sound clip, bundled with the world.
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Default,
+)]
+pub struct Audio {
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+    pub mime: String,
 }
 
 #[derive(
@@ -46,6 +81,15 @@ pub struct EventFlavor {
     pub arc: String,
     pub dialogue: Dialogue,
     pub image: Option<Image>,
+
+    /// Alternate images for the event. If the event has an
+    /// image, one of these or that image is shown at random.
+    #[serde(default)]
+    pub variants: Vec<Image>,
+
+    /// Played when the event occurs.
+    #[serde(default)]
+    pub sound: Option<Audio>,
 }
 
 #[derive(
@@ -53,6 +97,11 @@ pub struct EventFlavor {
 )]
 pub struct RegionFlavor {
     pub image: Image,
+
+    /// A representative location for the region,
+    /// as (latitude, longitude) in degrees.
+    #[serde(default)]
+    pub location: Option<(f32, f32)>,
 }
 
 #[derive(
@@ -124,6 +173,10 @@ pub struct Response {
 
     #[serde(default)]
     pub effects: Vec<Effect>,
+
+    /// Events to queue when this response is chosen.
+    #[serde(default)]
+    pub follow_ups: Vec<FollowUp>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -284,3 +337,32 @@ impl<'de> Deserialize<'de> for Speaker {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_image_credit() {
+        let mut image = Image::default();
+        assert_eq!(image.credit(), "");
+
+        image.license = "CC BY 4.0".into();
+        assert_eq!(image.credit(), "CC BY 4.0");
+
+        image.attribution = "Jane Doe".into();
+        assert_eq!(image.credit(), "Jane Doe (CC BY 4.0)");
+    }
+
+    #[test]
+    fn test_image_without_alt() {
+        let json = r#"{
+            "data": {"File": "DEFAULT.jpg"},
+            "attribution": "Jane Doe"
+        }"#;
+        let image: Image = serde_json::from_str(json).unwrap();
+        assert_eq!(image.attribution, "Jane Doe");
+        assert!(image.alt.is_empty());
+        assert!(image.license.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+//! Descriptions of struct fields so that the editor can
+//! generate forms for them, see `#[derive(EditorForm)]`.
+
+use crate::Bounds;
+pub use hes_editor_derive::{Documented, EditorForm};
+
+// Used by the derive macro.
+#[doc(hidden)]
+pub use strum;
+
+/// The value of a form field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Number(f32),
+    Text(String),
+    Toggle(bool),
+
+    /// An enum variant, along with all the variants.
+    Choice {
+        value: &'static str,
+        options: Vec<&'static str>,
+    },
+}
+
+/// A field shown in a form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Field {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub help: &'static str,
+
+    /// The valid range, for numbers. Unsigned
+    /// numbers are always non-negative.
+    pub bounds: Option<Bounds>,
+}
+
+pub trait EditorForm {
+    /// The fields to show, in order.
+    fn fields() -> Vec<Field>;
+
+    /// Get the value of the named field.
+    fn get_field(&self, name: &str) -> Option<FieldValue>;
+
+    /// Set the value of the named field, returning `false`
+    /// if there's no such field or the value is the wrong kind.
+    fn set_field(
+        &mut self,
+        name: &str,
+        value: FieldValue,
+    ) -> bool;
+}
+
+/// Documentation for a type's fields, taken from their
+/// doc comments, see `#[derive(Documented)]`. The editor
+/// uses this for help text so that it stays in sync
+/// with what the fields actually do.
+pub trait Documented {
+    /// Each documented field's name and documentation.
+    fn field_docs()
+        -> &'static [(&'static str, &'static str)];
+
+    /// The named field's documentation,
+    /// or an empty string if there is none.
+    fn field_doc(name: &str) -> &'static str {
+        Self::field_docs()
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map_or("", |(_, doc)| doc)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Upgrade, World};
+
+    #[test]
+    fn test_upgrade_form() {
+        let fields = Upgrade::fields();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].name, "cost");
+        assert_eq!(fields[0].label, "Cost");
+        assert_eq!(
+            fields[0].bounds,
+            Some(Bounds::NON_NEGATIVE)
+        );
+
+        let mut upgrade = Upgrade {
+            cost: 10,
+            ..Default::default()
+        };
+        assert_eq!(
+            upgrade.get_field("cost"),
+            Some(FieldValue::Number(10.))
+        );
+        assert!(
+            upgrade.set_field("cost", FieldValue::Number(25.))
+        );
+        assert_eq!(upgrade.cost, 25);
+
+        // Wrong kind of value or unknown field.
+        assert!(!upgrade
+            .set_field("cost", FieldValue::Toggle(true)));
+        assert!(!upgrade
+            .set_field("effects", FieldValue::Number(1.)));
+        assert_eq!(upgrade.get_field("active"), None);
+    }
+
+    #[test]
+    fn test_field_docs() {
+        assert_eq!(
+            World::field_doc("sea_level_rise"),
+            "The starting sea level rise (meters)."
+        );
+        assert_eq!(World::field_doc("regions"), "");
+        assert_eq!(World::field_doc("not_a_field"), "");
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{
+    events::Phase,
+    kinds::{KindMap, Output},
+    production::ProcessFeature,
+    regions::Region,
+    Bounds,
+    Collection,
+    World,
+};
+
+/// How much optimism can raise or lower outlooks,
+/// as a fraction of the base world's.
+const OPTIMISM_RANGE: f32 = 0.75;
+
+/// High-level settings for scaffolding a new world
+/// from the base world, for the author to refine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldParams {
+    /// How many regions there are. Regions are merged
+    /// to get there, so there can't be more regions
+    /// than the base world has.
+    pub regions: usize,
+
+    /// From -1 for a mostly fossil energy mix
+    /// to 1 for a mostly renewable one.
+    pub energy_bias: f32,
+
+    /// How often crises happen relative to
+    /// the base world, e.g. 2 for twice as often.
+    pub crisis_frequency: f32,
+
+    /// From -1 for a despairing public
+    /// to 1 for a hopeful one.
+    pub optimism: f32,
+}
+impl WorldParams {
+    pub const ENERGY_BIAS: Bounds = Bounds::new(-1., 1., 0.1);
+    pub const CRISIS_FREQUENCY: Bounds =
+        Bounds::new(0.25, 4., 0.25);
+    pub const OPTIMISM: Bounds = Bounds::new(-1., 1., 0.1);
+}
+
+impl World {
+    /// Scaffold a new world from the base world.
+    pub fn generate(params: &WorldParams) -> World {
+        let mut world = World::default();
+        world.merge_regions(params.regions);
+        world.bias_energy_mix(
+            WorldParams::ENERGY_BIAS.clamp(params.energy_bias),
+        );
+
+        let frequency = WorldParams::CRISIS_FREQUENCY
+            .clamp(params.crisis_frequency);
+        for event in world.events.iter_mut() {
+            if matches!(
+                event.phase,
+                Phase::WorldMain | Phase::Icon
+            ) {
+                event.prob_modifier *= frequency;
+            }
+        }
+
+        let outlook = 1.
+            + WorldParams::OPTIMISM.clamp(params.optimism)
+                * OPTIMISM_RANGE;
+        world.base_outlook *= outlook;
+        for region in world.regions.iter_mut() {
+            region.outlook *= outlook;
+        }
+        world
+    }
+
+    /// Merge regions until there are only `count`, each
+    /// time merging the least populous region into the
+    /// least populous one at the same latitude, if any.
+    fn merge_regions(&mut self, count: usize) {
+        let mut regions = self.regions.values().to_vec();
+        while regions.len() > count.max(1) {
+            let Some(smallest) =
+                least_populous(regions.iter().enumerate())
+            else {
+                break;
+            };
+            let region = regions.remove(smallest);
+            let into = least_populous(
+                regions.iter().enumerate().filter(|(_, r)| {
+                    r.latitude == region.latitude
+                }),
+            )
+            .or_else(|| {
+                least_populous(regions.iter().enumerate())
+            })
+            .expect("There's always another region");
+            absorb(&mut regions[into], region);
+        }
+        self.regions = Collection::from(regions);
+    }
+
+    /// Shift electricity and fuel mix shares towards
+    /// fossil (negative bias) or other processes
+    /// (positive bias), keeping each output's total.
+    fn bias_energy_mix(&mut self, bias: f32) {
+        for output in [Output::Electricity, Output::Fuel] {
+            let processes = self
+                .processes
+                .iter_mut()
+                .filter(|p| p.output == output)
+                .collect::<Vec<_>>();
+            let total: usize =
+                processes.iter().map(|p| p.mix_share).sum();
+            let weights = processes
+                .iter()
+                .map(|p| {
+                    let fossil = p
+                        .features
+                        .contains(&ProcessFeature::IsFossil);
+                    let factor = if fossil {
+                        1. - bias
+                    } else {
+                        1. + bias
+                    };
+                    p.mix_share as f32 * factor
+                })
+                .collect::<Vec<_>>();
+            let sum: f32 = weights.iter().sum();
+            if total == 0 || sum <= 0. {
+                continue;
+            }
+
+            let shares = apportion(&weights, sum, total);
+            for (process, share) in
+                processes.into_iter().zip(shares)
+            {
+                process.mix_share = share;
+            }
+        }
+    }
+}
+
+fn least_populous<'a>(
+    regions: impl Iterator<Item = (usize, &'a Region)>,
+) -> Option<usize> {
+    regions
+        .min_by(|(_, a), (_, b)| {
+            a.population.total_cmp(&b.population)
+        })
+        .map(|(idx, _)| idx)
+}
+
+/// Merge `other` into `region`, weighting
+/// its averages by population.
+fn absorb(region: &mut Region, other: Region) {
+    let population = region.population + other.population;
+    let weighted = |a: f32, b: f32| {
+        if population > 0. {
+            (a * region.population + b * other.population)
+                / population
+        } else {
+            a
+        }
+    };
+    let other_structure = other.age_structure();
+    let mut age_structure = region.age_structure();
+    for (cohort, share) in age_structure.items_mut() {
+        *share = weighted(*share, other_structure[cohort]);
+    }
+    region.age_structure = age_structure;
+    region.development =
+        weighted(region.development, other.development);
+    region.outlook = weighted(region.outlook, other.outlook);
+    region.base_habitability = weighted(
+        region.base_habitability,
+        other.base_habitability,
+    );
+    region.coastal_exposure = weighted(
+        region.coastal_exposure,
+        other.coastal_exposure,
+    );
+    region.vulnerability =
+        weighted(region.vulnerability, other.vulnerability);
+    region.continentality =
+        weighted(region.continentality, other.continentality);
+    for (kind, share) in region.land_use.items_mut() {
+        *share = weighted(*share, other.land_use[kind]);
+    }
+    region.temp_lo = region.temp_lo.min(other.temp_lo);
+    region.temp_hi = region.temp_hi.max(other.temp_hi);
+    region.precip_lo = region.precip_lo.min(other.precip_lo);
+    region.precip_hi = region.precip_hi.max(other.precip_hi);
+    region.pattern_idxs.extend(other.pattern_idxs);
+    region.population = population;
+}
+
+/// Split `total` into whole shares proportional to
+/// `weights`, giving what's left after rounding down
+/// to the shares with the largest remainders.
+fn apportion(
+    weights: &[f32],
+    sum: f32,
+    total: usize,
+) -> Vec<usize> {
+    let exact = weights
+        .iter()
+        .map(|w| w / sum * total as f32)
+        .collect::<Vec<_>>();
+    let mut shares = exact
+        .iter()
+        .map(|e| e.floor() as usize)
+        .collect::<Vec<_>>();
+    let remaining = total.saturating_sub(shares.iter().sum());
+    let mut by_remainder = (0..exact.len()).collect::<Vec<_>>();
+    by_remainder.sort_by(|a, b| {
+        (exact[*b] - exact[*b].floor())
+            .total_cmp(&(exact[*a] - exact[*a].floor()))
+    });
+    for idx in by_remainder.into_iter().take(remaining) {
+        shares[idx] += 1;
+    }
+    shares
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn energy_shares(world: &World, fossil: bool) -> usize {
+        world
+            .processes
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.output,
+                    Output::Electricity | Output::Fuel
+                ) && p
+                    .features
+                    .contains(&ProcessFeature::IsFossil)
+                    == fossil
+            })
+            .map(|p| p.mix_share)
+            .sum()
+    }
+
+    #[test]
+    fn test_generate() {
+        let base = World::default();
+        let world = World::generate(&WorldParams {
+            regions: 5,
+            energy_bias: 1.,
+            crisis_frequency: 2.,
+            optimism: 0.,
+        });
+
+        assert_eq!(world.regions.len(), 5);
+        let population = |world: &World| {
+            world
+                .regions
+                .iter()
+                .map(|r| r.population)
+                .sum::<f32>()
+        };
+        assert!(
+            (population(&world) - population(&base)).abs()
+                < population(&base) * 1e-4
+        );
+
+        assert_eq!(energy_shares(&world, true), 0);
+        assert_eq!(
+            energy_shares(&world, false),
+            energy_shares(&base, true)
+                + energy_shares(&base, false)
+        );
+        assert_eq!(world.base_outlook, base.base_outlook);
+        assert!(world
+            .events
+            .iter()
+            .filter(|e| e.phase == Phase::WorldMain)
+            .all(|e| e.prob_modifier == 2.));
+    }
+
+    #[test]
+    fn test_apportion() {
+        let shares = apportion(&[1., 1., 1.], 3., 20);
+        assert_eq!(shares.iter().sum::<usize>(), 20);
+        assert_eq!(shares, vec![7, 7, 6]);
+    }
+}
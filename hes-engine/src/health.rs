@@ -0,0 +1,189 @@
+//! Air quality and public health. The particulates in the
+//! air are spread over the regions by income level, as
+//! poorer regions burn dirtier, e.g. with fewer controls
+//! and more coal and biomass. Breathing dirtier air
+//! than at the start kills people, makes them less content
+//! and needs more healthcare, while cleaner air does the
+//! opposite. Children and the elderly are the most
+//! vulnerable to it. Only the change since the start counts,
+//! as the usual death rates already reflect the air then.
+
+use crate::{
+    kinds::CohortMap,
+    regions::Region,
+    state::State,
+    Collection,
+};
+
+/// Particulate concentration (µg/m3) people breathe on
+/// average for each Mt of particulates in the air.
+const CONCENTRATION_PER_MT: f32 = 0.7;
+
+/// How dirty the air people breathe is at each income
+/// level, relative to the high income level.
+const EXPOSURE_BY_INCOME: [f32; 4] = [4., 5.5, 4., 1.];
+
+/// The share of a region's population that dies each year
+/// for each µg/m3 past what it breathed at the start.
+const MORTALITY: f32 = 2e-5;
+
+/// How vulnerable each cohort is to dirty air,
+/// relative to the population as a whole.
+const AIR_VULNERABILITY: CohortMap = CohortMap {
+    children: 1.5,
+    adults: 0.5,
+    elderly: 4.,
+};
+
+/// Contentedness lost for each µg/m3
+/// people breathe on average.
+const AIR_OUTLOOK: f32 = 0.3;
+
+/// Electricity (kWh) needed each year for each person's
+/// healthcare for each µg/m3 past the start.
+const HEALTHCARE_ELECTRICITY: f32 = 3.;
+
+impl Region {
+    /// How much dirtier the region's air is than at the
+    /// start (µg/m3). Negative if it's cleaner.
+    pub fn excess_air_pollution(&self) -> f32 {
+        self.air_pollution - self.base_air_pollution
+    }
+
+    pub fn air_quality(&self) -> String {
+        format!("{}µg/m³", self.air_pollution.round())
+    }
+
+    fn air_exposure(&self) -> f32 {
+        EXPOSURE_BY_INCOME[self.income.level()]
+    }
+}
+
+impl Collection<Region> {
+    /// Particulate concentration (µg/m3) people
+    /// breathe, weighted by population.
+    pub fn air_pollution(&self) -> f32 {
+        let population = self.population();
+        if population <= 0. {
+            return 0.;
+        }
+        self.iter()
+            .map(|r| r.air_pollution * r.population)
+            .sum::<f32>()
+            / population
+    }
+
+    /// Electricity (kWh) needed for healthcare because of
+    /// air dirtier than at the start, or saved because of
+    /// air cleaner than it.
+    pub fn healthcare_demand(&self) -> f32 {
+        self.iter()
+            .filter(|r| !r.seceded)
+            .map(|r| {
+                r.population
+                    * r.excess_air_pollution()
+                    * HEALTHCARE_ELECTRICITY
+            })
+            .sum()
+    }
+}
+
+impl State {
+    /// Spread the particulates in the air over the regions,
+    /// returning the change in the concentration people
+    /// breathe on average.
+    pub(crate) fn update_air_pollution(&mut self) -> f32 {
+        let regions = &mut self.world.regions;
+        let prev = regions.air_pollution();
+        let population = regions.population();
+        let exposure = regions
+            .iter()
+            .map(|r| r.air_exposure() * r.population)
+            .sum::<f32>()
+            / population;
+        let concentration =
+            self.world.particulates * CONCENTRATION_PER_MT;
+        for region in regions.iter_mut() {
+            region.air_pollution = if exposure > 0. {
+                concentration * region.air_exposure() / exposure
+            } else {
+                0.
+            };
+        }
+        regions.air_pollution() - prev
+    }
+
+    /// Set the air regions breathe at the start, which
+    /// their population projections account for.
+    pub(crate) fn settle_air_pollution(&mut self) {
+        self.update_air_pollution();
+        for region in self.world.regions.iter_mut() {
+            region.base_air_pollution = region.air_pollution;
+        }
+    }
+
+    /// Update the air regions breathe, with dirtier air
+    /// killing more people and making them less content.
+    pub(crate) fn update_health(&mut self) {
+        let change = self.update_air_pollution();
+        self.world.base_outlook -= change * AIR_OUTLOOK;
+        for region in self.world.regions.iter_mut() {
+            let deaths =
+                region.excess_air_pollution() * MORTALITY;
+            region.kill(deaths, AIR_VULNERABILITY);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{events::LocalVariable, regions::Income};
+
+    #[test]
+    fn test_air_quality() {
+        let mut state = State::default();
+        let air = state.world.regions.air_pollution();
+        assert!(air > 0.);
+        assert_eq!(state.world.regions.healthcare_demand(), 0.);
+        let region = state.world.regions.first();
+        assert_eq!(
+            LocalVariable::AirPollution.value(region),
+            region.air_pollution
+        );
+
+        // Poorer regions breathe dirtier air.
+        let by_income = |state: &State, income: Income| {
+            state
+                .world
+                .regions
+                .iter()
+                .find(|r| r.income == income)
+                .map(|r| r.air_pollution)
+                .unwrap()
+        };
+        assert!(
+            by_income(&state, Income::LowerMiddle)
+                > by_income(&state, Income::High)
+        );
+
+        // Dirtier air kills people, makes them less
+        // content and needs more healthcare.
+        let population = state.world.regions.population();
+        let outlook = state.world.base_outlook;
+        state.world.particulates *= 2.;
+        state.update_health();
+        assert!(state.world.regions.air_pollution() > air);
+        assert!(state.world.regions.population() < population);
+        assert!(state.world.base_outlook < outlook);
+        assert!(state.world.regions.healthcare_demand() > 0.);
+
+        // Cleaner air does the opposite.
+        let population = state.world.regions.population();
+        state.world.particulates /= 4.;
+        state.update_health();
+        assert!(state.world.regions.population() > population);
+        assert!(state.world.base_outlook > outlook);
+        assert!(state.world.regions.healthcare_demand() < 0.);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{delta::Delta, state::State, Id};
+
+/// Extends the engine without changing it, e.g. for
+/// logging, achievements or custom mechanics. Every
+/// method does nothing by default, so hooks only need
+/// to implement the ones they care about.
+///
+/// Hooks are given the state mutably (except when it's
+/// only been observed), so they can change it too.
+pub trait Hook: Send {
+    /// At the start of each year, before anything's updated.
+    fn on_year_start(&mut self, _state: &mut State) {}
+
+    /// After an event's effects are applied.
+    fn on_event_triggered(
+        &mut self,
+        _state: &mut State,
+        _event: Id,
+        _region: Option<Id>,
+    ) {
+    }
+
+    /// After a project's finished and its effects applied.
+    fn on_project_completed(
+        &mut self,
+        _state: &mut State,
+        _project: Id,
+    ) {
+    }
+
+    /// At the end of each year, with what changed over it.
+    fn on_state_changed(
+        &mut self,
+        _state: &State,
+        _delta: &Delta,
+    ) {
+    }
+}
+
+/// The hooks added to a state.
+///
+/// Hooks belong to the game rather than its state, so
+/// they aren't saved, and cloned states (e.g. snapshots
+/// or batch runs) don't have any.
+#[derive(Default)]
+pub struct Hooks(Vec<Box<dyn Hook>>);
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl Clone for Hooks {
+    fn clone(&self) -> Self {
+        Hooks::default()
+    }
+}
+impl PartialEq for Hooks {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl State {
+    pub fn add_hook(&mut self, hook: impl Hook + 'static) {
+        self.hooks.0.push(Box::new(hook));
+    }
+
+    /// Run each of the hooks against the state.
+    pub(crate) fn run_hooks(
+        &mut self,
+        mut f: impl FnMut(&mut dyn Hook, &mut State),
+    ) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        let mut hooks = std::mem::take(&mut self.hooks);
+        for hook in &mut hooks.0 {
+            f(hook.as_mut(), self);
+        }
+
+        // Keep any hooks that were added by the hooks.
+        hooks.0.append(&mut self.hooks.0);
+        self.hooks = hooks;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        projects::Type as ProjectType,
+        simulation::Simulation,
+        world::World,
+    };
+
+    /// Records what it's called with.
+    #[derive(Default)]
+    struct Log(Arc<Mutex<Vec<String>>>);
+    impl Hook for Log {
+        fn on_year_start(&mut self, state: &mut State) {
+            self.push(format!("year {}", state.world.year));
+        }
+
+        fn on_event_triggered(
+            &mut self,
+            _state: &mut State,
+            _event: Id,
+            _region: Option<Id>,
+        ) {
+            self.push("event".into());
+        }
+
+        fn on_project_completed(
+            &mut self,
+            state: &mut State,
+            _project: Id,
+        ) {
+            self.push("project".into());
+            state.political_capital += 1000;
+        }
+
+        fn on_state_changed(
+            &mut self,
+            _state: &State,
+            delta: &Delta,
+        ) {
+            self.push(format!("changed {}", delta.world.len()));
+        }
+    }
+    impl Log {
+        fn push(&self, entry: String) {
+            self.0.lock().unwrap().push(entry);
+        }
+    }
+
+    #[test]
+    fn test_hooks() {
+        let mut sim =
+            Simulation::with_seed(World::default(), 0);
+        let log = Log::default();
+        let entries = log.0.clone();
+        sim.state.add_hook(log);
+
+        let year = sim.state.world.year;
+        let project = sim
+            .state
+            .world
+            .projects
+            .iter()
+            .find(|project| project.kind != ProjectType::Policy)
+            .unwrap()
+            .id;
+        sim.state.world.projects[&project].progress = 0.999;
+        sim.state.set_project_points(&project, 1);
+        sim.state.start_project(&project);
+        sim.step();
+
+        let entries = entries.lock().unwrap().clone();
+        assert_eq!(entries[0], format!("year {year}"));
+        assert!(entries.contains(&"project".to_string()));
+        assert!(entries
+            .iter()
+            .any(|entry| entry.starts_with("changed")));
+        assert!(sim.state.political_capital >= 1000);
+
+        // Hooks aren't part of the state.
+        let clone = sim.state.clone();
+        assert!(clone.hooks.is_empty());
+        assert!(clone == sim.state);
+    }
+}
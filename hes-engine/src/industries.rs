@@ -1,7 +1,9 @@
 use crate::{
     byproducts,
     flavor::IndustryFlavor,
+    form::Documented,
     kinds::{Byproduct, ByproductMap, Resource, ResourceMap},
+    par,
     resources,
     Collection,
     HasId,
@@ -11,18 +13,35 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(
-    Debug, Clone, Serialize, Deserialize, PartialEq, Default,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Default,
+    Documented,
 )]
 pub struct Industry {
     pub id: Id,
     pub name: String,
+
+    /// Resources used, per low-income-capita (LIC) per year.
     pub resources: ResourceMap,
+
+    /// Byproducts produced, per low-income-capita (LIC)
+    /// per year.
     pub byproducts: ByproductMap,
+
     pub resource_modifiers: ResourceMap,
     pub byproduct_modifiers: ByproductMap,
     pub demand_modifier: f32,
     pub flavor: IndustryFlavor,
+
+    /// Optional notes.
     pub notes: String,
+    /// Free-form tags for organizing entities in the editor.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl Display for Industry {
@@ -95,15 +114,19 @@ impl Industry {
 
 impl Collection<Industry> {
     pub fn resource_demand(&self, lic_pop: f32) -> ResourceMap {
-        self.iter().fold(resources!(), |acc, ind| {
-            acc + ind.adj_resources() * ind.demand(lic_pop)
+        par::map(self, |ind| {
+            ind.adj_resources() * ind.demand(lic_pop)
         })
+        .into_iter()
+        .fold(resources!(), |acc, demand| acc + demand)
     }
 
     pub fn byproducts(&self, lic_pop: f32) -> ByproductMap {
-        self.iter().fold(byproducts!(), |acc, ind| {
-            acc + ind.adj_byproducts() * ind.demand(lic_pop)
+        par::map(self, |ind| {
+            ind.adj_byproducts() * ind.demand(lic_pop)
         })
+        .into_iter()
+        .fold(byproducts!(), |acc, byproducts| acc + byproducts)
     }
 }
 
@@ -0,0 +1,204 @@
+use ed25519_dalek::{
+    Signature,
+    Signer,
+    Verifier,
+    VerifyingKey,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::World;
+
+pub use ed25519_dalek::SigningKey;
+
+/// Embedded in exported worlds to detect
+/// corrupted or tampered files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Integrity {
+    /// SHA-256 hash of the world's content, hex-encoded.
+    pub hash: String,
+
+    /// The author's signature of the hash, if signed.
+    #[serde(default)]
+    pub signature: Option<AuthorSignature>,
+}
+
+/// An ed25519 signature and the key that made it, hex-encoded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorSignature {
+    pub public_key: String,
+    pub signature: String,
+}
+impl AuthorSignature {
+    fn is_valid_for(&self, hash: &str) -> bool {
+        let key = hex::decode(&self.public_key)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .and_then(|bytes| {
+                VerifyingKey::from_bytes(&bytes).ok()
+            });
+        let sig = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(|bytes| Signature::from_bytes(&bytes));
+        match (key, sig) {
+            (Some(key), Some(sig)) => {
+                key.verify(hash.as_bytes(), &sig).is_ok()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The result of checking a world against its hash.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verification {
+    /// The world has no hash, e.g. because it
+    /// was never exported or predates hashing.
+    Unhashed,
+
+    /// The world matches its hash, and its signature
+    /// if signed, in which case `signer` is the
+    /// public key of who signed it.
+    Valid {
+        hash: String,
+        signer: Option<String>,
+    },
+
+    /// The world doesn't match its hash, so
+    /// it's been corrupted or edited by hand.
+    Corrupted { expected: String, actual: String },
+
+    /// The world matches its hash, but the
+    /// signature doesn't match either.
+    BadSignature { hash: String },
+}
+
+impl World {
+    /// Hash the world's content, i.e. everything but
+    /// its integrity info. Independent of the order
+    /// entities are in, as with exports.
+    pub fn content_hash(&self) -> String {
+        let mut world = self.canonical();
+        world.integrity = None;
+        let json = serde_json::to_vec(&world)
+            .expect("Worlds are always serializable");
+        hex::encode(Sha256::digest(json))
+    }
+
+    /// Hash the world's current content, keeping the
+    /// existing signature only if it's still valid.
+    pub fn seal(&mut self) {
+        let hash = self.content_hash();
+        let signature = self
+            .integrity
+            .take()
+            .and_then(|integrity| integrity.signature)
+            .filter(|sig| sig.is_valid_for(&hash));
+        self.integrity = Some(Integrity { hash, signature });
+    }
+
+    /// Hash and sign the world's current content.
+    pub fn sign(&mut self, key: &SigningKey) {
+        let hash = self.content_hash();
+        let signature = key.sign(hash.as_bytes());
+        self.integrity = Some(Integrity {
+            hash,
+            signature: Some(AuthorSignature {
+                public_key: hex::encode(
+                    key.verifying_key().to_bytes(),
+                ),
+                signature: hex::encode(signature.to_bytes()),
+            }),
+        });
+    }
+
+    /// Check the world against its hash and signature.
+    pub fn verify(&self) -> Verification {
+        let Some(integrity) = &self.integrity else {
+            return Verification::Unhashed;
+        };
+        let actual = self.content_hash();
+        if actual != integrity.hash {
+            return Verification::Corrupted {
+                expected: integrity.hash.clone(),
+                actual,
+            };
+        }
+        match &integrity.signature {
+            None => Verification::Valid {
+                hash: actual,
+                signer: None,
+            },
+            Some(sig) if sig.is_valid_for(&actual) => {
+                Verification::Valid {
+                    hash: actual,
+                    signer: Some(sig.public_key.clone()),
+                }
+            }
+            Some(_) => {
+                Verification::BadSignature { hash: actual }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verify_export() {
+        let world = World::default();
+        assert_eq!(world.verify(), Verification::Unhashed);
+
+        let json = world.to_export_json();
+        let mut loaded =
+            World::from_bytes(json.as_bytes()).unwrap();
+        assert!(matches!(
+            loaded.verify(),
+            Verification::Valid { signer: None, .. }
+        ));
+
+        loaded.year += 1;
+        assert!(matches!(
+            loaded.verify(),
+            Verification::Corrupted { .. }
+        ));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let mut world = World::default();
+        world.sign(&key);
+
+        let binary = world.to_binary();
+        let mut loaded = World::from_bytes(&binary).unwrap();
+        let public_key =
+            hex::encode(key.verifying_key().to_bytes());
+        assert_eq!(
+            loaded.verify(),
+            Verification::Valid {
+                hash: world.content_hash(),
+                signer: Some(public_key),
+            }
+        );
+
+        // Re-signing with a different key is
+        // caught, as is editing a signed world.
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let integrity = loaded.integrity.as_mut().unwrap();
+        integrity.signature.as_mut().unwrap().public_key =
+            hex::encode(other.verifying_key().to_bytes());
+        assert!(matches!(
+            loaded.verify(),
+            Verification::BadSignature { .. }
+        ));
+
+        let mut edited = world.clone();
+        edited.year += 1;
+        edited.seal();
+        assert!(edited.integrity.unwrap().signature.is_none());
+    }
+}
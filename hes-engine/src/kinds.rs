@@ -158,6 +158,7 @@ macro_rules! define_enum_map {
 
         paste! {
             #[derive(Default, Clone, Copy, Serialize, Deserialize, PartialEq, Debug)]
+            #[serde(default)]
             pub struct [<$name Map>] {
                 $(
                     pub [<$field:snake>]: f32,
@@ -167,7 +168,7 @@ macro_rules! define_enum_map {
             impl [<$name Map>] {
                 const N: usize = count!($($field)*);
 
-                pub fn values(&self) -> [f32; count!($($field)*)] {
+                pub fn values(&self) -> [f32; Self::N] {
                     [$(
                         self.[<$field:snake>],
                     )*]
@@ -320,7 +321,7 @@ macro_rules! define_enum_map {
                 }
             }
 
-            impl KindMap<{count!($($field)*)}> for [<$name Map>] {
+            impl KindMap<{ [<$name Map>]::N }> for [<$name Map>] {
                 type Key = $name;
 
                 fn splat(val: f32) -> Self {
@@ -414,7 +415,9 @@ define_enum_map!(Byproduct {
     Co2,
     Ch4,
     N2o,
-    Biodiversity
+    Biodiversity,
+    Particulates,
+    Plastic
 });
 
 define_enum_map!(Output {
@@ -424,6 +427,20 @@ define_enum_map!(Output {
     AnimalCalories
 });
 
+define_enum_map!(LandUse {
+    Forest,
+    Cropland,
+    Pasture,
+    Urban,
+    Protected
+});
+
+define_enum_map!(Cohort {
+    Children,
+    Adults,
+    Elderly
+});
+
 define_enum_map!(Feedstock {
     Soil,
     Oil,
@@ -646,6 +663,42 @@ impl Display for Resource {
     }
 }
 
+impl Display for LandUse {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LandUse::Forest => "Forest",
+                LandUse::Cropland => "Cropland",
+                LandUse::Pasture => "Pasture",
+                LandUse::Urban => "Urban",
+                LandUse::Protected => "Protected",
+            }
+        )
+    }
+}
+
+impl Display for Cohort {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Cohort::Children => "Children",
+                Cohort::Adults => "Adults",
+                Cohort::Elderly => "Elderly",
+            }
+        )
+    }
+}
+
 impl Display for Byproduct {
     fn fmt(
         &self,
@@ -660,6 +713,8 @@ impl Display for Byproduct {
                 Byproduct::Ch4 => "CH4 (Methane)",
                 Byproduct::Biodiversity =>
                     "Biodiversity Pressure",
+                Byproduct::Particulates => "Particulates",
+                Byproduct::Plastic => "Plastic",
             }
         )
     }
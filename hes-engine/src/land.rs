@@ -0,0 +1,219 @@
+//! Land use. Each region's land is split between forest,
+//! cropland, pasture, urban and protected land, with the
+//! rest (e.g. deserts and ice) in no particular use.
+//! Cropland and pasture follow the land that farming
+//! processes use, protected land follows land protection,
+//! and forest is cleared when they need more land than is
+//! free. Land that comes under forest or protection takes
+//! up CO2 and eases biodiversity pressure, and land lost
+//! from them does the opposite.
+
+use crate::{
+    kinds::{LandUse, LandUseMap, Output},
+    production::{Process, ProcessFeature},
+    regions::Region,
+    state::State,
+    world::World,
+    Collection,
+};
+
+/// Annual CO2 (g) taken up by each m2 of land that comes
+/// under forest or protection, about 1 tCO2/ha.
+const SEQUESTRATION: f32 = 100.;
+
+/// The share of land under forest or protection at which
+/// land use adds no biodiversity pressure, roughly what
+/// it is at the start.
+const NATURAL_REFERENCE: f32 = 0.4;
+
+/// Extinction rate for each share of land
+/// lost from forest and protection.
+const LAND_EXTINCTION: f32 = 20.;
+
+pub(crate) fn default_land_use() -> LandUseMap {
+    LandUseMap {
+        forest: 0.3,
+        cropland: 0.13,
+        pasture: 0.35,
+        urban: 0.01,
+        protected: 0.1,
+    }
+}
+
+impl Process {
+    /// The kind of land the process farms, if any.
+    /// Land used otherwise, e.g. for solar farms,
+    /// isn't any particular kind.
+    pub fn land_use(&self) -> Option<LandUse> {
+        if self
+            .features
+            .contains(&ProcessFeature::UsesLivestock)
+        {
+            Some(LandUse::Pasture)
+        } else if matches!(
+            self.output,
+            Output::PlantCalories | Output::AnimalCalories
+        ) || self
+            .features
+            .contains(&ProcessFeature::UsesPesticides)
+        {
+            Some(LandUse::Cropland)
+        } else {
+            None
+        }
+    }
+}
+
+impl Region {
+    /// The share of the region's land in no particular use.
+    pub fn unused_land(&self) -> f32 {
+        (1. - self.land_use.sum()).max(0.)
+    }
+
+    /// The share of the region's natural land, i.e. under
+    /// forest or protection, lost from what it'd have
+    /// with no land use pressure.
+    pub fn habitat_loss(&self) -> f32 {
+        let natural =
+            self.land_use.forest + self.land_use.protected;
+        (1. - natural / NATURAL_REFERENCE).max(0.)
+    }
+
+    /// Plant forest on some of the region's unused land,
+    /// or clear it back to unused land if negative.
+    pub fn afforest(&mut self, amount: f32) {
+        let amount = amount
+            .clamp(-self.land_use.forest, self.unused_land());
+        self.land_use.forest += amount;
+    }
+}
+
+impl Collection<Region> {
+    /// Mean share of the regions' land in each use.
+    pub fn land_use(&self) -> LandUseMap {
+        self.iter().fold(LandUseMap::default(), |acc, r| {
+            acc + r.land_use
+        }) / self.len() as f32
+    }
+}
+
+impl World {
+    /// Contribution to extinction rate from
+    /// land lost from forest and protection.
+    pub fn land_use_extinction_rate(&self) -> f32 {
+        let land_use = self.regions.land_use();
+        (NATURAL_REFERENCE
+            - land_use.forest
+            - land_use.protected)
+            * LAND_EXTINCTION
+    }
+}
+
+impl State {
+    /// Update the regions' land use from the land farming
+    /// processes use and from land protection, returning
+    /// the change in the mean share of natural land.
+    pub(crate) fn update_land_use(&mut self) -> f32 {
+        let land = self.world.starting_resources.land;
+        if land <= 0. {
+            return 0.;
+        }
+
+        let mut farmed = LandUseMap::default();
+        for process in self.world.processes.iter() {
+            if let Some(kind) = process.land_use() {
+                let amount = self
+                    .produced
+                    .by_process
+                    .get(&process.id)
+                    .unwrap_or(&0.);
+                farmed[kind] += amount
+                    * process.adj_resources().land
+                    / land;
+            }
+        }
+
+        let mean = self.world.regions.land_use();
+        let prev = mean.forest + mean.protected;
+        let protected = self.protected_land.clamp(0., 1.);
+        for region in self.world.regions.iter_mut() {
+            let land_use = &mut region.land_use;
+
+            // Farmland grows and shrinks in proportion
+            // to what each region already has.
+            for kind in [LandUse::Cropland, LandUse::Pasture] {
+                land_use[kind] = if mean[kind] > 0. {
+                    land_use[kind] * farmed[kind] / mean[kind]
+                } else {
+                    farmed[kind]
+                };
+            }
+            land_use.protected = protected;
+
+            // Forest is cleared for land
+            // needed beyond what's unused.
+            let overused = land_use.sum() - 1.;
+            if overused > 0. {
+                land_use.forest =
+                    (land_use.forest - overused).max(0.);
+            }
+        }
+
+        let mean = self.world.regions.land_use();
+        mean.forest + mean.protected - prev
+    }
+
+    /// Update land use, with land coming under forest or
+    /// protection taking up CO2 every year after, and land
+    /// lost from them no longer doing so.
+    pub(crate) fn update_land(&mut self) {
+        let change = self.update_land_use();
+        let area = change * self.world.starting_resources.land;
+        self.byproducts.modifier.co2 -= area * SEQUESTRATION;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::Effect;
+
+    #[test]
+    fn test_land_use() {
+        let mut state = State::default();
+        let land_use = state.world.regions.land_use();
+        assert!(land_use.cropland > 0.);
+        assert!(land_use.pasture > 0.);
+        assert!(
+            (land_use.protected - state.protected_land).abs()
+                < 1e-6
+        );
+
+        // Nothing changes when nothing has.
+        assert!(state.update_land_use().abs() < 1e-6);
+
+        // Protecting more land than is free clears forest.
+        let forest = land_use.forest;
+        let pressure = state.world.land_use_extinction_rate();
+        state.apply_effects(&[Effect::ProtectLand(0.3)], None);
+        let emissions = state.byproducts.modifier.co2;
+        state.update_land();
+        let land_use = state.world.regions.land_use();
+        assert!(land_use.forest < forest);
+        assert!((land_use.sum() - 1.).abs() < 1e-4);
+        assert!(state.byproducts.modifier.co2 < emissions);
+        assert!(
+            state.world.land_use_extinction_rate() < pressure
+        );
+
+        // Afforestation only uses what land is unused.
+        state.apply_effects(&[Effect::ProtectLand(-0.3)], None);
+        state.update_land();
+        let unused = state.world.regions.first().unused_land();
+        assert!(unused > 0.);
+        state.apply_effects(&[Effect::Afforest(1.)], None);
+        let region = state.world.regions.first();
+        assert_eq!(region.unused_land(), 0.);
+        assert!((region.land_use.sum() - 1.).abs() < 1e-4);
+    }
+}
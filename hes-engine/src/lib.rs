@@ -1,28 +1,77 @@
 #![feature(generic_arg_infer)]
 
+// So that `#[derive(EditorForm)]` works within this crate.
+extern crate self as hes_engine;
+
+mod batch;
+mod bounds;
+mod delta;
+mod demographics;
 mod diff;
+mod disasters;
+mod disease;
+mod encoding;
 mod events;
 pub mod flavor;
+pub mod form;
+mod generate;
+mod health;
+mod hooks;
 mod industries;
+mod integrity;
 mod kinds;
+mod land;
+mod lockstep;
+mod migrate;
 mod npcs;
+mod ocean;
+mod par;
+mod pollution;
+mod patch;
 mod production;
 mod projects;
 mod regions;
+mod replay;
+mod rng;
+mod sanitize;
+mod save;
+pub mod scripting;
+mod simulation;
+mod snapshot;
+mod soil;
 mod state;
+mod sweep;
+mod text;
+mod tipping;
 mod util;
+mod variables;
+mod water;
 mod world;
 
+pub use batch::{Batch, BatchSummary, Distribution, RunOutcome};
+pub use bounds::Bounds;
+pub use delta::{
+    Checkpoint,
+    Delta,
+    ProcessDelta,
+    ProjectDelta,
+    RegionDelta,
+};
+pub use demographics::Demographics;
 pub use diff::{Change, Diff};
+pub use encoding::LoadError;
 pub use events::{
+    expr,
     mean_demand_outlook_change,
     mean_income_outlook_change,
+    Comparator,
     Condition,
     ConditionKind,
     Effect,
     EffectKind,
     Event,
     Flag,
+    FollowUp,
     IconEvent,
     Likelihood,
     LocalVariable,
@@ -30,12 +79,30 @@ pub use events::{
     PlayerVariable,
     Probability,
     Request as NPCRequest,
+    Schedule,
+    ScheduleKind,
     WorldVariable,
     ICON_EVENTS,
 };
+pub use generate::WorldParams;
+pub use hooks::{Hook, Hooks};
 pub use industries::Industry;
+pub use integrity::{
+    AuthorSignature,
+    Integrity,
+    SigningKey,
+    Verification,
+};
 pub use kinds::*;
+pub use lockstep::{
+    Command,
+    Lockstep,
+    LockstepError,
+    TurnResult,
+};
+pub use migrate::WORLD_VERSION;
 pub use npcs::{NPCRelation, NPC};
+pub use patch::{Conflict, EntityChanges, Patch, PatchError};
 pub use production::{Process, ProcessFeature};
 pub use projects::{
     Cost,
@@ -49,9 +116,24 @@ pub use projects::{
     Upgrade,
 };
 pub use regions::{Income, Latitude, Region};
-pub use state::{Emissions, ResolvedEvent, State, Update};
+pub use replay::{Decision, Replay};
+pub use rng::Rng;
+pub use save::{SaveError, SavedGame, SAVE_VERSION};
+pub use simulation::{Simulation, YearReport};
+pub use snapshot::Snapshot;
+pub use state::{
+    Emissions,
+    ResolvedEvent,
+    State,
+    Update,
+    LIFESPAN,
+};
+pub use sweep::{Parameter, Sweep, SweepError, SweepRow};
+pub use text::TextField;
+pub use tipping::TippingPoint;
 pub use util::*;
-pub use world::World;
+pub use variables::{Variable, VariableKind};
+pub use world::{Metadata, World};
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,312 @@
+//! Deterministic lockstep, e.g. for a cooperative mode
+//! where a committee of players plans together.
+//!
+//! Every peer runs its own copy of the game. Players' decisions
+//! are sent to every peer as [`Command`]s, and a turn (a year)
+//! is only simulated once every player has ended it. As a year
+//! only depends on the state (including its seeded rng) and
+//! the decisions made in it, applied in the same order, every
+//! peer ends up with the same state. Peers can compare their
+//! [`State::checksum`]s after each turn to detect a desync.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    delta::Delta,
+    replay::Decision,
+    simulation::{Simulation, YearReport},
+    state::State,
+    world::World,
+};
+
+/// A decision made by one of the players,
+/// for the turn of the given year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Command {
+    pub player: usize,
+    pub year: usize,
+    pub decision: Decision,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LockstepError {
+    /// There's no player with this index.
+    UnknownPlayer(usize),
+
+    /// The command was for a turn that's already been run.
+    PastTurn { year: usize, current: usize },
+
+    /// The player already ended this turn, so
+    /// can't make any more decisions in it.
+    TurnEnded(usize),
+}
+impl Display for LockstepError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Self::UnknownPlayer(player) => {
+                write!(f, "There's no player {player}.")
+            }
+            Self::PastTurn { year, current } => write!(
+                f,
+                "The turn for {year} has already been run (it's now {current})."
+            ),
+            Self::TurnEnded(player) => write!(
+                f,
+                "Player {player} has already ended this turn."
+            ),
+        }
+    }
+}
+
+/// The result of running a turn, which is the
+/// same for every peer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnResult {
+    pub report: YearReport,
+
+    /// What changed over the turn,
+    /// including the players' decisions.
+    pub delta: Delta,
+
+    /// The state's checksum at the end of the turn.
+    pub checksum: String,
+}
+
+/// Queues the players' commands until
+/// every player has ended the turn.
+pub struct Lockstep {
+    sim: Simulation,
+    players: usize,
+
+    /// Commands for this and later turns,
+    /// in the order they were submitted.
+    queue: Vec<Command>,
+
+    /// Which players have ended the current turn.
+    ended: Vec<bool>,
+}
+impl Lockstep {
+    /// Every peer must start with the same world and seed.
+    pub fn new(
+        world: World,
+        seed: u64,
+        players: usize,
+    ) -> Self {
+        Lockstep {
+            sim: Simulation::with_seed(world, seed),
+            players,
+            queue: vec![],
+            ended: vec![false; players],
+        }
+    }
+
+    pub fn state(&self) -> &State {
+        &self.sim.state
+    }
+
+    /// The year of the current turn.
+    pub fn year(&self) -> usize {
+        self.sim.state.world.year
+    }
+
+    fn check_player(
+        &self,
+        player: usize,
+    ) -> Result<(), LockstepError> {
+        if player >= self.players {
+            Err(LockstepError::UnknownPlayer(player))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Queue a command, from this peer or another. Commands
+    /// for later turns are kept until their turn comes.
+    pub fn submit(
+        &mut self,
+        command: Command,
+    ) -> Result<(), LockstepError> {
+        self.check_player(command.player)?;
+        let current = self.year();
+        if command.year < current {
+            return Err(LockstepError::PastTurn {
+                year: command.year,
+                current,
+            });
+        }
+        if command.year == current && self.ended[command.player]
+        {
+            return Err(LockstepError::TurnEnded(
+                command.player,
+            ));
+        }
+        self.queue.push(command);
+        Ok(())
+    }
+
+    /// The player is done deciding for the current turn.
+    pub fn end_turn(
+        &mut self,
+        player: usize,
+    ) -> Result<(), LockstepError> {
+        self.check_player(player)?;
+        self.ended[player] = true;
+        Ok(())
+    }
+
+    /// The players that haven't ended the current turn yet.
+    pub fn waiting_on(&self) -> Vec<usize> {
+        (0..self.players)
+            .filter(|player| !self.ended[*player])
+            .collect()
+    }
+
+    /// If every player has ended the current turn.
+    pub fn is_ready(&self) -> bool {
+        self.ended.iter().all(|ended| *ended)
+    }
+
+    /// Run the current turn, if every player has ended it.
+    ///
+    /// Peers can receive commands from different players in
+    /// a different order, so the turn's decisions are made in
+    /// order of player and then in the order each player
+    /// submitted them.
+    pub fn advance(&mut self) -> Option<TurnResult> {
+        if !self.is_ready() {
+            return None;
+        }
+        let year = self.year();
+        let (mut turn, later): (Vec<_>, Vec<_>) = self
+            .queue
+            .drain(..)
+            .partition(|command| command.year == year);
+        self.queue = later;
+        turn.sort_by_key(|command| command.player);
+
+        let checkpoint = self.sim.state.checkpoint();
+        let report = self.sim.step_with(
+            turn.into_iter().map(|command| command.decision),
+        );
+        self.ended = vec![false; self.players];
+        Some(TurnResult {
+            report,
+            delta: self.sim.state.delta(&checkpoint),
+            checksum: self.sim.state.checksum(),
+        })
+    }
+}
+
+impl State {
+    /// A hash of the whole state, to check
+    /// that two copies of it are the same.
+    pub fn checksum(&self) -> String {
+        let json = serde_json::to_vec(self)
+            .expect("States are always serializable");
+        hex::encode(Sha256::digest(json))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lockstep() {
+        let world = World::default();
+        let project = world.projects.first().id;
+        let process = world.processes.first().id;
+        let commands = [
+            Command {
+                player: 1,
+                year: world.year,
+                decision: Decision::StartProject(project),
+            },
+            Command {
+                player: 0,
+                year: world.year,
+                decision: Decision::SetProjectPoints(
+                    project, 2,
+                ),
+            },
+            Command {
+                player: 1,
+                year: world.year + 1,
+                decision: Decision::ChangeMixShare(process, -1),
+            },
+        ];
+
+        // Two peers that receive the
+        // commands in a different order.
+        let mut a = Lockstep::new(world.clone(), 7, 2);
+        let mut b = Lockstep::new(world.clone(), 7, 2);
+        for command in &commands {
+            a.submit(command.clone()).unwrap();
+        }
+        for command in commands.iter().rev() {
+            b.submit(command.clone()).unwrap();
+        }
+
+        for peer in [&mut a, &mut b] {
+            peer.end_turn(0).unwrap();
+            assert!(peer.advance().is_none());
+            assert_eq!(peer.waiting_on(), vec![1]);
+            assert_eq!(
+                peer.submit(Command {
+                    player: 0,
+                    year: world.year,
+                    decision: Decision::StopProject(project),
+                }),
+                Err(LockstepError::TurnEnded(0))
+            );
+            peer.end_turn(1).unwrap();
+        }
+        let (turn_a, turn_b) =
+            (a.advance().unwrap(), b.advance().unwrap());
+        assert_eq!(turn_a, turn_b);
+        assert!(turn_a
+            .delta
+            .projects
+            .iter()
+            .any(|delta| delta.id == project));
+
+        // The player's points were set before the project
+        // was started, even though they were submitted after.
+        assert_eq!(
+            a.state().decisions[0].1,
+            Decision::SetProjectPoints(project, 2)
+        );
+
+        for peer in [&mut a, &mut b] {
+            peer.end_turn(0).unwrap();
+            peer.end_turn(1).unwrap();
+        }
+        let (turn_a, turn_b) =
+            (a.advance().unwrap(), b.advance().unwrap());
+        assert_eq!(turn_a.checksum, turn_b.checksum);
+        assert_eq!(turn_a.delta.processes.len(), 1);
+
+        assert_eq!(
+            a.submit(Command {
+                player: 2,
+                year: a.year(),
+                decision: Decision::StopProject(project),
+            }),
+            Err(LockstepError::UnknownPlayer(2))
+        );
+        assert!(matches!(
+            a.submit(Command {
+                player: 0,
+                year: world.year,
+                decision: Decision::StopProject(project),
+            }),
+            Err(LockstepError::PastTurn { .. })
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+use serde_json::{Map, Value};
+
+use crate::demographics::default_demographics;
+
+/// The current version of the world format.
+///
+/// Bump this and add a migration to `MIGRATIONS` whenever
+/// a change to the world's structure would otherwise
+/// break existing worlds.
+pub const WORLD_VERSION: u32 = 2;
+
+/// Upgrades a world's JSON from the previous version,
+/// noting each change it makes.
+type Migration = fn(&mut Map<String, Value>, &mut Vec<String>);
+
+/// Migrations in order, the first upgrading unversioned
+/// worlds (version 0) to version 1.
+const MIGRATIONS: [Migration; WORLD_VERSION as usize] =
+    [v0_to_v1, v1_to_v2];
+
+/// Upgrade a world's JSON to the current version,
+/// returning notes on what was migrated. Worlds from
+/// newer versions are left untouched and return the
+/// version they're from as an error.
+pub fn migrate(world: &mut Value) -> Result<Vec<String>, u32> {
+    let Some(obj) = world.as_object_mut() else {
+        return Ok(vec![]);
+    };
+    let version = version_of(obj);
+    if version > WORLD_VERSION {
+        return Err(version);
+    }
+
+    let mut notes = vec![];
+    for (from, migration) in
+        MIGRATIONS.iter().enumerate().skip(version as usize)
+    {
+        let mut changes = vec![];
+        migration(obj, &mut changes);
+        notes.extend(changes.into_iter().map(|change| {
+            format!("v{from} → v{}: {change}", from + 1)
+        }));
+    }
+    obj.insert("version".into(), WORLD_VERSION.into());
+    Ok(notes)
+}
+
+pub(crate) fn version_of(obj: &Map<String, Value>) -> u32 {
+    obj.get("version")
+        .and_then(|v| v.as_u64())
+        .map_or(0, |v| v as u32)
+}
+
+/// Set `key` to `default` if it's missing,
+/// returning whether it was.
+pub(crate) fn ensure(
+    obj: &mut Map<String, Value>,
+    key: &str,
+    default: Value,
+) -> bool {
+    if obj.contains_key(key) {
+        false
+    } else {
+        obj.insert(key.into(), default);
+        true
+    }
+}
+
+/// Call `f` on the `flavor` object of each item
+/// in the list under `key`, returning how many
+/// it returned true for.
+fn each_flavor(
+    obj: &mut Map<String, Value>,
+    key: &str,
+    f: impl Fn(&mut Map<String, Value>) -> bool,
+) -> usize {
+    let Some(items) =
+        obj.get_mut(key).and_then(|items| items.as_array_mut())
+    else {
+        return 0;
+    };
+    items
+        .iter_mut()
+        .filter_map(|item| item.get_mut("flavor"))
+        .filter_map(|flavor| flavor.as_object_mut())
+        .map(f)
+        .filter(|changed| *changed)
+        .count()
+}
+
+/// Unversioned worlds predate translations,
+/// region locations, and event sounds and variants.
+fn v0_to_v1(
+    world: &mut Map<String, Value>,
+    notes: &mut Vec<String>,
+) {
+    if ensure(world, "translations", Value::Object(Map::new()))
+    {
+        notes
+            .push("Added an empty set of translations.".into());
+    }
+
+    let regions = each_flavor(world, "regions", |flavor| {
+        ensure(flavor, "location", Value::Null)
+    });
+    if regions > 0 {
+        notes.push(format!(
+            "Added an empty location to {regions} regions."
+        ));
+    }
+
+    let events = each_flavor(world, "events", |flavor| {
+        let variants =
+            ensure(flavor, "variants", Value::Array(vec![]));
+        let sound = ensure(flavor, "sound", Value::Null);
+        variants || sound
+    });
+    if events > 0 {
+        notes.push(format!(
+            "Added empty image variants and sounds to {events} events."
+        ));
+    }
+}
+
+/// Population growth used to be projected from coefficients
+/// by income level, which don't translate to the birth and
+/// death rates that have replaced them.
+fn v1_to_v2(
+    world: &mut Map<String, Value>,
+    notes: &mut Vec<String>,
+) {
+    if world.remove("income_pop_coefs").is_some() {
+        notes.push(
+            "Dropped the population growth coefficients, which can't be converted to birth and death rates.".into(),
+        );
+    }
+    let demographics =
+        serde_json::to_value(default_demographics())
+            .expect("Demographics are always serializable");
+    if ensure(world, "demographics_by_income", demographics) {
+        notes.push(
+            "Added the default birth and death rates by income level.".into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::World;
+
+    #[test]
+    fn test_migrate_unversioned() {
+        let json = World::default().to_export_json();
+        let mut world: Value =
+            serde_json::from_str(&json).unwrap();
+        let obj = world.as_object_mut().unwrap();
+        obj.remove("version");
+        obj.remove("translations");
+        each_flavor(obj, "regions", |flavor| {
+            flavor.remove("location").is_some()
+        });
+
+        let notes = migrate(&mut world).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(world["version"], WORLD_VERSION);
+        assert!(world["translations"].is_object());
+        assert!(
+            world["regions"][0]["flavor"]["location"].is_null()
+        );
+        assert!(serde_json::from_value::<World>(world).is_ok());
+    }
+
+    #[test]
+    fn test_migrate_population_growth() {
+        let json = World::default().to_export_json();
+        let mut world: Value =
+            serde_json::from_str(&json).unwrap();
+        let obj = world.as_object_mut().unwrap();
+        obj.insert("version".into(), 1.into());
+        obj.remove("demographics_by_income");
+        obj.insert(
+            "income_pop_coefs".into(),
+            serde_json::to_value([[0.; 4]; 4]).unwrap(),
+        );
+
+        let notes = migrate(&mut world).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert!(notes
+            .iter()
+            .all(|note| note.starts_with("v1 → v2")));
+        assert!(world.get("income_pop_coefs").is_none());
+        let world: World =
+            serde_json::from_value(world).unwrap();
+        assert_eq!(
+            world.demographics_by_income,
+            default_demographics()
+        );
+    }
+
+    #[test]
+    fn test_migrate_current() {
+        let json = World::default().to_export_json();
+        let mut world: Value =
+            serde_json::from_str(&json).unwrap();
+        assert!(migrate(&mut world).unwrap().is_empty());
+
+        world["version"] = (WORLD_VERSION + 1).into();
+        assert_eq!(migrate(&mut world), Err(WORLD_VERSION + 1));
+    }
+}
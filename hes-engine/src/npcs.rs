@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, sync::LazyLock};
 
 use crate::{
     flavor::NPCFlavor,
@@ -12,6 +12,12 @@ use strum::{Display, EnumIter, EnumString, IntoStaticStr};
 
 pub const RELATIONSHIP_CHANGE_AMOUNT: f32 = 0.5;
 
+/// Parsed once, as every new game needs them.
+static NPCS: LazyLock<Collection<NPC>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../assets/npcs.json"))
+        .unwrap()
+});
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NPC {
     pub id: Id,
@@ -43,10 +49,7 @@ impl NPC {
     // NPCs are hardcoded cause it's a bit more complicated
     // to make them editable.
     pub fn load() -> Collection<Self> {
-        serde_json::from_str(include_str!(
-            "../assets/npcs.json"
-        ))
-        .unwrap()
+        NPCS.clone()
     }
 
     pub fn is_ally(&self) -> bool {
@@ -0,0 +1,98 @@
+//! Ocean acidification. The oceans take up a share of each
+//! year's CO2 emissions, which lowers their pH. Acidification
+//! adds to biodiversity pressure and makes marine food
+//! production, e.g. fisheries, less productive.
+
+use crate::{
+    kinds::Output,
+    production::ProcessFeature,
+    state::State,
+    world::World,
+};
+
+/// The share of CO2 emissions the oceans take up.
+const OCEAN_UPTAKE: f32 = 0.25;
+
+/// The drop in pH for each Gt of CO2 the oceans take up,
+/// roughly 0.1 for the ~600 Gt taken up since
+/// pre-industrial times.
+const ACIDIFICATION_PER_GT: f32 = 0.1 / 600.;
+
+/// How much less productive marine food production
+/// gets for each unit of acidification.
+const MARINE_FOOD_LOSS: f32 = 2.;
+
+pub(crate) fn default_acidification() -> f32 {
+    0.1
+}
+
+impl World {
+    /// Contribution to extinction rate from acidification.
+    pub fn acidification_extinction_rate(&self) -> f32 {
+        (self.acidification * 10.).powf(2.)
+    }
+}
+
+impl State {
+    /// Take up some of this year's CO2 emissions into
+    /// the oceans. Negative emissions draw it back out.
+    pub(crate) fn update_ocean(&mut self) {
+        let uptake = self.emissions.co2 * 1e-15 * OCEAN_UPTAKE;
+        self.world.ocean_uptake += uptake;
+
+        let prev = self.world.acidification;
+        self.world.acidification =
+            (prev + uptake * ACIDIFICATION_PER_GT).max(0.);
+        let change = self.world.acidification - prev;
+        for process in self.world.processes.iter_mut() {
+            let is_food = matches!(
+                process.output,
+                Output::PlantCalories | Output::AnimalCalories
+            );
+            if is_food
+                && process
+                    .features
+                    .contains(&ProcessFeature::IsMarine)
+            {
+                process.output_modifier -=
+                    change * MARINE_FOOD_LOSS;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{events::WorldVariable, Id};
+
+    #[test]
+    fn test_acidification() {
+        let mut state = State::default();
+        let fishery = state.world.processes.by_idx_mut(0);
+        fishery.output = Output::AnimalCalories;
+        fishery.features.push(ProcessFeature::IsMarine);
+        let id: Id = fishery.id;
+        let modifier = fishery.output_modifier;
+        let acidification = state.world.acidification;
+        let pressure =
+            state.world.acidification_extinction_rate();
+
+        state.emissions.co2 = 40. * 1e15;
+        state.update_ocean();
+        assert_eq!(state.world.ocean_uptake, 10.);
+        assert!(state.world.acidification > acidification);
+        assert!(
+            state.world.acidification_extinction_rate()
+                > pressure
+        );
+        assert!(
+            state.world.processes[&id].output_modifier
+                < modifier
+        );
+        assert_eq!(
+            WorldVariable::Acidification.value(&state),
+            state.world.acidification
+        );
+    }
+}
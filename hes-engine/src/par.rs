@@ -0,0 +1,52 @@
+//! Iteration that's spread across threads with rayon when the
+//! `parallel` feature is enabled, e.g. for batch tooling.
+//! WASM doesn't have threads, so it's always sequential there.
+//!
+//! Results are collected in order and any totals are summed
+//! sequentially afterwards, so that runs are identical with
+//! or without the feature.
+
+pub use imp::{for_each_mut, map};
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+mod imp {
+    use rayon::prelude::*;
+
+    /// Map each item, keeping their order.
+    pub fn map<'a, T: Sync, R: Send>(
+        items: &'a [T],
+        f: impl Fn(&'a T) -> R + Sync + Send,
+    ) -> Vec<R> {
+        items.par_iter().map(f).collect()
+    }
+
+    /// Update each item.
+    pub fn for_each_mut<T: Send>(
+        items: &mut [T],
+        f: impl Fn(&mut T) + Sync + Send,
+    ) {
+        items.par_iter_mut().for_each(f);
+    }
+}
+
+#[cfg(not(all(
+    feature = "parallel",
+    not(target_arch = "wasm32")
+)))]
+mod imp {
+    /// Map each item, keeping their order.
+    pub fn map<'a, T: Sync, R: Send>(
+        items: &'a [T],
+        f: impl Fn(&'a T) -> R + Sync + Send,
+    ) -> Vec<R> {
+        items.iter().map(f).collect()
+    }
+
+    /// Update each item.
+    pub fn for_each_mut<T: Send>(
+        items: &mut [T],
+        f: impl Fn(&mut T) + Sync + Send,
+    ) {
+        items.iter_mut().for_each(f);
+    }
+}
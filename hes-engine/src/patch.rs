@@ -0,0 +1,644 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::world::World;
+
+/// The world's collections of entities,
+/// as they're named in its JSON.
+const COLLECTIONS: [&str; 5] = [
+    "regions",
+    "industries",
+    "processes",
+    "projects",
+    "events",
+];
+
+/// Top-level fields that patches can't change.
+const FIXED: [&str; 2] = ["version", "integrity"];
+
+/// Changes to a world, e.g. a mod, so that mods only have to
+/// share what they change rather than the whole world, and
+/// several can be combined. Patches are JSON, e.g.:
+///
+/// ```json
+/// {
+///   "name": "Cheaper Solar",
+///   "set": { "temperature": 1.2 },
+///   "entities": {
+///     "processes": {
+///       "modify": { "Solar PV": { "byproducts": { "co2": 0 } } },
+///       "remove": ["Coal Power Generation"]
+///     }
+///   }
+/// }
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Default, Serialize, Deserialize,
+)]
+pub struct Patch {
+    pub name: String,
+
+    /// Top-level values to replace, e.g. `temperature`.
+    #[serde(default)]
+    pub set: Map<String, Value>,
+
+    /// Changes to entities, by the name of their
+    /// collection, e.g. `processes`.
+    #[serde(default)]
+    pub entities: BTreeMap<String, EntityChanges>,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Default, Serialize, Deserialize,
+)]
+pub struct EntityChanges {
+    /// Entities to add. One with the same id as
+    /// an existing entity replaces it instead.
+    #[serde(default)]
+    pub add: Vec<Value>,
+
+    /// Changes to existing entities, by their id or name.
+    /// Only the fields that change need to be given, as
+    /// nested objects are merged into the existing ones.
+    #[serde(default)]
+    pub modify: BTreeMap<String, Value>,
+
+    /// Entities to remove, by their id or name.
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// Something that more than one patch changed.
+/// The last patch's change is the one that's kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conflict {
+    /// What was changed, e.g.
+    /// `processes/Solar PV/byproducts/co2`.
+    pub path: String,
+
+    /// The patches that changed it, in the
+    /// order they were applied.
+    pub patches: Vec<String>,
+}
+impl Display for Conflict {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is changed by {}; {}'s change is kept.",
+            self.path,
+            self.patches.join(", "),
+            self.patches.last().map_or("", String::as_str)
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum PatchError {
+    /// The patch changes an entity that isn't in the world.
+    NotFound { patch: String, path: String },
+
+    /// The patch changes a collection the world doesn't
+    /// have, or a top-level value it can't change.
+    Unknown { patch: String, key: String },
+
+    /// An added entity isn't an object with an id.
+    BadEntity { patch: String, collection: String },
+
+    /// The world was invalid once the patches were applied.
+    Invalid(serde_json::Error),
+}
+impl Display for PatchError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Self::NotFound { patch, path } => write!(
+                f,
+                "{patch}: There's no `{path}` to change."
+            ),
+            Self::Unknown { patch, key } => {
+                write!(f, "{patch}: Can't change `{key}`.")
+            }
+            Self::BadEntity { patch, collection } => write!(
+                f,
+                "{patch}: Entities added to `{collection}` must be objects with an id."
+            ),
+            Self::Invalid(err) => {
+                write!(f, "The patched world is invalid: {err}")
+            }
+        }
+    }
+}
+
+/// What each patch changed, to find conflicts.
+#[derive(Default)]
+struct Changes {
+    /// Each path changed and which patch changed it.
+    paths: Vec<(String, usize)>,
+
+    /// Entities that were removed, as their collection,
+    /// id and name, and by which patch.
+    removed: Vec<([String; 3], usize)>,
+}
+impl Changes {
+    fn conflicts(&self, patches: &[Patch]) -> Vec<Conflict> {
+        let mut conflicts: BTreeMap<&str, Vec<usize>> =
+            BTreeMap::new();
+        for (i, (a, patch_a)) in self.paths.iter().enumerate() {
+            for (b, patch_b) in &self.paths[i + 1..] {
+                if patch_a == patch_b || !overlaps(a, b) {
+                    continue;
+                }
+                let path =
+                    if a.len() <= b.len() { a } else { b };
+                let by = conflicts.entry(path).or_default();
+                for patch in [patch_a, patch_b] {
+                    if !by.contains(patch) {
+                        by.push(*patch);
+                    }
+                }
+            }
+        }
+        conflicts
+            .into_iter()
+            .map(|(path, mut by)| {
+                by.sort();
+                Conflict {
+                    path: path.to_string(),
+                    patches: by
+                        .into_iter()
+                        .map(|i| patches[i].name.clone())
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// If the paths are the same, or one's within the other.
+fn overlaps(a: &str, b: &str) -> bool {
+    let within = |inner: &str, outer: &str| {
+        inner
+            .strip_prefix(outer)
+            .is_some_and(|rest| rest.starts_with('/'))
+    };
+    a == b || within(a, b) || within(b, a)
+}
+
+/// How an entity is referred to in paths.
+fn label(entity: &Value) -> String {
+    entity
+        .get("name")
+        .or_else(|| entity.get("id"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Find an entity by its id or name.
+fn find(entities: &[Value], key: &str) -> Option<usize> {
+    let is = |entity: &Value, field| {
+        entity.get(field).and_then(Value::as_str) == Some(key)
+    };
+    entities.iter().position(|entity| is(entity, "id")).or_else(
+        || {
+            entities
+                .iter()
+                .position(|entity| is(entity, "name"))
+        },
+    )
+}
+
+/// Merge `changes` into `value`, recording
+/// the path of each value that's changed.
+fn merge(
+    value: &mut Value,
+    changes: &Value,
+    path: &str,
+    changed: &mut Vec<String>,
+) {
+    match (value, changes) {
+        (Value::Object(value), Value::Object(changes)) => {
+            for (key, change) in changes {
+                let path = format!("{path}/{key}");
+                let field = value
+                    .entry(key.clone())
+                    .or_insert(Value::Null);
+                merge(field, change, &path, changed);
+            }
+        }
+        (value, change) => {
+            *value = change.clone();
+            changed.push(path.to_string());
+        }
+    }
+}
+
+/// The changes from `base` to `other`, with
+/// only the fields that changed in objects.
+fn changes(base: &Value, other: &Value) -> Option<Value> {
+    match (base, other) {
+        (Value::Object(base), Value::Object(other)) => {
+            let fields: Map<String, Value> = other
+                .iter()
+                .filter_map(|(key, value)| {
+                    let change = match base.get(key) {
+                        Some(base) => changes(base, value)?,
+                        None => value.clone(),
+                    };
+                    Some((key.clone(), change))
+                })
+                .collect();
+            (!fields.is_empty())
+                .then_some(Value::Object(fields))
+        }
+        _ => (base != other).then(|| other.clone()),
+    }
+}
+
+fn entities<'a>(
+    world: &'a mut Value,
+    patch: &Patch,
+    collection: &str,
+) -> Result<&'a mut Vec<Value>, PatchError> {
+    world
+        .get_mut(collection)
+        .and_then(Value::as_array_mut)
+        .filter(|_| COLLECTIONS.contains(&collection))
+        .ok_or_else(|| PatchError::Unknown {
+            patch: patch.name.clone(),
+            key: collection.to_string(),
+        })
+}
+
+impl Patch {
+    /// The patch that changes `base` into `other`,
+    /// e.g. to share a mod made in the editor.
+    /// Entities are referred to by id.
+    pub fn between(
+        base: &World,
+        other: &World,
+        name: impl Into<String>,
+    ) -> Self {
+        let json = |world| {
+            serde_json::to_value(world)
+                .expect("Worlds are always serializable")
+        };
+        let (base, other) = (json(base), json(other));
+        let (Value::Object(base), Value::Object(other)) =
+            (base, other)
+        else {
+            unreachable!("Worlds are serialized as objects");
+        };
+
+        let mut patch = Patch {
+            name: name.into(),
+            ..Default::default()
+        };
+        for (key, value) in &other {
+            if FIXED.contains(&key.as_str()) {
+                continue;
+            }
+            if COLLECTIONS.contains(&key.as_str()) {
+                let empty = vec![];
+                let base = base
+                    .get(key)
+                    .and_then(Value::as_array)
+                    .unwrap_or(&empty);
+                let other = value.as_array().unwrap_or(&empty);
+                let id = |entity: &Value| {
+                    entity
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let mut changes_ = EntityChanges::default();
+                for entity in other {
+                    match base
+                        .iter()
+                        .find(|b| id(b) == id(entity))
+                    {
+                        Some(base) => {
+                            if let Some(change) =
+                                changes(base, entity)
+                            {
+                                changes_
+                                    .modify
+                                    .insert(id(entity), change);
+                            }
+                        }
+                        None => {
+                            changes_.add.push(entity.clone())
+                        }
+                    }
+                }
+                for entity in base {
+                    if !other
+                        .iter()
+                        .any(|o| id(o) == id(entity))
+                    {
+                        changes_.remove.push(id(entity));
+                    }
+                }
+                if changes_ != EntityChanges::default() {
+                    patch
+                        .entities
+                        .insert(key.clone(), changes_);
+                }
+            } else if base.get(key) != Some(value) {
+                patch.set.insert(key.clone(), value.clone());
+            }
+        }
+        patch
+    }
+}
+
+impl World {
+    /// Apply patches to the world, in order, returning
+    /// the patched world and anything that more than one
+    /// patch changed, in which case the later patch wins.
+    pub fn patched(
+        &self,
+        patches: &[Patch],
+    ) -> Result<(World, Vec<Conflict>), PatchError> {
+        let mut world = serde_json::to_value(self)
+            .expect("Worlds are always serializable");
+        let mut changes = Changes::default();
+        for (i, patch) in patches.iter().enumerate() {
+            for (key, value) in &patch.set {
+                let can_set = !FIXED.contains(&key.as_str())
+                    && !COLLECTIONS.contains(&key.as_str())
+                    && world.get(key).is_some();
+                if !can_set {
+                    return Err(PatchError::Unknown {
+                        patch: patch.name.clone(),
+                        key: key.clone(),
+                    });
+                }
+                let mut changed = vec![];
+                merge(
+                    &mut world[key],
+                    value,
+                    key,
+                    &mut changed,
+                );
+                changes.paths.extend(
+                    changed.into_iter().map(|p| (p, i)),
+                );
+            }
+
+            for (collection, entity_changes) in &patch.entities
+            {
+                let entities =
+                    entities(&mut world, patch, collection)?;
+                for entity in &entity_changes.add {
+                    let Some(id) = entity
+                        .get("id")
+                        .and_then(Value::as_str)
+                    else {
+                        return Err(PatchError::BadEntity {
+                            patch: patch.name.clone(),
+                            collection: collection.clone(),
+                        });
+                    };
+                    let path = format!(
+                        "{collection}/{}",
+                        label(entity)
+                    );
+                    match find(entities, id) {
+                        Some(idx) => {
+                            entities[idx] = entity.clone()
+                        }
+                        None => entities.push(entity.clone()),
+                    }
+                    changes.paths.push((path, i));
+                }
+                for (key, change) in &entity_changes.modify {
+                    let Some(idx) = find(entities, key) else {
+                        changes.not_found(
+                            patch, i, collection, key,
+                        )?;
+                        continue;
+                    };
+                    let path = format!(
+                        "{collection}/{}",
+                        label(&entities[idx])
+                    );
+                    let mut changed = vec![];
+                    merge(
+                        &mut entities[idx],
+                        change,
+                        &path,
+                        &mut changed,
+                    );
+                    changes.paths.extend(
+                        changed.into_iter().map(|p| (p, i)),
+                    );
+                }
+                for key in &entity_changes.remove {
+                    let Some(idx) = find(entities, key) else {
+                        changes.not_found(
+                            patch, i, collection, key,
+                        )?;
+                        continue;
+                    };
+                    let entity = entities.remove(idx);
+                    let path = format!(
+                        "{collection}/{}",
+                        label(&entity)
+                    );
+                    let field = |field| {
+                        entity
+                            .get(field)
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    changes.removed.push((
+                        [
+                            collection.clone(),
+                            field("id"),
+                            field("name"),
+                        ],
+                        i,
+                    ));
+                    changes.paths.push((path, i));
+                }
+            }
+        }
+
+        let conflicts = changes.conflicts(patches);
+        let world = serde_json::from_value(world)
+            .map_err(PatchError::Invalid)?;
+        Ok((world, conflicts))
+    }
+}
+
+impl Changes {
+    /// A patch changes an entity that isn't in the world,
+    /// which is a conflict if an earlier patch removed it
+    /// and otherwise an error.
+    fn not_found(
+        &mut self,
+        patch: &Patch,
+        i: usize,
+        collection: &str,
+        key: &str,
+    ) -> Result<(), PatchError> {
+        let removed = self.removed.iter().find(
+            |([removed_from, id, name], _)| {
+                removed_from == collection
+                    && (id == key || name == key)
+            },
+        );
+        match removed {
+            Some(([_, id, name], _)) => {
+                let label =
+                    if name.is_empty() { id } else { name };
+                self.paths
+                    .push((format!("{collection}/{label}"), i));
+                Ok(())
+            }
+            None => Err(PatchError::NotFound {
+                patch: patch.name.clone(),
+                path: format!("{collection}/{key}"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(value: Value) -> Patch {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_patches() {
+        let world = World::default();
+        let process = world.processes.first().clone();
+        let project = world.projects.first().clone();
+        let mut added = world.projects.first().clone();
+        added.id = crate::Id::new_v4();
+        added.name = "A New Project".into();
+
+        let a = patch(json!({
+            "name": "A",
+            "set": { "temperature": 2.0 },
+            "entities": {
+                "processes": {
+                    "modify": {
+                        process.name.clone(): { "mix_share": 3 }
+                    }
+                },
+                "projects": {
+                    "add": [added],
+                }
+            }
+        }));
+        let b = patch(json!({
+            "name": "B",
+            "entities": {
+                "processes": {
+                    "modify": {
+                        process.id.to_string(): { "mix_share": 5, "limit": 10.0 }
+                    }
+                },
+                "projects": {
+                    "remove": [project.name.clone()],
+                }
+            }
+        }));
+
+        let (patched, conflicts) =
+            world.patched(&[a.clone(), b.clone()]).unwrap();
+        assert_eq!(patched.temperature, 2.);
+        assert_eq!(patched.processes[&process.id].mix_share, 5);
+        assert_eq!(
+            patched.processes[&process.id].limit,
+            Some(10.)
+        );
+        assert!(patched.projects.try_get(&added.id).is_some());
+        assert!(patched
+            .projects
+            .try_get(&project.id)
+            .is_none());
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                path: format!(
+                    "processes/{}/mix_share",
+                    process.name
+                ),
+                patches: vec!["A".into(), "B".into()],
+            }]
+        );
+
+        // Changing something another patch removed is
+        // a conflict, but it isn't if nothing removed it.
+        let c = patch(json!({
+            "name": "C",
+            "entities": {
+                "projects": {
+                    "modify": { project.name.clone(): { "cost": 1 } }
+                }
+            }
+        }));
+        let (_, conflicts) =
+            world.patched(&[b.clone(), c.clone()]).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                path: format!("projects/{}", project.name),
+                patches: vec!["B".into(), "C".into()],
+            }]
+        );
+        let mut missing = c.clone();
+        missing.entities.get_mut("projects").unwrap().modify =
+            BTreeMap::from([("Nothing".into(), json!({}))]);
+        assert!(matches!(
+            world.patched(&[missing]),
+            Err(PatchError::NotFound { .. })
+        ));
+
+        let unknown = patch(json!({
+            "name": "D",
+            "set": { "version": 0 },
+        }));
+        assert!(matches!(
+            world.patched(&[unknown]),
+            Err(PatchError::Unknown { .. })
+        ));
+    }
+
+    #[test]
+    fn test_between() {
+        let base = World::default();
+        let mut other = base.clone();
+        other.temperature = 3.;
+        other.processes.by_idx_mut(0).mix_share += 1;
+        let removed = other.events.by_idx(0).id;
+        other.events.remove(&removed);
+
+        let patch = Patch::between(&base, &other, "Mod");
+        assert_eq!(patch.set.len(), 1);
+        assert_eq!(patch.entities["events"].remove.len(), 1);
+        let (patched, conflicts) =
+            base.patched(&[patch]).unwrap();
+        assert!(conflicts.is_empty());
+        assert_eq!(
+            serde_json::to_value(&patched).unwrap(),
+            serde_json::to_value(&other).unwrap()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Particulates and plastic. Particulates only stay in the
+//! air for a short while, so the airborne load follows each
+//! year's emissions closely and clears soon after they're
+//! cut. Plastic barely breaks down, so it piles up in the
+//! environment for as long as it's released. Dirty air harms
+//! people's health (see [`crate::health`]), and plastic
+//! piling up adds to biodiversity pressure.
+
+use crate::{state::State, world::World};
+
+/// The share of the airborne particulate
+/// load that settles out each year.
+const SETTLING: f32 = 0.9;
+
+/// The share of plastic in the
+/// environment that breaks down each year.
+const BREAKDOWN: f32 = 0.001;
+
+/// Plastic (Mt) in the environment at which it adds no
+/// biodiversity pressure, roughly what's there at the start.
+const PLASTIC_REFERENCE: f32 = 5000.;
+
+/// Extinction rate for each Mt of
+/// plastic past the reference.
+const PLASTIC_EXTINCTION: f32 = 0.005;
+
+pub(crate) fn default_plastic() -> f32 {
+    PLASTIC_REFERENCE
+}
+
+impl World {
+    /// Contribution to extinction rate from plastic
+    /// in the environment.
+    pub fn plastic_extinction_rate(&self) -> f32 {
+        (self.plastic - PLASTIC_REFERENCE) * PLASTIC_EXTINCTION
+    }
+}
+
+impl State {
+    /// This year's particulate and plastic emissions (Mt).
+    fn pollution(&self) -> (f32, f32) {
+        let byproducts = self.byproducts.total();
+        (
+            byproducts.particulates * 1e-12,
+            byproducts.plastic * 1e-12,
+        )
+    }
+
+    /// Settle the airborne particulate load at
+    /// what this year's emissions keep it at.
+    pub(crate) fn settle_particulates(&mut self) {
+        let (particulates, _) = self.pollution();
+        self.world.particulates = particulates / SETTLING;
+    }
+
+    /// Add this year's particulates and plastic, with the
+    /// air getting cleaner or dirtier affecting health.
+    pub(crate) fn update_pollution(&mut self) {
+        let (particulates, plastic) = self.pollution();
+        self.world.particulates = self.world.particulates
+            * (1. - SETTLING)
+            + particulates;
+        self.update_health();
+
+        self.world.plastic =
+            (self.world.plastic * (1. - BREAKDOWN) + plastic)
+                .max(0.);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::kinds::Byproduct;
+
+    #[test]
+    fn test_pollution() {
+        let mut state = State::default();
+        let particulates = state.world.particulates;
+        assert!(particulates > 0.);
+        assert_eq!(state.world.plastic, PLASTIC_REFERENCE);
+        assert_eq!(state.world.plastic_extinction_rate(), 0.);
+
+        // The air stays as it is when emissions do.
+        let outlook = state.world.base_outlook;
+        state.update_pollution();
+        assert!(
+            (state.world.particulates - particulates).abs()
+                < 1e-3
+        );
+        assert!(
+            (state.world.base_outlook - outlook).abs() < 1e-3
+        );
+
+        // Plastic piles up.
+        assert!(state.world.plastic > PLASTIC_REFERENCE);
+        assert!(state.world.plastic_extinction_rate() > 0.);
+
+        // Cutting particulates clears the air within a year
+        // or so, and people are happier for it.
+        let coal = state
+            .world
+            .processes
+            .iter()
+            .find(|p| p.name == "Coal Power Generation")
+            .map(|p| p.id)
+            .unwrap();
+        let amount = state.produced.by_process[&coal];
+        let emitted = state.world.processes[&coal]
+            .adj_byproducts()[Byproduct::Particulates]
+            * amount;
+        state.byproducts.modifier.particulates -= emitted;
+        state.update_pollution();
+        state.update_pollution();
+        assert!(state.world.particulates < particulates);
+        assert!(state.world.base_outlook > outlook);
+    }
+}
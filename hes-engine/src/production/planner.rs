@@ -1,7 +1,4 @@
-use std::{
-    collections::BTreeMap,
-    ops::{Index, IndexMut},
-};
+use std::ops::{Index, IndexMut};
 
 use super::processes::Process;
 use crate::{byproducts, feedstocks, kinds::*, resources};
@@ -70,98 +67,121 @@ fn feedstock_score(
     }
 }
 
+/// A process's intensities and byproducts per unit of
+/// output, computed once per production run rather than
+/// each time the orders are ranked.
+struct Intensity {
+    resources: ResourceMap,
+    feedstock: Feedstock,
+    feedstock_amount: f32,
+    byproducts: ByproductMap,
+}
+impl Intensity {
+    fn of(process: &Process) -> Self {
+        Intensity {
+            resources: process.adj_resources(),
+            feedstock: process.feedstock.0,
+            feedstock_amount: process.adj_feedstock_amount(),
+            byproducts: process.adj_byproducts(),
+        }
+    }
+}
+
+/// Buffers for ranking orders, reused so that
+/// each ranking doesn't need to allocate.
+#[derive(Default)]
+struct Ranking {
+    scores: Vec<(f32, f32)>,
+    keys: Vec<(isize, usize)>,
+    sorted: Vec<usize>,
+}
+
 /// Rank production orders according to efficiency.
 /// That is we prioritize by lowest-intensity in terms
 /// of feedstock and resource usage.
 fn rank_orders(
-    orders: &[ProductionOrder],
+    intensities: &[Intensity],
     indices: &mut [usize],
     demand: (&ResourceMap, &FeedstockMap),
     resources: &ResourceMap,
     feedstocks: &FeedstockMap,
+    ranking: &mut Ranking,
 ) {
-    let mut resource_scores = vec![];
-    let mut feedstock_scores = vec![];
-    let mut scores: BTreeMap<usize, (f32, f32)> =
-        BTreeMap::default();
-
     let mut max_intensity = resources!();
+    let mut max_intensity_fs = feedstocks!();
     for i in indices.iter() {
-        let process = orders[*i].process;
-        let resources = process.adj_resources();
+        let intensity = &intensities[*i];
         for (k, val) in max_intensity.items_mut() {
-            *val = val.max(resources[k]);
+            *val = val.max(intensity.resources[k]);
         }
-    }
-    let mut max_intensity_fs = feedstocks!();
-    for i in indices.iter() {
-        let process = orders[*i].process;
-        let feedstock = process.adj_feedstock_amount();
-        max_intensity_fs[process.feedstock.0] =
-            max_intensity_fs[process.feedstock.0]
-                .max(feedstock);
+        let fs = intensity.feedstock;
+        max_intensity_fs[fs] = max_intensity_fs[fs]
+            .max(intensity.feedstock_amount);
     }
 
+    ranking.scores.clear();
+    let mut max_resource_score = -f32::INFINITY;
+    let mut max_feedstock_score = -f32::INFINITY;
     for i in indices.iter() {
-        let process = orders[*i].process;
-
-        let r = process.adj_resources();
+        let intensity = &intensities[*i];
         let resource_score = resource_score(
-            &r,
+            &intensity.resources,
             resources,
             demand.0,
             &max_intensity,
         );
-        resource_scores.push(resource_score);
-
-        let f = process.adj_feedstock_amount();
-        let fs = process.feedstock.0;
+        let fs = intensity.feedstock;
         let feedstock_score = feedstock_score(
-            f,
+            intensity.feedstock_amount,
             demand.1[fs],
             feedstocks[fs],
             max_intensity_fs[fs],
         );
-        feedstock_scores.push(feedstock_score);
-
-        scores.insert(*i, (resource_score, feedstock_score));
+        max_resource_score =
+            max_resource_score.max(resource_score);
+        max_feedstock_score =
+            max_feedstock_score.max(feedstock_score);
+        ranking.scores.push((resource_score, feedstock_score));
     }
 
-    let max_resource_score = resource_scores
-        .iter()
-        .fold(-f32::INFINITY, |a, &b| a.max(b));
-    let max_feedstock_score = feedstock_scores
-        .iter()
-        .fold(-f32::INFINITY, |a, &b| a.max(b));
-
-    // Sort so that the best (lowest) scoring are at the end
-    indices.sort_by_cached_key(|i| {
-        let (r, f) = scores.get(i).unwrap();
-        let resource_score = r / max_resource_score;
-        let feedstock_score = f / max_feedstock_score;
-        let score = feedstock_score.min(resource_score);
-
-        // Hacky
-        (score * 1e6).round() as isize
-    });
+    // Sort so that the best (lowest) scoring are at the end,
+    // keeping ties in their current order.
+    ranking.keys.clear();
+    ranking.keys.extend(ranking.scores.iter().enumerate().map(
+        |(pos, (r, f))| {
+            let resource_score = r / max_resource_score;
+            let feedstock_score = f / max_feedstock_score;
+            let score = feedstock_score.min(resource_score);
+
+            // Hacky
+            ((score * 1e6).round() as isize, pos)
+        },
+    ));
+    ranking.keys.sort_unstable();
+    ranking.sorted.clear();
+    ranking.sorted.extend(
+        ranking.keys.iter().map(|(_, pos)| indices[*pos]),
+    );
+    indices.copy_from_slice(&ranking.sorted);
 }
 
 fn produce_amount(
     order: &ProductionOrder,
+    intensity: &Intensity,
     available_resources: &mut ResourceMap,
     available_feedstocks: &mut FeedstockMap,
     produced_byproducts: &mut ByproductMap,
 ) -> f32 {
-    let byproducts = order.process.adj_byproducts();
-    let feedstock = order.process.feedstock.0;
-    let feedstock_amount = order.process.adj_feedstock_amount();
-    let resources = order.process.adj_resources();
-
+    let feedstock = intensity.feedstock;
     let feedstock_max = match feedstock {
         Feedstock::Other | Feedstock::Soil => order.amount,
-        _ => available_feedstocks[feedstock] / feedstock_amount,
+        _ => {
+            available_feedstocks[feedstock]
+                / intensity.feedstock_amount
+        }
     };
-    let resource_max = resources
+    let resource_max = intensity
+        .resources
         .items()
         .into_iter()
         .map(|(k, v)| available_resources[k] / v)
@@ -172,16 +192,16 @@ fn produce_amount(
         .min(feedstock_max.min(resource_max))
         .max(0.);
 
-    for (k, v) in resources.items() {
+    for (k, v) in intensity.resources.items() {
         available_resources[k] = (available_resources[k]
             - v * amount_produced)
             .max(0.);
     }
-    for (k, v) in byproducts.items() {
+    for (k, v) in intensity.byproducts.items() {
         produced_byproducts[k] += v * amount_produced;
     }
     available_feedstocks[feedstock] -=
-        feedstock_amount * amount_produced;
+        intensity.feedstock_amount * amount_produced;
 
     amount_produced
 }
@@ -249,6 +269,11 @@ pub fn calculate_production(
     let mut feedstocks = starting_feedstocks.clone();
     let mut produced_byproducts: ByproductMap = byproducts!();
     let mut produced = vec![0.; orders.len()];
+    let intensities: Vec<Intensity> = orders
+        .iter()
+        .map(|order| Intensity::of(order.process))
+        .collect();
+    let mut ranking = Ranking::default();
 
     let mut orders_by_output: Outputs = Outputs::default();
     for (i, order) in orders.iter().enumerate() {
@@ -263,11 +288,12 @@ pub fn calculate_production(
             }
 
             rank_orders(
-                orders,
+                &intensities,
                 order_idxs,
                 demand,
                 &resources,
                 &feedstocks,
+                &mut ranking,
             );
 
             // Ok to unwrap b/c we check if `orders` is empty
@@ -275,6 +301,7 @@ pub fn calculate_production(
 
             let amount = produce_amount(
                 &orders[order_idx],
+                &intensities[order_idx],
                 &mut resources,
                 &mut feedstocks,
                 &mut produced_byproducts,
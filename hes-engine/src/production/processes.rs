@@ -1,6 +1,7 @@
 use super::ProductionOrder;
 use crate::{
     flavor::ProcessFlavor,
+    form::Documented,
     kinds::{
         ByproductMap,
         Feedstock,
@@ -10,6 +11,8 @@ use crate::{
         ResourceMap,
     },
     npcs::RELATIONSHIP_CHANGE_AMOUNT,
+    par,
+    Bounds,
     Collection,
     HasId,
     Id,
@@ -43,34 +46,70 @@ pub enum ProcessFeature {
     IsCombustion,
     IsFossil,
     IsLaborIntensive,
+
+    /// Relies on the oceans, e.g. fisheries, and
+    /// so is affected by ocean acidification.
+    IsMarine,
+
+    /// Builds up the health of the soil it farms.
+    IsRegenerative,
 }
 
 #[derive(
-    Debug, Clone, Serialize, Deserialize, PartialEq, Default,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Default,
+    Documented,
 )]
 pub struct Process {
     pub id: Id,
     pub name: String,
+
+    /// What percent of total output production this
+    /// process represents at the start. Note that
+    /// 1 mix share = 5% of total output.
     pub mix_share: usize,
+
+    /// (Optional) This process can never produce more than
+    /// this much output, effectively setting a limit on its
+    /// mix share. This may be because, for example, of a
+    /// finite availability, e.g. with geothermal.
     pub limit: Option<f32>,
+
+    /// What this process produces.
     pub output: Output,
 
     pub output_modifier: f32,
     pub byproduct_modifiers: ByproductMap,
 
+    /// Resources used, per unit output.
     pub resources: ResourceMap,
+
+    /// Byproducts produced, per unit output.
     pub byproducts: ByproductMap,
     pub feedstock: (Feedstock, f32),
 
+    /// Special properties associated with this process.
     pub features: Vec<ProcessFeature>,
 
-    // If the player has unlocked this process.
+    /// If this process is locked at the start.
     pub locked: bool,
 
+    /// NPCs that support this process.
     pub supporters: Vec<Id>,
+
+    /// NPCs that oppose this process.
     pub opposers: Vec<Id>,
     pub flavor: ProcessFlavor,
+
+    /// Optional notes.
     pub notes: String,
+    /// Free-form tags for organizing entities in the editor.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl Display for Process {
@@ -89,6 +128,11 @@ impl HasId for Process {
 }
 
 impl Process {
+    /// Each mix share is 5% of the output's production,
+    /// so 20 shares is all of it.
+    pub const MIX_SHARE_BOUNDS: Bounds = Bounds::new(0., 20., 1.);
+    pub const FEEDSTOCK_BOUNDS: Bounds = Bounds::NON_NEGATIVE;
+
     pub fn new() -> Process {
         Process {
             id: Id::new_v4(),
@@ -246,9 +290,7 @@ impl Collection<Process> {
         &self,
         demand: &OutputMap,
     ) -> Vec<ProductionOrder> {
-        self.iter()
-            .map(|p| p.production_order(&demand))
-            .collect()
+        par::map(self, |p| p.production_order(demand))
     }
 
     pub fn max_shares(
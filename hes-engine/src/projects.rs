@@ -1,6 +1,7 @@
 use crate::{
     events::{Effect, Probability},
     flavor::ProjectFlavor,
+    form::{Documented, EditorForm},
     kinds::{Output, OutputMap},
     npcs::{NPCRelation, NPC, RELATIONSHIP_CHANGE_AMOUNT},
     Collection,
@@ -155,24 +156,55 @@ pub struct Outcome {
 
 /// An upgrade for a project.
 #[derive(
-    Debug, Deserialize, Serialize, Default, Clone, PartialEq,
+    Debug,
+    Deserialize,
+    Serialize,
+    Default,
+    Clone,
+    PartialEq,
+    EditorForm,
 )]
 pub struct Upgrade {
+    #[form(help = "The upgrade cost.")]
     pub cost: usize,
+
+    #[form(skip)]
     pub effects: Vec<Effect>,
+
+    /// Set when the upgrade is applied during play.
+    #[form(skip)]
     pub active: bool,
 }
 
 #[derive(
-    Debug, Clone, Serialize, Deserialize, PartialEq, Default,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Default,
+    Documented,
 )]
 pub struct Project {
     pub id: Id,
     pub name: String,
+
+    /// The type of project.
     pub kind: Type,
+
+    /// The project's category.
     pub group: Group,
+
+    /// Is this a one-and-done project, or does
+    /// it need continued maintenance?
     pub ongoing: bool,
+
+    /// Does this project have to be 100% finished before
+    /// the effects occur, or do they develop as the
+    /// project is developed?
     pub gradual: bool,
+
+    /// If this project is locked at the start.
     pub locked: bool,
 
     // For policies, the cost is the political capital cost;
@@ -192,11 +224,19 @@ pub struct Project {
     pub upgrades: Vec<Upgrade>,
     pub active_outcome: Option<usize>,
 
+    /// NPCs that support this project.
     pub supporters: Vec<Id>,
+
+    /// NPCs that oppose this project.
     pub opposers: Vec<Id>,
 
     pub flavor: ProjectFlavor,
+
+    /// Optional notes.
     pub notes: String,
+    /// Free-form tags for organizing entities in the editor.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl Display for Project {
@@ -1,10 +1,14 @@
 use std::fmt::Display;
 
 use crate::{
+    disasters::default_vulnerability,
     events::RegionFlag,
     flavor::RegionFlavor,
+    form::Documented,
     kinds::*,
+    land::default_land_use,
     outputs,
+    Bounds,
     HasId,
     Id,
 };
@@ -14,18 +18,64 @@ use strum::{Display, EnumIter, EnumString, IntoStaticStr};
 // 40 years per level
 const DEVELOP_SPEED: f32 = 1. / 40.;
 
+/// Habitability lost if a region's land were all flooded.
+const FLOODED_HABITABILITY: f32 = 50.;
+
+/// Outlook lost for each share of a region's land that
+/// floods, from the people displaced by it.
+const FLOODED_OUTLOOK: f32 = 200.;
+
+/// Regional warming (C) past which
+/// habitability starts to fall.
+const WARMING_TOLERANCE: f32 = 2.;
+
+/// Habitability lost for each degree of regional
+/// warming past the tolerance.
+const WARMING_HABITABILITY: f32 = 1.;
+
+/// Regional warming (C) past which crop yields start
+/// to fall, and the share lost for each degree past it.
+const CROP_TOLERANCE: f32 = 1.;
+const CROP_LOSS_PER_DEGREE: f32 = 0.03;
+
+fn default_coastal_exposure() -> f32 {
+    0.05
+}
+
+fn default_continentality() -> f32 {
+    0.5
+}
+
 #[derive(
-    Debug, Clone, Serialize, Deserialize, PartialEq, Default,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Default,
+    Documented,
 )]
 pub struct Region {
     pub id: Id,
 
     pub name: String,
 
+    /// The region's starting population.
     pub population: f32,
+
+    /// The share of the region's population in each age
+    /// cohort. If left empty the region starts with the
+    /// usual shares for its income.
+    #[serde(default)]
+    pub age_structure: CohortMap,
+
     pub seceded: bool,
 
+    /// The region's starting income level.
     pub income: Income,
+
+    /// The region's starting progress to the
+    /// next income level, from 0.0 to 1.0.
     pub development: f32,
 
     pub flags: Vec<RegionFlag>,
@@ -39,13 +89,67 @@ pub struct Region {
     /// from this value
     pub base_habitability: f32,
 
-    /// Local temperature and precipitation
+    /// The share of the region's land that's low-lying
+    /// coast, which floods for each meter of sea level rise.
+    #[serde(default = "default_coastal_exposure")]
+    pub coastal_exposure: f32,
+
+    /// The share of the region's land lost
+    /// to sea level rise so far.
+    #[serde(default)]
+    pub flooded: f32,
+
+    /// How vulnerable the region is to extreme weather,
+    /// relative to what its climate alone would make it.
+    #[serde(default = "default_vulnerability")]
+    pub vulnerability: f32,
+
+    /// The region's freshwater supply and
+    /// demand this year (L/year).
+    #[serde(default)]
+    pub water_supply: f32,
+    #[serde(default)]
+    pub water_demand: f32,
+
+    /// The particulate concentration (µg/m3) the region
+    /// breathes this year, and what it breathed when
+    /// the game started.
+    #[serde(default)]
+    pub air_pollution: f32,
+    #[serde(default)]
+    pub base_air_pollution: f32,
+
+    /// The share of the region's land in each use. Cropland
+    /// and pasture follow what farming processes use, and
+    /// protected land follows land protection.
+    #[serde(default = "default_land_use")]
+    pub land_use: LandUseMap,
+
+    /// The region's starting low and high temperatures (C).
+    /// These are updated from the climate model as the
+    /// world warms.
     pub temp_lo: f32,
     pub temp_hi: f32,
+
+    /// The region's starting low and high precipitation
+    /// (cm/year). These are updated from the climate model
+    /// as the world warms.
     pub precip_lo: f32,
     pub precip_hi: f32,
     pub latitude: Latitude,
 
+    /// How continental the region's climate is, from 0
+    /// (maritime) to 1 (far inland). Land further from
+    /// the oceans warms faster.
+    #[serde(default = "default_continentality")]
+    pub continentality: f32,
+
+    /// The region's current temperature anomaly (C), i.e.
+    /// the global one amplified by its latitude and
+    /// continentality.
+    #[serde(default)]
+    pub temp_anomaly: f32,
+
     pub flavor: RegionFlavor,
     pub pattern_idxs: Vec<usize>,
 }
@@ -57,6 +161,17 @@ impl HasId for Region {
 }
 
 impl Region {
+    /// Development is the progress towards
+    /// the next income level.
+    pub const DEVELOPMENT_BOUNDS: Bounds = Bounds::FRACTION;
+    pub const POPULATION_BOUNDS: Bounds = Bounds::NON_NEGATIVE;
+    pub const COASTAL_EXPOSURE_BOUNDS: Bounds =
+        Bounds::FRACTION;
+    pub const VULNERABILITY_BOUNDS: Bounds =
+        Bounds::NON_NEGATIVE;
+    pub const CONTINENTALITY_BOUNDS: Bounds = Bounds::FRACTION;
+    pub const LAND_USE_BOUNDS: Bounds = Bounds::FRACTION;
+
     pub fn develop(
         &mut self,
         speed: f32,
@@ -79,10 +194,54 @@ impl Region {
         // Factors:
         // - [X] regional temp
         // - [ ] precip TODO
-        // - [ ] sea_level_rise TODO
+        // - [X] sea_level_rise
         // - [X] number of negative events
+        // - [X] regional warming
         self.base_habitability
             - (f32::max(0., self.temp_hi - 35.).powf(2.) * 10.)
+            - self.flooded * FLOODED_HABITABILITY
+            - (self.temp_anomaly - WARMING_TOLERANCE).max(0.)
+                * WARMING_HABITABILITY
+    }
+
+    /// How much faster than the world as a whole the
+    /// region warms.
+    pub fn warming_factor(&self) -> f32 {
+        self.latitude.amplification()
+            * (1.2 + 0.4 * self.continentality)
+    }
+
+    /// The region's temperature anomaly
+    /// at a global temperature anomaly.
+    pub fn anomaly_at(&self, temperature: f32) -> f32 {
+        temperature * self.warming_factor()
+    }
+
+    /// The share of crop yields the region
+    /// has lost to its warming.
+    pub fn crop_yield_loss(&self) -> f32 {
+        ((self.temp_anomaly - CROP_TOLERANCE).max(0.)
+            * CROP_LOSS_PER_DEGREE)
+            .min(1.)
+    }
+
+    /// The share of the region's land that's
+    /// flooded at a given sea level rise.
+    pub fn flooded_at(&self, sea_level_rise: f32) -> f32 {
+        (self.coastal_exposure * sea_level_rise).clamp(0., 1.)
+    }
+
+    /// Flood the region's coasts up to the given sea level
+    /// rise, returning how many people are displaced.
+    /// Displacement hurts the region's outlook.
+    pub fn flood(&mut self, sea_level_rise: f32) -> f32 {
+        let flooded = self.flooded_at(sea_level_rise);
+        let change = (flooded - self.flooded).max(0.);
+        self.flooded = self.flooded.max(flooded);
+        self.outlook -= change * FLOODED_OUTLOOK;
+        let displaced = self.population * change;
+        self.population -= displaced;
+        displaced
     }
 
     pub fn set_income_level(&mut self, level: usize) {
@@ -121,20 +280,6 @@ impl Region {
         demand_levels
     }
 
-    pub fn update_pop(
-        &mut self,
-        year: f32,
-        modifier: f32,
-        income_pop_coefs: &[[f32; 4]; 4],
-    ) {
-        let coefs = income_pop_coefs[self.income.level()];
-        let change = coefs[0]
-            + (coefs[1] * year)
-            + (coefs[2] * year.powf(2.0))
-            + (coefs[3] * year.powf(3.0));
-        self.population *= 1. + (change * modifier);
-    }
-
     // Outlook slowly rebounds over time
     pub fn update_outlook(
         &mut self,
@@ -167,17 +312,18 @@ impl Region {
         output_demand: &[OutputDemand; 4],
     ) -> OutputMap {
         let mut demand = outputs!();
+        let consumers = self.consumers();
         let idx = self.income.level();
         if idx < 3 {
             let upper_demand = output_demand[idx + 1].total();
             for (k, v_a) in output_demand[idx].total().items() {
                 let v_b = upper_demand[k];
                 let v = (v_b - v_a) * self.development + v_a;
-                demand[k] = v * self.population;
+                demand[k] = v * consumers;
             }
         } else {
             for (k, v) in output_demand[idx].total().items() {
-                demand[k] = v * self.population;
+                demand[k] = v * consumers;
             }
         }
         demand
@@ -199,7 +345,7 @@ impl Region {
         } else {
             materials_by_income[idx]
         };
-        self.population * per_capita_demand
+        self.consumers() * per_capita_demand
             / materials_by_income[0]
     }
 
@@ -211,6 +357,10 @@ impl Region {
         )
     }
 
+    pub fn temp_anomaly(&self) -> String {
+        format!("{:+.1}°C", self.temp_anomaly)
+    }
+
     pub fn precip_range(&self) -> String {
         format!(
             "{}-{}cm/yr",
@@ -319,3 +469,25 @@ pub enum Latitude {
     Temperate,
     Frigid,
 }
+impl Latitude {
+    /// The band a latitude, in degrees, falls in.
+    pub fn of_degrees(lat: f32) -> Latitude {
+        match lat.abs() {
+            lat if lat < 23.5 => Latitude::Tropic,
+            lat if lat < 35. => Latitude::Subtropic,
+            lat if lat < 66.5 => Latitude::Temperate,
+            _ => Latitude::Frigid,
+        }
+    }
+
+    /// How much faster than the global average the
+    /// band warms, e.g. from Arctic amplification.
+    pub fn amplification(&self) -> f32 {
+        match self {
+            Latitude::Tropic => 0.9,
+            Latitude::Subtropic => 1.,
+            Latitude::Temperate => 1.2,
+            Latitude::Frigid => 2.5,
+        }
+    }
+}
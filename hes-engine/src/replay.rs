@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::Phase,
+    flavor::DialogueNext,
+    simulation::{Simulation, YearReport},
+    state::State,
+    world::World,
+    Id,
+};
+
+/// A decision made by the player, recorded so
+/// that a run can be played back later.
+///
+/// What the game does outside of the yearly step, like
+/// rolling a phase's events, is recorded the same way, as
+/// it depends on where the player is in the game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Decision {
+    StartProject(Id),
+    StopProject(Id),
+    UpgradeProject(Id),
+    DowngradeProject(Id),
+    SetProjectPoints(Id, usize),
+    ChangeMixShare(Id, isize),
+
+    /// Political capital spent on (or refunded from) the
+    /// other decisions, or gained from the game's reports,
+    /// which happen outside of the simulation.
+    ChangePoliticalCapital(isize),
+
+    /// A response chosen in an event's dialogue, by the
+    /// line it was a response to and the response's id.
+    Respond {
+        event: Id,
+        region: Option<Id>,
+        line: usize,
+        response: usize,
+    },
+
+    /// A phase's events were rolled.
+    RollEvents(Phase),
+
+    /// A planning cycle was finished.
+    FinishCycle,
+
+    /// A disaster (an icon event) struck a region,
+    /// with an intensity in habitability.
+    Disaster {
+        event: Id,
+        region: Id,
+        intensity: isize,
+    },
+}
+impl Decision {
+    /// Whether this was made by the game
+    /// rather than by the player.
+    fn is_automatic(&self) -> bool {
+        matches!(
+            self,
+            Decision::RollEvents(_)
+                | Decision::FinishCycle
+                | Decision::Disaster { .. }
+        )
+    }
+}
+
+impl State {
+    /// Make a decision, recording it for replays.
+    pub fn decide(&mut self, decision: Decision) {
+        match &decision {
+            // These record themselves.
+            Decision::RollEvents(phase) => {
+                self.roll_events(*phase);
+                return;
+            }
+            Decision::FinishCycle => {
+                self.finish_cycle();
+                return;
+            }
+            Decision::Disaster {
+                event,
+                region,
+                intensity,
+            } => {
+                self.apply_disaster(*intensity, region);
+                self.apply_event(*event, Some(*region));
+            }
+            Decision::StartProject(id) => {
+                self.start_project(id)
+            }
+            Decision::StopProject(id) => self.stop_project(id),
+            Decision::UpgradeProject(id) => {
+                self.upgrade_project(id)
+            }
+            Decision::DowngradeProject(id) => {
+                self.downgrade_project(id)
+            }
+            Decision::SetProjectPoints(id, points) => {
+                self.set_project_points(id, *points)
+            }
+            Decision::ChangeMixShare(id, change) => {
+                self.change_process_mix_share(id, *change)
+            }
+            Decision::ChangePoliticalCapital(amount) => {
+                self.change_political_capital(*amount)
+            }
+            Decision::Respond {
+                event,
+                region,
+                line,
+                response,
+            } => {
+                let resp = self
+                    .event_pool
+                    .events
+                    .try_get(event)
+                    .and_then(|event| {
+                        event.flavor.dialogue.lines.get(*line)
+                    })
+                    .and_then(|line| match &line.next {
+                        Some(DialogueNext::Responses(
+                            resps,
+                        )) => resps
+                            .iter()
+                            .find(|resp| resp.id == *response),
+                        _ => None,
+                    })
+                    .cloned();
+                if let Some(resp) = resp {
+                    let effects =
+                        self.event_pool.scaled_effects(
+                            event,
+                            *region,
+                            &resp.effects,
+                        );
+                    self.apply_effects(&effects, *region);
+                    for follow_up in &resp.follow_ups {
+                        self.event_pool.queue_follow_up(
+                            *event, follow_up, *region,
+                        );
+                    }
+                }
+            }
+        }
+        self.decisions.push((self.world.year, decision));
+    }
+}
+
+/// Everything needed to play a run back: the world it
+/// started from, its seed, the player's decisions (and
+/// the game's), each with the year it was made in, and
+/// the temperature anomaly each year was played with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub world: World,
+    pub seed: u64,
+    pub decisions: Vec<(usize, Decision)>,
+
+    /// The temperature anomaly (C) of each year. Games
+    /// get these from Hector, which replays don't have,
+    /// so years past these are approximated instead
+    /// (see [`Simulation`]) and won't play out exactly
+    /// as they would have.
+    #[serde(default)]
+    pub tgavs: Vec<f32>,
+}
+impl Replay {
+    /// The replay of a game so far, given
+    /// the world it was started from.
+    pub fn new(world: World, state: &State) -> Self {
+        Replay {
+            world,
+            seed: state.seed,
+            decisions: state.decisions.clone(),
+            tgavs: state.tgavs.clone(),
+        }
+    }
+
+    /// Re-simulate the run for the given number of years,
+    /// making each decision in the year it was made in and
+    /// using each year's recorded temperature anomaly.
+    /// Returns the final state and a report for each year.
+    ///
+    /// Recorded years are played back exactly, with their
+    /// events rolled when they were. Other years, such as
+    /// those of a batch's scripted decisions, roll events
+    /// as a [`Simulation`] does.
+    pub fn run(
+        &self,
+        years: usize,
+    ) -> (State, Vec<YearReport>) {
+        let mut sim = Simulation::with_seed(
+            self.world.clone(),
+            self.seed,
+        );
+        let recorded = self
+            .decisions
+            .iter()
+            .any(|(_, decision)| decision.is_automatic());
+        let mut decisions = self.decisions.iter().peekable();
+        let mut reports = vec![];
+        for i in 0..years {
+            let year = sim.state.world.year;
+            while let Some((_, decision)) = decisions
+                .next_if(|(decided, _)| *decided <= year)
+            {
+                play(&mut sim.state, decision);
+            }
+            let report = match self.tgavs.get(i) {
+                Some(tgav) if recorded => {
+                    sim.step_only(*tgav);
+
+                    // The year's report is from after its
+                    // events, before the player's decisions.
+                    let year = sim.state.world.year;
+                    let mut events = vec![];
+                    while let Some((_, decision)) = decisions
+                        .next_if(|(decided, decision)| {
+                            *decided <= year
+                                && decision.is_automatic()
+                        })
+                    {
+                        events.extend(play(
+                            &mut sim.state,
+                            decision,
+                        ));
+                    }
+                    sim.report_with(events)
+                }
+                Some(tgav) => sim.step_at(*tgav),
+                None => sim.step(),
+            };
+            reports.push(report);
+            if sim.state.game_over {
+                break;
+            }
+        }
+
+        // And those made after the last year.
+        let year = sim.state.world.year;
+        while let Some((_, decision)) =
+            decisions.next_if(|(decided, _)| *decided <= year)
+        {
+            play(&mut sim.state, decision);
+        }
+        (sim.state, reports)
+    }
+}
+
+/// Make a decision, returning the names
+/// of any events it rolled.
+fn play(state: &mut State, decision: &Decision) -> Vec<String> {
+    match decision {
+        Decision::RollEvents(phase) => state
+            .roll_events(*phase)
+            .into_iter()
+            .map(|ev| ev.name.clone())
+            .collect(),
+        decision => {
+            state.decide(decision.clone());
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let world = World::default();
+        let mut sim = Simulation::with_seed(world.clone(), 3);
+        let project = sim.state.world.projects.first().id;
+        let process = sim.state.world.processes.first().id;
+
+        let mut reports = sim.run(2);
+        sim.state.decide(Decision::ChangePoliticalCapital(-10));
+        sim.state
+            .decide(Decision::SetProjectPoints(project, 1));
+        sim.state.decide(Decision::StartProject(project));
+        reports.extend(sim.run(3));
+        sim.state.decide(Decision::ChangeMixShare(process, -1));
+        reports.extend(sim.run(5));
+
+        let replay = Replay::new(world.clone(), &sim.state);
+        let player = replay
+            .decisions
+            .iter()
+            .filter(|(_, decision)| !decision.is_automatic());
+        assert_eq!(player.count(), 4);
+
+        let json = serde_json::to_string(&replay).unwrap();
+        let replay: Replay =
+            serde_json::from_str(&json).unwrap();
+        let (state, replayed) = replay.run(10);
+        assert_eq!(replayed, reports);
+        assert!(state == sim.state);
+
+        // Years are replayed at the temperature anomaly
+        // they were played at, e.g. from Hector.
+        let mut sim = Simulation::with_seed(world.clone(), 3);
+        let played: Vec<_> = [1.5, 1.7, 1.6]
+            .into_iter()
+            .map(|tgav| sim.step_at(tgav))
+            .collect();
+        let (_, replayed) =
+            Replay::new(world, &sim.state).run(3);
+        assert_eq!(replayed, played);
+    }
+
+    #[test]
+    fn test_replay_game() {
+        let world = World::default();
+        let mut state = State::with_seed(world.clone(), 5);
+        let project = state.world.projects.first().id;
+
+        // Play as the game does, rolling events as the
+        // player moves through its phases.
+        let mut disasters = 0;
+        for cycle in 0..2 {
+            state.roll_events(Phase::PlanningStart);
+            state.roll_events(Phase::PlanningAdd);
+            if cycle == 0 {
+                state.decide(Decision::SetProjectPoints(
+                    project, 1,
+                ));
+                state.decide(Decision::StartProject(project));
+            }
+            state.roll_events(Phase::PlanningPlanChange);
+            state.roll_events(Phase::WorldStart);
+            let start = state.world.year;
+            while state.world.year == start
+                || !state.is_planning_year()
+            {
+                state.step_year(1.4 + cycle as f32 * 0.1);
+                state.roll_events(Phase::WorldMain);
+                if state.is_planning_year() {
+                    state.finish_cycle();
+                    state.roll_events(Phase::ReportStart);
+                    state.decide(
+                        Decision::ChangePoliticalCapital(10),
+                    );
+                } else {
+                    for ev in state.roll_events(Phase::Icon) {
+                        if let Some((region, _)) = ev.region {
+                            disasters += 1;
+                            state.decide(Decision::Disaster {
+                                event: ev.id,
+                                region,
+                                intensity: 1,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        assert!(disasters > 0);
+        assert!(!state.game_over);
+
+        let replay = Replay::new(world, &state);
+        let (replayed, _) = replay.run(state.tgavs.len());
+        assert!(replayed == state);
+    }
+}
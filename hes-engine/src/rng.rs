@@ -0,0 +1,85 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The random number generator all of the simulation's
+/// randomness goes through, so that runs with the same
+/// seed and decisions play out identically.
+///
+/// It's serialized as its current state, so that
+/// saved games resume with the same rolls.
+#[derive(Debug, Clone)]
+pub struct Rng(fastrand::Rng);
+impl Rng {
+    pub fn with_seed(seed: u64) -> Self {
+        Rng(fastrand::Rng::with_seed(seed))
+    }
+
+    /// A seed for a new game, when one isn't given.
+    pub fn random_seed() -> u64 {
+        fastrand::u64(..)
+    }
+
+    /// The generator's current state, which isn't
+    /// the seed it started from once it's been used.
+    pub fn state(&self) -> u64 {
+        self.0.get_seed()
+    }
+}
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::with_seed(0)
+    }
+}
+impl PartialEq for Rng {
+    fn eq(&self, other: &Self) -> bool {
+        self.state() == other.state()
+    }
+}
+impl Deref for Rng {
+    type Target = fastrand::Rng;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl DerefMut for Rng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Serialize for Rng {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.state().serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Rng {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Rng::with_seed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_resumes_from_state() {
+        let mut rng = Rng::with_seed(7);
+        rng.f32();
+        let json = serde_json::to_string(&rng).unwrap();
+        let mut resumed: Rng =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(resumed, rng);
+        assert_eq!(resumed.u32(..), rng.u32(..));
+    }
+}
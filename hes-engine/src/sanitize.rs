@@ -0,0 +1,168 @@
+//! Replaces NaN and infinite numbers in loaded worlds,
+//! which would otherwise poison the simulation.
+//!
+//! JSON can't express them directly but numbers too
+//! large for an `f32` become infinite, and binary
+//! worlds can hold any float at all.
+
+use rmp::Marker;
+
+use crate::World;
+
+impl World {
+    /// Replace any NaN or infinite numbers with zero,
+    /// returning how many were replaced.
+    pub fn sanitize(&mut self) -> usize {
+        let Ok(mut bytes) = rmp_serde::to_vec_named(self)
+        else {
+            return 0;
+        };
+        let mut replaced = 0;
+        if walk(&mut bytes, &mut 0, &mut replaced).is_none()
+            || replaced == 0
+        {
+            return 0;
+        }
+        match rmp_serde::from_slice(&bytes) {
+            Ok(world) => {
+                *self = world;
+                replaced
+            }
+            Err(_) => 0,
+        }
+    }
+}
+
+fn read_len(
+    bytes: &[u8],
+    pos: &mut usize,
+    size: usize,
+) -> Option<usize> {
+    let data = bytes.get(*pos..*pos + size)?;
+    *pos += size;
+    Some(
+        data.iter()
+            .fold(0, |len, byte| (len << 8) | *byte as usize),
+    )
+}
+
+/// Walk a MessagePack value, zeroing non-finite floats.
+fn walk(
+    bytes: &mut [u8],
+    pos: &mut usize,
+    replaced: &mut usize,
+) -> Option<()> {
+    let marker = Marker::from_u8(*bytes.get(*pos)?);
+    *pos += 1;
+    let skip = match marker {
+        Marker::F32 => {
+            let data = bytes.get_mut(*pos..*pos + 4)?;
+            let val = f32::from_be_bytes(data.try_into().ok()?);
+            if !val.is_finite() {
+                data.copy_from_slice(&0f32.to_be_bytes());
+                *replaced += 1;
+            }
+            4
+        }
+        Marker::F64 => {
+            let data = bytes.get_mut(*pos..*pos + 8)?;
+            let val = f64::from_be_bytes(data.try_into().ok()?);
+            if !val.is_finite() {
+                data.copy_from_slice(&0f64.to_be_bytes());
+                *replaced += 1;
+            }
+            8
+        }
+        Marker::FixArray(n) => {
+            return walk_n(bytes, pos, replaced, n as usize)
+        }
+        Marker::Array16 | Marker::Array32 => {
+            let size =
+                if marker == Marker::Array16 { 2 } else { 4 };
+            let n = read_len(bytes, pos, size)?;
+            return walk_n(bytes, pos, replaced, n);
+        }
+        Marker::FixMap(n) => {
+            return walk_n(bytes, pos, replaced, n as usize * 2)
+        }
+        Marker::Map16 | Marker::Map32 => {
+            let size =
+                if marker == Marker::Map16 { 2 } else { 4 };
+            let n = read_len(bytes, pos, size)?;
+            return walk_n(bytes, pos, replaced, n * 2);
+        }
+        Marker::U8 | Marker::I8 => 1,
+        Marker::U16 | Marker::I16 => 2,
+        Marker::U32 | Marker::I32 => 4,
+        Marker::U64 | Marker::I64 => 8,
+        Marker::FixStr(n) => n as usize,
+        Marker::Str8 | Marker::Bin8 => read_len(bytes, pos, 1)?,
+        Marker::Str16 | Marker::Bin16 => {
+            read_len(bytes, pos, 2)?
+        }
+        Marker::Str32 | Marker::Bin32 => {
+            read_len(bytes, pos, 4)?
+        }
+        Marker::FixExt1 => 2,
+        Marker::FixExt2 => 3,
+        Marker::FixExt4 => 5,
+        Marker::FixExt8 => 9,
+        Marker::FixExt16 => 17,
+        Marker::Ext8 => read_len(bytes, pos, 1)? + 1,
+        Marker::Ext16 => read_len(bytes, pos, 2)? + 1,
+        Marker::Ext32 => read_len(bytes, pos, 4)? + 1,
+        Marker::FixPos(_)
+        | Marker::FixNeg(_)
+        | Marker::Null
+        | Marker::True
+        | Marker::False => 0,
+        Marker::Reserved => return None,
+    };
+    *pos += skip;
+    (*pos <= bytes.len()).then_some(())
+}
+
+fn walk_n(
+    bytes: &mut [u8],
+    pos: &mut usize,
+    replaced: &mut usize,
+    n: usize,
+) -> Option<()> {
+    for _ in 0..n {
+        walk(bytes, pos, replaced)?;
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let mut world = World::default();
+        assert_eq!(world.sanitize(), 0);
+
+        world.temperature = f32::NAN;
+        world.regions.by_idx_mut(0).population = f32::INFINITY;
+        world.starting_resources.water = f32::NEG_INFINITY;
+        assert_eq!(world.sanitize(), 3);
+        assert_eq!(world.temperature, 0.);
+        assert_eq!(world.regions.by_idx(0).population, 0.);
+        assert_eq!(world.starting_resources.water, 0.);
+    }
+
+    #[test]
+    fn test_load_overflow() {
+        // Too large for an `f32`, so it'd be infinite.
+        let mut json: serde_json::Value = serde_json::from_str(
+            &World::default().to_export_json(),
+        )
+        .unwrap();
+        json["temperature"] = serde_json::json!(1e300);
+        let data = serde_json::to_vec(&json).unwrap();
+        let (world, notes) = World::load(&data).unwrap();
+        assert_eq!(world.temperature, 0.);
+        assert!(!notes.is_empty());
+    }
+}
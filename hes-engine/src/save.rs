@@ -0,0 +1,479 @@
+use std::{fmt::Display, io::Read};
+
+use ruzstd::{
+    decoding::StreamingDecoder,
+    encoding::{compress_to_vec, CompressionLevel},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    encoding::ZSTD_MAGIC,
+    migrate::{self, version_of, WORLD_VERSION},
+    state::State,
+    World,
+};
+
+/// The current version of the saved game format.
+///
+/// Bump this and add a migration to `MIGRATIONS` whenever
+/// a change to the game state's structure would otherwise
+/// break existing saves. Changes to the world's structure
+/// are handled by the world's own migrations.
+pub const SAVE_VERSION: u32 = 1;
+
+/// Upgrades a saved game's JSON from the previous version,
+/// noting each change it makes.
+type Migration = fn(&mut Map<String, Value>, &mut Vec<String>);
+
+/// Migrations in order, the first upgrading
+/// version 1 saves to version 2.
+const MIGRATIONS: [Migration; SAVE_VERSION as usize - 1] = [];
+
+#[derive(Debug)]
+pub enum SaveError {
+    Json(serde_json::Error),
+    Decompress(String),
+    Binary(rmp_serde::decode::Error),
+    Version(u32),
+
+    /// What about the save couldn't be
+    /// loaded, even after migrating it.
+    Incompatible(Vec<String>),
+}
+impl Display for SaveError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Json(err) => {
+                write!(f, "Invalid saved game JSON: {err}")
+            }
+            Self::Decompress(err) => {
+                write!(
+                    f,
+                    "Couldn't decompress saved game: {err}"
+                )
+            }
+            Self::Binary(err) => {
+                write!(f, "Invalid binary saved game: {err}")
+            }
+            Self::Version(version)
+                if *version > SAVE_VERSION =>
+            {
+                write!(
+                    f,
+                    "This game was saved by a newer version ({version}) than is supported ({SAVE_VERSION})."
+                )
+            }
+            Self::Version(version) => {
+                write!(
+                    f,
+                    "This game was saved by an unsupported version ({version})."
+                )
+            }
+            Self::Incompatible(problems) => {
+                write!(
+                    f,
+                    "This saved game isn't compatible with this version:"
+                )?;
+                for problem in problems {
+                    write!(f, "\n- {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A game in progress, with everything needed to resume
+/// it exactly where it left off: the full state, including
+/// the world as it's been changed by play, the events
+/// waiting to happen, and the random number generator.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SavedGame {
+    /// The version of the save format this was saved with.
+    pub version: u32,
+
+    pub state: State,
+}
+impl SavedGame {
+    /// Save the game as it is now.
+    pub fn new(state: &State) -> Self {
+        SavedGame {
+            version: SAVE_VERSION,
+            state: state.clone(),
+        }
+    }
+
+    /// Resume the saved game. The state includes its random
+    /// number generator, so the same rolls happen after
+    /// resuming as would have happened without saving.
+    pub fn resume(self) -> State {
+        self.state
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .expect("Saved games are always serializable")
+    }
+
+    pub fn from_json(data: &str) -> Result<Self, SaveError> {
+        let value = serde_json::from_str(data)
+            .map_err(SaveError::Json)?;
+        SavedGame::from_value(value).map(|(save, _)| save)
+    }
+
+    /// Serialize the game in a compact binary format,
+    /// the same as for binary worlds.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let bytes = rmp_serde::to_vec_named(self)
+            .expect("Saved games are always serializable");
+        compress_to_vec(
+            bytes.as_slice(),
+            CompressionLevel::Fastest,
+        )
+    }
+
+    pub fn from_binary(data: &[u8]) -> Result<Self, SaveError> {
+        SavedGame::decode_binary(data).map(|(save, _)| save)
+    }
+
+    /// Decode a binary save, upgrading it if it or its world
+    /// is from an older version. Also returns notes on what
+    /// was changed.
+    fn decode_binary(
+        data: &[u8],
+    ) -> Result<(Self, Vec<String>), SaveError> {
+        let mut decoder =
+            StreamingDecoder::new(data).map_err(|err| {
+                SaveError::Decompress(err.to_string())
+            })?;
+        let mut bytes = vec![];
+        decoder.read_to_end(&mut bytes).map_err(|err| {
+            SaveError::Decompress(err.to_string())
+        })?;
+
+        let versioned: Versioned =
+            rmp_serde::from_slice(&bytes)
+                .map_err(SaveError::Binary)?;
+        if versioned.version == SAVE_VERSION
+            && versioned.state.world.version == WORLD_VERSION
+        {
+            let save = rmp_serde::from_slice(&bytes)
+                .map_err(SaveError::Binary)?;
+            return Ok((save, vec![]));
+        }
+
+        // Other versions go through JSON to be migrated,
+        // the same as JSON saves are.
+        let value = rmp_serde::from_slice(&bytes)
+            .map_err(SaveError::Binary)?;
+        SavedGame::from_value(value)
+    }
+
+    /// Load a saved game from its JSON, upgrading it if it's
+    /// from an older version. Also returns notes on what
+    /// was changed, if anything.
+    pub fn from_value(
+        mut value: Value,
+    ) -> Result<(Self, Vec<String>), SaveError> {
+        let notes = migrate(&mut value)?;
+        match SavedGame::deserialize(&value) {
+            Ok(save) => Ok((save, notes)),
+            Err(err) => {
+                let mut problems = incompatibilities(&value);
+                if problems.is_empty() {
+                    problems.push(err.to_string());
+                }
+                Err(SaveError::Incompatible(problems))
+            }
+        }
+    }
+
+    /// Load a saved game from either a binary or JSON save,
+    /// upgrading it if it's from an older version.
+    pub fn load(
+        data: &[u8],
+    ) -> Result<(Self, Vec<String>), SaveError> {
+        if data.starts_with(&ZSTD_MAGIC) {
+            SavedGame::decode_binary(data)
+        } else {
+            let value = serde_json::from_slice(data)
+                .map_err(SaveError::Json)?;
+            SavedGame::from_value(value)
+        }
+    }
+}
+
+/// Just enough of a save to check its
+/// version and its world's version.
+#[derive(Deserialize)]
+struct Versioned {
+    #[serde(default)]
+    version: u32,
+    state: VersionedState,
+}
+
+#[derive(Deserialize)]
+struct VersionedState {
+    world: VersionedWorld,
+}
+
+#[derive(Deserialize)]
+struct VersionedWorld {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Upgrade a saved game's JSON to the current version,
+/// along with the world it's being played in, returning
+/// notes on what was migrated.
+fn migrate(save: &mut Value) -> Result<Vec<String>, SaveError> {
+    let Some(obj) = save.as_object_mut() else {
+        return Ok(vec![]);
+    };
+    let version = version_of(obj);
+    if version == 0 || version > SAVE_VERSION {
+        return Err(SaveError::Version(version));
+    }
+
+    let mut notes = vec![];
+    for (i, migration) in
+        MIGRATIONS.iter().enumerate().skip(version as usize - 1)
+    {
+        let from = i + 1;
+        let mut changes = vec![];
+        migration(obj, &mut changes);
+        notes.extend(changes.into_iter().map(|change| {
+            format!("v{from} → v{}: {change}", from + 1)
+        }));
+    }
+    obj.insert("version".into(), SAVE_VERSION.into());
+
+    if let Some(world) = obj
+        .get_mut("state")
+        .and_then(|state| state.get_mut("world"))
+    {
+        let changes = migrate::migrate(world).map_err(|version| {
+            SaveError::Incompatible(vec![format!(
+                "The world is from a newer version ({version}) than is supported ({WORLD_VERSION})."
+            )])
+        })?;
+        notes.extend(
+            changes
+                .into_iter()
+                .map(|note| format!("World {note}")),
+        );
+    }
+    Ok(notes)
+}
+
+/// Find which fields of a saved game's state can't be
+/// loaded, to explain why a save is incompatible.
+fn incompatibilities(save: &Value) -> Vec<String> {
+    let reference = serde_json::to_value(State::default())
+        .expect("States are always serializable");
+    let (Some(saved), Value::Object(reference)) = (
+        save.get("state").and_then(Value::as_object),
+        reference,
+    ) else {
+        return vec!["The game state is missing.".into()];
+    };
+
+    let mut problems = vec![];
+    for field in broken_fields::<State>(saved, &reference) {
+        // The world is large enough that it's
+        // worth saying what's wrong with it.
+        let world = (field == "world")
+            .then(|| {
+                saved
+                    .get("world")
+                    .and_then(Value::as_object)
+                    .zip(
+                        reference
+                            .get("world")
+                            .and_then(Value::as_object),
+                    )
+            })
+            .flatten();
+        match world {
+            Some((saved, reference)) => problems.extend(
+                broken_fields::<World>(saved, reference)
+                    .into_iter()
+                    .map(|field| {
+                        describe(
+                            &format!("world.{field}"),
+                            saved.get(&field),
+                        )
+                    }),
+            ),
+            None => problems
+                .push(describe(&field, saved.get(&field))),
+        }
+    }
+    problems
+}
+
+fn describe(path: &str, value: Option<&Value>) -> String {
+    match value {
+        Some(_) => {
+            format!("`{path}` has an incompatible value.")
+        }
+        None => format!("`{path}` is missing."),
+    }
+}
+
+/// Which of the saved object's fields can't be loaded as
+/// part of a `T`. Each field is checked by swapping it into
+/// the known-good reference object, so that one broken
+/// field doesn't hide the others.
+fn broken_fields<T: DeserializeOwned>(
+    saved: &Map<String, Value>,
+    reference: &Map<String, Value>,
+) -> Vec<String> {
+    reference
+        .keys()
+        .filter(|key| {
+            let mut candidate = reference.clone();
+            match saved.get(*key) {
+                Some(value) => {
+                    candidate
+                        .insert((*key).clone(), value.clone());
+                }
+                None => {
+                    candidate.remove(*key);
+                }
+            }
+            serde_json::from_value::<T>(Value::Object(
+                candidate,
+            ))
+            .is_err()
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EventPhase;
+
+    /// Play some years, returning the events that happened.
+    fn play(state: &mut State, years: usize) -> Vec<String> {
+        let mut events = vec![];
+        for _ in 0..years {
+            state.step_year(state.world.temperature);
+            for phase in
+                [EventPhase::WorldStart, EventPhase::WorldMain]
+            {
+                events.extend(
+                    state
+                        .roll_events(phase)
+                        .into_iter()
+                        .map(|ev| ev.name.clone()),
+                );
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_resume_exactly() {
+        let mut state = State::with_seed(World::default(), 42);
+        play(&mut state, 5);
+
+        let saved = SavedGame::new(&state);
+        let json = saved.to_json();
+        let binary = saved.to_binary();
+        assert!(binary.len() < json.len());
+
+        // Keep playing without saving.
+        let expected = play(&mut state, 10);
+
+        // Resuming should play out the same, including
+        // which events happen.
+        for saved in [
+            SavedGame::from_json(&json).unwrap(),
+            SavedGame::from_binary(&binary).unwrap(),
+        ] {
+            let mut resumed = saved.resume();
+            assert_eq!(play(&mut resumed, 10), expected);
+            assert!(resumed == state);
+        }
+    }
+
+    #[test]
+    fn test_migrate_binary() {
+        let mut state = State::default();
+        state.rng.f32();
+        let saved = SavedGame::new(&state);
+
+        // A save whose world is from before
+        // worlds were versioned.
+        let mut value = serde_json::to_value(&saved).unwrap();
+        let world =
+            value["state"]["world"].as_object_mut().unwrap();
+        world.remove("version");
+        world.remove("translations");
+        let bytes = rmp_serde::to_vec_named(&value).unwrap();
+        let binary = compress_to_vec(
+            bytes.as_slice(),
+            CompressionLevel::Fastest,
+        );
+
+        let (loaded, notes) = SavedGame::load(&binary).unwrap();
+        assert!(loaded == saved);
+        assert!(notes
+            .iter()
+            .any(|note| note.starts_with("World")));
+    }
+
+    #[test]
+    fn test_incompatible() {
+        let saved = SavedGame::new(&State::default());
+        let mut value = serde_json::to_value(&saved).unwrap();
+        value["version"] = (SAVE_VERSION + 1).into();
+        assert!(matches!(
+            SavedGame::from_value(value.clone()),
+            Err(SaveError::Version(_))
+        ));
+        let bytes = rmp_serde::to_vec_named(&value).unwrap();
+        assert!(matches!(
+            SavedGame::from_binary(&compress_to_vec(
+                bytes.as_slice(),
+                CompressionLevel::Fastest,
+            )),
+            Err(SaveError::Version(_))
+        ));
+
+        // Saves have always been versioned.
+        let mut unversioned = value.clone();
+        unversioned.as_object_mut().unwrap().remove("version");
+        assert!(matches!(
+            SavedGame::from_value(unversioned),
+            Err(SaveError::Version(0))
+        ));
+
+        // Each problem should be listed.
+        value["version"] = SAVE_VERSION.into();
+        let state = value["state"].as_object_mut().unwrap();
+        state.remove("political_capital");
+        state["runs"] = "many".into();
+        state["world"]["year"] = Value::Null;
+        let Err(SaveError::Incompatible(problems)) =
+            SavedGame::from_value(value)
+        else {
+            panic!("Expected the save to be incompatible");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                "`political_capital` is missing.",
+                "`runs` has an incompatible value.",
+                "`world.year` has an incompatible value.",
+            ]
+        );
+    }
+}
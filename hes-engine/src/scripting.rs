@@ -0,0 +1,465 @@
+//! Small scripts that worlds can attach to events and
+//! projects, for logic the built-in effects can't express.
+//! Scripts are written in [Rhai](https://rhai.rs) and run
+//! in a sandbox: they can't touch anything outside of the
+//! game and are limited in how much work they can do, so
+//! a broken script can't hang the game.
+//!
+//! Scripts can read (but not change) these values:
+//!
+//! - `year`, `population`, `population_growth`,
+//!   `emissions`, `extinction_rate`, `outlook`,
+//!   `temperature`, `sea_level_rise`,
+//!   `sea_level_rise_rate` and `precipitation`
+//! - `political_capital`, `research_points`
+//!   and `years_to_death`, which (like `year`)
+//!   are whole numbers
+//! - `flags`, the names of the flags that are set,
+//!   e.g. `flags.contains("Vegetarian")`
+//! - `variables`, the world's own variables by name,
+//!   e.g. `variables["Public Trust"]`
+//! - `region`, if the script is for a region, with its
+//!   `name`, `population`, `outlook`, `habitability`,
+//!   `water_stress`, `air_pollution`, `income`
+//!   (e.g. `"LowerMiddle"`) and `flags`,
+//!   otherwise `()`
+//!
+//! Effect scripts ([`crate::Effect::Script`]) make changes
+//! by adding effects to `effects`, written as they are
+//! in world files, e.g.:
+//!
+//! ```text
+//! let change = if temperature > 2. { -10. } else { -5. };
+//! effects.push(#{ WorldVariable: ["Outlook", change] });
+//! if flags.contains("Degrowth") {
+//!     effects.push(#{ AddFlag: "LifeGoesOn" });
+//! }
+//! ```
+//!
+//! Text scripts are `{{ ... }}` blocks in flavor text,
+//! which are replaced with the script's value, e.g.
+//! `The seas have risen {{ sea_level_rise.round() }}m.`
+//! They end at the first `}}`.
+//!
+//! Scripts only run with the `scripting` feature.
+//! Without it, effect scripts do nothing and
+//! text scripts are left out.
+
+use std::fmt::Display;
+
+use crate::{Effect, Id, State};
+
+pub use imp::check;
+use imp::{eval_effects, eval_text};
+
+/// The most operations a script can run, so that
+/// a script that loops forever doesn't hang the game.
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+const MAX_OPERATIONS: u64 = 50_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    /// Scripts aren't supported in this build.
+    Disabled,
+
+    /// The script couldn't be parsed.
+    Invalid(String),
+
+    /// The script failed while running.
+    Failed(String),
+
+    /// An effect the script added isn't valid.
+    BadEffect(String),
+}
+impl Display for ScriptError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Disabled => {
+                write!(f, "Scripts aren't supported.")
+            }
+            Self::Invalid(err) => {
+                write!(f, "The script is invalid: {err}")
+            }
+            Self::Failed(err) => {
+                write!(f, "The script failed: {err}")
+            }
+            Self::BadEffect(err) => {
+                write!(
+                    f,
+                    "The script added a bad effect: {err}"
+                )
+            }
+        }
+    }
+}
+
+/// The scripts in a piece of text, as their byte
+/// ranges (including the braces) and their code.
+pub fn text_scripts(
+    text: &str,
+) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut scripts = vec![];
+    let mut from = 0;
+    while let Some(start) = text[from..].find("{{") {
+        let start = from + start;
+        let Some(len) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        scripts.push((start..end, &text[start + 2..end - 2]));
+        from = end;
+    }
+    scripts
+}
+
+impl State {
+    /// Run an effect script, returning the effects it adds.
+    pub fn script_effects(
+        &self,
+        script: &str,
+        region_id: Option<Id>,
+    ) -> Result<Vec<Effect>, ScriptError> {
+        let effects = eval_effects(script, self, region_id)?;
+        if effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::Script(..)))
+        {
+            return Err(ScriptError::BadEffect(
+                "Scripts can't add other scripts.".into(),
+            ));
+        }
+        Ok(effects)
+    }
+
+    /// Replace the text's scripts with their values.
+    /// Scripts that fail are left out.
+    pub fn fill_scripts(
+        &self,
+        text: &str,
+        region_id: Option<Id>,
+    ) -> String {
+        let mut filled = String::with_capacity(text.len());
+        let mut last = 0;
+        for (range, script) in text_scripts(text) {
+            filled.push_str(&text[last..range.start]);
+            match eval_text(script, self, region_id) {
+                Ok(value) => filled.push_str(&value),
+                Err(err) => {
+                    tracing::warn!("Text script failed: {err}")
+                }
+            }
+            last = range.end;
+        }
+        filled.push_str(&text[last..]);
+        filled
+    }
+}
+
+#[cfg(feature = "scripting")]
+mod imp {
+    use std::sync::LazyLock;
+
+    use rhai::{Array, Dynamic, Engine, Map, Scope};
+    use strum::IntoEnumIterator;
+
+    use super::{ScriptError, MAX_OPERATIONS};
+    use crate::{
+        events::{
+            LocalVariable,
+            PlayerVariable,
+            WorldVariable,
+        },
+        Effect,
+        Id,
+        State,
+    };
+
+    static ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_call_levels(16);
+        engine.set_max_expr_depths(32, 32);
+        engine.set_max_string_size(10_000);
+        engine.set_max_array_size(1_000);
+        engine.set_max_map_size(1_000);
+        engine.disable_symbol("eval");
+        engine.on_print(|text| tracing::debug!("{text}"));
+        engine.on_debug(|text, _, _| tracing::debug!("{text}"));
+        engine
+    });
+
+    /// Check that the script parses.
+    pub fn check(script: &str) -> Result<(), ScriptError> {
+        ENGINE.compile(script).map(|_| ()).map_err(|err| {
+            ScriptError::Invalid(err.to_string())
+        })
+    }
+
+    /// `SeaLevelRise` to `sea_level_rise`.
+    fn snake_case(name: &str) -> String {
+        let mut snake = String::new();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        }
+        snake
+    }
+
+    fn names<T: std::fmt::Debug>(
+        items: impl IntoIterator<Item = T>,
+    ) -> Array {
+        items
+            .into_iter()
+            .map(|item| format!("{item:?}").into())
+            .collect()
+    }
+
+    fn scope(
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Scope<'static> {
+        let mut scope = Scope::new();
+        for var in WorldVariable::iter() {
+            let name = snake_case(var.into());
+            let value = var.value(state);
+            let value: Dynamic = match var {
+                WorldVariable::Year => {
+                    (value as rhai::INT).into()
+                }
+                _ => (value as rhai::FLOAT).into(),
+            };
+            scope.push_constant_dynamic(name, value);
+        }
+
+        // These are always whole numbers.
+        for var in PlayerVariable::iter() {
+            let value = var.value(state) as rhai::INT;
+            scope.push_constant(snake_case(var.into()), value);
+        }
+        scope.push_constant("flags", names(&state.flags));
+        let variables: Map = state
+            .world
+            .variables
+            .iter()
+            .map(|var| {
+                let value = var.value as rhai::FLOAT;
+                (var.name.as_str().into(), value.into())
+            })
+            .collect();
+        scope.push_constant("variables", variables);
+
+        let region = region_id
+            .and_then(|id| state.world.regions.try_get(&id))
+            .map(|region| {
+                let mut map = Map::new();
+                map.insert(
+                    "name".into(),
+                    region.name.clone().into(),
+                );
+                for var in LocalVariable::iter() {
+                    let value =
+                        var.value(region) as rhai::FLOAT;
+                    map.insert(
+                        snake_case(var.into()).into(),
+                        value.into(),
+                    );
+                }
+                map.insert(
+                    "income".into(),
+                    format!("{:?}", region.income).into(),
+                );
+                map.insert(
+                    "flags".into(),
+                    names(&region.flags).into(),
+                );
+                Dynamic::from_map(map)
+            })
+            .unwrap_or(Dynamic::UNIT);
+        scope.push_constant("region", region);
+        scope
+    }
+
+    pub fn eval_effects(
+        script: &str,
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Result<Vec<Effect>, ScriptError> {
+        let mut scope = scope(state, region_id);
+        scope.push("effects", Array::new());
+        ENGINE.run_with_scope(&mut scope, script).map_err(
+            |err| ScriptError::Failed(err.to_string()),
+        )?;
+        let effects: Array =
+            scope.get_value("effects").unwrap_or_default();
+        effects
+            .iter()
+            .map(|effect| {
+                // Via JSON, which converts the script's numbers
+                // (which are all `i64` or `f64`) as needed.
+                rhai::serde::from_dynamic::<serde_json::Value>(
+                    effect,
+                )
+                .map_err(|err| err.to_string())
+                .and_then(|value| {
+                    serde_json::from_value(value)
+                        .map_err(|err| err.to_string())
+                })
+                .map_err(|err| {
+                    ScriptError::BadEffect(format!(
+                        "{effect}: {err}"
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    pub fn eval_text(
+        script: &str,
+        state: &State,
+        region_id: Option<Id>,
+    ) -> Result<String, ScriptError> {
+        let mut scope = scope(state, region_id);
+        ENGINE
+            .eval_with_scope::<Dynamic>(&mut scope, script)
+            .map(|value| value.to_string())
+            .map_err(|err| ScriptError::Failed(err.to_string()))
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+mod imp {
+    use super::ScriptError;
+    use crate::{Effect, Id, State};
+
+    /// Scripts can't be checked without the feature,
+    /// so they're assumed to be fine.
+    pub fn check(_script: &str) -> Result<(), ScriptError> {
+        Ok(())
+    }
+
+    pub fn eval_effects(
+        _script: &str,
+        _state: &State,
+        _region_id: Option<Id>,
+    ) -> Result<Vec<Effect>, ScriptError> {
+        Err(ScriptError::Disabled)
+    }
+
+    pub fn eval_text(
+        _script: &str,
+        _state: &State,
+        _region_id: Option<Id>,
+    ) -> Result<String, ScriptError> {
+        Err(ScriptError::Disabled)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_text_scripts() {
+        let text = "A {{ 1 + 1 }} b {{ \"c\" }} {{ unclosed";
+        let scripts: Vec<_> = text_scripts(text)
+            .into_iter()
+            .map(|(_, script)| script)
+            .collect();
+        assert_eq!(scripts, vec![" 1 + 1 ", " \"c\" "]);
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_scripts() {
+        use crate::{
+            events::{Flag, PlayerVariable, WorldVariable},
+            world::World,
+        };
+
+        let mut state = State::with_seed(World::default(), 0);
+        state.flags.push(Flag::Vegetarian);
+        let region = state.world.regions.first().id;
+
+        let effects = state
+            .script_effects(
+                r#"
+                let change = if temperature > 100. { 1 } else { 2 };
+                effects.push(#{ PlayerVariable: ["PoliticalCapital", change * 5.] });
+                if flags.contains("Vegetarian") {
+                    effects.push(#{ WorldVariable: ["Outlook", region.outlook / region.outlook] });
+                }
+                effects.push("GameOver");
+                "#,
+                Some(region),
+            )
+            .unwrap();
+        assert_eq!(
+            effects,
+            vec![
+                Effect::PlayerVariable(
+                    PlayerVariable::PoliticalCapital,
+                    10.
+                ),
+                Effect::WorldVariable(
+                    WorldVariable::Outlook,
+                    1.
+                ),
+                Effect::GameOver,
+            ]
+        );
+
+        assert!(matches!(
+            state.script_effects(
+                "effects.push(#{ Nope: 1 });",
+                None
+            ),
+            Err(ScriptError::BadEffect(..))
+        ));
+        assert!(matches!(
+            state.script_effects(
+                r#"effects.push(#{ Script: "" });"#,
+                None
+            ),
+            Err(ScriptError::BadEffect(..))
+        ));
+        assert!(matches!(
+            state.script_effects("loop {}", None),
+            Err(ScriptError::Failed(..))
+        ));
+        assert!(matches!(
+            state.script_effects("year = 1;", None),
+            Err(ScriptError::Failed(..))
+        ));
+        assert!(matches!(
+            check("let = ;"),
+            Err(ScriptError::Invalid(..))
+        ));
+
+        let text = state.fill_scripts(
+            "It's {{ year }}{{ nope }}, in {{ region.name }}.",
+            Some(region),
+        );
+        assert_eq!(
+            text,
+            format!(
+                "It's {}, in {}.",
+                state.world.year,
+                state.world.regions[&region].name
+            )
+        );
+
+        // The effect applies the script's effects.
+        let capital = state.political_capital;
+        Effect::Script(
+            r#"effects.push(#{ PlayerVariable: ["PoliticalCapital", 3] });"#
+                .into(),
+        )
+        .apply(&mut state, None);
+        assert_eq!(state.political_capital, capital + 3);
+    }
+}
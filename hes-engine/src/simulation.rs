@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::Phase,
+    kinds::OutputMap,
+    replay::Decision,
+    state::State,
+    world::World,
+};
+
+/// Transient climate response to cumulative emissions,
+/// in degrees C per GtCO2eq (roughly 0.45C per 1000 GtCO2).
+///
+/// Headless runs don't have access to Hector, so this
+/// is used to approximate the temperature anomaly instead.
+const TCRE: f32 = 0.00045;
+
+/// Event phases that are rolled each simulated year.
+/// The other phases depend on the player being
+/// in a particular part of the game.
+const PHASES: [Phase; 2] =
+    [Phase::WorldStart, Phase::WorldMain];
+
+/// A summary of the state at the end of a simulated year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearReport {
+    pub year: usize,
+
+    /// Temperature anomaly, in C.
+    pub temperature: f32,
+
+    /// Emissions, in GtCO2eq.
+    pub emissions: f32,
+
+    /// Emissions from processes grouped by
+    /// what they produce, in GtCO2eq.
+    pub emissions_by_output: OutputMap,
+
+    /// Emissions from non-modeled industries, in GtCO2eq.
+    pub industry_emissions: f32,
+
+    /// Emissions not from production, e.g. from tipping
+    /// points, less what's taken up by land, in GtCO2eq.
+    #[serde(default)]
+    pub other_emissions: f32,
+
+    pub extinction_rate: f32,
+
+    /// Contentedness.
+    pub outlook: f32,
+
+    pub population: f32,
+    pub political_capital: isize,
+    pub produced: OutputMap,
+    pub demand: OutputMap,
+
+    /// Names of the events that occurred this year.
+    pub events: Vec<String>,
+}
+
+impl YearReport {
+    /// Summarize the state. Emissions are broken down as of
+    /// when they were calculated for the year, before events
+    /// or the rest of the year could change production.
+    fn from_state(state: &State, events: Vec<String>) -> Self {
+        let production = state.byproducts.base.gtco2eq();
+        let emissions_by_output = state.produced.emissions;
+        YearReport {
+            year: state.world.year,
+            temperature: state.world.temperature,
+            emissions: state.emissions.as_gtco2eq(),
+            emissions_by_output,
+            industry_emissions: production
+                - emissions_by_output.sum(),
+            other_emissions: state.emissions.as_gtco2eq()
+                - production,
+            extinction_rate: state.world.extinction_rate,
+            outlook: state.outlook(),
+            population: state.world.regions.population(),
+            political_capital: state.political_capital,
+            produced: state.produced.total(),
+            demand: state.output_demand.total(),
+            events,
+        }
+    }
+}
+
+/// Runs a world forward without any player input,
+/// e.g. to preview the consequences of a world's values.
+pub struct Simulation {
+    pub state: State,
+    tgav: f32,
+}
+impl Simulation {
+    pub fn new(world: World) -> Self {
+        Simulation::from_state(State::new(world))
+    }
+
+    /// Simulate with a seed, so that the
+    /// same years are simulated each time.
+    pub fn with_seed(world: World, seed: u64) -> Self {
+        Simulation::from_state(State::with_seed(world, seed))
+    }
+
+    fn from_state(state: State) -> Self {
+        let tgav = state.world.temperature
+            - state.world.temperature_modifier;
+        Simulation { state, tgav }
+    }
+
+    /// Approximate the next year's temperature anomaly
+    /// from the current emissions.
+    fn next_tgav(&self) -> f32 {
+        self.tgav + self.state.emissions.as_gtco2eq() * TCRE
+    }
+
+    /// Set the temperature anomaly directly, e.g. to
+    /// skip ahead to a warmer world when playtesting.
+    /// Later years warm from there.
+    pub fn set_temperature(&mut self, temperature: f32) {
+        self.tgav =
+            temperature - self.state.world.temperature_modifier;
+        self.state.world.temperature = temperature;
+    }
+
+    /// A summary of the current state, without
+    /// simulating a year.
+    pub fn report(&self) -> YearReport {
+        self.report_with(vec![])
+    }
+
+    /// A summary of the current state, with
+    /// the events that occurred this year.
+    pub(crate) fn report_with(
+        &self,
+        events: Vec<String>,
+    ) -> YearReport {
+        YearReport::from_state(&self.state, events)
+    }
+
+    /// Make the decisions, in order, then simulate a year.
+    /// The year only depends on the state and the
+    /// decisions, so it's the same wherever it's run.
+    pub fn step_with(
+        &mut self,
+        decisions: impl IntoIterator<Item = Decision>,
+    ) -> YearReport {
+        for decision in decisions {
+            self.state.decide(decision);
+        }
+        self.step()
+    }
+
+    /// Simulate a single year.
+    pub fn step(&mut self) -> YearReport {
+        self.step_at(self.next_tgav())
+    }
+
+    /// Simulate a single year with the given temperature
+    /// anomaly, e.g. as Hector calculated it in a game.
+    pub fn step_at(&mut self, tgav: f32) -> YearReport {
+        self.step_only(tgav);
+
+        let mut events = vec![];
+        for phase in PHASES {
+            events.extend(
+                self.state
+                    .roll_events(phase)
+                    .into_iter()
+                    .map(|ev| ev.name.clone()),
+            );
+        }
+
+        if self.state.is_planning_year() {
+            self.state.finish_cycle();
+        }
+
+        self.report_with(events)
+    }
+
+    /// Simulate a single year without rolling any events,
+    /// e.g. to play back a game that recorded its own.
+    pub(crate) fn step_only(&mut self, tgav: f32) {
+        self.tgav = tgav;
+        self.state.step_year(self.tgav);
+    }
+
+    /// Simulate up to the given number of years,
+    /// stopping early if the game ends.
+    pub fn run(&mut self, years: usize) -> Vec<YearReport> {
+        let mut reports = vec![];
+        for _ in 0..years {
+            reports.push(self.step());
+            if self.state.game_over {
+                break;
+            }
+        }
+        reports
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Collection;
+
+    #[test]
+    fn test_simulation() {
+        // Without events, as some cool the world.
+        let world = World {
+            events: Collection::default(),
+            ..Default::default()
+        };
+        let start_year = world.year;
+        let start_temp = world.temperature;
+
+        let mut sim = Simulation::new(world);
+        let reports = sim.run(10);
+        assert!(!reports.is_empty());
+        assert_eq!(reports[0].year, start_year + 1);
+
+        // Emissions are positive in the default world,
+        // so it should have gotten warmer.
+        let last = reports.last().unwrap();
+        assert!(last.emissions > 0.);
+        assert!(last.temperature > start_temp);
+
+        // Sector emissions should add up to the total.
+        let by_sector = last.emissions_by_output.sum()
+            + last.industry_emissions
+            + last.other_emissions;
+        let diff = (by_sector - last.emissions).abs();
+        assert!(diff < 0.01 * last.emissions);
+        assert!(last.emissions_by_output.sum() > 0.);
+        assert!(last.industry_emissions > 0.);
+    }
+
+    #[test]
+    fn test_seeded() {
+        let run = |seed| {
+            let world = World::default();
+            Simulation::with_seed(world, seed).run(20)
+        };
+        assert_eq!(run(1), run(1));
+
+        // Different seeds should have different events.
+        let events = |seed| -> Vec<String> {
+            run(seed)
+                .into_iter()
+                .flat_map(|report| report.events)
+                .collect()
+        };
+        assert!((2..10).any(|seed| events(seed) != events(1)));
+    }
+
+    #[test]
+    fn test_set_temperature() {
+        let world = World {
+            events: Collection::default(),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::new(world);
+        sim.set_temperature(3.);
+        assert_eq!(sim.report().temperature, 3.);
+
+        // It should keep warming from the new temperature.
+        let report = sim.step();
+        assert!(report.temperature > 3.);
+        assert!(report.temperature < 3.1);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    flavor::{
+        EventFlavor,
+        IndustryFlavor,
+        ProcessFlavor,
+        ProjectFlavor,
+        RegionFlavor,
+    },
+    state::State,
+    HasId,
+    Id,
+};
+
+/// A copy of the state that can be rolled back to,
+/// e.g. to preview the effects of a plan before
+/// committing to it.
+///
+/// The entities' flavor (descriptions, images, dialogue)
+/// and the world's translations never change during play
+/// but make up most of a world's size, so they're left out
+/// of the copy and taken from the live state on rollback.
+#[derive(Clone)]
+pub struct Snapshot {
+    state: State,
+}
+impl Snapshot {
+    /// The year the snapshot was taken in.
+    pub fn year(&self) -> usize {
+        self.state.world.year
+    }
+}
+
+impl State {
+    /// Take a snapshot of the state as it is now.
+    pub fn snapshot(&mut self) -> Snapshot {
+        let unchanging = Unchanging::take(self);
+        let state = self.clone();
+        unchanging.restore(self);
+        Snapshot { state }
+    }
+
+    /// Return to the state as it was when
+    /// the snapshot was taken.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        let unchanging = Unchanging::take(self);
+        let hooks = std::mem::take(&mut self.hooks);
+        *self = snapshot.state;
+        self.hooks = hooks;
+        unchanging.restore(self);
+    }
+
+    /// Run `f` against the state, e.g. making decisions and
+    /// stepping ahead some years, then roll back to how the
+    /// state was before, returning what `f` returned.
+    /// Hooks aren't run for any of it.
+    pub fn what_if<R>(
+        &mut self,
+        f: impl FnOnce(&mut State) -> R,
+    ) -> R {
+        // Hooks are for what actually happens,
+        // so they aren't run for what-ifs.
+        let hooks = std::mem::take(&mut self.hooks);
+        let snapshot = self.snapshot();
+        let result = f(self);
+        self.rollback(snapshot);
+        self.hooks = hooks;
+        result
+    }
+}
+
+/// The parts of the state that don't change during play.
+struct Unchanging {
+    regions: BTreeMap<Id, RegionFlavor>,
+    industries: BTreeMap<Id, IndustryFlavor>,
+    processes: BTreeMap<Id, ProcessFlavor>,
+    projects: BTreeMap<Id, ProjectFlavor>,
+    events: BTreeMap<Id, EventFlavor>,
+    pool_events: BTreeMap<Id, EventFlavor>,
+    state_events: BTreeMap<Id, EventFlavor>,
+    translations: BTreeMap<String, BTreeMap<String, String>>,
+}
+impl Unchanging {
+    fn take(state: &mut State) -> Self {
+        let world = &mut state.world;
+        Unchanging {
+            regions: take(world.regions.iter_mut(), |r| {
+                &mut r.flavor
+            }),
+            industries: take(
+                world.industries.iter_mut(),
+                |i| &mut i.flavor,
+            ),
+            processes: take(world.processes.iter_mut(), |p| {
+                &mut p.flavor
+            }),
+            projects: take(world.projects.iter_mut(), |p| {
+                &mut p.flavor
+            }),
+            events: take(world.events.iter_mut(), |e| {
+                &mut e.flavor
+            }),
+            pool_events: take(
+                state.event_pool.events.iter_mut(),
+                |e| &mut e.flavor,
+            ),
+            state_events: take(state.events.iter_mut(), |e| {
+                &mut e.flavor
+            }),
+            translations: std::mem::take(
+                &mut world.translations,
+            ),
+        }
+    }
+
+    /// Put these back into the state, matching entities by
+    /// id. Entities that didn't exist when these were taken
+    /// are left with their default flavor.
+    fn restore(self, state: &mut State) {
+        let world = &mut state.world;
+        restore(world.regions.iter_mut(), self.regions, |r| {
+            &mut r.flavor
+        });
+        restore(
+            world.industries.iter_mut(),
+            self.industries,
+            |i| &mut i.flavor,
+        );
+        restore(
+            world.processes.iter_mut(),
+            self.processes,
+            |p| &mut p.flavor,
+        );
+        restore(
+            world.projects.iter_mut(),
+            self.projects,
+            |p| &mut p.flavor,
+        );
+        restore(world.events.iter_mut(), self.events, |e| {
+            &mut e.flavor
+        });
+        restore(
+            state.event_pool.events.iter_mut(),
+            self.pool_events,
+            |e| &mut e.flavor,
+        );
+        restore(
+            state.events.iter_mut(),
+            self.state_events,
+            |e| &mut e.flavor,
+        );
+        world.translations = self.translations;
+    }
+}
+
+fn take<'a, T: HasId + 'a, F: Default>(
+    items: impl Iterator<Item = &'a mut T>,
+    flavor: impl Fn(&mut T) -> &mut F,
+) -> BTreeMap<Id, F> {
+    items
+        .map(|item| (*item.id(), std::mem::take(flavor(item))))
+        .collect()
+}
+
+fn restore<'a, T: HasId + 'a, F>(
+    items: impl Iterator<Item = &'a mut T>,
+    mut flavors: BTreeMap<Id, F>,
+    flavor: impl Fn(&mut T) -> &mut F,
+) {
+    for item in items {
+        if let Some(taken) = flavors.remove(item.id()) {
+            *flavor(item) = taken;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{replay::Decision, world::World};
+
+    fn step(state: &mut State, years: usize) {
+        for _ in 0..years {
+            state.step_year(state.world.temperature);
+        }
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut state = State::with_seed(World::default(), 5);
+        step(&mut state, 2);
+        let original = state.clone();
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.year(), original.world.year);
+        assert!(snapshot
+            .state
+            .world
+            .projects
+            .iter()
+            .all(|project| project.flavor
+                == ProjectFlavor::default()));
+        assert!(state == original);
+
+        let project = state.world.projects.first().id;
+        state.decide(Decision::SetProjectPoints(project, 1));
+        state.decide(Decision::StartProject(project));
+        step(&mut state, 3);
+        assert!(state != original);
+
+        state.rollback(snapshot);
+        assert!(state == original);
+    }
+
+    #[test]
+    fn test_what_if() {
+        let mut state = State::with_seed(World::default(), 5);
+        let original = state.clone();
+        let process = state.world.processes.first().id;
+
+        let projected = state.what_if(|state| {
+            state.decide(Decision::ChangeMixShare(process, -1));
+            step(state, 5);
+            state.world.year
+        });
+        assert_eq!(projected, original.world.year + 5);
+        assert!(state == original);
+
+        // The preview's rolls are undone too, so the
+        // same years play out the same way for real.
+        let mut untouched = original.clone();
+        step(&mut untouched, 5);
+        step(&mut state, 5);
+        assert!(state == untouched);
+    }
+}
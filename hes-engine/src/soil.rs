@@ -0,0 +1,156 @@
+//! Soil health. Farming soil with synthetic fertilizers and
+//! pesticides wears it down a little every year, while
+//! regenerative farming builds it back up. Crops grow
+//! better or worse as soil health changes, so what the
+//! food system looks like now affects yields for decades.
+
+use crate::{
+    kinds::{Feedstock, Output},
+    production::{Process, ProcessFeature},
+    state::State,
+};
+
+/// Soil health lost each year if all
+/// farmed soil were farmed intensively.
+const DEGRADATION_RATE: f32 = 0.003;
+
+/// Soil health regained each year if all
+/// farmed soil were farmed regeneratively.
+const REGENERATION_RATE: f32 = 0.01;
+
+/// The healthiest soils can get,
+/// relative to how they are at the start.
+const MAX_SOIL_HEALTH: f32 = 1.2;
+
+pub(crate) fn default_soil_health() -> f32 {
+    1.
+}
+
+impl Process {
+    /// If the process farms soil.
+    pub fn farms_soil(&self) -> bool {
+        self.feedstock.0 == Feedstock::Soil
+    }
+
+    /// If the process wears down the soil it farms.
+    pub fn is_intensive(&self) -> bool {
+        self.features
+            .contains(&ProcessFeature::UsesSynFertilizer)
+            || self
+                .features
+                .contains(&ProcessFeature::UsesPesticides)
+    }
+}
+
+impl State {
+    /// Wear down or build up soil health
+    /// depending on how the soil is farmed, by land.
+    pub(crate) fn update_soil(&mut self) {
+        let (mut total, mut intensive, mut regenerative) =
+            (0., 0., 0.);
+        for process in self.world.processes.iter() {
+            if !process.farms_soil() {
+                continue;
+            }
+            let amount = self
+                .produced
+                .by_process
+                .get(&process.id)
+                .unwrap_or(&0.);
+            let land = amount * process.adj_resources().land;
+            total += land;
+            if process.is_intensive() {
+                intensive += land;
+            } else if process
+                .features
+                .contains(&ProcessFeature::IsRegenerative)
+            {
+                regenerative += land;
+            }
+        }
+        if total > 0. {
+            self.change_soil_health(
+                (regenerative * REGENERATION_RATE
+                    - intensive * DEGRADATION_RATE)
+                    / total,
+            );
+        }
+    }
+
+    /// Change soil health, with crops grown in
+    /// the soil doing better or worse for it.
+    pub fn change_soil_health(&mut self, change: f32) {
+        let prev = self.world.soil_health;
+        self.world.soil_health =
+            (prev + change).clamp(0., MAX_SOIL_HEALTH);
+        let change = self.world.soil_health - prev;
+        for process in self.world.processes.iter_mut() {
+            if process.output == Output::PlantCalories
+                && process.farms_soil()
+            {
+                process.output_modifier += change;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::events::{Effect, WorldVariable};
+
+    #[test]
+    fn test_soil_health() {
+        let mut state = State::default();
+        let crops = state
+            .world
+            .processes
+            .iter()
+            .find(|p| {
+                p.output == Output::PlantCalories
+                    && p.farms_soil()
+            })
+            .map(|p| (p.id, p.output_modifier))
+            .unwrap();
+
+        // Farming is mostly intensive at the start.
+        state.update_soil();
+        assert!(state.world.soil_health < 1.);
+        assert!(
+            state.world.processes[&crops.0].output_modifier
+                < crops.1
+        );
+        assert_eq!(
+            WorldVariable::SoilHealth.value(&state),
+            state.world.soil_health
+        );
+
+        // Regenerative farming builds it back up.
+        let health = state.world.soil_health;
+        for process in state.world.processes.iter_mut() {
+            if process.farms_soil() {
+                process.features.clear();
+                process
+                    .features
+                    .push(ProcessFeature::IsRegenerative);
+            }
+        }
+        state.update_soil();
+        assert!(state.world.soil_health > health);
+
+        // Effects on soil health change yields too.
+        let modifier =
+            state.world.processes[&crops.0].output_modifier;
+        state.apply_effects(
+            &[Effect::WorldVariable(
+                WorldVariable::SoilHealth,
+                0.05,
+            )],
+            None,
+        );
+        assert!(
+            state.world.processes[&crops.0].output_modifier
+                > modifier
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use crate::{
+    disease::Outbreak,
     events::{
         Condition,
         Effect,
@@ -10,6 +11,7 @@ use crate::{
         Phase,
         Request,
     },
+    hooks::Hooks,
     kinds::*,
     npcs::NPC,
     outputs,
@@ -22,14 +24,18 @@ use crate::{
         Status,
         Type as ProjectType,
     },
+    replay::Decision,
     resources,
+    rng::Rng,
     world::World,
     Collection,
     Id,
 };
 use serde::{Deserialize, Serialize};
 
-const LIFESPAN: usize = 60;
+/// How many years the player has, from the world's
+/// starting year, before the game ends.
+pub const LIFESPAN: usize = 60;
 const PRODUCTION_SHORTAGE_PENALTY: f32 = 60.;
 
 /// Have to all be below these values to win
@@ -76,6 +82,10 @@ pub struct State {
 
     pub protected_land: f32,
 
+    /// Disease outbreaks still running.
+    #[serde(default)]
+    pub outbreaks: Vec<Outbreak>,
+
     pub shortages_outlook: f32,
     pub emissions: Emissions,
     pub last_outlook: f32,
@@ -83,6 +93,30 @@ pub struct State {
     pub events: Vec<Event>,
 
     pub event_pool: EventPool,
+
+    /// The seed the game was started with,
+    /// e.g. to reproduce a run.
+    #[serde(default)]
+    pub seed: u64,
+
+    /// All of the game's randomness comes from this.
+    #[serde(default)]
+    pub rng: Rng,
+
+    /// The player's decisions so far, along with the
+    /// events rolled and cycles finished, each with the
+    /// year it happened in, for replays.
+    #[serde(default)]
+    pub decisions: Vec<(usize, Decision)>,
+
+    /// The temperature anomaly (C) each year was stepped
+    /// with, e.g. from Hector, for replays.
+    #[serde(default)]
+    pub tgavs: Vec<f32>,
+
+    /// Hooks into the game, which aren't saved.
+    #[serde(skip)]
+    pub hooks: Hooks,
 }
 
 impl Default for State {
@@ -92,7 +126,14 @@ impl Default for State {
 }
 
 impl State {
-    pub fn new(mut world: World) -> State {
+    /// Start a new game with a random seed.
+    pub fn new(world: World) -> State {
+        State::with_seed(world, Rng::random_seed())
+    }
+
+    /// Start a new game, seeding its randomness so that
+    /// it plays out the same given the same decisions.
+    pub fn with_seed(mut world: World, seed: u64) -> State {
         let mut npcs = NPC::load();
         let n_npcs =
             npcs.iter().filter(|npc| !npc.locked).count()
@@ -103,6 +144,17 @@ impl State {
             }
         }
 
+        // Land already lost to the sea when the game starts
+        // doesn't displace anyone, and crop yields already
+        // account for the warming so far.
+        let sea_level_rise = world.sea_level_rise;
+        let temperature = world.temperature;
+        for region in world.regions.iter_mut() {
+            region.flooded = region.flooded_at(sea_level_rise);
+            region.temp_anomaly =
+                region.anomaly_at(temperature);
+        }
+
         // Ensure that all projects have at least one outcome.
         for project in world.projects.iter_mut() {
             if project.outcomes.is_empty() {
@@ -127,6 +179,7 @@ impl State {
             feedstocks,
 
             protected_land: 0.1, // Starts at 10%
+            outbreaks: vec![],
 
             events: vec![],
             event_pool: EventPool::new(events),
@@ -145,6 +198,12 @@ impl State {
             flags: vec![],
             requests: vec![],
             policy_queue: vec![],
+
+            seed,
+            rng: Rng::with_seed(seed),
+            decisions: vec![],
+            tgavs: vec![],
+            hooks: Hooks::default(),
         };
         state.initialize();
         state
@@ -156,6 +215,14 @@ impl State {
         self.step_production();
         self.update_project_costs();
         self.world.update_climate(self.world.temperature);
+
+        // Crop yields and emissions already account for
+        // water shortfalls and land use when the game starts,
+        // and the air is as dirty as emissions keep it.
+        self.update_water_stress();
+        self.update_land_use();
+        self.settle_particulates();
+        self.settle_air_pollution();
     }
 
     /// If we won the game.
@@ -196,13 +263,23 @@ impl State {
     }
 
     pub fn step_year(&mut self, tgav: f32) -> Vec<Update> {
+        self.run_hooks(|hook, state| hook.on_year_start(state));
+        let checkpoint =
+            (!self.hooks.is_empty()).then(|| self.checkpoint());
+
+        self.tgavs.push(tgav);
+
         let mut updates = vec![];
         let changes = self.step_projects();
         for (id, changes) in changes {
-            if changes.completed {
+            let completed = changes.completed;
+            self.apply_changes(changes);
+            if completed {
                 updates.push(Update::Project { id });
+                self.run_hooks(|hook, state| {
+                    hook.on_project_completed(state, id)
+                });
             }
-            self.apply_changes(changes);
         }
 
         self.update_demand();
@@ -220,6 +297,13 @@ impl State {
             updates.append(&mut outcomes);
         }
 
+        if let Some(checkpoint) = checkpoint {
+            let delta = self.delta(&checkpoint);
+            self.run_hooks(|hook, state| {
+                hook.on_state_changed(state, &delta)
+            });
+        }
+
         updates
     }
 
@@ -246,17 +330,20 @@ impl State {
         event_id: Id,
         region_id: Option<Id>,
     ) {
-        let mut effects = vec![];
         let event = &self.event_pool.events[&event_id];
         self.events.push(event.clone());
 
-        for effect in &event.effects {
-            effects.push((effect.clone(), region_id));
-        }
-
-        for (effect, region_id) in effects {
+        let effects = self.event_pool.scaled_effects(
+            &event_id,
+            region_id,
+            &event.effects,
+        );
+        for effect in effects {
             effect.apply(self, region_id);
         }
+        self.run_hooks(|hook, state| {
+            hook.on_event_triggered(state, event_id, region_id)
+        });
     }
 
     pub fn eval_conditions(
@@ -323,6 +410,13 @@ impl State {
                 output_demand.fuel -= electrified;
             }
 
+            // Healthcare for air dirtier than at the start,
+            // or less of it for air cleaner than it.
+            output_demand.electricity = (output_demand
+                .electricity
+                + world.regions.healthcare_demand())
+            .max(0.);
+
             // For vegan-ish diets, move some animal calorie
             // demand to plants.
             let cal_change =
@@ -361,14 +455,15 @@ impl State {
         let total_demand = self.output_demand.total();
         let orders = self.world.processes.orders(&total_demand);
 
-        // Apply land protection
+        // Apply land protection and land lost to the sea
         self.resources.available.land =
             self.world.starting_resources.land
-                * (1. - self.protected_land);
+                * (1. - self.protected_land)
+                * (1. - self.world.regions.flooded());
 
         // Run production function
         let (
-            produced_by_process,
+            mut produced_by_process,
             produced_by_type,
             consumed_resources,
             consumed_feedstocks,
@@ -380,8 +475,23 @@ impl State {
             &self.feedstocks.available,
         );
 
+        // Outbreaks hold back production.
+        let output_left =
+            (1. - self.outbreak_output_loss()).max(0.);
+        for amount in produced_by_process.values_mut() {
+            *amount *= output_left;
+        }
+        let production_byproducts =
+            production_byproducts * output_left;
+
         self.produced.by_process = produced_by_process;
-        self.produced.amount = produced_by_type;
+        self.produced.amount = produced_by_type * output_left;
+        self.produced.emissions = OutputMap::default();
+        for (id, amount) in &self.produced.by_process {
+            let process = &self.world.processes[id];
+            self.produced.emissions[process.output] +=
+                process.adj_byproducts().gtco2eq() * amount;
+        }
 
         resource_demand.water += required_resources.water;
         resource_demand.land += required_resources.land;
@@ -458,6 +568,13 @@ impl State {
 
         self.world.update_populations();
         let temp_change = self.world.update_climate(tgav);
+        self.cross_tipping_points();
+        self.update_ocean();
+        self.update_water();
+        self.update_land();
+        self.update_soil();
+        self.update_pollution();
+        self.update_outbreaks();
 
         let stop = self.flags.contains(&Flag::StopDevelopment);
         let fast = self.flags.contains(&Flag::FastDevelopment);
@@ -495,6 +612,8 @@ impl State {
         self.npcs
             .update_seats(outlook_change, &recent_projects);
         self.last_outlook = self.outlook();
+        self.decisions
+            .push((self.world.year, Decision::FinishCycle));
     }
 
     pub fn check_requests(
@@ -555,13 +674,18 @@ impl State {
         &mut self,
         phase: Phase,
     ) -> Vec<ResolvedEvent> {
-        let mut pool = self.event_pool.clone();
-        let events = pool.roll_for_phase(phase, &self);
-        self.event_pool = pool;
-
-        let events: Vec<ResolvedEvent> = events
+        // Taken rather than cloned, as cloning all of the
+        // events (with their flavor) is the slowest part of a
+        // year. Rolling doesn't look at the state's pool.
+        let mut pool = std::mem::take(&mut self.event_pool);
+        let mut rng = std::mem::take(&mut self.rng);
+        let events = pool.roll_for_phase(phase, self, &mut rng);
+        let mut events: Vec<ResolvedEvent> = events
             .into_iter()
             .map(|(ev, region_id)| ResolvedEvent {
+                magnitude: pool.resolve_magnitude(
+                    &ev, region_id, &mut rng,
+                ),
                 event: ev,
                 region: region_id.map(|id| {
                     (
@@ -573,6 +697,43 @@ impl State {
                 }),
             })
             .collect();
+        self.event_pool = pool;
+        self.rng = rng;
+
+        // Extreme weather, droughts and disease outbreaks,
+        // on top of any from their events' own probabilities.
+        if phase == Phase::Icon {
+            let mut disasters = self.roll_disasters();
+            disasters.extend(self.roll_droughts());
+            disasters.extend(self.roll_outbreaks());
+            for (event_id, region_id) in disasters {
+                let occurring = events.iter().any(|ev| {
+                    ev.id == event_id
+                        && ev.region.as_ref().map(|r| r.0)
+                            == Some(region_id)
+                });
+                if !occurring {
+                    let event =
+                        self.event_pool.events[&event_id].clone();
+                    let magnitude =
+                        self.event_pool.resolve_magnitude(
+                            &event,
+                            Some(region_id),
+                            &mut self.rng,
+                        );
+                    events.push(ResolvedEvent {
+                        event,
+                        region: Some((
+                            region_id,
+                            self.world.regions[&region_id]
+                                .name
+                                .to_string(),
+                        )),
+                        magnitude,
+                    });
+                }
+            }
+        }
 
         // Icon events, aka disasters,
         // are handled differently, so we don't
@@ -586,6 +747,12 @@ impl State {
             }
         }
 
+        // Which phases are rolled, and when, depends on
+        // where the player is in the game.
+        self.decisions.push((
+            self.world.year,
+            Decision::RollEvents(phase),
+        ));
         events
     }
 }
@@ -597,10 +764,13 @@ impl State {
             self.world.projects.step(self.world.year);
 
         let mut outcomes: Vec<(Id, usize)> = Vec::new();
+        let mut rng = std::mem::take(&mut self.rng);
         for (id, changes) in &mut changes {
             if changes.completed {
                 let project = &self.world.projects[&id];
-                match self.roll_project_outcome(project) {
+                let outcome =
+                    self.roll_project_outcome(project, &mut rng);
+                match outcome {
                     Some((outcome, i)) => {
                         for effect in &outcome.effects {
                             changes
@@ -614,6 +784,8 @@ impl State {
             }
         }
 
+        self.rng = rng;
+
         for (id, i) in outcomes {
             self.world.projects[&id].active_outcome = Some(i);
         }
@@ -763,13 +935,14 @@ impl State {
     fn roll_project_outcome<'a>(
         &self,
         project: &'a Project,
+        rng: &mut Rng,
     ) -> Option<(&'a Outcome, usize)> {
         let mut outcome = None;
         for (i, o) in project.outcomes.iter().enumerate() {
             match o.probability.eval(self, None) {
                 Some(likelihood) => {
                     let prob = likelihood.p();
-                    if fastrand::f32() <= prob {
+                    if rng.f32() <= prob {
                         outcome = Some((o, i));
                         break;
                     }
@@ -787,10 +960,11 @@ impl State {
         let mut effects: Vec<Effect> = Vec::new();
         let ids: Vec<Id> =
             self.policy_queue.drain(..).collect();
+        let mut rng = std::mem::take(&mut self.rng);
         for id in &ids {
             let mut active_outcome = None;
             let proj = &self.world.projects[id];
-            match self.roll_project_outcome(proj) {
+            match self.roll_project_outcome(proj, &mut rng) {
                 Some((outcome, i)) => {
                     for effect in &outcome.effects {
                         effects.push(effect.clone());
@@ -806,6 +980,7 @@ impl State {
                 effects.push(effect.clone());
             }
         }
+        self.rng = rng;
 
         for effect in effects {
             effect.apply(self, None);
@@ -822,12 +997,18 @@ impl State {
 pub struct Production {
     pub amount: OutputMap,
     pub by_process: BTreeMap<Id, f32>,
+
+    /// Emissions from producing each output, in GtCO2eq,
+    /// as of when the year's production ran.
+    #[serde(default)]
+    pub emissions: OutputMap,
 }
 impl Default for Production {
     fn default() -> Self {
         Self {
             amount: OutputMap::default(),
             by_process: BTreeMap::default(),
+            emissions: OutputMap::default(),
         }
     }
 }
@@ -907,6 +1088,10 @@ impl Changes for ProjectChanges {
 pub struct ResolvedEvent {
     pub event: Event,
     pub region: Option<(Id, String)>,
+
+    /// The magnitude the event occurred with, if it has one.
+    #[serde(default)]
+    pub magnitude: Option<f32>,
 }
 impl std::ops::Deref for ResolvedEvent {
     type Target = Event;
@@ -0,0 +1,314 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    batch::{Batch, BatchSummary},
+    replay::Decision,
+    world::World,
+};
+
+/// A world parameter to vary and the values to try.
+///
+/// Parameters are referred to by their path in the world's
+/// JSON, separated by `/`. Entities are referred to by their
+/// name and other lists by index, e.g.
+/// `processes/Coal Power Generation/byproducts/co2` or
+/// `per_capita_demand/0/fuel`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Parameter {
+    pub path: String,
+    pub values: Vec<f32>,
+}
+impl Parameter {
+    /// Vary the parameter from `from` to `to` (inclusive)
+    /// in evenly spaced steps.
+    pub fn range(
+        path: impl Into<String>,
+        from: f32,
+        to: f32,
+        steps: usize,
+    ) -> Self {
+        let values = match steps {
+            0 => vec![],
+            1 => vec![from],
+            _ => (0..steps)
+                .map(|i| {
+                    let t = i as f32 / (steps - 1) as f32;
+                    from + (to - from) * t
+                })
+                .collect(),
+        };
+        Parameter {
+            path: path.into(),
+            values,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SweepError {
+    /// No such parameter in the world.
+    NotFound(String),
+
+    /// The parameter isn't a number.
+    NotANumber(String),
+
+    /// The world was invalid once the parameters were set,
+    /// e.g. a whole number was set to a fraction.
+    Invalid(serde_json::Error),
+}
+impl Display for SweepError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match self {
+            Self::NotFound(path) => {
+                write!(f, "There's no parameter `{path}`.")
+            }
+            Self::NotANumber(path) => {
+                write!(
+                    f,
+                    "The parameter `{path}` isn't a number."
+                )
+            }
+            Self::Invalid(err) => {
+                write!(f, "Couldn't set the parameters: {err}")
+            }
+        }
+    }
+}
+
+/// Runs a world with every combination of the parameters'
+/// values, e.g. to find a balanced value for them without
+/// trial and error.
+#[derive(Debug, Clone)]
+pub struct Sweep {
+    pub world: World,
+    pub parameters: Vec<Parameter>,
+
+    /// Decisions to make in every run,
+    /// each with the year to make it in.
+    pub decisions: Vec<(usize, Decision)>,
+
+    /// How many years to run each run for,
+    /// unless the game ends first.
+    pub years: usize,
+
+    /// Each combination is run once per seed.
+    pub seeds: Vec<u64>,
+}
+
+/// The outcomes for one combination of parameter values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepRow {
+    /// The value of each parameter, in
+    /// the order the parameters were given.
+    pub values: Vec<f32>,
+    pub summary: BatchSummary,
+}
+
+impl Sweep {
+    /// Run every combination, returning a row of outcomes
+    /// for each. Fails before running anything if any of
+    /// the parameters can't be set.
+    pub fn run(&self) -> Result<Vec<SweepRow>, SweepError> {
+        let world = serde_json::to_value(&self.world)
+            .expect("Worlds are always serializable");
+        for param in &self.parameters {
+            match lookup(&world, &param.path) {
+                None => {
+                    return Err(SweepError::NotFound(
+                        param.path.clone(),
+                    ))
+                }
+                Some(value) if !value.is_number() => {
+                    return Err(SweepError::NotANumber(
+                        param.path.clone(),
+                    ))
+                }
+                _ => (),
+            }
+        }
+
+        let mut rows = vec![];
+        for values in self.combinations() {
+            let mut json = world.clone();
+            for (param, value) in
+                self.parameters.iter().zip(&values)
+            {
+                if let Some(field) =
+                    lookup_mut(&mut json, &param.path)
+                {
+                    // Keep whole numbers whole, so that
+                    // integer parameters can be swept too.
+                    *field = if field.is_f64()
+                        || value.fract() != 0.
+                    {
+                        Value::from(*value)
+                    } else {
+                        Value::from(*value as i64)
+                    };
+                }
+            }
+            let world: World = serde_json::from_value(json)
+                .map_err(SweepError::Invalid)?;
+            let batch = Batch {
+                world,
+                decisions: self.decisions.clone(),
+                years: self.years,
+            };
+            rows.push(SweepRow {
+                values,
+                summary: batch.run(self.seeds.iter().copied()),
+            });
+        }
+        Ok(rows)
+    }
+
+    /// Every combination of the parameters' values.
+    fn combinations(&self) -> Vec<Vec<f32>> {
+        self.parameters.iter().fold(
+            vec![vec![]],
+            |combos, param| {
+                combos
+                    .iter()
+                    .flat_map(|combo| {
+                        param.values.iter().map(|value| {
+                            let mut combo = combo.clone();
+                            combo.push(*value);
+                            combo
+                        })
+                    })
+                    .collect()
+            },
+        )
+    }
+}
+
+/// Find the index of a path segment in a list,
+/// either by the name of an item or by its index.
+fn find(items: &[Value], segment: &str) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| {
+            item.get("name").and_then(Value::as_str)
+                == Some(segment)
+        })
+        .or_else(|| {
+            segment.parse().ok().filter(|i| *i < items.len())
+        })
+}
+
+fn lookup<'a>(
+    value: &'a Value,
+    path: &str,
+) -> Option<&'a Value> {
+    path.split('/').try_fold(
+        value,
+        |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => {
+                find(items, segment).map(|i| &items[i])
+            }
+            _ => None,
+        },
+    )
+}
+
+fn lookup_mut<'a>(
+    value: &'a mut Value,
+    path: &str,
+) -> Option<&'a mut Value> {
+    path.split('/').try_fold(
+        value,
+        |value, segment| match value {
+            Value::Object(map) => map.get_mut(segment),
+            Value::Array(items) => {
+                find(items, segment).map(|i| &mut items[i])
+            }
+            _ => None,
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let param = Parameter::range("x", 0., 1., 5);
+        assert_eq!(param.values, vec![0., 0.25, 0.5, 0.75, 1.]);
+        assert_eq!(
+            Parameter::range("x", 2., 3., 1).values,
+            vec![2.]
+        );
+    }
+
+    #[test]
+    fn test_sweep() {
+        let world = World::default();
+        let process = world.processes.first().name.clone();
+        let sweep = Sweep {
+            world,
+            parameters: vec![
+                Parameter::range("temperature", 1., 2., 2),
+                Parameter::range(
+                    format!(
+                        "processes/{process}/byproducts/co2"
+                    ),
+                    0.,
+                    1.,
+                    3,
+                ),
+            ],
+            decisions: vec![],
+            years: 1,
+            seeds: vec![0],
+        };
+        let rows = sweep.run().unwrap();
+        let values: Vec<_> =
+            rows.iter().map(|row| row.values.clone()).collect();
+        assert_eq!(
+            values,
+            vec![
+                vec![1., 0.],
+                vec![1., 0.5],
+                vec![1., 1.],
+                vec![2., 0.],
+                vec![2., 0.5],
+                vec![2., 1.],
+            ]
+        );
+        assert!(rows
+            .iter()
+            .all(|row| row.summary.runs.len() == 1));
+
+        let mut sweep = sweep;
+        sweep.parameters =
+            vec![Parameter::range("nope", 0., 1., 2)];
+        assert!(matches!(
+            sweep.run(),
+            Err(SweepError::NotFound(_))
+        ));
+        sweep.parameters = vec![Parameter::range(
+            "processes/0/name",
+            0.,
+            1.,
+            2,
+        )];
+        assert!(matches!(
+            sweep.run(),
+            Err(SweepError::NotANumber(_))
+        ));
+
+        // Whole numbers, e.g. years, can be swept too.
+        sweep.parameters =
+            vec![Parameter::range("year", 2030., 2031., 2)];
+        let rows = sweep.run().unwrap();
+        assert_eq!(rows[1].summary.runs[0].year, 2032);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{flavor::DialogueNext, World};
+
+/// Where a piece of the world's text is,
+/// e.g. the name of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextField {
+    /// What the text belongs to, e.g. "Event: Heatwave".
+    pub owner: String,
+
+    /// Which of its fields the text is, e.g. "Name".
+    pub field: &'static str,
+}
+impl TextField {
+    fn new(owner: &str, field: &'static str) -> Self {
+        TextField {
+            owner: owner.to_string(),
+            field,
+        }
+    }
+}
+
+/// Collect the world's text fields, shared so that
+/// the shared and mutable versions can't drift apart.
+/// Owners are labeled before their fields are borrowed.
+macro_rules! text_fields {
+    ($world:expr, $iter:ident $(, $mut_:tt)?) => {{
+        let mut fields = vec![];
+        for region in $world.regions.$iter() {
+            let owner = format!("Region: {}", region.name);
+            fields.push((
+                TextField::new(&owner, "Name"),
+                & $($mut_)? region.name,
+            ));
+            fields.push((
+                TextField::new(&owner, "Image Description"),
+                & $($mut_)? region.flavor.image.alt,
+            ));
+        }
+        for industry in $world.industries.$iter() {
+            let owner = format!("Industry: {}", industry.name);
+            fields.push((
+                TextField::new(&owner, "Name"),
+                & $($mut_)? industry.name,
+            ));
+            fields.push((
+                TextField::new(&owner, "Description"),
+                & $($mut_)? industry.flavor.description,
+            ));
+            fields.push((
+                TextField::new(&owner, "Image Description"),
+                & $($mut_)? industry.flavor.image.alt,
+            ));
+        }
+        for process in $world.processes.$iter() {
+            let owner = format!("Process: {}", process.name);
+            fields.push((
+                TextField::new(&owner, "Name"),
+                & $($mut_)? process.name,
+            ));
+            fields.push((
+                TextField::new(&owner, "Description"),
+                & $($mut_)? process.flavor.description,
+            ));
+            fields.push((
+                TextField::new(&owner, "Image Description"),
+                & $($mut_)? process.flavor.image.alt,
+            ));
+        }
+        for project in $world.projects.$iter() {
+            let owner = format!("Project: {}", project.name);
+            fields.push((
+                TextField::new(&owner, "Name"),
+                & $($mut_)? project.name,
+            ));
+            fields.push((
+                TextField::new(&owner, "Description"),
+                & $($mut_)? project.flavor.description,
+            ));
+            fields.push((
+                TextField::new(&owner, "Image Description"),
+                & $($mut_)? project.flavor.image.alt,
+            ));
+        }
+        for event in $world.events.$iter() {
+            let owner = format!("Event: {}", event.name);
+            fields.push((
+                TextField::new(&owner, "Name"),
+                & $($mut_)? event.name,
+            ));
+            let flavor = & $($mut_)? event.flavor;
+            fields.push((
+                TextField::new(&owner, "Arc"),
+                & $($mut_)? flavor.arc,
+            ));
+            for image in flavor
+                .image
+                .$iter()
+                .chain(& $($mut_)? flavor.variants)
+            {
+                fields.push((
+                    TextField::new(&owner, "Image Description"),
+                    & $($mut_)? image.alt,
+                ));
+            }
+            for line in & $($mut_)? flavor.dialogue.lines {
+                fields.push((
+                    TextField::new(&owner, "Dialogue"),
+                    & $($mut_)? line.text,
+                ));
+                if let Some(DialogueNext::Responses(
+                    responses,
+                )) = & $($mut_)? line.next
+                {
+                    for response in responses {
+                        fields.push((
+                            TextField::new(&owner, "Response"),
+                            & $($mut_)? response.text,
+                        ));
+                    }
+                }
+            }
+        }
+        fields
+    }};
+}
+
+impl World {
+    /// The world's names and flavor text, always in the
+    /// same order for the same world, e.g. for searching
+    /// across all of it.
+    pub fn text_fields(&self) -> Vec<(TextField, &String)> {
+        text_fields!(self, iter)
+    }
+
+    /// Like [`World::text_fields`], but for editing them.
+    pub fn text_fields_mut(
+        &mut self,
+    ) -> Vec<(TextField, &mut String)> {
+        text_fields!(self, iter_mut, mut)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_text_fields() {
+        let mut world = World::default();
+        let n = world.text_fields().len();
+        assert_eq!(n, world.text_fields_mut().len());
+
+        let (field, text) = world.text_fields_mut().remove(0);
+        assert_eq!(field.field, "Name");
+        assert!(field.owner.starts_with("Region: "));
+        *text = "Renamed".into();
+        assert_eq!(world.regions.by_idx(0).name, "Renamed");
+
+        // Everything translatable is covered.
+        let texts = world
+            .text_fields()
+            .into_iter()
+            .map(|(_, text)| text.clone())
+            .collect::<Vec<_>>();
+        for s in world.translatable_strings() {
+            assert!(texts.contains(&s));
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Climate tipping points, e.g. permafrost methane release,
+//! which can be crossed once the temperature anomaly rises
+//! past their thresholds. Once crossed they stay crossed,
+//! and their effects keep feeding back into the climate.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{Effect, WorldVariable},
+    form::Documented,
+    state::State,
+    Collection,
+    HasId,
+    Id,
+};
+
+/// A threshold in the climate system which,
+/// once crossed, can't be uncrossed.
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Documented,
+)]
+pub struct TippingPoint {
+    pub id: Id,
+    pub name: String,
+
+    /// What crossing the tipping point means.
+    #[serde(default)]
+    pub description: String,
+
+    /// The temperature anomaly (C) above
+    /// which it can be crossed.
+    pub threshold: f32,
+
+    /// The chance of crossing it each year, for every
+    /// degree the temperature is above the threshold.
+    pub probability: f32,
+
+    /// Applied when it's crossed. Most effects last, e.g.
+    /// extra emissions every year, so these are how it
+    /// feeds back into the climate.
+    pub effects: Vec<Effect>,
+
+    /// If it's been crossed.
+    #[serde(default)]
+    pub crossed: bool,
+}
+impl HasId for TippingPoint {
+    fn id(&self) -> &Id {
+        &self.id
+    }
+}
+impl std::fmt::Display for TippingPoint {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Default for TippingPoint {
+    fn default() -> Self {
+        TippingPoint {
+            id: Id::new_v4(),
+            name: "New Tipping Point".into(),
+            description: String::new(),
+            threshold: 2.,
+            probability: 0.05,
+            effects: vec![],
+            crossed: false,
+        }
+    }
+}
+
+impl TippingPoint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The chance of crossing it this year.
+    pub fn chance(&self, temperature: f32) -> f32 {
+        if self.crossed || temperature <= self.threshold {
+            0.
+        } else {
+            (self.probability * (temperature - self.threshold))
+                .min(1.)
+        }
+    }
+}
+
+pub const PERMAFROST: Id =
+    Id::from_u128(0x6b1e0f0a_52d3_4f2e_9c1a_7d0b3e5a1c01);
+pub const AMOC: Id =
+    Id::from_u128(0x6b1e0f0a_52d3_4f2e_9c1a_7d0b3e5a1c02);
+pub const ICE_SHEETS: Id =
+    Id::from_u128(0x6b1e0f0a_52d3_4f2e_9c1a_7d0b3e5a1c03);
+
+/// The tipping points worlds have unless they define
+/// their own, loosely based on Armstrong McKay et al. 2022.
+pub fn default_tipping_points() -> Collection<TippingPoint> {
+    vec![
+        TippingPoint {
+            id: PERMAFROST,
+            name: "Permafrost Thaw".into(),
+            description: "Thawing permafrost releases the methane and carbon dioxide it has locked away.".into(),
+            threshold: 1.5,
+            probability: 0.05,
+            effects: vec![
+                Effect::WorldVariable(
+                    WorldVariable::Emissions,
+                    1.5,
+                ),
+                Effect::WorldVariable(
+                    WorldVariable::Temperature,
+                    0.05,
+                ),
+            ],
+            crossed: false,
+        },
+        TippingPoint {
+            id: AMOC,
+            name: "AMOC Weakening".into(),
+            description: "The Atlantic Meridional Overturning Circulation slows, disrupting weather and ecosystems around the Atlantic.".into(),
+            threshold: 3.,
+            probability: 0.03,
+            effects: vec![
+                Effect::WorldVariable(
+                    WorldVariable::Outlook,
+                    -10.,
+                ),
+                Effect::WorldVariable(
+                    WorldVariable::Precipitation,
+                    -0.1,
+                ),
+                Effect::WorldVariable(
+                    WorldVariable::ExtinctionRate,
+                    5.,
+                ),
+            ],
+            crossed: false,
+        },
+        TippingPoint {
+            id: ICE_SHEETS,
+            name: "Ice Sheet Collapse".into(),
+            description: "The Greenland and West Antarctic ice sheets pass the point of no return, committing the world to meters of sea level rise.".into(),
+            threshold: 2.,
+            probability: 0.02,
+            effects: vec![Effect::WorldVariable(
+                WorldVariable::SeaLevelRiseRate,
+                0.005,
+            )],
+            crossed: false,
+        },
+    ]
+    .into()
+}
+
+impl State {
+    /// Roll for tipping points to be crossed at the current
+    /// temperature, applying the effects of those that are
+    /// and returning their ids.
+    pub(crate) fn cross_tipping_points(&mut self) -> Vec<Id> {
+        let temperature = self.world.temperature;
+        let mut crossed = vec![];
+        for point in self.world.tipping_points.iter_mut() {
+            let chance = point.chance(temperature);
+            if chance > 0. && self.rng.f32() < chance {
+                point.crossed = true;
+                crossed.push(point.id);
+            }
+        }
+        for id in &crossed {
+            let effects =
+                self.world.tipping_points[id].effects.clone();
+            self.apply_effects(&effects, None);
+        }
+        crossed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        events::{expr::ConditionExpr, Condition},
+        world::World,
+    };
+
+    #[test]
+    fn test_tipping_points() {
+        let world = World::default();
+        assert_eq!(world.tipping_points.len(), 3);
+        let mut state = State::with_seed(world, 0);
+
+        let crossed =
+            Condition::TippingPointCrossed(PERMAFROST);
+        let not_crossed =
+            Condition::TippingPointNotCrossed(PERMAFROST);
+
+        // Below every threshold nothing can be crossed.
+        state.world.temperature = 1.;
+        assert!(state.cross_tipping_points().is_empty());
+        assert!(!crossed.eval(&state, None));
+        assert!(not_crossed.eval(&state, None));
+
+        // Far enough above every threshold it's certain,
+        // e.g. 52C for the ice sheets at 2% per degree.
+        state.world.temperature = 60.;
+        let emissions = state.byproducts.modifier.co2;
+        let ids = state.cross_tipping_points();
+        assert_eq!(ids.len(), 3);
+        assert!(crossed.eval(&state, None));
+        assert!(!not_crossed.eval(&state, None));
+        let expr = ConditionExpr::parse(
+            "tipping_point(PermafrostThaw)",
+        )
+        .unwrap();
+        assert_eq!(expr.eval(&state, None), Ok(true));
+        assert!(state.byproducts.modifier.co2 > emissions);
+
+        // And they're only crossed once.
+        assert!(state.cross_tipping_points().is_empty());
+    }
+}
@@ -94,6 +94,13 @@ impl<T: HasId> Collection<T> {
         });
         self.reindex();
     }
+
+    /// Sort the values by id, so that their order doesn't
+    /// depend on when or where they were added.
+    pub fn sort_by_id(&mut self) {
+        self.values.sort_by_key(|value| *value.id());
+        self.reindex();
+    }
 }
 impl<T: HasId> From<Vec<T>> for Collection<T> {
     fn from(values: Vec<T>) -> Collection<T> {
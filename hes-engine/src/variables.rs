@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::{form::Documented, HasId, Id};
+
+/// What kind of values a [`Variable`] holds.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    Display,
+)]
+pub enum VariableKind {
+    /// Either set (1) or not (0).
+    Flag,
+
+    /// A whole number.
+    Counter,
+
+    /// Any number.
+    #[default]
+    Float,
+}
+
+/// A variable defined by the world rather than the engine,
+/// e.g. "Public Trust", for mechanics of the world's own.
+/// Variables are changed by effects and read by conditions.
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Documented,
+)]
+pub struct Variable {
+    pub id: Id,
+    pub name: String,
+
+    /// What the variable is for.
+    #[serde(default)]
+    pub description: String,
+
+    /// Flags are either 0 or 1 and counters
+    /// are always whole numbers.
+    pub kind: VariableKind,
+
+    /// The variable's value, which starts
+    /// as the value in the world file.
+    pub value: f32,
+}
+impl HasId for Variable {
+    fn id(&self) -> &Id {
+        &self.id
+    }
+}
+impl std::fmt::Display for Variable {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Default for Variable {
+    fn default() -> Self {
+        Variable {
+            id: Id::new_v4(),
+            name: "New Variable".into(),
+            description: String::new(),
+            kind: VariableKind::default(),
+            value: 0.,
+        }
+    }
+}
+
+impl Variable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value, as the kind of variable allows:
+    /// flags are set by any non-zero value and
+    /// counters are rounded to whole numbers.
+    pub fn set(&mut self, value: f32) {
+        self.value = match self.kind {
+            VariableKind::Flag => {
+                if value == 0. {
+                    0.
+                } else {
+                    1.
+                }
+            }
+            VariableKind::Counter => value.round(),
+            VariableKind::Float => value,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        events::{Comparator, Condition, Effect},
+        state::State,
+        world::World,
+    };
+
+    #[test]
+    fn test_variables() {
+        let mut world = World::default();
+        let mut trust = Variable::new();
+        trust.name = "Public Trust".into();
+        trust.kind = VariableKind::Counter;
+        trust.value = 2.;
+        let mut scandal = Variable::new();
+        scandal.kind = VariableKind::Flag;
+        let (trust_id, scandal_id) = (trust.id, scandal.id);
+        world.variables.push(trust);
+        world.variables.push(scandal);
+
+        let mut state = State::with_seed(world, 0);
+        let above = Condition::Variable(
+            trust_id,
+            Comparator::GreaterEqual,
+            5.,
+        );
+        assert!(!above.eval(&state, None));
+
+        let change = Effect::ChangeVariable(trust_id, 2.6);
+        change.apply(&mut state, None);
+        assert_eq!(state.world.variables[&trust_id].value, 5.);
+        assert!(above.eval(&state, None));
+        change.unapply(&mut state, None);
+        assert_eq!(state.world.variables[&trust_id].value, 2.);
+
+        Effect::SetVariable(scandal_id, 0.5)
+            .apply(&mut state, None);
+        assert_eq!(
+            state.world.variables[&scandal_id].value,
+            1.
+        );
+        assert!(Condition::Variable(
+            scandal_id,
+            Comparator::Equal,
+            1.
+        )
+        .eval(&state, None));
+
+        // Conditions on deleted variables don't hold.
+        state.world.variables.remove(&scandal_id);
+        assert!(!Condition::Variable(
+            scandal_id,
+            Comparator::Less,
+            1.
+        )
+        .eval(&state, None));
+    }
+}
@@ -0,0 +1,199 @@
+//! Regional water stress. Each region gets a share of the
+//! world's freshwater by how much rain it gets, so regions
+//! drying out as the world warms have less. Each needs
+//! water for its households, by income level, and for its
+//! share of agriculture and industry, by population. Regions
+//! needing more than they have suffer droughts, and crop
+//! yields fall with the water they go without.
+
+use crate::{
+    kinds::{Output, Resource},
+    production::ProcessFeature,
+    regions::Region,
+    state::State,
+    Collection,
+    Id,
+};
+
+/// The mean precipitation (cm/year) at which a region
+/// gets an even share of the world's freshwater, roughly
+/// the mean across regions at the start.
+const REFERENCE_PRECIP: f32 = 128.;
+
+/// The chance of a drought each year in a region,
+/// for each unit of water stress past 1.
+const DROUGHT_CHANCE: f32 = 0.05;
+
+/// Water stress past which droughts lead to famine.
+const FAMINE_STRESS: f32 = 3.;
+
+/// The share of crop yields lost for
+/// each share of water demand not met.
+const SHORTFALL_YIELD_LOSS: f32 = 0.5;
+
+const CROP_FAILURES: Id =
+    Id::from_u128(0xa146d743_cff3_4e72_9b67_d5eb8b18c166);
+const FAMINE: Id =
+    Id::from_u128(0xfc84a530_5764_4649_87c5_bc351e154d7e);
+
+impl Region {
+    /// The region's water demand relative to its supply.
+    /// Above 1 it needs more water than it has.
+    pub fn water_stress(&self) -> f32 {
+        self.water_demand / self.water_supply.max(1.)
+    }
+
+    /// How much water the region needs
+    /// beyond what it has (L/year).
+    pub fn water_shortfall(&self) -> f32 {
+        (self.water_demand - self.water_supply).max(0.)
+    }
+
+    fn precip_mean(&self) -> f32 {
+        (self.precip_lo + self.precip_hi) / 2.
+    }
+}
+
+impl Collection<Region> {
+    /// The share of the regions' water demand that isn't met.
+    pub fn water_shortfall(&self) -> f32 {
+        let demand: f32 =
+            self.iter().map(|r| r.water_demand).sum();
+        if demand <= 0. {
+            return 0.;
+        }
+        self.iter().map(|r| r.water_shortfall()).sum::<f32>()
+            / demand
+    }
+}
+
+impl State {
+    /// Update the regions' water supply and demand,
+    /// returning the change in the share of water
+    /// demand that isn't met.
+    pub(crate) fn update_water_stress(&mut self) -> f32 {
+        let prev = self.world.regions.water_shortfall();
+        let supply = self.resources.available.water
+            / self.world.regions.len() as f32;
+        let demand = self.resource_demand.of(Resource::Water)
+            * (1. - self.world.water_efficiency).max(0.);
+        let population = self.world.regions.population();
+        let water_by_income = self.world.water_by_income;
+        for region in self.world.regions.iter_mut() {
+            region.water_supply = supply * region.precip_mean()
+                / REFERENCE_PRECIP;
+
+            // Household demand is per month.
+            let households = region.population
+                * water_by_income[region.income.level()]
+                * 12.;
+            let share = if population > 0. {
+                region.population / population
+            } else {
+                0.
+            };
+            region.water_demand = households + demand * share;
+        }
+        self.world.regions.water_shortfall() - prev
+    }
+
+    /// Update water stress, with crops on land
+    /// going without water being less productive.
+    pub(crate) fn update_water(&mut self) {
+        let change = self.update_water_stress();
+        for process in self.world.processes.iter_mut() {
+            let is_food = matches!(
+                process.output,
+                Output::PlantCalories | Output::AnimalCalories
+            );
+            if is_food
+                && !process
+                    .features
+                    .contains(&ProcessFeature::IsMarine)
+            {
+                process.output_modifier -=
+                    change * SHORTFALL_YIELD_LOSS;
+            }
+        }
+    }
+
+    /// Roll for droughts in regions under water stress,
+    /// returning the ids of the events for any that occur
+    /// and the regions they occur in.
+    pub(crate) fn roll_droughts(&mut self) -> Vec<(Id, Id)> {
+        let mut droughts = vec![];
+        for region in self.world.regions.iter() {
+            let stress = region.water_stress();
+            if region.seceded || stress <= 1. {
+                continue;
+            }
+            let chance = (DROUGHT_CHANCE * (stress - 1.))
+                * region.vulnerability;
+            if self.rng.f32() >= chance.min(1.) {
+                continue;
+            }
+
+            let event = if stress > FAMINE_STRESS {
+                FAMINE
+            } else {
+                CROP_FAILURES
+            };
+            if self.event_pool.events.try_get(&event).is_some()
+            {
+                droughts.push((event, region.id));
+            }
+        }
+        droughts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{events::LocalVariable, world::World};
+
+    #[test]
+    fn test_water_stress() {
+        let world = World::default();
+        let mut state = State::with_seed(world, 0);
+        let region = state.world.regions.first();
+        let id = region.id;
+        assert!(region.water_supply > 0.);
+        assert!(region.water_demand > 0.);
+        assert_eq!(
+            LocalVariable::WaterStress.value(region),
+            region.water_stress()
+        );
+
+        // Nothing changes when nothing has.
+        assert!(state.update_water_stress().abs() < 1e-6);
+
+        // Dry out a region.
+        let crops = state
+            .world
+            .processes
+            .iter()
+            .find(|p| p.output == Output::PlantCalories)
+            .map(|p| (p.id, p.output_modifier))
+            .unwrap();
+        let region = &mut state.world.regions[&id];
+        region.precip_lo = 0.;
+        region.precip_hi = 0.;
+        region.vulnerability = 100.;
+        state.update_water();
+        let region = &state.world.regions[&id];
+        assert_eq!(region.water_supply, 0.);
+        assert!(region.water_stress() > FAMINE_STRESS);
+        assert!(
+            state.world.processes[&crops.0].output_modifier
+                < crops.1
+        );
+        assert!(state.roll_droughts().contains(&(FAMINE, id)));
+
+        // Efficiency lowers demand.
+        let demand = state.world.regions[&id].water_demand;
+        state.world.water_efficiency = 0.5;
+        state.update_water_stress();
+        assert!(state.world.regions[&id].water_demand < demand);
+    }
+}
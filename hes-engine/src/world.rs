@@ -1,14 +1,31 @@
-use std::{collections::BTreeMap, sync::LazyLock};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::LazyLock,
+};
 
 use crate::{
+    demographics::{default_demographics, Demographics},
+    disasters::default_disaster_rate,
+    disease::default_outbreak_rate,
     events::Event,
+    flavor::DialogueNext,
+    form::{Documented, EditorForm},
     industries::Industry,
+    integrity::Integrity,
     kinds::{FeedstockMap, Output, OutputMap, ResourceMap},
+    migrate::WORLD_VERSION,
     outputs,
-    production::Process,
+    par,
+    production::{Process, ProcessFeature},
     projects::Project,
     regions::{Income, Region},
+    ocean::default_acidification,
+    pollution::default_plastic,
+    soil::default_soil_health,
     round_to,
+    tipping::{default_tipping_points, TippingPoint},
+    variables::Variable,
+    Bounds,
     Collection,
     Id,
     OutputDemand,
@@ -24,16 +41,57 @@ pub static CLIMATES: LazyLock<BTreeMap<String, Vec<[f32; 4]>>> =
 /// The `World` represents a game configuration,
 /// defining the world's parameters as well
 /// as the projects, processes, regions, and industries.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Documented,
+)]
 pub struct World {
+    /// The version of the world format this world
+    /// is from. Worlds without one are from before
+    /// the format was versioned.
+    #[serde(default)]
+    pub version: u32,
+
+    #[serde(default)]
+    pub metadata: Metadata,
+
     pub year: usize,
 
+    /// The starting world contentedness.
     pub base_outlook: f32,
     pub temp_outlook: f32,
 
+    /// The starting extinction pressure.
     pub extinction_rate: f32,
-    pub temperature: f32, // global temp anomaly, C
-    pub sea_level_rise: f32, // meters
+
+    /// The starting global temperature anomaly (C).
+    pub temperature: f32,
+
+    /// The starting sea level rise (meters).
+    pub sea_level_rise: f32,
+
+    /// The starting ocean acidification, as the drop
+    /// in the oceans' pH since pre-industrial times.
+    #[serde(default = "default_acidification")]
+    pub acidification: f32,
+
+    /// CO2 the oceans have taken up during the game (Gt).
+    #[serde(default)]
+    pub ocean_uptake: f32,
+
+    /// The health of farmed soils, relative to the start.
+    /// Crops grow better or worse as it changes.
+    #[serde(default = "default_soil_health")]
+    pub soil_health: f32,
+
+    /// Particulates in the air (Mt). This is settled at
+    /// what emissions keep it at when the game starts.
+    #[serde(default)]
+    pub particulates: f32,
+
+    /// The starting plastic in the environment (Mt).
+    #[serde(default = "default_plastic")]
+    pub plastic: f32,
+
     pub temperature_modifier: f32,
     pub population_growth_modifier: f32,
     pub sea_level_rise_modifier: f32, // meters
@@ -41,9 +99,24 @@ pub struct World {
 
     pub regions: Collection<Region>,
     pub per_capita_demand: [OutputDemand; 4],
+
+    /// Per-capita municipal/household water demand
+    /// by income level, in L/month.
     pub water_by_income: [f32; 4],
+
+    /// The share of agricultural and industrial water
+    /// demand saved by efficiency, e.g. better irrigation.
+    #[serde(default)]
+    pub water_efficiency: f32,
+
+    /// Per-capita material intensity by income level in
+    /// metric tons/year, though the units are less important
+    /// as these values are used for scaling.
     pub materials_by_income: [f32; 4],
-    pub income_pop_coefs: [[f32; 4]; 4],
+
+    /// Birth and death rates by income level.
+    #[serde(default = "default_demographics")]
+    pub demographics_by_income: [Demographics; 4],
 
     pub industries: Collection<Industry>,
     pub projects: Collection<Project>,
@@ -51,8 +124,81 @@ pub struct World {
     pub project_lockers: BTreeMap<Id, Id>,
     pub events: Collection<Event>,
 
+    /// Variables for the world's own mechanics,
+    /// changed by effects and read by conditions.
+    #[serde(default)]
+    pub variables: Collection<Variable>,
+
+    /// Climate tipping points, which are crossed with some
+    /// chance once the temperature is above their thresholds.
+    #[serde(default = "default_tipping_points")]
+    pub tipping_points: Collection<TippingPoint>,
+
+    /// How often extreme weather strikes as the world warms,
+    /// relative to the default. At zero, disasters only
+    /// occur from their events' own probabilities.
+    #[serde(default = "default_disaster_rate")]
+    pub disaster_rate: f32,
+
+    /// How often disease outbreaks occur, relative to the
+    /// default. At zero, outbreaks only occur from their
+    /// events' own probabilities.
+    #[serde(default = "default_outbreak_rate")]
+    pub outbreak_rate: f32,
+
+    /// How prepared the world is for disease outbreaks, from
+    /// 0 to 1. Preparedness makes them rarer, milder and
+    /// shorter.
+    #[serde(default)]
+    pub preparedness: f32,
+
+    /// The starting feedstock reserves.
     pub feedstock_reserves: FeedstockMap,
+
+    /// The starting resource availability.
     pub starting_resources: ResourceMap,
+
+    /// Translations of the world's text, keyed by locale
+    /// and then by the source (English) text, the same
+    /// way the game's own phrases are translated.
+    #[serde(default)]
+    pub translations:
+        BTreeMap<String, BTreeMap<String, String>>,
+
+    /// The world's content hash and signature,
+    /// as of when it was last exported.
+    #[serde(default)]
+    pub integrity: Option<Integrity>,
+}
+
+/// Information about a world, for players
+/// deciding whether to play it.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    EditorForm,
+)]
+pub struct Metadata {
+    /// The world's name.
+    pub title: String,
+
+    /// Who made the world.
+    pub author: String,
+
+    /// The world's own version, e.g. "1.2".
+    pub version: String,
+
+    /// What sets the world apart.
+    #[form(skip)]
+    pub description: String,
+
+    /// What the world can be shared and modified
+    /// under, e.g. "CC BY-SA 4.0".
+    pub license: String,
 }
 
 impl Default for World {
@@ -65,15 +211,54 @@ impl Default for World {
 }
 
 impl World {
+    pub const EXTINCTION_RATE_BOUNDS: Bounds =
+        Bounds::NON_NEGATIVE;
+    pub const SEA_LEVEL_RISE_BOUNDS: Bounds =
+        Bounds::NON_NEGATIVE;
+    pub const ACIDIFICATION_BOUNDS: Bounds =
+        Bounds::NON_NEGATIVE;
+    pub const DISASTER_RATE_BOUNDS: Bounds =
+        Bounds::NON_NEGATIVE;
+    pub const OUTBREAK_RATE_BOUNDS: Bounds =
+        Bounds::NON_NEGATIVE;
+    pub const PREPAREDNESS_BOUNDS: Bounds = Bounds::FRACTION;
+    pub const WATER_EFFICIENCY_BOUNDS: Bounds = Bounds::FRACTION;
+    pub const SOIL_HEALTH_BOUNDS: Bounds = Bounds::NON_NEGATIVE;
+    pub const PLASTIC_BOUNDS: Bounds = Bounds::NON_NEGATIVE;
+
     pub fn update_climate(&mut self, tgav: f32) -> f32 {
         let prev_temp = self.temperature;
         self.temperature = tgav + self.temperature_modifier;
         let temp_change = prev_temp - self.temperature;
         self.regions.update_climates(tgav);
+        self.update_regional_warming();
         self.sea_level_rise += self.sea_level_rise_rate();
+        self.regions.update_flooding(self.sea_level_rise);
         temp_change
     }
 
+    /// Update the regions' temperature anomalies from
+    /// the global one. Crops are less productive
+    /// the more the regions they're grown in warm.
+    fn update_regional_warming(&mut self) {
+        let prev_loss = self.regions.crop_yield_loss();
+        let temperature = self.temperature;
+        for region in self.regions.iter_mut() {
+            region.temp_anomaly =
+                region.anomaly_at(temperature);
+        }
+        let change = self.regions.crop_yield_loss() - prev_loss;
+        for process in self.processes.iter_mut() {
+            if process.output == Output::PlantCalories
+                && !process
+                    .features
+                    .contains(&ProcessFeature::IsMarine)
+            {
+                process.output_modifier -= change;
+            }
+        }
+    }
+
     pub fn update_outlook(
         &mut self,
         temp_change: f32,
@@ -97,13 +282,11 @@ impl World {
     }
 
     pub fn update_populations(&mut self) {
-        for region in self.regions.iter_mut() {
-            region.update_pop(
-                self.year as f32,
-                1. + self.population_growth_modifier,
-                &self.income_pop_coefs,
-            );
-        }
+        let modifier = 1. + self.population_growth_modifier;
+        let demographics = &self.demographics_by_income;
+        par::for_each_mut(&mut self.regions, |region| {
+            region.update_demographics(demographics, modifier);
+        });
     }
 
     pub fn update_extinction_rate(
@@ -111,24 +294,27 @@ impl World {
         produced_by_process: &BTreeMap<Id, f32>,
     ) {
         let base = self.tgav_extinction_rate()
-            + self.slr_extinction_rate();
+            + self.slr_extinction_rate()
+            + self.acidification_extinction_rate()
+            + self.land_use_extinction_rate()
+            + self.plastic_extinction_rate();
         let lic_pop = self.lic_population();
+        let land = self.starting_resources.land;
         let from_processes =
-            self.processes.iter().fold(0., |acc, p| {
+            par::map(&self.processes, |p| {
                 let amount = produced_by_process
                     .get(&p.id)
                     .unwrap_or(&0.);
-                let contrib = p.extinction_rate(
-                    self.starting_resources.land,
-                ) * amount;
-                acc + contrib
-            });
+                p.extinction_rate(land) * amount
+            })
+            .into_iter()
+            .fold(0., |acc, contrib| acc + contrib);
         let from_industries =
-            self.industries.iter().fold(0., |acc, ind| {
-                acc + ind.extinction_rate(
-                    self.starting_resources.land,
-                ) * ind.demand(lic_pop)
-            });
+            par::map(&self.industries, |ind| {
+                ind.extinction_rate(land) * ind.demand(lic_pop)
+            })
+            .into_iter()
+            .fold(0., |acc, contrib| acc + contrib);
         let rate = base + from_industries + from_processes;
         self.extinction_rate = rate;
     }
@@ -160,13 +346,14 @@ impl World {
     }
 
     pub fn region_demand(&self) -> OutputMap {
-        self.regions.iter().fold(
-            outputs!(),
-            |mut acc, region| {
-                acc += region.demand(&self.per_capita_demand);
-                acc
-            },
-        )
+        par::map(&self.regions, |region| {
+            region.demand(&self.per_capita_demand)
+        })
+        .into_iter()
+        .fold(outputs!(), |mut acc, demand| {
+            acc += demand;
+            acc
+        })
     }
 
     pub fn demand_by_income_levels(
@@ -180,6 +367,105 @@ impl World {
             .try_into()
             .expect("Mapping from same size arrays")
     }
+
+    /// All the text in the world that's shown to
+    /// the player and so should be translated.
+    pub fn translatable_strings(&self) -> Vec<String> {
+        let mut strings = BTreeSet::new();
+        for region in self.regions.iter() {
+            strings.insert(&region.name);
+            strings.insert(&region.flavor.image.alt);
+        }
+        for industry in self.industries.iter() {
+            strings.insert(&industry.name);
+            strings.insert(&industry.flavor.description);
+            strings.insert(&industry.flavor.image.alt);
+        }
+        for process in self.processes.iter() {
+            strings.insert(&process.name);
+            strings.insert(&process.flavor.description);
+            strings.insert(&process.flavor.image.alt);
+        }
+        for project in self.projects.iter() {
+            strings.insert(&project.name);
+            strings.insert(&project.flavor.description);
+            strings.insert(&project.flavor.image.alt);
+        }
+        for event in self.events.iter() {
+            strings.insert(&event.name);
+            strings.insert(&event.flavor.arc);
+            for image in event
+                .flavor
+                .image
+                .iter()
+                .chain(&event.flavor.variants)
+            {
+                strings.insert(&image.alt);
+            }
+            for line in &event.flavor.dialogue.lines {
+                strings.insert(&line.text);
+                if let Some(DialogueNext::Responses(
+                    responses,
+                )) = &line.next
+                {
+                    for response in responses {
+                        strings.insert(&response.text);
+                    }
+                }
+            }
+        }
+        strings
+            .into_iter()
+            .filter(|s| !s.trim().is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// How many of the world's strings are
+    /// missing a translation for the locale.
+    pub fn missing_translations(&self, locale: &str) -> usize {
+        let translations = self.translations.get(locale);
+        self.translatable_strings()
+            .iter()
+            .filter(|s| {
+                translations
+                    .and_then(|t| t.get(*s))
+                    .is_none_or(|t| t.trim().is_empty())
+            })
+            .count()
+    }
+
+    /// The world as it's exported, stamped with the
+    /// current format version.
+    ///
+    /// Industries, projects, processes, events, variables
+    /// and tipping points are sorted by id so that the same
+    /// world always exports the same way, and editing it
+    /// only changes the lines that were actually edited.
+    /// Regions keep their order as it's significant.
+    pub(crate) fn canonical(&self) -> World {
+        let mut world = self.clone();
+        world.version = WORLD_VERSION;
+        world.industries.sort_by_id();
+        world.projects.sort_by_id();
+        world.processes.sort_by_id();
+        world.events.sort_by_id();
+        world.variables.sort_by_id();
+        world.tipping_points.sort_by_id();
+        world
+    }
+
+    /// Serialize the world for export, with a
+    /// hash of its content (see `World::seal`).
+    pub fn to_export_json(&self) -> String {
+        let mut world = self.canonical();
+        world.seal();
+
+        let mut json = serde_json::to_string_pretty(&world)
+            .expect("Worlds are always serializable");
+        json.push('\n');
+        json
+    }
 }
 
 impl Collection<Region> {
@@ -211,17 +497,35 @@ impl Collection<Region> {
         self.iter().map(|r| r.population).sum()
     }
 
+    /// Flood coastal land as the sea rises. People displaced
+    /// by it migrate, spread across the regions.
+    fn update_flooding(&mut self, sea_level_rise: f32) {
+        let displaced: f32 = self
+            .iter_mut()
+            .map(|region| region.flood(sea_level_rise))
+            .sum();
+        if displaced > 0. {
+            self.change_population(displaced);
+        }
+    }
+
+    /// Mean share of the regions' land that's been flooded.
+    pub fn flooded(&self) -> f32 {
+        self.iter().map(|r| r.flooded).sum::<f32>()
+            / self.len() as f32
+    }
+
     fn update_outlook(
         &mut self,
         wretched_ally: bool,
         consumerist_ally: bool,
     ) {
-        for region in self.iter_mut() {
+        par::for_each_mut(self, |region| {
             region.update_outlook(
                 wretched_ally,
                 consumerist_ally,
             );
-        }
+        });
     }
 
     fn update_climates(&mut self, temp: f32) {
@@ -240,6 +544,19 @@ impl Collection<Region> {
         }
     }
 
+    /// Share of crop yields lost to regional
+    /// warming, weighted by population.
+    pub fn crop_yield_loss(&self) -> f32 {
+        let population = self.population();
+        if population <= 0. {
+            return 0.;
+        }
+        self.iter()
+            .map(|r| r.crop_yield_loss() * r.population)
+            .sum::<f32>()
+            / population
+    }
+
     /// Mean outlook of all regions.
     pub fn outlook(&self) -> f32 {
         self.iter().map(|r| r.outlook).sum::<f32>()
@@ -271,3 +588,118 @@ impl Collection<Region> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::regions::Latitude;
+
+    #[test]
+    fn test_missing_translations() {
+        let mut world = World::default();
+        let strings = world.translatable_strings();
+        assert!(!strings.is_empty());
+        assert_eq!(
+            world.missing_translations("es"),
+            strings.len()
+        );
+
+        let phrases =
+            world.translations.entry("es".into()).or_default();
+        phrases.insert(strings[0].clone(), "traducción".into());
+        phrases.insert(strings[1].clone(), " ".into());
+        assert_eq!(
+            world.missing_translations("es"),
+            strings.len() - 1
+        );
+    }
+
+    #[test]
+    fn test_export_is_stable() {
+        let world = World::default();
+        let mut reordered = world.clone();
+        reordered.projects =
+            world.projects.iter().rev().cloned().collect();
+        reordered.events =
+            world.events.iter().rev().cloned().collect();
+        reordered.tipping_points = world
+            .tipping_points
+            .iter()
+            .rev()
+            .cloned()
+            .collect();
+        assert_eq!(
+            world.to_export_json(),
+            reordered.to_export_json()
+        );
+
+        let exported = world.to_export_json();
+        let reimported: World =
+            serde_json::from_str(&exported).unwrap();
+        assert_eq!(reimported.to_export_json(), exported);
+    }
+
+    #[test]
+    fn test_sea_level_flooding() {
+        let mut world = World::default();
+        for region in world.regions.iter_mut() {
+            region.coastal_exposure = 0.;
+        }
+        let coastal = world.regions.by_idx_mut(0);
+        coastal.coastal_exposure = 0.5;
+        let id = coastal.id;
+        let habitability = coastal.habitability();
+        let outlook = coastal.outlook;
+        let population = world.regions.population();
+
+        world.sea_level_rise = 1.;
+        world.regions.update_flooding(world.sea_level_rise);
+        let coastal = &world.regions[&id];
+        assert_eq!(coastal.flooded, 0.5);
+        assert!(coastal.habitability() < habitability);
+        assert!(coastal.outlook < outlook);
+        assert_eq!(
+            world.regions.flooded(),
+            0.5 / world.regions.len() as f32
+        );
+
+        // The displaced move elsewhere.
+        let total = world.regions.population();
+        assert!((total - population).abs() / population < 1e-4);
+
+        // Land that's flooded stays flooded.
+        world.regions.update_flooding(0.5);
+        assert_eq!(world.regions[&id].flooded, 0.5);
+    }
+
+    #[test]
+    fn test_regional_warming() {
+        let mut world = World::default();
+        let tropic = world.regions.by_idx_mut(0);
+        tropic.latitude = Latitude::Tropic;
+        let tropic = tropic.id;
+        let arctic = world.regions.by_idx_mut(1);
+        arctic.latitude = Latitude::Frigid;
+        let arctic = arctic.id;
+        let crops = world
+            .processes
+            .iter()
+            .find(|p| p.output == Output::PlantCalories)
+            .map(|p| (p.id, p.output_modifier))
+            .unwrap();
+        world.update_regional_warming();
+        let habitability =
+            world.regions[&arctic].habitability();
+
+        world.temperature = 4.;
+        world.update_regional_warming();
+        let (tropic, arctic) =
+            (&world.regions[&tropic], &world.regions[&arctic]);
+        assert!(tropic.temp_anomaly > 0.);
+        assert!(arctic.temp_anomaly > tropic.temp_anomaly * 2.);
+        assert!(arctic.habitability() < habitability);
+        assert!(
+            world.processes[&crops.0].output_modifier < crops.1
+        );
+    }
+}
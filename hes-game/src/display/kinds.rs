@@ -131,6 +131,12 @@ impl AsText for ProcessFeature {
             ProcessFeature::IsLaborIntensive => {
                 "This process is especially labor-intensive."
             }
+            ProcessFeature::IsMarine => {
+                "This process relies on the oceans."
+            }
+            ProcessFeature::IsRegenerative => {
+                "This process builds up the health of the soil."
+            }
         }
     }
 
@@ -164,6 +170,10 @@ impl AsText for ProcessFeature {
             ProcessFeature::UsesSynFertilizer => {
                 "processes that use synthetic fertilizers"
             }
+            ProcessFeature::IsMarine => "marine processes",
+            ProcessFeature::IsRegenerative => {
+                "regenerative processes"
+            }
         }
     }
 }
@@ -195,6 +205,8 @@ impl AsText for Byproduct {
             Byproduct::Ch4 => "CH4",
             Byproduct::N2o => "N2O",
             Byproduct::Biodiversity => "biodiversity",
+            Byproduct::Particulates => "particulates",
+            Byproduct::Plastic => "plastic",
         }
     }
 
@@ -204,6 +216,8 @@ impl AsText for Byproduct {
             Byproduct::Ch4 => "CH4",
             Byproduct::N2o => "N2O",
             Byproduct::Biodiversity => "Biodiversity",
+            Byproduct::Particulates => "Particulates",
+            Byproduct::Plastic => "Plastic",
         }
     }
 }
@@ -10,7 +10,7 @@ use leptos_use::{
 };
 use std::{collections::BTreeMap, rc::Rc};
 
-use crate::state::Settings;
+use crate::state::{Settings, WORLD_TRANSLATIONS};
 
 const DEFAULT_LANGUAGE: &str = "en";
 pub const AVAILABLE_LANGUAGES: &[&str] = &[
@@ -64,6 +64,22 @@ macro_rules! t {
 
 pub fn t(s: &str) -> String {
     if let Some(lang) = use_context::<Rc<Language>>() {
+        // Translations from the world take precedence,
+        // as custom worlds may have their own text.
+        let from_world = WORLD_TRANSLATIONS
+            .read()
+            .ok()
+            .and_then(|translations| {
+                translations
+                    .get(lang.locale)?
+                    .get(s)
+                    .filter(|t| !t.is_empty())
+                    .cloned()
+            });
+        if let Some(translated) = from_world {
+            return translated;
+        }
+
         match &lang.phrases {
             None => s.to_string(),
             Some(phrases) => phrases
@@ -78,6 +78,8 @@ icons! {
     CO2: "/icons/emissions.png",
     N2O: "/icons/emissions.png",
     CH4: "/icons/emissions.png",
+    PARTICULATES: "/icons/emissions.png",
+    PLASTIC: "/icons/extinction.png",
     WARMING: "/icons/warming.png",
     CONTENTEDNESS: "/icons/contentedness.png",
     EXTINCTION_RATE: "/icons/extinction.png",
@@ -116,6 +118,7 @@ icons! {
     UNLOCKS: "/icons/unlocks.png",
     LOCKS: "/icons/locks.png",
     PROTECT: "/icons/protect.png",
+    PREPAREDNESS: "/icons/protect.png",
     CHANCE: "/icons/chance.png",
     COST: "/icons/cost.png",
     REQUEST: "/icons/request.png",
@@ -198,6 +201,8 @@ icons! {
     USES_LIVESTOCK: "/icons/features/uses_livestock.png",
     USES_PESTICIDES: "/icons/features/uses_pesticides.png",
     USES_SYN_FERTILIZER: "/icons/features/uses_syn_fertilizer.png",
+    IS_MARINE: "/icons/ocean.png",
+    IS_REGENERATIVE: "/icons/feedstocks/soil.png",
 }
 
 pub trait HasIcon {
@@ -289,6 +294,8 @@ impl HasIcon for ProcessFeature {
             ProcessFeature::IsLaborIntensive => {
                 IS_LABOR_INTENSIVE
             }
+            ProcessFeature::IsMarine => IS_MARINE,
+            ProcessFeature::IsRegenerative => IS_REGENERATIVE,
         }
     }
 }
@@ -297,6 +304,8 @@ impl HasIcon for Byproduct {
     fn icon(&self) -> &'static str {
         match self {
             Byproduct::Biodiversity => BIODIVERSITY,
+            Byproduct::Particulates => PARTICULATES,
+            Byproduct::Plastic => PLASTIC,
             _ => EMISSIONS,
         }
     }
@@ -352,6 +361,8 @@ impl HasIcon for Condition {
                 LocalVariable::Outlook => CONTENTEDNESS,
                 LocalVariable::Habitability => HABITABILITY,
                 LocalVariable::Population => POPULATION,
+                LocalVariable::WaterStress => WATER,
+                LocalVariable::AirPollution => PARTICULATES,
             },
             Condition::WorldVariable(var, ..) => match var {
                 WorldVariable::Temperature => WARMING,
@@ -362,6 +373,10 @@ impl HasIcon for Condition {
                 WorldVariable::Outlook => CONTENTEDNESS,
                 WorldVariable::Emissions => EMISSIONS,
                 WorldVariable::Precipitation => PRECIPITATION,
+                WorldVariable::Acidification => OCEAN,
+                WorldVariable::WaterEfficiency => WATER,
+                WorldVariable::SoilHealth => SOIL,
+                WorldVariable::Preparedness => PREPAREDNESS,
                 WorldVariable::Population => POPULATION,
                 WorldVariable::PopulationGrowth => POPULATION,
                 WorldVariable::ExtinctionRate => {
@@ -91,7 +91,9 @@ pub impl State {
         } else {
             let cost = self.next_point_cost(&kind) as isize;
             if cost <= self.political_capital {
-                self.change_political_capital(-cost);
+                self.decide(Decision::ChangePoliticalCapital(
+                    -cost,
+                ));
                 match kind {
                     ProjectType::Research => {
                         points.research += 1
@@ -119,9 +121,9 @@ pub impl State {
         if project.status == Status::Inactive
             && available >= project.cost as isize
         {
-            self.change_political_capital(
+            self.decide(Decision::ChangePoliticalCapital(
                 -(project.cost as isize),
-            );
+            ));
             true
         } else {
             false
@@ -143,9 +145,14 @@ pub impl State {
             ProjectType::Policy => return,
         };
         if *points > 0 && cur_points < consts::MAX_POINTS {
-            self.set_project_points(project_id, cur_points + 1);
+            self.decide(Decision::SetProjectPoints(
+                *project_id,
+                cur_points + 1,
+            ));
             if status != Status::Building {
-                self.start_project(project_id);
+                self.decide(Decision::StartProject(
+                    *project_id,
+                ));
             }
             *points -= 1;
         }
@@ -161,9 +168,12 @@ pub impl State {
             (project.points, project.status)
         };
         let new_points = current_points - points;
-        self.set_project_points(project_id, new_points);
+        self.decide(Decision::SetProjectPoints(
+            *project_id,
+            new_points,
+        ));
         if status == Status::Building && new_points == 0 {
-            self.stop_project(project_id);
+            self.decide(Decision::StopProject(*project_id));
         }
     }
 
@@ -173,7 +183,7 @@ pub impl State {
             project.kind
         };
         if kind == ProjectType::Policy {
-            self.start_project(project_id);
+            self.decide(Decision::StartProject(*project_id));
         }
     }
 
@@ -184,8 +194,10 @@ pub impl State {
         };
 
         if kind == ProjectType::Policy {
-            self.change_political_capital(cost as isize);
-            self.stop_project(project_id);
+            self.decide(Decision::ChangePoliticalCapital(
+                cost as isize,
+            ));
+            self.decide(Decision::StopProject(*project_id));
         }
     }
 
@@ -203,8 +215,10 @@ pub impl State {
             let available = self.political_capital;
             if is_free || available >= upgrade.cost as isize {
                 if !is_free {
-                    self.change_political_capital(
-                        -(upgrade.cost as isize),
+                    self.decide(
+                        Decision::ChangePoliticalCapital(
+                            -(upgrade.cost as isize),
+                        ),
                     );
                 }
             }
@@ -212,7 +226,9 @@ pub impl State {
             match kind {
                 // Policies upgraded instantly
                 ProjectType::Policy => {
-                    self.upgrade_project(project_id);
+                    self.decide(Decision::UpgradeProject(
+                        *project_id,
+                    ));
                 }
                 _ => {
                     queued_upgrades.insert(*project_id, true);
@@ -235,11 +251,13 @@ pub impl State {
         };
 
         if let Some(upgrade) = prev_upgrade {
-            self.change_political_capital(
+            self.decide(Decision::ChangePoliticalCapital(
                 upgrade.cost as isize,
-            );
+            ));
             if kind == ProjectType::Policy {
-                self.downgrade_project(project_id);
+                self.decide(Decision::DowngradeProject(
+                    *project_id,
+                ));
             } else {
                 queued_upgrades.insert(*project_id, false);
             }
@@ -271,7 +289,7 @@ pub impl State {
         for (id, queued) in upgrades.iter_mut() {
             if *queued {
                 *queued = false;
-                self.upgrade_project(id);
+                self.decide(Decision::UpgradeProject(*id));
             }
         }
     }
@@ -285,11 +303,11 @@ pub impl State {
         let effect = event.intensity as f32
             * consts::EVENT_INTENSITY_TO_CONTENTEDNESS;
 
-        self.apply_disaster(
-            -effect.round() as isize,
-            region_id,
-        );
-        self.apply_event(*event_id, Some(*region_id));
+        self.decide(Decision::Disaster {
+            event: *event_id,
+            region: *region_id,
+            intensity: -effect.round() as isize,
+        });
     }
 
     fn update_processes(
@@ -307,16 +325,18 @@ pub impl State {
                 for (process_id, change) in changes.iter_mut() {
                     if *change < 0 && rem_pts > 0 {
                         rem_pts -= 1;
-                        self.change_process_mix_share(
-                            process_id, -1,
-                        );
+                        self.decide(Decision::ChangeMixShare(
+                            *process_id,
+                            -1,
+                        ));
                         total -= 1;
                         *change += 1;
                     } else if *change > 0 && add_pts > 0 {
                         add_pts -= 1;
-                        self.change_process_mix_share(
-                            process_id, 1,
-                        );
+                        self.decide(Decision::ChangeMixShare(
+                            *process_id,
+                            1,
+                        ));
                         total -= 1;
                         *change -= 1;
                     }
@@ -13,10 +13,19 @@ pub use ui::{
     UIState,
 };
 
-use std::sync::{LazyLock, RwLock};
+use std::{
+    collections::BTreeMap,
+    sync::{LazyLock, RwLock},
+};
 
 use enum_map::EnumMap;
-use hes_engine::{Output, OutputMap, State, World};
+use hes_engine::{
+    Output,
+    OutputMap,
+    SavedGame,
+    State,
+    World,
+};
 use leptos::*;
 
 use crate::{
@@ -30,6 +39,12 @@ pub static BASE_OUTPUT_DEMAND: LazyLock<
     RwLock<[OutputMap; 4]>,
 > = LazyLock::new(|| RwLock::new([OutputMap::default(); 4]));
 
+/// Translations provided by the world being played,
+/// keyed by locale and then by source text.
+pub static WORLD_TRANSLATIONS: LazyLock<
+    RwLock<BTreeMap<String, BTreeMap<String, String>>>,
+> = LazyLock::new(|| RwLock::new(BTreeMap::default()));
+
 pub static FACTORS: LazyLock<
     RwLock<EnumMap<Var, Vec<Factor>>>,
 > = LazyLock::new(|| RwLock::new(EnumMap::default()));
@@ -53,16 +68,35 @@ pub fn base_demand_by_income_levels(
         .expect("Mapping from same size arrays")
 }
 
-fn read_save() -> Result<Option<(State, UIState)>, anyhow::Error>
-{
+fn read_save(
+) -> Result<Option<(SavedGame, UIState)>, anyhow::Error> {
     if let Some(storage) = window().local_storage().unwrap() {
         storage
             .get_item(SAVE_KEY)
             .unwrap()
             .map(|ser| {
-                Ok(serde_json::from_str::<(State, UIState)>(
-                    &ser,
-                )?)
+                let (save, ui) = serde_json::from_str::<(
+                    serde_json::Value,
+                    UIState,
+                )>(&ser)?;
+
+                // Saves from before the random number
+                // generator was saved along with the game.
+                if save.get("state").is_none() {
+                    let game = serde_json::from_value(save)?;
+                    return Ok((SavedGame::new(&game), ui));
+                }
+
+                // Upgrade saves from older versions.
+                let (save, notes) = SavedGame::from_value(save)
+                    .map_err(|err| anyhow::anyhow!("{err}"))?;
+                if !notes.is_empty() {
+                    tracing::debug!(
+                        "Upgraded save: {}",
+                        notes.join(" ")
+                    );
+                }
+                Ok((save, ui))
             })
             .transpose()
     } else {
@@ -74,7 +108,8 @@ fn write_save(
     ui: &UIState,
 ) -> Result<(), anyhow::Error> {
     if let Some(storage) = window().local_storage().unwrap() {
-        let ser = serde_json::to_string(&(game, ui))?;
+        let ser =
+            serde_json::to_string(&(SavedGame::new(game), ui))?;
         storage.set_item(SAVE_KEY, &ser).unwrap();
     }
     Ok(())
@@ -126,12 +161,17 @@ fn init_vars(game: &State) {
         .write()
         .expect("Can write to shared value") =
         game.world.per_capita_demand.clone().map(|d| d.base);
+    *WORLD_TRANSLATIONS
+        .write()
+        .expect("Can write to shared value") =
+        game.world.translations.clone();
 }
 
 pub fn load() -> (State, UIState) {
     tracing::debug!("Loading saved game...");
     let save = read_save().unwrap();
-    if let Some((game, mut ui)) = save {
+    if let Some((saved, mut ui)) = save {
+        let game = saved.resume();
         init_vars(&game);
         ui.phase = Phase::Planning;
         (game, ui)
@@ -149,11 +189,11 @@ pub fn has_save() -> bool {
     match read_save() {
         Ok(Some(_)) => true,
         Ok(None) => false,
-        Err(_) => {
-            // May mean something about the serialization
-            // structure changed, so clear to avoid a crash.
+        Err(err) => {
+            // The save couldn't be upgraded to the current
+            // version, so clear it to avoid a crash.
             tracing::debug!(
-                "Failed to deserialize save, clearing."
+                "Failed to deserialize save, clearing: {err}"
             );
             clear_save();
             false
@@ -1,6 +1,6 @@
 use base64::prelude::*;
 use extend::ext;
-use hes_engine::flavor::{Image, ImageData};
+use hes_engine::flavor::{Audio, Image, ImageData};
 use html::ElementDescriptor;
 use leptos::{wasm_bindgen::JsCast, *};
 use leptos_use::use_window;
@@ -148,3 +148,14 @@ pub impl Image {
         }
     }
 }
+
+#[ext]
+pub impl Audio {
+    fn src(&self) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.mime,
+            BASE64_STANDARD.encode(&self.bytes)
+        )
+    }
+}
@@ -20,12 +20,18 @@ pub fn EventCard(
         });
     });
 
+    // Pick between the event's image and its variants.
     let image_info = with!(|event| {
         event.flavor.image.as_ref().map(move |image| {
-            (image.src(), image.attribution.clone())
+            let variants = &event.flavor.variants;
+            let idx = (js_sys::Math::random()
+                * (variants.len() + 1) as f64)
+                .floor() as usize;
+            let image = variants.get(idx).unwrap_or(image);
+            (image.src(), image.credit(), t!(&image.alt))
         })
     });
-    image_info.map(|(image, attrib)| {
+    image_info.map(|(image, attrib, alt)| {
         let (arc, name, factors_list) = with!(|event| {
             let arc = t!(&event.flavor.arc);
             let name = t!(&event.name);
@@ -63,6 +69,7 @@ pub fn EventCard(
                 style:background-image={background}
             >
                 <Help text={factor_tip.get_value()} x=0.55 y=-18.0 center=false/>
+                <div class="visually-hidden">{alt}</div>
                 <div class="arc">{arc}</div>
                 <div class="event--factors">{factors_list}</div>
                 <div class="image-attribution">
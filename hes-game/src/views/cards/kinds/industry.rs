@@ -95,12 +95,11 @@ pub fn IndustryCard(
 
     let image_url =
         move || with!(|industry| industry.flavor.image.src());
+    let image_alt = move || {
+        with!(|industry| t!(&industry.flavor.image.alt))
+    };
     let image_attrib = move || {
-        with!(|industry| industry
-            .flavor
-            .image
-            .attribution
-            .clone())
+        with!(|industry| industry.flavor.image.credit())
     };
 
     let description = move || {
@@ -117,7 +116,7 @@ pub fn IndustryCard(
                 <div>{t!("Sector")}</div>
             </Header>
             <Figure slot>
-                <img class="card-image" src=image_url/>
+                <img class="card-image" src=image_url alt=image_alt/>
             </Figure>
             <Name slot>
                 <div>{name}</div>
@@ -265,6 +265,8 @@ pub fn ProcessCard(
 
     let image =
         move || with!(|process| process.flavor.image.src());
+    let image_alt =
+        move || with!(|process| t!(&process.flavor.image.alt));
 
     let process_excess = move || {
         with!(|process| {
@@ -344,7 +346,7 @@ pub fn ProcessCard(
     };
     let image_attrib = move || {
         with!(|process| {
-            process.flavor.image.attribution.clone()
+            process.flavor.image.credit()
         })
     };
     let process_mix_tip = move || {
@@ -506,7 +508,7 @@ pub fn ProcessCard(
             <Name slot><div ref=name_ref>{name}</div></Name>
 
             <Figure slot>
-                <img class="card-image" src=image/>
+                <img class="card-image" src=image alt=image_alt/>
                 <Show when=move || max_share.get() < 20>
                     <HasTip tip=alert_tip.into_signal()>
                         <div class="process-limit-alert">
@@ -189,6 +189,8 @@ pub fn ProjectCard(
     };
     let image =
         move || with!(|project| project.flavor.image.src());
+    let image_alt =
+        move || with!(|project| t!(&project.flavor.image.alt));
     let has_points = move || {
         with!(|project| {
             project.kind != ProjectType::Policy
@@ -375,7 +377,7 @@ pub fn ProjectCard(
     };
     let image_attrib = move || {
         with!(|project| {
-            project.flavor.image.attribution.clone()
+            project.flavor.image.credit()
         })
     };
 
@@ -436,7 +438,7 @@ pub fn ProjectCard(
                         </div>
                     </div>
                 </Show>
-                <img class="card-image" src=image/>
+                <img class="card-image" src=image alt=image_alt/>
                 <Show when=has_points>
                     <div class="card-tack-ul project-points">
                         {points_display}
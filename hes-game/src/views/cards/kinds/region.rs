@@ -68,11 +68,17 @@ pub fn RegionCard(
     let seceded = move || with!(|region| region.seceded);
     let temp_range =
         move || with!(|region| region.temp_range());
+    let temp_anomaly =
+        move || with!(|region| region.temp_anomaly());
     let precip_range =
         move || with!(|region| region.precip_range());
+    let air_quality =
+        move || with!(|region| region.air_quality());
 
     let image =
         move || with!(|region| region.flavor.image.src());
+    let image_alt =
+        move || with!(|region| t!(&region.flavor.image.alt));
 
     view! {
         <Card class="region">
@@ -82,11 +88,14 @@ pub fn RegionCard(
             </Header>
 
             <Figure slot>
-                <img class="card-image" src=image/>
+                <img class="card-image" src=image alt=image_alt/>
                 <div class="card-tack-ur">
                     <HasTip tip=tip(
                         icons::WARMING,
-                        t!("This region's current temperature range."),
+                        t!(
+                            "This region's current temperature range. It has warmed by {anomaly} since pre-industrial times.",
+                            anomaly : temp_anomaly(),
+                        ),
                     )>
                         <div class="region-stat">
                             <img src=icons::WARMING/>
@@ -106,6 +115,16 @@ pub fn RegionCard(
                             {precip_range}
                         </div>
                     </HasTip>
+                    <br/>
+                    <HasTip tip=tip(
+                        icons::PARTICULATES,
+                        t!("This region's current air pollution. Air dirtier than it was at the start kills people and makes them less content."),
+                    )>
+                        <div class="region-stat">
+                            <img src=icons::PARTICULATES/>
+                            {air_quality}
+                        </div>
+                    </HasTip>
                 </div>
                 <Show when=seceded>
                     <div class="card-tack-cb">Seceded</div>
@@ -31,7 +31,8 @@ pub fn DebugEvents() -> impl IntoView {
                     Some(region.clone())
                 } else {
                     None
-                }
+                },
+                magnitude: event.magnitude.map(|(min, _)| min),
             };
 
             view! {
@@ -39,6 +39,8 @@ impl AsKey for Byproduct {
             Byproduct::N2o => "n2o",
             Byproduct::Ch4 => "ch4",
             Byproduct::Biodiversity => "biodiversity",
+            Byproduct::Particulates => "particulates",
+            Byproduct::Plastic => "plastic",
         }
     }
 }
@@ -87,6 +89,16 @@ macro_rules! text {
         };
     }
 
+/// The variable's description, if it has one.
+fn variable_tip(var: &Variable, text: &str) -> Tip {
+    let desc = if var.description.is_empty() {
+        text.to_string()
+    } else {
+        t!(&var.description)
+    };
+    tip(icons::HELP, desc)
+}
+
 pub fn flag_tip(flag: Flag, demand: &OutputMap) -> Tip {
     let demand = display::outputs(demand);
     match flag {
@@ -408,11 +420,11 @@ impl DisplayEffect {
                 WorldVariable::PopulationGrowth => (
                     tip! {
                         icons::POPULATION,
-                        "The number of people on the planet.",
+                        "The number of people on the planet. Changes to births take a generation to fully show.",
                     },
                     text! {
                         "population",
-                        "{changeDir} global population growth by {amount}<strong>%.</strong>",
+                        "{changeDir} global birth rates by {amount}<strong>%.</strong>",
                         changeDir: self.change_dir(*amount),
                         amount: display::percent(amount.abs(), false)
                     },
@@ -455,6 +467,58 @@ impl DisplayEffect {
                         amount: self.fmt_param(amount * 1000.)
                     },
                 ),
+                WorldVariable::Acidification => (
+                    tip! {
+                        icons::OCEAN,
+                        "Ocean acidification is currently {amount}pH below pre-industrial levels.",
+                        amount: format!("{:.2}", state.world.acidification)
+                    },
+                    text! {
+                        "ocean",
+                        "{changeDir} ocean acidification by {amount}pH.",
+                        changeDir: self.change_dir(*amount),
+                        amount: self.fmt_param(*amount)
+                    },
+                ),
+                WorldVariable::WaterEfficiency => (
+                    tip! {
+                        icons::WATER,
+                        "Efficiency currently saves {amount}% of agricultural and industrial water use.",
+                        amount: format!("{:.0}", state.world.water_efficiency * 100.)
+                    },
+                    text! {
+                        "water",
+                        "{changeDir} water efficiency by {amount}%.",
+                        changeDir: self.change_dir(*amount),
+                        amount: self.fmt_param(amount * 100.)
+                    },
+                ),
+                WorldVariable::SoilHealth => (
+                    tip! {
+                        icons::SOIL,
+                        "Soil health is currently {amount}% of what it was at the start. Crops grow better in healthier soil.",
+                        amount: format!("{:.0}", state.world.soil_health * 100.)
+                    },
+                    text! {
+                        "soil",
+                        "{changeDir} soil health by {amount}%.",
+                        changeDir: self.change_dir(*amount),
+                        amount: self.fmt_param(amount * 100.)
+                    },
+                ),
+                WorldVariable::Preparedness => (
+                    tip! {
+                        icons::PREPAREDNESS,
+                        "The world is currently {amount}% prepared for disease outbreaks. Preparedness makes them rarer, milder and shorter.",
+                        amount: format!("{:.0}", state.world.preparedness * 100.)
+                    },
+                    text! {
+                        "preparedness",
+                        "{changeDir} disease preparedness by {amount}%.",
+                        changeDir: self.change_dir(*amount),
+                        amount: self.fmt_param(amount * 100.)
+                    },
+                ),
                 WorldVariable::Year => return Err(()),
             },
             Effect::PlayerVariable(var, amount) => match var {
@@ -1161,6 +1225,10 @@ impl DisplayEffect {
                     Byproduct::Ch4 => {
                         t!("{type} emissions", type: t!("CH4"))
                     }
+                    Byproduct::Particulates => {
+                        t!("particulate pollution")
+                    }
+                    Byproduct::Plastic => t!("plastic waste"),
                 };
                 let tag = card_tag(&t!(&process.name));
                 let tip_text = if self.is_unknown {
@@ -1188,6 +1256,24 @@ impl DisplayEffect {
                                 fromAmount: current,
                             )
                         }
+                        Byproduct::Particulates
+                        | Byproduct::Plastic => {
+                            // Mt
+                            let current = process
+                                .adj_byproducts()[*byproduct]
+                                * state
+                                    .produced
+                                    .by_process
+                                    .get(id)
+                                    .unwrap_or(&0.)
+                                * 1e-12;
+                            let after = current * (1. + amount);
+                            t!(r#"{fromAmount}Mt to {toAmount}Mt<img src="{icon}">."#,
+                                icon: byproduct.icon(),
+                                toAmount: display::rounded(after),
+                                fromAmount: display::rounded(current),
+                            )
+                        }
                         _ => {
                             let current = process
                                 .adj_byproducts()
@@ -1213,6 +1299,8 @@ impl DisplayEffect {
                         icon: byproduct.icon(),
                         short: match byproduct {
                             Byproduct::Biodiversity => t!("biodiversity pressure"),
+                            Byproduct::Particulates => t!("particulate pollution"),
+                            Byproduct::Plastic => t!("plastic waste"),
                             _ => t!("emissions"),
                         },
                         change: change,
@@ -1223,6 +1311,8 @@ impl DisplayEffect {
                     text! {
                         match byproduct {
                             Byproduct::Biodiversity => "biodiversity",
+                            Byproduct::Particulates => "particulates",
+                            Byproduct::Plastic => "plastic",
                             _ => "emissions",
                         },
                         "{changeDir} {label} for {tag} by <strong>{amount}</strong>.",
@@ -1331,6 +1421,21 @@ impl DisplayEffect {
                     },
                 )
             }
+            Effect::Afforest(amount) => {
+                let forest = state.world.regions.land_use().forest;
+                (
+                    tip! {
+                        icons::LAND,
+                        "Forests take up CO2 and ease biodiversity pressure. They currently cover {forest}% of land.",
+                        forest: display::percent(forest, true),
+                    },
+                    text! {
+                        "land",
+                        "Plant forest on <strong>{percent}%</strong> of unused land.",
+                        percent: display::signed_percent(*amount, true),
+                    },
+                )
+            }
             Effect::Feedstock(feedstock, amount) => {
                 let estimate = match feedstock {
                     Feedstock::Other | Feedstock::Soil => None,
@@ -1431,6 +1536,32 @@ impl DisplayEffect {
                     return Err(());
                 }
             }
+            Effect::SetVariable(id, value) => {
+                let var = &state.world.variables[id];
+                let name = t!(&var.name);
+                let text = match var.kind {
+                    VariableKind::Flag if *value == 0. => {
+                        t!("Clears <strong>{name}</strong>.", name: name)
+                    }
+                    VariableKind::Flag => {
+                        t!("Sets <strong>{name}</strong>.", name: name)
+                    }
+                    _ => t!("Sets {name} to {value}.",
+                    name: name,
+                    value: self.fmt_param(*value),
+                    ),
+                };
+                (variable_tip(var, &text), text)
+            }
+            Effect::ChangeVariable(id, amount) => {
+                let var = &state.world.variables[id];
+                let text = t!("{changeDir} {name} by {amount}.",
+                name: t!(&var.name),
+                amount: self.fmt_param(*amount),
+                changeDir: self.change_dir(*amount),
+                );
+                (variable_tip(var, &text), text)
+            }
             Effect::AddEvent(..)
             | Effect::TriggerEvent(..)
             | Effect::RegionLeave
@@ -1438,7 +1569,9 @@ impl DisplayEffect {
             | Effect::AddRegionFlag(..)
             | Effect::GameOver
             | Effect::BailOut(..)
-            | Effect::NPCRelationship(..) => {
+            | Effect::NPCRelationship(..)
+            | Effect::Script(..)
+            | Effect::Expr(..) => {
                 return Err(());
             }
         };
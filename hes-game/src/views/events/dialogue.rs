@@ -6,7 +6,7 @@ use crate::{
     views::{effects::DisplayEffect, Effects},
 };
 use ev::MouseEvent;
-use hes_engine::{flavor::*, Id, State};
+use hes_engine::{flavor::*, Decision, Id, State};
 use leptos::*;
 use leptos_use::{use_document, use_event_listener};
 use regex_lite::Regex;
@@ -79,11 +79,14 @@ pub fn Dialogue(
 
     let text = move || {
         let line = line.get();
+        let text = game.with(|game| {
+            game.fill_scripts(&t!(&line.text), region_id.get())
+        });
         with!(|context| {
             if context.is_empty() {
-                fill_icons(&t!(&line.text))
+                fill_icons(&text)
             } else {
-                fill_icons(&fill_vars(&t!(&line.text), context))
+                fill_icons(&fill_vars(&text, context))
             }
         })
     };
@@ -225,12 +228,15 @@ pub fn Dialogue(
             // fully support project dialogues with branch effects.
             // So we just assume project dialogues won't have branch effects
             // which, at time of writing, none of them do.
-            if event_id.get().is_some() {
+            if let Some(event) = event_id.get() {
+                let line = line.get_untracked().id;
                 update!(|game| {
-                    game.apply_effects(
-                        &response.effects,
-                        region_id.get(),
-                    );
+                    game.decide(Decision::Respond {
+                        event,
+                        region: region_id.get(),
+                        line,
+                        response: response.id,
+                    });
                 });
             }
 
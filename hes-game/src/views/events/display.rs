@@ -34,8 +34,13 @@ impl DisplayEvent {
         factors.sort();
         factors.dedup();
 
-        let effects = event
-            .effects
+        let effects = state
+            .event_pool
+            .scaled_effects(
+                &event.id,
+                event.region.as_ref().map(|(id, _)| *id),
+                &event.effects,
+            )
             .iter()
             .map(DisplayEffect::from)
             .collect::<Vec<_>>();
@@ -103,6 +108,8 @@ fn describe_condition(
                 ProcessFeature::UsesPesticides => Some(t!("This event is influenced by how much food production uses pesticides.")),
                 ProcessFeature::UsesLivestock => Some(t!("This event is influenced by how much food production uses livestock.")),
                 ProcessFeature::IsIntermittent => Some(t!("This event is influenced by how much energy production is intermittent.")),
+                ProcessFeature::IsMarine => Some(t!("This event is influenced by how much production relies on the oceans.")),
+                ProcessFeature::IsRegenerative => Some(t!("This event is influenced by how much farming is regenerative.")),
                 _ => None
             }
         }
@@ -131,9 +138,24 @@ fn describe_condition(
                 LocalVariable::Habitability => {
                     Some(t!("This event is influenced by the habitability of regions."))
                 }
+                LocalVariable::WaterStress => {
+                    Some(t!("This event is influenced by water stress in regions."))
+                }
+                LocalVariable::AirPollution => {
+                    Some(t!("This event is influenced by air pollution in regions."))
+                }
                 _ => None,
             }
         }
+        Condition::Variable(id, _, _) => {
+            let name = &state.world.variables[id].name;
+            Some(t!("This event is influenced by {name}.", name: t!(name)))
+        }
+        Condition::TippingPointCrossed(id)
+        | Condition::TippingPointNotCrossed(id) => {
+            let name = &state.world.tipping_points[id].name;
+            Some(t!("This event is influenced by {name}.", name: t!(name)))
+        }
         Condition::Demand(output, _, _) => {
             match output {
                 Output::AnimalCalories => Some(t!("This event is influenced by the demand for animal calories.")),
@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 
 use super::{Dialogue, DisplayEvent};
-use crate::views::cards::EventCard;
+use crate::{audio, util::AudioExt, views::cards::EventCard};
 use leptos::*;
 
 #[component]
@@ -19,6 +19,12 @@ pub fn Event(
                     name.to_string(),
                 );
             }
+            if let Some(magnitude) = event.magnitude {
+                ctx.insert(
+                    "magnitude".to_string(),
+                    format!("{magnitude:.1}"),
+                );
+            }
             ctx
         })
     };
@@ -37,6 +43,15 @@ pub fn Event(
         }
     };
 
+    // Play the event's sound, if it has one.
+    create_effect(move |_| {
+        if let Some(sound) =
+            with!(|event| event.flavor.sound.clone())
+        {
+            audio::play_one_shot(&sound.src());
+        }
+    });
+
     view! {
         <div class="event">
             <EventCard event />
@@ -532,6 +532,14 @@ pub fn rank(state: &State) -> EnumMap<Var, Vec<Factor>> {
                         .round(),
                     display: None,
                 });
+                rankings.push(Factor::Event {
+                    name: "Ocean Acidification".into(),
+                    amount: state
+                        .world
+                        .acidification_extinction_rate()
+                        .round(),
+                    display: None,
+                });
             }
             _ => {}
         }
@@ -16,7 +16,7 @@ use crate::{
         HasTip,
     },
 };
-use hes_engine::{EventPhase, NPCRequest, State};
+use hes_engine::{Decision, EventPhase, NPCRequest, State};
 use leptos::*;
 
 pub struct Request {
@@ -304,7 +304,9 @@ pub fn Report() -> impl IntoView {
     let next_phase = move || {
         let pc_change = pc_change();
         game.update_untracked(|game| {
-            game.change_political_capital(pc_change);
+            game.decide(Decision::ChangePoliticalCapital(
+                pc_change,
+            ));
 
             ui.update_untracked(|ui| {
                 // Reset session plan changes
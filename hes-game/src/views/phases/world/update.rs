@@ -96,11 +96,27 @@ fn Update(
                 EngineUpdate::Project { id }
                 | EngineUpdate::Policy { id } => {
                     let proj = &projects[id];
-                    proj.flavor.image.attribution.clone()
+                    proj.flavor.image.credit()
                 }
                 EngineUpdate::Region { id, .. } => {
                     let region = &regions[id];
-                    region.flavor.image.attribution.clone()
+                    region.flavor.image.credit()
+                }
+            }
+        })
+    };
+
+    let image_alt = move || {
+        with!(|projects, regions, update| {
+            match update {
+                EngineUpdate::Project { id }
+                | EngineUpdate::Policy { id } => {
+                    let proj = &projects[id];
+                    t!(&proj.flavor.image.alt)
+                }
+                EngineUpdate::Region { id, .. } => {
+                    let region = &regions[id];
+                    t!(&region.flavor.image.alt)
                 }
             }
         })
@@ -296,6 +312,7 @@ fn Update(
             on:click=try_done
             class:regionup=is_region>
               <div class="event--body">
+                  <div class="visually-hidden">{image_alt}</div>
                   <div class="arc">{title}</div>
                   <div class="image-attribution">
                       {attribution}
@@ -7,7 +7,14 @@ use crate::{
     t,
     views::cards::ProjectCard,
 };
-use hes_engine::{Id, Project, ProjectType, State, Status};
+use hes_engine::{
+    Decision,
+    Id,
+    Project,
+    ProjectType,
+    State,
+    Status,
+};
 use leptos::*;
 
 use super::{
@@ -306,15 +313,15 @@ impl ScannerSpec for ProjectScanner {
                                                 ui.points.research += excess_points as isize;
                                             }
                                             game.unassign_points(&p.id, points);
-                                            game.change_political_capital(refund as isize);
+                                            game.decide(Decision::ChangePoliticalCapital(refund as isize));
                                             changes.points = 0;
                                         }
                                     } else if p.can_downgrade() {
-                                        game.downgrade_project(&p.id);
+                                        game.decide(Decision::DowngradeProject(p.id));
                                         keep_withdrawing = p.level > 0;
                                         changes.downgrades += 1;
                                     } else {
-                                        game.stop_project(&p.id);
+                                        game.decide(Decision::StopProject(p.id));
                                         changes.withdrawn = true;
                                     }
                                     changed = true;
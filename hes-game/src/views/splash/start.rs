@@ -6,7 +6,7 @@ use crate::{
     t,
     util::is_steam,
 };
-use hes_engine::{State, World};
+use hes_engine::{Metadata, State, Verification, World};
 use leptos::*;
 use std::rc::Rc;
 use wasm_bindgen::{closure::Closure, JsCast};
@@ -26,6 +26,64 @@ impl WorldStatus {
     }
 }
 
+/// Details about a custom world, from its metadata.
+fn world_metadata(meta: &Metadata) -> impl IntoView {
+    let byline = [&meta.version, &meta.author, &meta.license]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let title = (!meta.title.is_empty()).then(|| {
+        view! { <div class="world-title">{meta.title.clone()}</div> }
+    });
+    let byline = (!byline.is_empty()).then(|| {
+        view! { <div class="world-byline">{byline}</div> }
+    });
+    let description = (!meta.description.is_empty()).then(|| {
+        view! { <p>{meta.description.clone()}</p> }
+    });
+    view! {
+        <div class="world-metadata">
+            {title}
+            {byline}
+            {description}
+        </div>
+    }
+}
+
+/// Whether a custom world matches its hash, and the
+/// hash itself so that it can be included in bug reports.
+fn world_integrity(verification: &Verification) -> impl IntoView {
+    let (hash, note) = match verification {
+        Verification::Unhashed => return None,
+        Verification::Valid { hash, signer: None } => {
+            (hash.clone(), None)
+        }
+        Verification::Valid {
+            hash,
+            signer: Some(key),
+        } => (
+            hash.clone(),
+            Some(t!("Signed by {key}.", key: &key[..12])),
+        ),
+        Verification::Corrupted { actual, .. } => (
+            actual.clone(),
+            Some(t!("This world doesn't match its hash, so it may be corrupted.")),
+        ),
+        Verification::BadSignature { hash } => (
+            hash.clone(),
+            Some(t!("This world's signature is invalid, so it may have been tampered with.")),
+        ),
+    };
+    Some(view! {
+        <div class="world-integrity">
+            <div class="world-hash">{t!("Hash")}: {hash}</div>
+            {note.map(|note| view! { <div>{note}</div> })}
+        </div>
+    })
+}
+
 #[component]
 pub fn Start(
     #[prop(into)] on_ready: Callback<()>,
@@ -45,6 +103,7 @@ pub fn Start(
     let game = expect_context::<RwSignal<State>>();
     let ui = expect_context::<RwSignal<UIState>>();
     let world = create_rw_signal(WorldStatus::Default);
+    let verification = create_rw_signal(None::<Verification>);
 
     // Show git commit for this build.
     let git_hash = env!("GIT_HASH");
@@ -140,19 +199,21 @@ pub fn Start(
                                                 let reader_clone = reader.clone();
                                                 let onloadend = Closure::wrap(Box::new(move || {
                                                     if let Ok(result) = reader_clone.result() {
-                                                        if let Some(text) = result.as_string() {
-                                                            let w = serde_json::from_str::<World>(&text);
-                                                            if let Ok(w) = w {
-                                                                world.set(WorldStatus::Custom(name.clone(), w));
-                                                            } else {
-                                                                world.set(WorldStatus::FailedToParse);
-                                                            }
+                                                        // Worlds may be JSON or binary.
+                                                        let bytes = js_sys::Uint8Array::new(&result).to_vec();
+                                                        let w = World::load(&bytes);
+                                                        if let Ok((w, migrated)) = w {
+                                                            // Upgraded worlds no longer match their hash.
+                                                            verification.set(migrated.is_empty().then(|| w.verify()));
+                                                            world.set(WorldStatus::Custom(name.clone(), w));
+                                                        } else {
+                                                            world.set(WorldStatus::FailedToParse);
                                                         }
                                                     }
                                                 }) as Box<dyn Fn()>);
 
                                                 reader.set_onloadend(Some(onloadend.as_ref().unchecked_ref()));
-                                                reader.read_as_text(&file).unwrap();
+                                                reader.read_as_array_buffer(&file).unwrap();
                                                 onloadend.forget();
                                             }
                                         }
@@ -170,6 +231,13 @@ pub fn Start(
                                     </span>
                                 </label>
                                 <div class="world-details">
+                                    {move || with!(|world| match world {
+                                        WorldStatus::Custom(_, world) => {
+                                            Some(world_metadata(&world.metadata))
+                                        }
+                                        _ => None,
+                                    })}
+                                    {move || verification.with(|v| v.as_ref().map(world_integrity))}
                                     {t!("Click to load a custom world.")}<br />
                                     {t!("New worlds can be made using the editor.")}<br />
                                     {t!("Warning: Custom worlds may break parts of the game!")}
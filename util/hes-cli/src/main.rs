@@ -0,0 +1,433 @@
+mod policy;
+
+use std::{fs, process::ExitCode};
+
+use hes_engine::{
+    Batch,
+    BatchSummary,
+    Decision,
+    Distribution,
+    Parameter,
+    Patch,
+    Replay,
+    Rng,
+    Sweep,
+    SweepRow,
+    World,
+    YearReport,
+};
+
+const USAGE: &str = "\
+Usage: hes-cli [batch|sweep] <world> [options]
+
+Runs a world without the game, printing a summary of each year.
+With `batch`, runs it many times with different seeds instead
+and summarizes how the runs turned out. With `sweep`, runs a
+batch for every combination of the `--param` values.
+
+Options:
+    --years <n>        How many years to run (default: 10)
+    --seed <n>         The run's seed, to reproduce it, or the first
+                       of a batch's seeds (default: random)
+    --runs <n>         How many times to run a batch (default: 100),
+                       or each of a sweep's combinations (default: 10)
+    --param <path>=<from>:<to>:<steps>
+                       A world parameter for a sweep to vary, e.g.
+                       processes/Solar PV/byproducts/co2=0:10:5
+    --patch <file>     A patch (e.g. a mod) to apply to the world;
+                       can be given more than once, applied in order
+    --policy <file>    A policy script of decisions to make during the run
+    --out <file>       Also write each year's full report, or a
+                       batch's or sweep's outcomes, to a JSON file
+";
+
+const DEFAULT_YEARS: usize = 10;
+const DEFAULT_BATCH_RUNS: usize = 100;
+const DEFAULT_SWEEP_RUNS: usize = 10;
+
+enum Command {
+    Run,
+    Batch,
+    Sweep,
+}
+
+struct Args {
+    command: Command,
+    world: String,
+    years: usize,
+    runs: Option<usize>,
+    params: Vec<Parameter>,
+    patches: Vec<String>,
+    seed: Option<u64>,
+    policy: Option<String>,
+    out: Option<String>,
+}
+
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Args, String> {
+    let mut command = Command::Run;
+    let mut world = None;
+    let mut years = DEFAULT_YEARS;
+    let mut runs = None;
+    let mut params = vec![];
+    let mut patches = vec![];
+    let mut seed = None;
+    let mut policy = None;
+    let mut out = None;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("{arg} needs a value."))
+        };
+        match arg.as_str() {
+            "--years" => {
+                years = value()?.parse().map_err(|_| {
+                    "--years must be a whole number."
+                        .to_string()
+                })?;
+            }
+            "--seed" => {
+                seed =
+                    Some(value()?.parse().map_err(|_| {
+                        "--seed must be a whole number."
+                            .to_string()
+                    })?);
+            }
+            "--runs" => {
+                runs =
+                    Some(value()?.parse().map_err(|_| {
+                        "--runs must be a whole number."
+                            .to_string()
+                    })?);
+            }
+            "--param" => params.push(parse_param(&value()?)?),
+            "--patch" => patches.push(value()?),
+            "--policy" => policy = Some(value()?),
+            "--out" => out = Some(value()?),
+            _ if arg.starts_with("--") => {
+                return Err(format!("Unknown option {arg}."));
+            }
+            "batch" if world.is_none() => {
+                command = Command::Batch
+            }
+            "sweep" if world.is_none() => {
+                command = Command::Sweep
+            }
+            _ if world.is_none() => world = Some(arg),
+            _ => {
+                return Err(format!(
+                    "Unexpected argument {arg}."
+                ))
+            }
+        }
+    }
+    Ok(Args {
+        command,
+        world: world.ok_or("A world file is required.")?,
+        years,
+        runs,
+        params,
+        patches,
+        seed,
+        policy,
+        out,
+    })
+}
+
+/// Parse a sweep parameter, as `<path>=<from>:<to>:<steps>`.
+fn parse_param(param: &str) -> Result<Parameter, String> {
+    let invalid = || {
+        format!(
+            "--param {param} should be <path>=<from>:<to>:<steps>."
+        )
+    };
+    let (path, range) =
+        param.rsplit_once('=').ok_or_else(invalid)?;
+    let mut parts = range.split(':');
+    let (Some(from), Some(to), Some(steps), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return Err(invalid());
+    };
+    Ok(Parameter::range(
+        path,
+        from.parse().map_err(|_| invalid())?,
+        to.parse().map_err(|_| invalid())?,
+        steps.parse().map_err(|_| invalid())?,
+    ))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    let result = parse_args(args.into_iter())
+        .map_err(|err| format!("{err}\n\n{USAGE}"))
+        .and_then(run);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read(path: &str) -> Result<Vec<u8>, String> {
+    fs::read(path)
+        .map_err(|err| format!("Couldn't read {path}: {err}"))
+}
+
+fn write(
+    path: &str,
+    value: &impl serde::Serialize,
+) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .expect("Reports are always serializable");
+    fs::write(path, json).map_err(|err| {
+        format!("Couldn't write {path}: {err}")
+    })?;
+    eprintln!("Wrote {path}.");
+    Ok(())
+}
+
+/// Apply the patches to the world, in order.
+fn patch(
+    world: World,
+    paths: &[String],
+) -> Result<World, String> {
+    if paths.is_empty() {
+        return Ok(world);
+    }
+    let patches = paths
+        .iter()
+        .map(|path| {
+            serde_json::from_slice::<Patch>(&read(path)?)
+                .map_err(|err| {
+                    format!("{path} isn't a valid patch: {err}")
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (world, conflicts) = world
+        .patched(&patches)
+        .map_err(|err| err.to_string())?;
+    for conflict in conflicts {
+        eprintln!("Conflict: {conflict}");
+    }
+    Ok(world)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let data = read(&args.world)?;
+    let (world, notes) =
+        World::load(&data).map_err(|err| err.to_string())?;
+    for note in notes {
+        eprintln!("Note: {note}");
+    }
+    let world = patch(world, &args.patches)?;
+
+    let decisions = match &args.policy {
+        Some(path) => {
+            let script = String::from_utf8(read(path)?)
+                .map_err(|_| format!("{path} isn't text."))?;
+            policy::parse(&script, &world)?
+        }
+        None => vec![],
+    };
+
+    // The seed is always shown so that
+    // any run can be reproduced.
+    let seed = args.seed.unwrap_or_else(Rng::random_seed);
+    match args.command {
+        Command::Run => run_once(&args, world, seed, decisions),
+        Command::Batch => {
+            run_batch(&args, world, seed, decisions)
+        }
+        Command::Sweep => {
+            run_sweep(&args, world, seed, decisions)
+        }
+    }
+}
+
+fn run_once(
+    args: &Args,
+    world: World,
+    seed: u64,
+    decisions: Vec<(usize, Decision)>,
+) -> Result<(), String> {
+    eprintln!("Running {} years with seed {seed}.", args.years);
+    let replay = Replay {
+        world,
+        seed,
+        decisions,
+        tgavs: vec![],
+    };
+    let (state, reports) = replay.run(args.years);
+    print_reports(&reports);
+    if state.game_over {
+        println!("The game ended in {}.", state.world.year);
+    }
+    match &args.out {
+        Some(path) => write(path, &reports),
+        None => Ok(()),
+    }
+}
+
+fn run_batch(
+    args: &Args,
+    world: World,
+    seed: u64,
+    decisions: Vec<(usize, Decision)>,
+) -> Result<(), String> {
+    let runs = args.runs.unwrap_or(DEFAULT_BATCH_RUNS);
+    eprintln!(
+        "Running {} years {} times with seeds from {seed}.",
+        args.years, runs
+    );
+    let batch = Batch {
+        world,
+        decisions,
+        years: args.years,
+    };
+    let summary = batch.run(seeds(seed, runs));
+    print_summary(&summary);
+    match &args.out {
+        Some(path) => write(path, &summary),
+        None => Ok(()),
+    }
+}
+
+fn run_sweep(
+    args: &Args,
+    world: World,
+    seed: u64,
+    decisions: Vec<(usize, Decision)>,
+) -> Result<(), String> {
+    if args.params.is_empty() {
+        return Err(
+            "A sweep needs at least one --param.".into()
+        );
+    }
+    let runs = args.runs.unwrap_or(DEFAULT_SWEEP_RUNS);
+    let sweep = Sweep {
+        world,
+        parameters: args.params.clone(),
+        decisions,
+        years: args.years,
+        seeds: seeds(seed, runs).collect(),
+    };
+    let combinations: usize = args
+        .params
+        .iter()
+        .map(|param| param.values.len())
+        .product();
+    eprintln!(
+        "Running {} years {} times for each of {combinations} combinations, with seeds from {seed}.",
+        args.years, runs
+    );
+    let rows = sweep.run().map_err(|err| err.to_string())?;
+    print_sweep(&args.params, &rows);
+    match &args.out {
+        Some(path) => write(path, &rows),
+        None => Ok(()),
+    }
+}
+
+/// Consecutive seeds, starting from the given one.
+fn seeds(first: u64, runs: usize) -> impl Iterator<Item = u64> {
+    (0..runs).map(move |i| first.wrapping_add(i as u64))
+}
+
+fn print_reports(reports: &[YearReport]) {
+    println!(
+        "{:>6} {:>8} {:>10} {:>10} {:>8} {:>11} {:>6}  Events",
+        "Year",
+        "Temp",
+        "Emissions",
+        "Extinction",
+        "Outlook",
+        "Population",
+        "PC"
+    );
+    for report in reports {
+        println!(
+            "{:>6} {:>7.2}C {:>8.2}Gt {:>10.1} {:>8.1} {:>10.2}B {:>6}  {}",
+            report.year,
+            report.temperature,
+            report.emissions,
+            report.extinction_rate,
+            report.outlook,
+            report.population / 1e9,
+            report.political_capital,
+            report.events.join(", ")
+        );
+    }
+}
+
+fn print_summary(summary: &BatchSummary) {
+    println!(
+        "{:<16} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "", "Mean", "Min", "10%", "Median", "90%", "Max"
+    );
+    let row = |label: &str, dist: &Distribution| {
+        println!(
+            "{:<16} {:>8.2} {:>8.2} {:>8.2} {:>8.2} {:>8.2} {:>8.2}",
+            label,
+            dist.mean,
+            dist.min,
+            dist.p10,
+            dist.median,
+            dist.p90,
+            dist.max
+        );
+    };
+    row("Temperature (C)", &summary.temperature);
+    row("Extinction", &summary.extinction_rate);
+    row("Emissions (Gt)", &summary.emissions);
+    row("Outlook", &summary.outlook);
+    println!(
+        "Collapsed in {:.1}% of runs.",
+        summary.collapse_rate * 100.
+    );
+}
+
+fn print_sweep(params: &[Parameter], rows: &[SweepRow]) {
+    for (i, param) in params.iter().enumerate() {
+        println!("P{}: {}", i + 1, param.path);
+    }
+    let labels: Vec<_> = (1..=params.len())
+        .map(|i| format!("{:>10}", format!("P{i}")))
+        .collect();
+    println!(
+        "{} {:>8} {:>10} {:>10} {:>8} {:>9}",
+        labels.join(" "),
+        "Temp",
+        "Extinction",
+        "Emissions",
+        "Outlook",
+        "Collapsed"
+    );
+    for row in rows {
+        let values: Vec<_> = row
+            .values
+            .iter()
+            .map(|value| format!("{value:>10.3}"))
+            .collect();
+        let summary = &row.summary;
+        println!(
+            "{} {:>7.2}C {:>10.1} {:>8.2}Gt {:>8.1} {:>8.1}%",
+            values.join(" "),
+            summary.temperature.mean,
+            summary.extinction_rate.mean,
+            summary.emissions.mean,
+            summary.outlook.mean,
+            summary.collapse_rate * 100.
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Policy scripts, the decisions to make during a run.
+//! Each line is a decision as `<year> <action> ...`, e.g.:
+//!
+//! ```text
+//! # Comments start with a `#`.
+//! 2025 capital 100
+//! 2025 points 5 Solar Subsidies
+//! 2025 start Solar Subsidies
+//! 2030 upgrade Solar Subsidies
+//! 2030 mix -2 Coal Power Generation
+//! ```
+//!
+//! Projects and processes are referred to by name.
+//! The actions are:
+//!
+//! - `start`, `stop`, `upgrade`, `downgrade <project>`
+//! - `points <points> <project>`
+//! - `mix <change> <process>`
+//! - `capital <change>`
+
+use hes_engine::{Decision, Id, World};
+
+/// Parse a policy script into decisions and the years
+/// they're made in, ordered by year.
+pub fn parse(
+    script: &str,
+    world: &World,
+) -> Result<Vec<(usize, Decision)>, String> {
+    let mut decisions = vec![];
+    for (i, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let decision = parse_line(line, world)
+            .map_err(|err| format!("Line {}: {err}", i + 1))?;
+        decisions.push(decision);
+    }
+    decisions.sort_by_key(|(year, _)| *year);
+    Ok(decisions)
+}
+
+fn parse_line(
+    line: &str,
+    world: &World,
+) -> Result<(usize, Decision), String> {
+    let (year, rest) = split(line);
+    let year = number(year)?;
+    let (action, rest) = split(rest);
+    let decision = match action {
+        "start" => {
+            Decision::StartProject(project(rest, world)?)
+        }
+        "stop" => Decision::StopProject(project(rest, world)?),
+        "upgrade" => {
+            Decision::UpgradeProject(project(rest, world)?)
+        }
+        "downgrade" => {
+            Decision::DowngradeProject(project(rest, world)?)
+        }
+        "points" => {
+            let (points, rest) = split(rest);
+            Decision::SetProjectPoints(
+                project(rest, world)?,
+                number(points)?,
+            )
+        }
+        "mix" => {
+            let (change, rest) = split(rest);
+            Decision::ChangeMixShare(
+                process(rest, world)?,
+                number(change.trim_start_matches('+'))?,
+            )
+        }
+        "capital" => Decision::ChangePoliticalCapital(number(
+            rest.trim_start_matches('+'),
+        )?),
+        "" => return Err("Missing an action.".into()),
+        _ => {
+            return Err(format!("Unknown action \"{action}\"."))
+        }
+    };
+    Ok((year, decision))
+}
+
+/// Split off the first word.
+fn split(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    text.split_once(char::is_whitespace)
+        .map(|(word, rest)| (word, rest.trim()))
+        .unwrap_or((text, ""))
+}
+
+fn number<T: std::str::FromStr>(
+    text: &str,
+) -> Result<T, String> {
+    text.parse().map_err(|_| {
+        format!("\"{text}\" isn't a valid number.")
+    })
+}
+
+fn project(name: &str, world: &World) -> Result<Id, String> {
+    world
+        .projects
+        .iter()
+        .find(|project| project.name.eq_ignore_ascii_case(name))
+        .map(|project| project.id)
+        .ok_or_else(|| format!("No project named \"{name}\"."))
+}
+
+fn process(name: &str, world: &World) -> Result<Id, String> {
+    world
+        .processes
+        .iter()
+        .find(|process| process.name.eq_ignore_ascii_case(name))
+        .map(|process| process.id)
+        .ok_or_else(|| format!("No process named \"{name}\"."))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let world = World::default();
+        let project = world.projects.first();
+        let process = world.processes.first();
+        let script = format!(
+            "# A comment.\n\
+             2030 mix +2 {}\n\
+             \n\
+             2025 points 3 {}\n\
+             2025 start {}\n\
+             2025 capital -10\n",
+            process.name,
+            project.name,
+            project.name.to_uppercase(),
+        );
+        let decisions = parse(&script, &world).unwrap();
+        assert_eq!(
+            decisions,
+            vec![
+                (
+                    2025,
+                    Decision::SetProjectPoints(project.id, 3)
+                ),
+                (2025, Decision::StartProject(project.id)),
+                (2025, Decision::ChangePoliticalCapital(-10)),
+                (2030, Decision::ChangeMixShare(process.id, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let world = World::default();
+        let err = |script| parse(script, &world).unwrap_err();
+        assert_eq!(
+            err("2025 start Nothing At All"),
+            "Line 1: No project named \"Nothing At All\"."
+        );
+        assert_eq!(
+            err("\nsoon capital 5"),
+            "Line 2: \"soon\" isn't a valid number."
+        );
+        assert_eq!(
+            err("2025 repeal Something"),
+            "Line 1: Unknown action \"repeal\"."
+        );
+        assert_eq!(err("2025"), "Line 1: Missing an action.");
+    }
+}
@@ -274,9 +274,104 @@ fn extract_strings(from: &str) -> Vec<String> {
     literals.into_iter().filter(|lit| !lit.is_empty()).collect()
 }
 
-fn main() {
-    let expected = extract_strings("/tmp/expanded");
-    for entry in glob("util/i18n/transl/*.csv").unwrap() {
+/// Strings in the editor's own interface: those passed
+/// to its `t!` macro, the labels and help text passed
+/// to its input components, and the messages of its
+/// conflict rules, all of which are translated.
+fn extract_editor_strings() -> Vec<String> {
+    const PREFIXES: [&str; 6] = [
+        "t!(\"",
+        "label=\"",
+        "help=\"",
+        "help=r#\"",
+        "title=t!(\"",
+        "message: \"",
+    ];
+
+    let mut literals = vec![];
+    for entry in glob("hes-editor/src/**/*.rs").unwrap() {
+        let contents =
+            std::fs::read_to_string(entry.unwrap()).unwrap();
+        for prefix in PREFIXES {
+            let raw = prefix.ends_with("r#\"");
+            let end = if raw { "\"#" } else { "\"" };
+            let mut rest = contents.as_str();
+            while let Some(start) = rest.find(prefix) {
+                rest = &rest[start + prefix.len()..];
+                let Some(len) = find_end(rest, end) else {
+                    break;
+                };
+                let lit = &rest[..len];
+                literals.push(if raw {
+                    lit.to_string()
+                } else {
+                    lit.replace("\\\"", "\"")
+                });
+                rest = &rest[len..];
+            }
+        }
+    }
+    literals.extend(extract_field_docs());
+    literals.sort_unstable();
+    literals.dedup();
+    literals.into_iter().filter(|lit| !lit.is_empty()).collect()
+}
+
+/// The engine's field docs, which the editor uses as help
+/// text; see `#[derive(Documented)]`. Each field's doc
+/// comment lines are joined the same way the derive does.
+fn extract_field_docs() -> Vec<String> {
+    let mut docs = vec![];
+    for entry in glob("hes-engine/src/**/*.rs").unwrap() {
+        let contents =
+            std::fs::read_to_string(entry.unwrap()).unwrap();
+        let mut documented = false;
+        let mut in_struct = false;
+        let mut lines: Vec<&str> = vec![];
+        for line in contents.lines().map(str::trim) {
+            if line.ends_with("Documented,")
+                || line.ends_with("Documented)]")
+            {
+                documented = true;
+            } else if line.starts_with("pub struct") {
+                in_struct = documented;
+                documented = false;
+            } else if line == "}" {
+                in_struct = false;
+            } else if !in_struct {
+                continue;
+            } else if let Some(doc) = line.strip_prefix("///") {
+                lines.push(doc.trim());
+            } else if line.starts_with("pub ") {
+                if !lines.is_empty() {
+                    docs.push(lines.join(" "));
+                }
+                lines.clear();
+            }
+        }
+    }
+    docs
+}
+
+/// Find the end of a string literal, skipping escaped quotes.
+fn find_end(s: &str, end: &str) -> Option<usize> {
+    let mut idx = 0;
+    while let Some(n) = s[idx..].find(end) {
+        let at = idx + n;
+        if end == "\"" && s[..at].ends_with('\\') {
+            idx = at + 1;
+            continue;
+        }
+        return Some(at);
+    }
+    None
+}
+
+/// Check translations against the expected strings
+/// and save them as JSON for loading in the browser.
+fn process(expected: &[String], csvs: &str, out_dir: &str) {
+    std::fs::create_dir_all(out_dir).unwrap();
+    for entry in glob(csvs).unwrap() {
         let path = entry.unwrap();
         let stem = path.file_stem().unwrap().to_str().unwrap();
 
@@ -284,7 +379,7 @@ fn main() {
 
         let mut missing = vec![];
         let mut extra = vec![];
-        for key in &expected {
+        for key in expected {
             if !mapping.contains_key(key) {
                 missing.push(key);
             }
@@ -331,10 +426,25 @@ fn main() {
         println!("Saving {:?}", stem);
         let ser =
             serde_json::to_string_pretty(&mapping).unwrap();
-        let output =
-            format!("hes-game/public/assets/lang/{stem}.json");
+        let output = format!("{out_dir}/{stem}.json");
         std::fs::write(&output, ser).unwrap_or_else(|_| {
             panic!("Couldn't write file: {:?}", &output)
         });
     }
 }
+
+fn main() {
+    let expected = extract_strings("/tmp/expanded");
+    process(
+        &expected,
+        "util/i18n/transl/*.csv",
+        "hes-game/public/assets/lang",
+    );
+
+    let expected = extract_editor_strings();
+    process(
+        &expected,
+        "util/i18n/transl/editor/*.csv",
+        "hes-editor/public/lang",
+    );
+}
@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast,
+};
+use tokio_tungstenite::{
+    accept_hdr_async_with_config,
+    tungstenite::{
+        handshake::server::{Request, Response},
+        protocol::WebSocketConfig,
+        Message,
+    },
+};
+
+/// Only reachable from this machine by default;
+/// pass an address to share it on a network.
+const DEFAULT_ADDR: &str = "127.0.0.1:9001";
+
+/// The largest message (and frame, as browsers send a
+/// message in one frame) the relay accepts. World
+/// snapshots are the largest, at a few megabytes.
+const MAX_MESSAGE_SIZE: usize = 16 << 20;
+
+/// How many messages a slow peer can fall behind
+/// before it starts missing them.
+const BACKLOG: usize = 256;
+
+/// Messages in a room, along with which connection
+/// sent them so they aren't echoed back.
+type Room = broadcast::Sender<(usize, String)>;
+type Rooms = Arc<Mutex<HashMap<String, Room>>>;
+
+/// Relays messages between editors in the same room,
+/// where the room is the path they connect to,
+/// e.g. `ws://localhost:9001/my-world`.
+///
+/// The relay doesn't keep any state of its own;
+/// editors sync the world amongst themselves.
+#[tokio::main]
+async fn main() {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDR.to_string());
+    let listener = TcpListener::bind(&addr)
+        .await
+        .expect("Failed to bind relay address");
+    println!("Relaying on {addr}");
+
+    let rooms = Rooms::default();
+    let mut next_conn = 0;
+    while let Ok((stream, _)) = listener.accept().await {
+        next_conn += 1;
+        tokio::spawn(handle(stream, next_conn, rooms.clone()));
+    }
+}
+
+// The handshake callback's error type is set by tungstenite.
+#[allow(clippy::result_large_err)]
+async fn handle(stream: TcpStream, conn: usize, rooms: Rooms) {
+    let mut path = String::new();
+    let config = WebSocketConfig {
+        max_message_size: Some(MAX_MESSAGE_SIZE),
+        max_frame_size: Some(MAX_MESSAGE_SIZE),
+        ..Default::default()
+    };
+    let socket = accept_hdr_async_with_config(
+        stream,
+        |req: &Request, res: Response| {
+            path =
+                req.uri().path().trim_matches('/').to_string();
+            Ok(res)
+        },
+        Some(config),
+    )
+    .await;
+    let Ok(socket) = socket else {
+        return;
+    };
+
+    let room = rooms
+        .lock()
+        .unwrap()
+        .entry(path.clone())
+        .or_insert_with(|| broadcast::channel(BACKLOG).0)
+        .clone();
+    let mut incoming = room.subscribe();
+    let (mut sink, mut stream) = socket.split();
+
+    // The editor's client id, from its messages, so the
+    // others can be told when it leaves.
+    let mut client = None;
+    loop {
+        tokio::select! {
+            msg = stream.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    if client.is_none() {
+                        client = client_id(&text);
+                    }
+                    let _ = room.send((conn, text));
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => (),
+            },
+            msg = incoming.recv() => match msg {
+                Ok((from, text)) if from != conn => {
+                    if sink.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => (),
+
+                // Missed messages, so have the editor
+                // ask the others for the world again.
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    let resync =
+                        serde_json::json!({ "kind": "Resync" });
+                    let msg = Message::Text(resync.to_string());
+                    if sink.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+
+    if let Some(client) = client {
+        let leave = serde_json::json!({
+            "kind": "Leave",
+            "client": client,
+        });
+        let _ = room.send((conn, leave.to_string()));
+    }
+
+    // Close the room once everyone's left.
+    drop(incoming);
+    let mut rooms = rooms.lock().unwrap();
+    if room.receiver_count() == 0 {
+        rooms.remove(&path);
+    }
+}
+
+fn client_id(text: &str) -> Option<u64> {
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()?
+        .get("client")?
+        .as_u64()
+}